@@ -13,6 +13,7 @@ export interface CndConfigFile {
     data?: { dir: string };
     network: { listen: string[] };
     logging: { level: string };
+    expiries: { min_delta_secs: number };
 }
 
 export interface HttpApi {
@@ -54,6 +55,10 @@ export class E2ETestActorConfig {
             logging: {
                 level: "Trace",
             },
+            // The default expiries of the tests are only seconds apart
+            expiries: {
+                min_delta_secs: 0,
+            },
             ...this.createLedgerConnectors(ledgerConfig),
         };
     }
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+CREATE TABLE seed_fingerprint
+(
+    id SERIAL   PRIMARY KEY,
+    fingerprint TEXT NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_ledger_events
+(
+    id SERIAL      PRIMARY KEY,
+    swap_id        TEXT NOT NULL,
+    ledger         TEXT NOT NULL,
+    event          TEXT NOT NULL,
+    transaction_id TEXT NOT NULL,
+    data           TEXT NOT NULL,
+    at TIMESTAMP   NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (swap_id, ledger, event)
+);
@@ -0,0 +1,221 @@
+-- This file should undo anything in `up.sql`
+
+CREATE TABLE rfc003_bitcoin_ethereum_bitcoin_ether_request_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT   NOT NULL UNIQUE,
+    bitcoin_network          TEXT   NOT NULL,
+    ethereum_chain_id        BIGINT NOT NULL,
+    bitcoin_amount           TEXT   NOT NULL,
+    ether_amount             TEXT   NOT NULL,
+    hash_function            TEXT   NOT NULL,
+    bitcoin_refund_identity  TEXT   NOT NULL,
+    ethereum_redeem_identity TEXT   NOT NULL,
+    bitcoin_expiry           BIGINT NOT NULL,
+    ethereum_expiry          BIGINT NOT NULL,
+    secret_hash              TEXT   NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_bitcoin_ether_bitcoin_request_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT   NOT NULL UNIQUE,
+    bitcoin_network          TEXT   NOT NULL,
+    ethereum_chain_id        BIGINT NOT NULL,
+    bitcoin_amount           TEXT   NOT NULL,
+    ether_amount             TEXT   NOT NULL,
+    hash_function            TEXT   NOT NULL,
+    bitcoin_redeem_identity  TEXT   NOT NULL,
+    ethereum_refund_identity TEXT   NOT NULL,
+    bitcoin_expiry           BIGINT NOT NULL,
+    ethereum_expiry          BIGINT NOT NULL,
+    secret_hash              TEXT   NOT NULL
+);
+
+CREATE TABLE rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT   NOT NULL UNIQUE,
+    bitcoin_network          TEXT   NOT NULL,
+    ethereum_chain_id        BIGINT NOT NULL,
+    bitcoin_amount           TEXT   NOT NULL,
+    erc20_amount             TEXT   NOT NULL,
+    erc20_token_contract     TEXT   NOT NULL,
+    hash_function            TEXT   NOT NULL,
+    bitcoin_refund_identity  TEXT   NOT NULL,
+    ethereum_redeem_identity TEXT   NOT NULL,
+    bitcoin_expiry           BIGINT NOT NULL,
+    ethereum_expiry          BIGINT NOT NULL,
+    secret_hash              TEXT   NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT   NOT NULL UNIQUE,
+    bitcoin_network          TEXT   NOT NULL,
+    ethereum_chain_id        BIGINT NOT NULL,
+    bitcoin_amount           TEXT   NOT NULL,
+    erc20_amount             TEXT   NOT NULL,
+    erc20_token_contract     TEXT   NOT NULL,
+    hash_function            TEXT   NOT NULL,
+    bitcoin_redeem_identity  TEXT   NOT NULL,
+    ethereum_refund_identity TEXT   NOT NULL,
+    bitcoin_expiry           BIGINT NOT NULL,
+    ethereum_expiry          BIGINT NOT NULL,
+    secret_hash              TEXT   NOT NULL
+);
+
+CREATE TABLE rfc003_ethereum_bitcoin_accept_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT NOT NULL UNIQUE,
+    bitcoin_refund_identity  TEXT NOT NULL,
+    ethereum_redeem_identity TEXT NOT NULL,
+    at TIMESTAMP             NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE rfc003_bitcoin_ethereum_accept_messages
+(
+    id SERIAL                PRIMARY KEY,
+    swap_id                  TEXT NOT NULL UNIQUE,
+    bitcoin_redeem_identity  TEXT NOT NULL,
+    ethereum_refund_identity TEXT NOT NULL,
+    at TIMESTAMP             NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE rfc003_decline_messages
+(
+    id SERIAL PRIMARY KEY,
+    swap_id   TEXT NOT NULL UNIQUE,
+    reason    TEXT
+);
+
+CREATE TABLE rfc003_swaps
+(
+    id SERIAL    PRIMARY KEY,
+    swap_id      TEXT NOT NULL UNIQUE,
+    role         TEXT NOT NULL,
+    counterparty TEXT NOT NULL
+);
+
+INSERT INTO rfc003_swaps (swap_id, role, counterparty)
+SELECT swap_id, role, counterparty
+FROM swaps;
+
+INSERT INTO rfc003_bitcoin_ethereum_bitcoin_ether_request_messages (swap_id, bitcoin_network, ethereum_chain_id, bitcoin_amount, ether_amount, hash_function, bitcoin_refund_identity, ethereum_redeem_identity, bitcoin_expiry, ethereum_expiry, secret_hash)
+SELECT s.swap_id,
+       al.bitcoin_network,
+       bl.ethereum_chain_id,
+       aa.quantity,
+       ba.quantity,
+       s.hash_function,
+       al.refund_identity,
+       bl.redeem_identity,
+       al.expiry,
+       bl.expiry,
+       s.secret_hash
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+         JOIN swap_assets aa ON aa.swap_id = s.swap_id AND aa.side = 'Alpha'
+         JOIN swap_assets ba ON ba.swap_id = s.swap_id AND ba.side = 'Beta'
+WHERE al.ledger = 'Bitcoin'
+  AND bl.ledger = 'Ethereum'
+  AND aa.asset = 'Bitcoin'
+  AND ba.asset = 'Ether';
+
+INSERT INTO rfc003_ethereum_bitcoin_ether_bitcoin_request_messages (swap_id, bitcoin_network, ethereum_chain_id, bitcoin_amount, ether_amount, hash_function, ethereum_refund_identity, bitcoin_redeem_identity, ethereum_expiry, bitcoin_expiry, secret_hash)
+SELECT s.swap_id,
+       bl.bitcoin_network,
+       al.ethereum_chain_id,
+       ba.quantity,
+       aa.quantity,
+       s.hash_function,
+       al.refund_identity,
+       bl.redeem_identity,
+       al.expiry,
+       bl.expiry,
+       s.secret_hash
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+         JOIN swap_assets aa ON aa.swap_id = s.swap_id AND aa.side = 'Alpha'
+         JOIN swap_assets ba ON ba.swap_id = s.swap_id AND ba.side = 'Beta'
+WHERE al.ledger = 'Ethereum'
+  AND bl.ledger = 'Bitcoin'
+  AND aa.asset = 'Ether'
+  AND ba.asset = 'Bitcoin';
+
+INSERT INTO rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages (swap_id, bitcoin_network, ethereum_chain_id, bitcoin_amount, erc20_amount, erc20_token_contract, hash_function, bitcoin_refund_identity, ethereum_redeem_identity, bitcoin_expiry, ethereum_expiry, secret_hash)
+SELECT s.swap_id,
+       al.bitcoin_network,
+       bl.ethereum_chain_id,
+       aa.quantity,
+       ba.quantity,
+       ba.token_contract,
+       s.hash_function,
+       al.refund_identity,
+       bl.redeem_identity,
+       al.expiry,
+       bl.expiry,
+       s.secret_hash
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+         JOIN swap_assets aa ON aa.swap_id = s.swap_id AND aa.side = 'Alpha'
+         JOIN swap_assets ba ON ba.swap_id = s.swap_id AND ba.side = 'Beta'
+WHERE al.ledger = 'Bitcoin'
+  AND bl.ledger = 'Ethereum'
+  AND aa.asset = 'Bitcoin'
+  AND ba.asset = 'Erc20';
+
+INSERT INTO rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages (swap_id, bitcoin_network, ethereum_chain_id, bitcoin_amount, erc20_amount, erc20_token_contract, hash_function, ethereum_refund_identity, bitcoin_redeem_identity, ethereum_expiry, bitcoin_expiry, secret_hash)
+SELECT s.swap_id,
+       bl.bitcoin_network,
+       al.ethereum_chain_id,
+       ba.quantity,
+       aa.quantity,
+       aa.token_contract,
+       s.hash_function,
+       al.refund_identity,
+       bl.redeem_identity,
+       al.expiry,
+       bl.expiry,
+       s.secret_hash
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+         JOIN swap_assets aa ON aa.swap_id = s.swap_id AND aa.side = 'Alpha'
+         JOIN swap_assets ba ON ba.swap_id = s.swap_id AND ba.side = 'Beta'
+WHERE al.ledger = 'Ethereum'
+  AND bl.ledger = 'Bitcoin'
+  AND aa.asset = 'Erc20'
+  AND ba.asset = 'Bitcoin';
+
+INSERT INTO rfc003_bitcoin_ethereum_accept_messages (swap_id, bitcoin_redeem_identity, ethereum_refund_identity, at)
+SELECT s.swap_id, al.redeem_identity, bl.refund_identity, s.accepted_at
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+WHERE s.accepted_at IS NOT NULL
+  AND al.ledger = 'Bitcoin'
+  AND bl.ledger = 'Ethereum';
+
+INSERT INTO rfc003_ethereum_bitcoin_accept_messages (swap_id, ethereum_redeem_identity, bitcoin_refund_identity, at)
+SELECT s.swap_id, al.redeem_identity, bl.refund_identity, s.accepted_at
+FROM swaps s
+         JOIN swap_ledgers al ON al.swap_id = s.swap_id AND al.side = 'Alpha'
+         JOIN swap_ledgers bl ON bl.swap_id = s.swap_id AND bl.side = 'Beta'
+WHERE s.accepted_at IS NOT NULL
+  AND al.ledger = 'Ethereum'
+  AND bl.ledger = 'Bitcoin';
+
+INSERT INTO rfc003_decline_messages (swap_id, reason)
+SELECT swap_id, reason
+FROM swap_declines;
+
+DROP TABLE swap_declines;
+DROP TABLE swap_assets;
+DROP TABLE swap_ledgers;
+DROP TABLE swaps;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+CREATE TABLE seed_fingerprint
+(
+    id INTEGER  NOT NULL PRIMARY KEY,
+    fingerprint NOT NULL
+);
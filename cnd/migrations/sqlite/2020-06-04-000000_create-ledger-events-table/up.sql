@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+CREATE TABLE rfc003_ledger_events
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        NOT NULL,
+    ledger         NOT NULL,
+    event          NOT NULL,
+    transaction_id NOT NULL,
+    data           NOT NULL,
+    at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (swap_id, ledger, event)
+);
@@ -0,0 +1,146 @@
+-- Your SQL goes here
+
+CREATE TABLE swaps
+(
+    id INTEGER    NOT NULL PRIMARY KEY,
+    swap_id       TEXT NOT NULL UNIQUE,
+    role          TEXT NOT NULL,
+    counterparty  TEXT NOT NULL,
+    hash_function TEXT,
+    secret_hash   TEXT,
+    accepted_at   DATETIME
+);
+
+CREATE TABLE swap_ledgers
+(
+    id INTEGER        NOT NULL PRIMARY KEY,
+    swap_id           TEXT    NOT NULL,
+    side              TEXT    NOT NULL,
+    ledger            TEXT    NOT NULL,
+    bitcoin_network   TEXT,
+    ethereum_chain_id INTEGER,
+    expiry            INTEGER NOT NULL,
+    redeem_identity   TEXT,
+    refund_identity   TEXT,
+    UNIQUE (swap_id, side)
+);
+
+CREATE TABLE swap_assets
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        TEXT NOT NULL,
+    side           TEXT NOT NULL,
+    asset          TEXT NOT NULL,
+    quantity       TEXT NOT NULL,
+    token_contract TEXT,
+    UNIQUE (swap_id, side)
+);
+
+CREATE TABLE swap_declines
+(
+    id INTEGER NOT NULL PRIMARY KEY,
+    swap_id    TEXT NOT NULL UNIQUE,
+    reason     TEXT
+);
+
+INSERT INTO swaps (swap_id, role, counterparty, hash_function, secret_hash, accepted_at)
+SELECT s.swap_id,
+       s.role,
+       s.counterparty,
+       COALESCE(r1.hash_function, r2.hash_function, r3.hash_function, r4.hash_function),
+       COALESCE(r1.secret_hash, r2.secret_hash, r3.secret_hash, r4.secret_hash),
+       COALESCE(a1.at, a2.at)
+FROM rfc003_swaps s
+         LEFT JOIN rfc003_bitcoin_ethereum_bitcoin_ether_request_messages r1 ON r1.swap_id = s.swap_id
+         LEFT JOIN rfc003_ethereum_bitcoin_ether_bitcoin_request_messages r2 ON r2.swap_id = s.swap_id
+         LEFT JOIN rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages r3 ON r3.swap_id = s.swap_id
+         LEFT JOIN rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages r4 ON r4.swap_id = s.swap_id
+         LEFT JOIN rfc003_bitcoin_ethereum_accept_messages a1 ON a1.swap_id = s.swap_id
+         LEFT JOIN rfc003_ethereum_bitcoin_accept_messages a2 ON a2.swap_id = s.swap_id;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, bitcoin_network, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Alpha', 'Bitcoin', r.bitcoin_network, r.bitcoin_expiry, a.bitcoin_redeem_identity, r.bitcoin_refund_identity
+FROM rfc003_bitcoin_ethereum_bitcoin_ether_request_messages r
+         LEFT JOIN rfc003_bitcoin_ethereum_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, ethereum_chain_id, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Beta', 'Ethereum', r.ethereum_chain_id, r.ethereum_expiry, r.ethereum_redeem_identity, a.ethereum_refund_identity
+FROM rfc003_bitcoin_ethereum_bitcoin_ether_request_messages r
+         LEFT JOIN rfc003_bitcoin_ethereum_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Alpha', 'Bitcoin', bitcoin_amount
+FROM rfc003_bitcoin_ethereum_bitcoin_ether_request_messages;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Beta', 'Ether', ether_amount
+FROM rfc003_bitcoin_ethereum_bitcoin_ether_request_messages;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, ethereum_chain_id, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Alpha', 'Ethereum', r.ethereum_chain_id, r.ethereum_expiry, a.ethereum_redeem_identity, r.ethereum_refund_identity
+FROM rfc003_ethereum_bitcoin_ether_bitcoin_request_messages r
+         LEFT JOIN rfc003_ethereum_bitcoin_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, bitcoin_network, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Beta', 'Bitcoin', r.bitcoin_network, r.bitcoin_expiry, r.bitcoin_redeem_identity, a.bitcoin_refund_identity
+FROM rfc003_ethereum_bitcoin_ether_bitcoin_request_messages r
+         LEFT JOIN rfc003_ethereum_bitcoin_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Alpha', 'Ether', ether_amount
+FROM rfc003_ethereum_bitcoin_ether_bitcoin_request_messages;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Beta', 'Bitcoin', bitcoin_amount
+FROM rfc003_ethereum_bitcoin_ether_bitcoin_request_messages;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, bitcoin_network, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Alpha', 'Bitcoin', r.bitcoin_network, r.bitcoin_expiry, a.bitcoin_redeem_identity, r.bitcoin_refund_identity
+FROM rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages r
+         LEFT JOIN rfc003_bitcoin_ethereum_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, ethereum_chain_id, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Beta', 'Ethereum', r.ethereum_chain_id, r.ethereum_expiry, r.ethereum_redeem_identity, a.ethereum_refund_identity
+FROM rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages r
+         LEFT JOIN rfc003_bitcoin_ethereum_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Alpha', 'Bitcoin', bitcoin_amount
+FROM rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity, token_contract)
+SELECT swap_id, 'Beta', 'Erc20', erc20_amount, erc20_token_contract
+FROM rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, ethereum_chain_id, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Alpha', 'Ethereum', r.ethereum_chain_id, r.ethereum_expiry, a.ethereum_redeem_identity, r.ethereum_refund_identity
+FROM rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages r
+         LEFT JOIN rfc003_ethereum_bitcoin_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, bitcoin_network, expiry, redeem_identity, refund_identity)
+SELECT r.swap_id, 'Beta', 'Bitcoin', r.bitcoin_network, r.bitcoin_expiry, r.bitcoin_redeem_identity, a.bitcoin_refund_identity
+FROM rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages r
+         LEFT JOIN rfc003_ethereum_bitcoin_accept_messages a ON a.swap_id = r.swap_id;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity, token_contract)
+SELECT swap_id, 'Alpha', 'Erc20', erc20_amount, erc20_token_contract
+FROM rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity)
+SELECT swap_id, 'Beta', 'Bitcoin', bitcoin_amount
+FROM rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages;
+
+-- Declines are only kept for swaps whose role and counterparty are known.
+INSERT INTO swap_declines (swap_id, reason)
+SELECT d.swap_id, d.reason
+FROM rfc003_decline_messages d
+         JOIN swaps s ON s.swap_id = d.swap_id;
+
+DROP TABLE rfc003_decline_messages;
+DROP TABLE rfc003_bitcoin_ethereum_bitcoin_ether_request_messages;
+DROP TABLE rfc003_ethereum_bitcoin_ether_bitcoin_request_messages;
+DROP TABLE rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages;
+DROP TABLE rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages;
+DROP TABLE rfc003_ethereum_bitcoin_accept_messages;
+DROP TABLE rfc003_bitcoin_ethereum_accept_messages;
+DROP TABLE rfc003_swaps;
@@ -0,0 +1,45 @@
+-- This file should undo anything in `up.sql`
+
+INSERT INTO swaps (swap_id, role, counterparty, hash_function, secret_hash, accepted_at)
+SELECT swap_id, role, counterparty, hash_function, secret_hash, accepted_at
+FROM archived_swaps;
+
+INSERT INTO swap_ledgers (swap_id, side, ledger, bitcoin_network, ethereum_chain_id, expiry, redeem_identity, refund_identity)
+SELECT swap_id, side, ledger, bitcoin_network, ethereum_chain_id, expiry, redeem_identity, refund_identity
+FROM archived_swap_ledgers;
+
+INSERT INTO swap_assets (swap_id, side, asset, quantity, token_contract)
+SELECT swap_id, side, asset, quantity, token_contract
+FROM archived_swap_assets;
+
+INSERT INTO swap_declines (swap_id, reason)
+SELECT swap_id, reason
+FROM archived_swap_declines;
+
+INSERT INTO rfc003_ledger_events (swap_id, ledger, event, transaction_id, data, at)
+SELECT swap_id, ledger, event, transaction_id, data, at
+FROM archived_ledger_events;
+
+DROP TABLE archived_ledger_events;
+DROP TABLE archived_swap_declines;
+DROP TABLE archived_swap_assets;
+DROP TABLE archived_swap_ledgers;
+DROP TABLE archived_swaps;
+
+CREATE TABLE swaps_without_final_status
+(
+    id INTEGER    NOT NULL PRIMARY KEY,
+    swap_id       TEXT NOT NULL UNIQUE,
+    role          TEXT NOT NULL,
+    counterparty  TEXT NOT NULL,
+    hash_function TEXT,
+    secret_hash   TEXT,
+    accepted_at   DATETIME
+);
+
+INSERT INTO swaps_without_final_status (id, swap_id, role, counterparty, hash_function, secret_hash, accepted_at)
+SELECT id, swap_id, role, counterparty, hash_function, secret_hash, accepted_at
+FROM swaps;
+
+DROP TABLE swaps;
+ALTER TABLE swaps_without_final_status RENAME TO swaps;
@@ -0,0 +1,61 @@
+-- Your SQL goes here
+
+ALTER TABLE swaps ADD COLUMN final_status TEXT;
+ALTER TABLE swaps ADD COLUMN finished_at DATETIME;
+
+CREATE TABLE archived_swaps
+(
+    id INTEGER    NOT NULL PRIMARY KEY,
+    swap_id       TEXT     NOT NULL UNIQUE,
+    role          TEXT     NOT NULL,
+    counterparty  TEXT     NOT NULL,
+    hash_function TEXT,
+    secret_hash   TEXT,
+    accepted_at   DATETIME,
+    final_status  TEXT     NOT NULL,
+    finished_at   DATETIME NOT NULL
+);
+
+CREATE TABLE archived_swap_ledgers
+(
+    id INTEGER        NOT NULL PRIMARY KEY,
+    swap_id           TEXT    NOT NULL,
+    side              TEXT    NOT NULL,
+    ledger            TEXT    NOT NULL,
+    bitcoin_network   TEXT,
+    ethereum_chain_id INTEGER,
+    expiry            INTEGER NOT NULL,
+    redeem_identity   TEXT,
+    refund_identity   TEXT,
+    UNIQUE (swap_id, side)
+);
+
+CREATE TABLE archived_swap_assets
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        TEXT NOT NULL,
+    side           TEXT NOT NULL,
+    asset          TEXT NOT NULL,
+    quantity       TEXT NOT NULL,
+    token_contract TEXT,
+    UNIQUE (swap_id, side)
+);
+
+CREATE TABLE archived_swap_declines
+(
+    id INTEGER NOT NULL PRIMARY KEY,
+    swap_id    TEXT NOT NULL UNIQUE,
+    reason     TEXT
+);
+
+CREATE TABLE archived_ledger_events
+(
+    id INTEGER     NOT NULL PRIMARY KEY,
+    swap_id        TEXT     NOT NULL,
+    ledger         TEXT     NOT NULL,
+    event          TEXT     NOT NULL,
+    transaction_id TEXT     NOT NULL,
+    data           TEXT     NOT NULL,
+    at             DATETIME NOT NULL,
+    UNIQUE (swap_id, ledger, event)
+);
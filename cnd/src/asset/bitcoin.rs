@@ -1,5 +1,6 @@
 use bitcoin::{util::amount::Denomination, Amount};
-use std::fmt;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
 
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
 pub struct Bitcoin(Amount);
@@ -27,6 +28,42 @@ impl fmt::Display for Bitcoin {
     }
 }
 
+impl<'de> Deserialize<'de> for Bitcoin {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'vde> de::Visitor<'vde> for Visitor {
+            type Value = Bitcoin;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                formatter.write_str("A string representing a satoshi quantity")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Bitcoin, E>
+            where
+                E: de::Error,
+            {
+                let sat = u64::from_str(v).map_err(E::custom)?;
+                Ok(Bitcoin::from_sat(sat))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl Serialize for Bitcoin {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_sat().to_string().as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::asset;
@@ -38,4 +75,15 @@ mod tests {
             "9000.00000000 BTC"
         );
     }
+
+    #[test]
+    fn serialize_bitcoin_as_satoshi_string() {
+        let bitcoin = asset::Bitcoin::from_sat(100_000_000);
+
+        let serialized = serde_json::to_string(&bitcoin).unwrap();
+        let deserialized = serde_json::from_str::<asset::Bitcoin>(&serialized).unwrap();
+
+        assert_eq!(serialized, "\"100000000\"");
+        assert_eq!(deserialized, bitcoin);
+    }
 }
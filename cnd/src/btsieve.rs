@@ -4,11 +4,17 @@
 pub mod bitcoin;
 pub mod ethereum;
 
-use crate::Never;
+use crate::{timestamp::Timestamp, Never};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
 use genawaiter::sync::Co;
-use std::{collections::HashSet, hash::Hash};
+use std::{
+    collections::HashSet,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
 
 #[async_trait]
 pub trait LatestBlock: Send + Sync + 'static {
@@ -25,6 +31,50 @@ pub trait BlockByHash: Send + Sync + 'static {
     async fn block_by_hash(&self, block_hash: Self::BlockHash) -> anyhow::Result<Self::Block>;
 }
 
+/// Abstracts over the ability of getting the time against which the consensus
+/// rules of a ledger check absolute timelocks.
+#[async_trait]
+pub trait ConsensusTime: Send + Sync + 'static {
+    async fn consensus_time(&self) -> anyhow::Result<Timestamp>;
+}
+
+/// How long a consensus time is reused before asking the node again. Listing
+/// the swaps needs the consensus time of every ledger of every swap, a stale
+/// time only delays offering a refund.
+const CONSENSUS_TIME_MAX_AGE: Duration = Duration::from_secs(5);
+
+/// Remembers the last consensus time a connector returned.
+#[derive(Clone)]
+pub struct CachedConsensusTime(Arc<Mutex<Option<(Instant, Timestamp)>>>);
+
+impl Default for CachedConsensusTime {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+}
+
+impl CachedConsensusTime {
+    pub async fn get_or_fetch<C>(&self, connector: &C) -> anyhow::Result<Timestamp>
+    where
+        C: ConsensusTime,
+    {
+        // Holding the lock while fetching makes concurrent callers wait for a
+        // single request to the node.
+        let mut guard = self.0.lock().await;
+
+        if let Some((fetched_at, time)) = *guard {
+            if fetched_at.elapsed() < CONSENSUS_TIME_MAX_AGE {
+                return Ok(time);
+            }
+        }
+
+        let time = connector.consensus_time().await?;
+        *guard = Some((Instant::now(), time));
+
+        Ok(time)
+    }
+}
+
 /// Checks if a given block predates a certain timestamp.
 pub trait Predates {
     fn predates(&self, timestamp: NaiveDateTime) -> bool;
@@ -155,3 +205,34 @@ where
         have_seen_block || predates_start_of_swap
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct CountingConnector(AtomicU32);
+
+    #[async_trait]
+    impl ConsensusTime for CountingConnector {
+        async fn consensus_time(&self) -> anyhow::Result<Timestamp> {
+            let calls = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Ok(Timestamp::from(calls))
+        }
+    }
+
+    #[tokio::test]
+    async fn consensus_time_is_reused_while_fresh() {
+        let connector = CountingConnector::default();
+        let cached = CachedConsensusTime::default();
+
+        let first = cached.get_or_fetch(&connector).await.unwrap();
+        let second = cached.get_or_fetch(&connector).await.unwrap();
+
+        assert_that(&first).is_equal_to(second);
+        assert_that(&connector.0.load(Ordering::SeqCst)).is_equal_to(1);
+    }
+}
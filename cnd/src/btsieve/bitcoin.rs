@@ -65,6 +65,24 @@ pub async fn watch_for_created_outpoint<C>(
     start_of_swap: NaiveDateTime,
     compute_address: bitcoin::Address,
 ) -> anyhow::Result<(bitcoin::Transaction, bitcoin::OutPoint)>
+where
+    C: LatestBlock<Block = Block> + BlockByHash<Block = Block, BlockHash = Hash>,
+{
+    watch_for_created_output(
+        blockchain_connector,
+        start_of_swap,
+        compute_address.script_pubkey(),
+    )
+    .await
+}
+
+/// Like `watch_for_created_outpoint` but matches on the `script_pubkey` of the
+/// output, which also covers outputs that have no `Address` representation.
+pub async fn watch_for_created_output<C>(
+    blockchain_connector: &C,
+    start_of_swap: NaiveDateTime,
+    script_pubkey: bitcoin::Script,
+) -> anyhow::Result<(bitcoin::Transaction, bitcoin::OutPoint)>
 where
     C: LatestBlock<Block = Block> + BlockByHash<Block = Block, BlockHash = Hash>,
 {
@@ -81,7 +99,7 @@ where
                 #[allow(clippy::cast_possible_truncation)]
                 (index as u32, txout)
             })
-            .find(|(_, txout)| txout.script_pubkey == compute_address.script_pubkey())
+            .find(|(_, txout)| txout.script_pubkey == script_pubkey)
             .map(|(vout, _txout)| OutPoint { txid, vout })
     })
     .await?;
@@ -1,6 +1,10 @@
 use crate::{
-    btsieve::{bitcoin::bitcoin_http_request_for_hex_encoded_object, BlockByHash, LatestBlock},
+    btsieve::{
+        bitcoin::bitcoin_http_request_for_hex_encoded_object, BlockByHash, ConsensusTime,
+        LatestBlock,
+    },
     config::validation::FetchNetworkId,
+    timestamp::Timestamp,
 };
 use async_trait::async_trait;
 use bitcoin::{BlockHash, Network};
@@ -11,6 +15,8 @@ use serde::Deserialize;
 pub struct ChainInfo {
     bestblockhash: BlockHash,
     pub chain: Network,
+    /// The median time past of the best block, see BIP113.
+    mediantime: u32,
 }
 
 #[derive(Debug)]
@@ -34,23 +40,27 @@ impl BitcoindConnector {
             .join(&format!("{}.hex", block_hash))
             .expect("building url should work")
     }
-}
-
-#[async_trait]
-impl LatestBlock for BitcoindConnector {
-    type Block = bitcoin::Block;
-
-    async fn latest_block(&self) -> anyhow::Result<Self::Block> {
-        let chaininfo_url = self.chaininfo_url.clone();
 
+    async fn chain_info(&self) -> anyhow::Result<ChainInfo> {
         let chain_info = self
             .client
-            .get(chaininfo_url)
+            .get(self.chaininfo_url.clone())
             .send()
             .await?
             .json::<ChainInfo>()
             .await?;
 
+        Ok(chain_info)
+    }
+}
+
+#[async_trait]
+impl LatestBlock for BitcoindConnector {
+    type Block = bitcoin::Block;
+
+    async fn latest_block(&self) -> anyhow::Result<Self::Block> {
+        let chain_info = self.chain_info().await?;
+
         let block = self.block_by_hash(chain_info.bestblockhash).await?;
 
         Ok(block)
@@ -80,15 +90,7 @@ impl BlockByHash for BitcoindConnector {
 #[async_trait]
 impl FetchNetworkId<Network> for BitcoindConnector {
     async fn network_id(&self) -> anyhow::Result<Network> {
-        let client = self.client.clone();
-        let chaininfo_url = self.chaininfo_url.clone();
-
-        let chain_info: ChainInfo = client
-            .get(chaininfo_url)
-            .send()
-            .await?
-            .json::<ChainInfo>()
-            .await?;
+        let chain_info = self.chain_info().await?;
 
         tracing::debug!("Fetched chain info: {:?} from bitcoind", chain_info);
 
@@ -96,6 +98,18 @@ impl FetchNetworkId<Network> for BitcoindConnector {
     }
 }
 
+/// A transaction with a timestamp lock time is final once the median time past
+/// of the best block is greater than the lock time (BIP113), block timestamps
+/// themselves may lag behind the wall clock or even go backwards.
+#[async_trait]
+impl ConsensusTime for BitcoindConnector {
+    async fn consensus_time(&self) -> anyhow::Result<Timestamp> {
+        let chain_info = self.chain_info().await?;
+
+        Ok(Timestamp::from(chain_info.mediantime))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -110,6 +124,29 @@ mod tests {
         ]
     }
 
+    #[test]
+    fn deserializes_median_time_past_from_chain_info() {
+        let json = r#"{
+            "chain": "regtest",
+            "blocks": 101,
+            "headers": 101,
+            "bestblockhash": "2a593b84b1943521be01f97a59fc7feba30e7e8527fb2ba20b0158ca09016d02",
+            "difficulty": 4.656542373906925e-10,
+            "mediantime": 1591000000,
+            "verificationprogress": 1,
+            "initialblockdownload": false,
+            "chainwork": "00000000000000000000000000000000000000000000000000000000000000cc",
+            "size_on_disk": 30865,
+            "pruned": false,
+            "warnings": ""
+        }"#;
+
+        let chain_info = serde_json::from_str::<ChainInfo>(json).unwrap();
+
+        assert_eq!(chain_info.chain, Network::Regtest);
+        assert_eq!(chain_info.mediantime, 1_591_000_000);
+    }
+
     #[test]
     fn constructor_does_not_fail_for_base_urls() {
         for base_url in base_urls() {
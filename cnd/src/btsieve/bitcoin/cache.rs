@@ -1,4 +1,7 @@
-use crate::btsieve::{BlockByHash, LatestBlock};
+use crate::{
+    btsieve::{BlockByHash, CachedConsensusTime, ConsensusTime, LatestBlock},
+    timestamp::Timestamp,
+};
 use async_trait::async_trait;
 use bitcoin::{util::hash::BitcoinHash, Block, BlockHash as Hash, BlockHash};
 use derivative::Derivative;
@@ -12,6 +15,8 @@ pub struct Cache<C> {
     pub connector: C,
     #[derivative(Debug = "ignore")]
     pub block_cache: Arc<Mutex<LruCache<BlockHash, Block>>>,
+    #[derivative(Debug = "ignore")]
+    pub consensus_time: CachedConsensusTime,
 }
 
 impl<C> Cache<C> {
@@ -20,6 +25,7 @@ impl<C> Cache<C> {
         Cache {
             connector,
             block_cache,
+            consensus_time: CachedConsensusTime::default(),
         }
     }
 }
@@ -69,3 +75,13 @@ where
         Ok(block)
     }
 }
+
+#[async_trait]
+impl<C> ConsensusTime for Cache<C>
+where
+    C: ConsensusTime,
+{
+    async fn consensus_time(&self) -> anyhow::Result<Timestamp> {
+        self.consensus_time.get_or_fetch(&self.connector).await
+    }
+}
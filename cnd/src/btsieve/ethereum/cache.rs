@@ -1,9 +1,10 @@
 use crate::{
     btsieve::{
         ethereum::{self, Hash, ReceiptByHash},
-        BlockByHash, LatestBlock,
+        BlockByHash, CachedConsensusTime, ConsensusTime, LatestBlock,
     },
     ethereum::TransactionReceipt,
+    timestamp::Timestamp,
 };
 use async_trait::async_trait;
 use derivative::Derivative;
@@ -23,6 +24,8 @@ pub struct Cache<C> {
     pub block_cache: Arc<Mutex<LruCache<Hash, Block>>>,
     #[derivative(Debug = "ignore")]
     pub receipt_cache: Arc<Mutex<LruCache<Hash, TransactionReceipt>>>,
+    #[derivative(Debug = "ignore")]
+    pub consensus_time: CachedConsensusTime,
 }
 
 impl<C> Cache<C> {
@@ -37,6 +40,7 @@ impl<C> Cache<C> {
             connector,
             block_cache,
             receipt_cache,
+            consensus_time: CachedConsensusTime::default(),
         }
     }
 }
@@ -113,3 +117,13 @@ where
         Ok(receipt)
     }
 }
+
+#[async_trait]
+impl<C> ConsensusTime for Cache<C>
+where
+    C: ConsensusTime,
+{
+    async fn consensus_time(&self) -> anyhow::Result<Timestamp> {
+        self.consensus_time.get_or_fetch(&self.connector).await
+    }
+}
@@ -1,11 +1,13 @@
 use crate::{
-    btsieve::{ethereum::ReceiptByHash, BlockByHash, LatestBlock},
+    btsieve::{ethereum::ReceiptByHash, BlockByHash, ConsensusTime, LatestBlock},
     config::validation::FetchNetworkId,
-    ethereum::{Hash, TransactionReceipt},
+    ethereum::{Hash, TransactionReceipt, U256},
     jsonrpc,
     swap_protocols::ledger::ethereum::ChainId,
+    timestamp::Timestamp,
 };
 use async_trait::async_trait;
+use serde::Deserialize;
 
 #[derive(Debug)]
 pub struct Web3Connector {
@@ -42,6 +44,30 @@ impl LatestBlock for Web3Connector {
     }
 }
 
+/// A transaction that checks `block.timestamp` is evaluated against the
+/// timestamp of the block it is mined in, which is strictly greater than the
+/// one of the latest block.
+#[async_trait]
+impl ConsensusTime for Web3Connector {
+    async fn consensus_time(&self) -> anyhow::Result<Timestamp> {
+        // Without the transactions the node only sends the header fields.
+        let header: BlockHeader = self
+            .client
+            .send(jsonrpc::Request::new("eth_getBlockByNumber", vec![
+                jsonrpc::serialize("latest")?,
+                jsonrpc::serialize(false)?,
+            ]))
+            .await?;
+
+        Ok(Timestamp::from(header.timestamp.low_u32()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    timestamp: U256,
+}
+
 #[async_trait]
 impl BlockByHash for Web3Connector {
     type Block = crate::ethereum::Block;
@@ -13,4 +13,80 @@ pub struct Options {
     /// Display the current version
     #[structopt(short = "V", long = "version")]
     pub version: bool,
+
+    /// Read the passphrase of an encrypted seed file from this file
+    /// descriptor instead of the CND_SEED_PASSPHRASE environment variable or
+    /// a prompt
+    #[structopt(long = "passphrase-fd")]
+    pub passphrase_fd: Option<u32>,
+
+    /// Record the fingerprint of the seed even though the database already
+    /// contains swaps from before fingerprints were recorded
+    #[structopt(long = "trust-seed")]
+    pub trust_seed: bool,
+
+    #[structopt(subcommand)]
+    pub cmd: Option<Command>,
+}
+
+#[derive(structopt::StructOpt, Debug)]
+pub enum Command {
+    /// Back up or restore the seed all keys of this node are derived from
+    Seed(Seed),
+
+    /// Look up the HTLCs of all swaps in the database on chain, print which
+    /// of them can be refunded or redeemed and exit
+    Recover(Recover),
+
+    /// Print all swaps in the database as JSON and exit
+    Export,
+
+    /// Import swaps that were exported with `cnd export` and exit
+    Import {
+        /// The file written by `cnd export`
+        #[structopt(long = "file", parse(from_os_str))]
+        file: PathBuf,
+    },
+}
+
+#[derive(structopt::StructOpt, Debug)]
+pub enum Seed {
+    /// Print the seed as a BIP39 mnemonic and exit
+    ExportMnemonic,
+
+    /// Encrypt an existing plaintext seed file with a passphrase and exit
+    Encrypt,
+
+    /// Write the seed encoded in the given BIP39 mnemonic to the data
+    /// directory and exit
+    Restore {
+        /// The 24 words of the mnemonic, separated by spaces
+        #[structopt(long = "mnemonic")]
+        mnemonic: String,
+    },
+}
+
+#[derive(structopt::StructOpt, Debug)]
+pub struct Recover {
+    /// Also build the refund and redeem transactions
+    #[structopt(long = "build-transactions")]
+    pub build_transactions: bool,
+
+    /// The address refunded or redeemed Bitcoin is sent to
+    #[structopt(long = "bitcoin-address")]
+    pub bitcoin_address: Option<bitcoin::Address>,
+
+    /// The address refunded or redeemed Litecoin is sent to
+    #[structopt(long = "litecoin-address")]
+    pub litecoin_address: Option<String>,
+
+    /// The fee per weight unit of Bitcoin and Litecoin transactions, in
+    /// satoshi or litoshi
+    #[structopt(long = "fee-per-wu")]
+    pub fee_per_wu: Option<usize>,
+
+    /// How long to wait for an HTLC event before assuming it did not happen
+    /// (yet)
+    #[structopt(long = "scan-timeout-secs", default_value = "60")]
+    pub scan_timeout_secs: u64,
 }
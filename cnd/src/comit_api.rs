@@ -1,7 +1,9 @@
 use crate::{
     asset::{self, AssetKind},
     libp2p_comit_ext::{FromHeader, ToHeader},
-    swap_protocols::{ledger::Ethereum, rfc003::messages::Decision, SwapId, SwapProtocol},
+    swap_protocols::{
+        ledger::Ethereum, rfc003::messages::Decision, HashFunction, SwapId, SwapProtocol,
+    },
 };
 use libp2p_comit::frame::Header;
 use serde::de::Error;
@@ -13,9 +15,25 @@ pub enum LedgerKind {
     BitcoinMainnet,
     BitcoinTestnet,
     BitcoinRegtest,
+    LitecoinMainnet,
+    LitecoinTestnet,
+    LitecoinRegtest,
     Ethereum(Ethereum),
 }
 
+impl LedgerKind {
+    /// The Bitcoin network whose rules a Litecoin ledger follows, `None` if
+    /// this is not a Litecoin ledger.
+    pub fn litecoin_network(&self) -> Option<::bitcoin::Network> {
+        match self {
+            LedgerKind::LitecoinMainnet => Some(::bitcoin::Network::Bitcoin),
+            LedgerKind::LitecoinTestnet => Some(::bitcoin::Network::Testnet),
+            LedgerKind::LitecoinRegtest => Some(::bitcoin::Network::Regtest),
+            _ => None,
+        }
+    }
+}
+
 impl FromHeader for LedgerKind {
     fn from_header(mut header: Header) -> Result<Self, serde_json::Error> {
         Ok(match header.value::<String>()?.as_str() {
@@ -29,6 +47,16 @@ impl FromHeader for LedgerKind {
                     ))
                 }
             },
+            "litecoin" => match header.take_parameter::<String>("network")?.as_ref() {
+                "mainnet" => LedgerKind::LitecoinMainnet,
+                "testnet" => LedgerKind::LitecoinTestnet,
+                "regtest" => LedgerKind::LitecoinRegtest,
+                _ => {
+                    return Err(serde_json::Error::custom(
+                        "unexpected litecoin network variant",
+                    ))
+                }
+            },
             "ethereum" => LedgerKind::Ethereum(Ethereum::new(header.take_parameter("chain_id")?)),
             unknown => {
                 return Err(serde_json::Error::custom(format!(
@@ -53,6 +81,15 @@ impl ToHeader for LedgerKind {
             LedgerKind::BitcoinRegtest => {
                 Header::with_str_value("bitcoin").with_parameter("network", "regtest")?
             }
+            LedgerKind::LitecoinMainnet => {
+                Header::with_str_value("litecoin").with_parameter("network", "mainnet")?
+            }
+            LedgerKind::LitecoinTestnet => {
+                Header::with_str_value("litecoin").with_parameter("network", "testnet")?
+            }
+            LedgerKind::LitecoinRegtest => {
+                Header::with_str_value("litecoin").with_parameter("network", "regtest")?
+            }
 
             LedgerKind::Ethereum(ethereum) => {
                 Header::with_str_value("ethereum").with_parameter("chain_id", ethereum.chain_id)?
@@ -76,7 +113,7 @@ impl ToHeader for SwapId {
 impl FromHeader for SwapProtocol {
     fn from_header(mut header: Header) -> Result<Self, serde_json::Error> {
         Ok(match header.value::<String>()?.as_str() {
-            "comit-rfc-003" => SwapProtocol::Rfc003(header.take_parameter("hash_function")?),
+            "comit-rfc-003" => SwapProtocol::Rfc003(take_hash_function(&mut header)?),
             unknown => {
                 return Err(serde_json::Error::custom(format!(
                     "unknown swap protocol: {}",
@@ -87,6 +124,14 @@ impl FromHeader for SwapProtocol {
     }
 }
 
+fn take_hash_function(header: &mut Header) -> Result<HashFunction, serde_json::Error> {
+    let hash_function = header.take_parameter::<String>("hash_function")?;
+
+    HashFunction::from_str(&hash_function).map_err(|_| {
+        serde_json::Error::custom(format!("unsupported hash function: {}", hash_function))
+    })
+}
+
 impl ToHeader for SwapProtocol {
     fn to_header(&self) -> Result<Header, serde_json::Error> {
         Ok(match self {
@@ -203,6 +248,31 @@ mod tests {
         assert_eq!(header, protocol);
     }
 
+    #[test]
+    fn rfc003_swap_protocol_with_sha3_256_from_header() {
+        let header = Header::with_str_value("comit-rfc-003")
+            .with_parameter("hash_function", "SHA3-256")
+            .unwrap();
+
+        let protocol = SwapProtocol::from_header(header).unwrap();
+
+        assert!(matches!(
+            protocol,
+            SwapProtocol::Rfc003(HashFunction::Sha3_256)
+        ));
+    }
+
+    #[test]
+    fn unknown_hash_function_is_rejected() {
+        let header = Header::with_str_value("comit-rfc-003")
+            .with_parameter("hash_function", "BLAKE2b")
+            .unwrap();
+
+        let error = SwapProtocol::from_header(header).unwrap_err();
+
+        assert_eq!(error.to_string(), "unsupported hash function: BLAKE2b");
+    }
+
     #[test]
     fn bitcoin_quantity_to_header() {
         let quantity = asset::Bitcoin::from_sat(100_000_000);
@@ -275,4 +345,40 @@ mod tests {
         assert_eq!(serialized_headers, headers);
         assert_eq!(constructed_ledgerkinds, ledgerkinds);
     }
+
+    #[test]
+    fn litecoin_ledger_to_header_roundtrip() {
+        let ledgerkinds = vec![
+            LedgerKind::LitecoinMainnet,
+            LedgerKind::LitecoinTestnet,
+            LedgerKind::LitecoinRegtest,
+        ];
+
+        let headers = vec![
+            Header::with_str_value("litecoin")
+                .with_parameter("network", "mainnet")
+                .unwrap(),
+            Header::with_str_value("litecoin")
+                .with_parameter("network", "testnet")
+                .unwrap(),
+            Header::with_str_value("litecoin")
+                .with_parameter("network", "regtest")
+                .unwrap(),
+        ];
+
+        let serialized_headers = ledgerkinds
+            .iter()
+            .map(|ledger| ledger.to_header())
+            .collect::<Result<Vec<Header>, serde_json::Error>>()
+            .unwrap();
+
+        let constructed_ledgerkinds = serialized_headers
+            .iter()
+            .map(|header| LedgerKind::from_header(header.clone()))
+            .collect::<Result<Vec<LedgerKind>, serde_json::Error>>()
+            .unwrap();
+
+        assert_eq!(serialized_headers, headers);
+        assert_eq!(constructed_ledgerkinds, ledgerkinds);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    config::{Bitcoind, Data, Network, Parity},
+    config::{Bitcoind, Data, Expiries, Funding, LogFormat, Network, Parity, Watchdog},
     swap_protocols::ledger::ethereum,
 };
 use config as config_rs;
@@ -24,7 +24,16 @@ pub struct File {
     pub logging: Option<Logging>,
     pub bitcoin: Option<Bitcoin>,
     pub ethereum: Option<Ethereum>,
+    /// Ethereum chains other than `ethereum` that swaps may use, one node per
+    /// chain id.
+    pub additional_ethereum_chains: Option<Vec<Ethereum>>,
     pub lightning: Option<Lightning>,
+    pub funding: Option<Funding>,
+    /// Litecoin uses the same node software as Bitcoin, hence it is
+    /// configured the same way.
+    pub litecoin: Option<Bitcoin>,
+    pub expiries: Option<Expiries>,
+    pub watchdog: Option<Watchdog>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -61,7 +70,12 @@ impl File {
             logging: Option::None,
             bitcoin: Option::None,
             ethereum: Option::None,
+            additional_ethereum_chains: Option::None,
             lightning: Option::None,
+            funding: Option::None,
+            litecoin: Option::None,
+            expiries: Option::None,
+            watchdog: Option::None,
         }
     }
 
@@ -77,9 +91,18 @@ impl File {
     }
 }
 
-#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct Logging {
     pub level: Option<Level>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<LogFormat>,
+    /// Per-module directives in the syntax of `RUST_LOG`, e.g.
+    /// `cnd::network=trace,libp2p=warn`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Spans are exported to this OTLP collector, e.g. `localhost:55680`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, PartialEq)]
@@ -151,7 +174,10 @@ pub enum None {
 mod tests {
     use super::*;
     use crate::{
-        config::{Bitcoind, Parity, Settings},
+        config::{
+            Archive, Backend, Bitcoind, ExpiryLedger, ExpiryRules, LedgerPairExpiries, LogFormat,
+            Parity, Settings,
+        },
         swap_protocols::ledger::ethereum,
     };
     use reqwest::Url;
@@ -218,9 +244,17 @@ allowed_origins = "all"
 
 [data]
 dir = "/tmp/comit/"
+backend = "postgres"
+postgres_url = "postgres://cnd@localhost/cnd"
+
+[data.archive]
+after_days = 30
 
 [logging]
 level = "Debug"
+format = "json"
+filter = "cnd::network=trace,libp2p=warn"
+otlp_endpoint = "localhost:55680"
 
 [bitcoin]
 network = "regtest"
@@ -234,16 +268,50 @@ chain_id = 17
 [ethereum.parity]
 node_url = "http://localhost:8545/"
 
+[[additional_ethereum_chains]]
+chain_id = 3
+
+[additional_ethereum_chains.parity]
+node_url = "http://localhost:8546/"
+
 [lightning]
 network = "regtest"
 
 [lightning.lnd]
 rest_api_url = "https://localhost:8080"
 dir = "/foo/bar"
+
+[funding]
+accept_overfunding = false
+
+[litecoin]
+network = "regtest"
+
+[litecoin.bitcoind]
+node_url = "http://localhost:19443/"
+
+[expiries]
+min_delta_secs = 3600
+min_expiry_secs = 600
+
+[[expiries.pairs]]
+alpha_ledger = "bitcoin"
+beta_ledger = "ethereum"
+min_delta_secs = 7200
+min_expiry_secs = 1200
+max_expiry_secs = 604800
+
+[watchdog]
+funding_timeout_percent = 30
+min_redeem_secs = 3600
 "#;
         let file = File {
             network: Some(Network {
                 listen: vec!["/ip4/0.0.0.0/tcp/9939".parse().unwrap()],
+                external_addresses: vec![],
+                upnp: false,
+                request_timeout_secs: 60,
+                request_retries: 0,
             }),
             http_api: Some(HttpApi {
                 socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000),
@@ -253,9 +321,15 @@ dir = "/foo/bar"
             }),
             data: Some(Data {
                 dir: PathBuf::from("/tmp/comit/"),
+                backend: Backend::Postgres,
+                postgres_url: Some("postgres://cnd@localhost/cnd".to_owned()),
+                archive: Some(Archive { after_days: 30 }),
             }),
             logging: Some(Logging {
                 level: Some(Level::Debug),
+                format: Some(LogFormat::Json),
+                filter: Some("cnd::network=trace,libp2p=warn".to_owned()),
+                otlp_endpoint: Some("localhost:55680".to_owned()),
             }),
             bitcoin: Some(Bitcoin {
                 network: bitcoin::Network::Regtest,
@@ -269,6 +343,12 @@ dir = "/foo/bar"
                     node_url: "http://localhost:8545".parse().unwrap(),
                 }),
             }),
+            additional_ethereum_chains: Some(vec![Ethereum {
+                chain_id: ethereum::ChainId::ropsten(),
+                parity: Some(Parity {
+                    node_url: "http://localhost:8546".parse().unwrap(),
+                }),
+            }]),
             lightning: Some(Lightning {
                 network: bitcoin::Network::Regtest,
                 lnd: Some(Lnd {
@@ -276,6 +356,35 @@ dir = "/foo/bar"
                     dir: PathBuf::from("/foo/bar"),
                 }),
             }),
+            funding: Some(Funding {
+                accept_overfunding: false,
+            }),
+            litecoin: Some(Bitcoin {
+                network: bitcoin::Network::Regtest,
+                bitcoind: Some(Bitcoind {
+                    node_url: "http://localhost:19443".parse().unwrap(),
+                }),
+            }),
+            expiries: Some(Expiries {
+                rules: ExpiryRules {
+                    min_delta_secs: 3600,
+                    min_expiry_secs: 600,
+                    max_expiry_secs: None,
+                },
+                pairs: vec![LedgerPairExpiries {
+                    alpha_ledger: ExpiryLedger::Bitcoin,
+                    beta_ledger: ExpiryLedger::Ethereum,
+                    rules: ExpiryRules {
+                        min_delta_secs: 7200,
+                        min_expiry_secs: 1200,
+                        max_expiry_secs: Some(604_800),
+                    },
+                }],
+            }),
+            watchdog: Some(Watchdog {
+                funding_timeout_percent: 30,
+                min_redeem_secs: 3600,
+            }),
         };
 
         let config = toml::from_str::<File>(contents);
@@ -1,6 +1,10 @@
-use crate::config::{
-    default_lnd_cert_path, default_lnd_readonly_macaroon_path, file, Bitcoin, Bitcoind, Data,
-    Ethereum, File, Lightning, Lnd, Network, Parity,
+use crate::{
+    config::{
+        default_lnd_cert_path, default_lnd_readonly_macaroon_path, file, Backend, Bitcoin,
+        Bitcoind, Data, Ethereum, Expiries, File, Funding, Lightning, Lnd, LogFormat, Network,
+        Parity, Watchdog,
+    },
+    swap_protocols::ledger::{self, utxo_chain::ChainParams},
 };
 use anyhow::Context;
 use log::LevelFilter;
@@ -21,32 +25,34 @@ pub struct Settings {
     pub logging: Logging,
     pub bitcoin: Bitcoin,
     pub ethereum: Ethereum,
+    pub additional_ethereum_chains: Vec<Ethereum>,
     pub lightning: Lightning,
+    pub funding: Funding,
+    /// Only set if the config file has a `[litecoin]` section.
+    pub litecoin: Option<Bitcoin>,
+    pub expiries: Expiries,
+    pub watchdog: Watchdog,
 }
 
 fn derive_url_bitcoin(bitcoin: Option<file::Bitcoin>) -> Bitcoin {
     match bitcoin {
         None => Bitcoin::default(),
-        Some(bitcoin) => {
-            let node_url = match bitcoin.bitcoind {
-                Some(bitcoind) => bitcoind.node_url,
-                None => match bitcoin.network {
-                    bitcoin::Network::Bitcoin => "http://localhost:8332"
-                        .parse()
-                        .expect("to be valid static string"),
-                    bitcoin::Network::Testnet => "http://localhost:18332"
-                        .parse()
-                        .expect("to be valid static string"),
-                    bitcoin::Network::Regtest => "http://localhost:18443"
-                        .parse()
-                        .expect("to be valid static string"),
-                },
-            };
-            Bitcoin {
-                network: bitcoin.network,
-                bitcoind: Bitcoind { node_url },
-            }
-        }
+        Some(bitcoin) => derive_url_utxo_chain(bitcoin, ledger::bitcoin::chain_params),
+    }
+}
+
+fn derive_url_utxo_chain(
+    chain: file::Bitcoin,
+    chain_params: fn(bitcoin::Network) -> ChainParams,
+) -> Bitcoin {
+    let node_url = match chain.bitcoind {
+        Some(bitcoind) => bitcoind.node_url,
+        None => chain_params(chain.network).default_node_url(),
+    };
+
+    Bitcoin {
+        network: chain.network,
+        bitcoind: Bitcoind { node_url },
     }
 }
 
@@ -85,10 +91,21 @@ impl From<Settings> for File {
             network,
             http_api: HttpApi { socket, cors },
             data,
-            logging: Logging { level },
+            logging:
+                Logging {
+                    level,
+                    format,
+                    filter,
+                    otlp_endpoint,
+                },
             bitcoin,
             ethereum,
+            additional_ethereum_chains,
             lightning,
+            funding,
+            litecoin,
+            expiries,
+            watchdog,
         } = settings;
 
         File {
@@ -106,10 +123,23 @@ impl From<Settings> for File {
             data: Some(data),
             logging: Some(file::Logging {
                 level: Some(level.into()),
+                format: Some(format),
+                filter,
+                otlp_endpoint,
             }),
             bitcoin: Some(bitcoin.into()),
             ethereum: Some(ethereum.into()),
+            additional_ethereum_chains: Some(
+                additional_ethereum_chains
+                    .into_iter()
+                    .map(file::Ethereum::from)
+                    .collect(),
+            ),
             lightning: Some(lightning.into()),
+            funding: Some(funding),
+            litecoin: litecoin.map(file::Bitcoin::from),
+            expiries: Some(expiries),
+            watchdog: Some(watchdog),
         }
     }
 }
@@ -149,11 +179,17 @@ pub enum AllowedOrigins {
     Some(Vec<String>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, derivative::Derivative)]
+#[derive(Clone, Debug, PartialEq, derivative::Derivative)]
 #[derivative(Default)]
 pub struct Logging {
     #[derivative(Default(value = "LevelFilter::Info"))]
     pub level: LevelFilter,
+    pub format: LogFormat,
+    /// Directives in the syntax of `RUST_LOG` that override `level` for
+    /// individual modules.
+    pub filter: Option<String>,
+    /// The OTLP collector spans are exported to.
+    pub otlp_endpoint: Option<String>,
 }
 
 impl Settings {
@@ -165,7 +201,12 @@ impl Settings {
             logging,
             bitcoin,
             ethereum,
+            additional_ethereum_chains,
             lightning,
+            funding,
+            litecoin,
+            expiries,
+            watchdog,
         } = config_file;
 
         Ok(Self {
@@ -176,6 +217,10 @@ impl Settings {
 
                 Network {
                     listen: vec![default_socket],
+                    external_addresses: vec![],
+                    upnp: false,
+                    request_timeout_secs: 60,
+                    request_retries: 0,
                 }
             }),
             http_api: http_api
@@ -202,22 +247,35 @@ impl Settings {
                     crate::data_dir().context("unable to determine default data path")?;
                 data.unwrap_or_else(|| Data {
                     dir: default_data_dir,
+                    backend: Backend::default(),
+                    postgres_url: None,
+                    archive: None,
                 })
             },
 
             logging: {
                 match logging {
                     None => Logging::default(),
-                    Some(inner) => match inner {
-                        file::Logging { level: None } => Logging::default(),
-                        file::Logging { level: Some(level) } => Logging {
-                            level: level.into(),
-                        },
+                    Some(file::Logging {
+                        level,
+                        format,
+                        filter,
+                        otlp_endpoint,
+                    }) => Logging {
+                        level: level.map(LevelFilter::from).unwrap_or(LevelFilter::Info),
+                        format: format.unwrap_or_default(),
+                        filter,
+                        otlp_endpoint,
                     },
                 }
             },
             bitcoin: derive_url_bitcoin(bitcoin),
             ethereum: derive_url_ethereum(ethereum),
+            additional_ethereum_chains: additional_ethereum_chains
+                .unwrap_or_default()
+                .into_iter()
+                .map(|ethereum| derive_url_ethereum(Some(ethereum)))
+                .collect(),
             lightning: match lightning {
                 None => Lightning::default(),
                 Some(lightning) => Lightning {
@@ -236,6 +294,11 @@ impl Settings {
                     },
                 },
             },
+            funding: funding.unwrap_or_default(),
+            litecoin: litecoin
+                .map(|litecoin| derive_url_utxo_chain(litecoin, ledger::litecoin::chain_params)),
+            expiries: expiries.unwrap_or_default(),
+            watchdog: watchdog.unwrap_or_default(),
         })
     }
 }
@@ -262,6 +325,9 @@ mod tests {
             .map(|settings| &settings.logging)
             .is_equal_to(Logging {
                 level: LevelFilter::Info,
+                format: LogFormat::Text,
+                filter: None,
+                otlp_endpoint: None,
             })
     }
 
@@ -319,6 +385,10 @@ mod tests {
             .map(|settings| &settings.network)
             .is_equal_to(Network {
                 listen: vec!["/ip4/0.0.0.0/tcp/9939".parse().unwrap()],
+                external_addresses: vec![],
+                upnp: false,
+                request_timeout_secs: 60,
+                request_retries: 0,
             })
     }
 
@@ -419,6 +489,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn additional_ethereum_chains_default_node_url() {
+        let config_file = File {
+            additional_ethereum_chains: Some(vec![file::Ethereum {
+                chain_id: ethereum::ChainId::ropsten(),
+                parity: None,
+            }]),
+            ..File::default()
+        };
+
+        let settings = Settings::from_config_file_and_defaults(config_file);
+
+        assert_that(&settings)
+            .is_ok()
+            .map(|settings| &settings.additional_ethereum_chains)
+            .is_equal_to(vec![Ethereum {
+                chain_id: ethereum::ChainId::ropsten(),
+                parity: Parity {
+                    node_url: "http://localhost:8545".parse().unwrap(),
+                },
+            }])
+    }
+
+    #[test]
+    fn litecoin_defaults_node_url_per_network() {
+        let defaults = vec![
+            (bitcoin::Network::Bitcoin, "http://localhost:9332"),
+            (bitcoin::Network::Testnet, "http://localhost:19332"),
+            (bitcoin::Network::Regtest, "http://localhost:19443"),
+        ];
+
+        for (network, url) in defaults {
+            let config_file = File {
+                litecoin: Some(file::Bitcoin {
+                    network,
+                    bitcoind: None,
+                }),
+                ..File::default()
+            };
+
+            let settings = Settings::from_config_file_and_defaults(config_file);
+
+            assert_that(&settings)
+                .is_ok()
+                .map(|settings| &settings.litecoin)
+                .is_equal_to(Some(Bitcoin {
+                    network,
+                    bitcoind: Bitcoind {
+                        node_url: url.parse().unwrap(),
+                    },
+                }))
+        }
+    }
+
+    #[test]
+    fn litecoin_is_optional() {
+        let settings = Settings::from_config_file_and_defaults(File::default());
+
+        assert_that(&settings)
+            .is_ok()
+            .map(|settings| &settings.litecoin)
+            .is_none();
+    }
+
+    #[test]
+    fn funding_section_defaults_to_accepting_overfunding() {
+        let config_file = File {
+            funding: None,
+            ..File::default()
+        };
+
+        let settings = Settings::from_config_file_and_defaults(config_file);
+
+        assert_that(&settings)
+            .is_ok()
+            .map(|settings| &settings.funding)
+            .is_equal_to(Funding {
+                accept_overfunding: true,
+            })
+    }
+
     #[test]
     fn lightning_section_defaults() {
         let config_file = File {
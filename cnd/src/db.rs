@@ -1,8 +1,15 @@
+#[macro_use]
+mod connection;
+mod archive;
+mod export;
 #[cfg(test)]
 mod integration_tests;
+mod ledger_events;
 mod load_swaps;
+mod rows;
 mod save;
 mod schema;
+mod seed_fingerprint;
 mod wrapper_types;
 #[macro_use]
 mod swap;
@@ -10,9 +17,12 @@ mod swap;
 mod swap_types;
 #[macro_use]
 pub mod with_swap_types;
-embed_migrations!("./migrations");
 
 pub use self::{
+    archive::{ArchivedSwap, FinalStatus},
+    connection::Connection,
+    export::{LedgerTransactions, SwapDocument, UnsupportedVersion, EXPORT_VERSION},
+    ledger_events::{LedgerEvent, LoadLedgerEvents, TransactionId},
     load_swaps::{AcceptedSwap, LoadAcceptedSwap},
     save::*,
     swap::*,
@@ -20,30 +30,43 @@ pub use self::{
 };
 
 use crate::{
-    db::wrapper_types::custom_sql_types::Text,
-    swap_protocols::{Role, SwapId},
+    config::{self, Backend},
+    db::connection::Pool,
 };
-use diesel::{self, prelude::*, sqlite::SqliteConnection};
+use anyhow::Context;
 use std::{
     ffi::OsStr,
     path::{Path, PathBuf},
-    sync::Arc,
 };
-use tokio::sync::Mutex;
 
-/// This module provides persistent storage by way of Sqlite.
+/// This module provides persistent storage by way of SQLite or PostgreSQL.
 
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
-pub struct Sqlite {
+pub struct Database {
     #[derivative(Debug = "ignore")]
-    connection: Arc<Mutex<SqliteConnection>>,
+    pool: Pool,
 }
 
-impl Sqlite {
+impl Database {
+    /// Return a handle to the database configured in the `[data]` section.
+    pub fn open(data: &config::Data) -> anyhow::Result<Self> {
+        match data.backend {
+            Backend::Sqlite => Database::new_in_dir(&data.dir),
+            Backend::Postgres => {
+                let url = data
+                    .postgres_url
+                    .as_ref()
+                    .context("`postgres_url` is required for the postgres backend")?;
+
+                Database::new_postgres(url)
+            }
+        }
+    }
+
     /// Return a handle that can be used to access the database.
     ///
-    /// When this returns, an Sqlite database file 'cnd.sql' exists in 'dir', a
+    /// When this returns, an SQLite database file 'cnd.sql' exists in 'dir', a
     /// successful connection to the database has been made, and the database
     /// migrations have been run.
     pub fn new_in_dir<D>(dir: D) -> anyhow::Result<Self>
@@ -52,57 +75,78 @@ impl Sqlite {
     {
         let dir = Path::new(&dir);
         let path = db_path_from_dir(dir);
-        Sqlite::new(&path)
+        Database::new(&path)
     }
 
     /// Return a handle that can be used to access the database.
     ///
     /// Reads or creates an SQLite database file at 'file'.  When this returns
-    /// an Sqlite database exists, a successful connection to the database has
+    /// an SQLite database exists, a successful connection to the database has
     /// been made, and the database migrations have been run.
     pub fn new(file: &Path) -> anyhow::Result<Self> {
         ensure_folder_tree_exists(file)?;
 
-        let connection = SqliteConnection::establish(&format!("file:{}", file.display()))?;
-        embedded_migrations::run(&connection)?;
+        let in_memory = file == Path::new(":memory:");
+        let pool = Pool::sqlite(&format!("file:{}", file.display()), in_memory)?;
 
         tracing::info!("SQLite database file: {}", file.display());
 
-        Ok(Sqlite {
-            connection: Arc::new(Mutex::new(connection)),
-        })
+        Ok(Database { pool })
     }
 
-    async fn do_in_transaction<F, T, E>(&self, f: F) -> Result<T, E>
+    /// Return a handle that can be used to access the database.
+    ///
+    /// Connects to the PostgreSQL database at 'url'. When this returns a
+    /// successful connection to the database has been made and the database
+    /// migrations have been run.
+    pub fn new_postgres(url: &str) -> anyhow::Result<Self> {
+        let pool = Pool::postgres(url)?;
+
+        tracing::info!("connected to PostgreSQL database");
+
+        Ok(Database { pool })
+    }
+
+    /// Runs `f` in a transaction on a connection from the pool.
+    ///
+    /// Diesel blocks, hence this happens on the thread pool for blocking
+    /// tasks instead of the threads of the async runtime.
+    async fn do_in_transaction<F, T, E>(&self, f: F) -> anyhow::Result<T>
     where
-        F: Fn(&SqliteConnection) -> Result<T, E>,
-        E: From<diesel::result::Error>,
+        F: FnOnce(&Connection) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<diesel::result::Error> + Send + 'static,
+        anyhow::Error: From<E>,
     {
-        let guard = self.connection.lock().await;
-        let connection = &*guard;
+        let pool = self.pool.clone();
 
-        let result = connection.transaction(|| f(&connection))?;
+        tokio::task::spawn_blocking(move || -> anyhow::Result<T> {
+            let connection = pool.get()?;
+            let result = connection.transaction(|| f(&connection))?;
 
-        Ok(result)
+            Ok(result)
+        })
+        .await?
     }
 
-    async fn role(&self, key: &SwapId) -> anyhow::Result<Role> {
-        use self::schema::rfc003_swaps as swaps;
-
-        let record: QueryableSwapRole = self
-            .do_in_transaction(|connection| {
-                let key = Text(key);
+    /// Like [`Database::do_in_transaction`] but for transactions that write,
+    /// see [`Connection::write_transaction`].
+    async fn do_in_write_transaction<F, T, E>(&self, f: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T, E> + Send + 'static,
+        T: Send + 'static,
+        E: From<diesel::result::Error> + Send + 'static,
+        anyhow::Error: From<E>,
+    {
+        let pool = self.pool.clone();
 
-                swaps::table
-                    .filter(swaps::swap_id.eq(key))
-                    .select((swaps::swap_id, swaps::role))
-                    .first(connection)
-                    .optional()
-            })
-            .await?
-            .ok_or(Error::SwapNotFound)?;
+        tokio::task::spawn_blocking(move || -> anyhow::Result<T> {
+            let connection = pool.get()?;
+            let result = connection.write_transaction(|| f(&connection))?;
 
-        Ok(*record.role)
+            Ok(result)
+        })
+        .await?
     }
 }
 
@@ -120,23 +164,31 @@ fn ensure_folder_tree_exists(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-#[derive(Queryable, Debug, Clone, PartialEq)]
-struct QueryableSwapRole {
-    pub swap_id: Text<SwapId>,
-    pub role: Text<Role>,
-}
-
 #[derive(Debug, Clone, Copy, thiserror::Error)]
 pub enum Error {
     #[error("swap not found")]
     SwapNotFound,
+    #[error("request of the swap not found")]
+    RequestNotFound,
+    #[error("swap has not been accepted")]
+    SwapNotAccepted,
+    #[error("the seed differs from the one the swaps in the database were created with")]
+    SeedMismatch,
+    #[error("the database already contains swaps but no seed fingerprint, pass --trust-seed if the seed is the one they were created with")]
+    UnverifiableSeed,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        db::wrapper_types::custom_sql_types::Text,
+        swap_protocols::{Role, SwapId},
+    };
+    use diesel::prelude::*;
+    use libp2p::PeerId;
     use spectral::prelude::*;
-    use std::path::PathBuf;
+    use std::{path::PathBuf, time::Duration};
 
     fn temp_db() -> PathBuf {
         let temp_file = tempfile::Builder::new()
@@ -151,7 +203,7 @@ mod tests {
     fn can_create_a_new_temp_db() {
         let path = temp_db();
 
-        let db = Sqlite::new(&path);
+        let db = Database::new(&path);
 
         assert_that(&db).is_ok();
     }
@@ -162,7 +214,7 @@ mod tests {
         // validate assumptions: the db does not exist yet
         assert_that(&path.as_path()).does_not_exist();
 
-        let db = Sqlite::new(&path);
+        let db = Database::new(&path);
 
         assert_that(&db).is_ok();
         assert_that(&path.as_path()).exists();
@@ -184,9 +236,42 @@ mod tests {
         assert_that(&path).does_not_exist();
         assert_that(&path.parent()).is_some().does_not_exist();
 
-        let db = Sqlite::new(&path);
+        let db = Database::new(&path);
 
         assert_that(&db).is_ok();
         assert_that(&path).exists();
     }
+
+    #[tokio::test]
+    async fn concurrent_read_then_write_transactions_do_not_fail() {
+        let db = Database::new(&temp_db()).unwrap();
+
+        let writers = (0..8).map(|_| {
+            db.do_in_write_transaction(|connection| {
+                with_connection!(connection => {
+                    let before = schema::swaps::table
+                        .count()
+                        .get_result::<i64>(connection)?;
+
+                    // let the other writers read before this one writes
+                    std::thread::sleep(Duration::from_millis(50));
+
+                    diesel::insert_into(schema::swaps::table)
+                        .values((
+                            schema::swaps::swap_id.eq(Text(SwapId::default())),
+                            schema::swaps::role.eq(Text(Role::Alice)),
+                            schema::swaps::counterparty.eq(Text(PeerId::random())),
+                        ))
+                        .execute(connection)?;
+
+                    Ok::<_, diesel::result::Error>(before)
+                })
+            })
+        });
+
+        let mut counts = futures::future::try_join_all(writers).await.unwrap();
+        counts.sort();
+
+        assert_that(&counts).is_equal_to((0..8).collect::<Vec<i64>>());
+    }
 }
@@ -0,0 +1,511 @@
+use crate::{
+    db::{
+        load_swaps,
+        rows::{
+            load_swap_rows, AssetRow, LedgerRow, StoredAsset, StoredIdentity, StoredLedger,
+            SwapRow, SwapRows,
+        },
+        schema::{
+            archived_ledger_events, archived_swap_assets, archived_swap_declines,
+            archived_swap_ledgers, archived_swaps, rfc003_ledger_events, swap_assets,
+            swap_declines, swap_ledgers, swaps,
+        },
+        wrapper_types::custom_sql_types::Text,
+        Connection, Database, Error, Swap, SwapTypes,
+    },
+    swap_protocols::{rfc003::Request, LedgerSide, SwapId},
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use strum_macros::{Display, EnumString};
+
+/// How a swap ended.
+///
+/// Swaps with a final status no longer need their ledgers to be watched and
+/// are moved to the archive tables once they are old enough.
+#[derive(Clone, Copy, Debug, Display, EnumString, PartialEq)]
+pub enum FinalStatus {
+    /// Both parties redeemed.
+    Swapped,
+    /// The swap was declined or both HTLCs expired before any was funded.
+    NotSwapped,
+    /// At least one party refunded.
+    Refunded,
+    /// The swap request could not be sent or was not answered.
+    Failed,
+}
+
+impl FinalStatus {
+    /// Determines the final status from the names of the events observed on
+    /// the alpha and beta ledger of an accepted swap and whether both of its
+    /// HTLCs have expired.
+    ///
+    /// Returns `None` as long as more events may happen.
+    fn from_events(alpha: &[&str], beta: &[&str], expired: bool) -> Option<Self> {
+        let redeemed = |events: &[&str]| events.contains(&"Redeemed");
+        let refunded = |events: &[&str]| events.contains(&"Refunded");
+        let funded = |events: &[&str]| events.contains(&"Funded");
+        let settled = |events: &[&str]| redeemed(events) || refunded(events);
+
+        if redeemed(alpha) && redeemed(beta) {
+            return Some(FinalStatus::Swapped);
+        }
+
+        if expired && !funded(alpha) && !funded(beta) {
+            return Some(FinalStatus::NotSwapped);
+        }
+
+        let alpha_done = settled(alpha) || (!funded(alpha) && refunded(beta));
+        let beta_done = settled(beta) || (!funded(beta) && refunded(alpha));
+
+        if alpha_done && beta_done {
+            Some(FinalStatus::Refunded)
+        } else {
+            None
+        }
+    }
+}
+
+/// A swap that has been moved to the archive tables.
+#[derive(Debug, Clone)]
+pub struct ArchivedSwap {
+    pub swap: Swap,
+    pub types: SwapTypes,
+    pub final_status: FinalStatus,
+    pub finished_at: NaiveDateTime,
+    rows: SwapRows,
+}
+
+impl ArchivedSwap {
+    pub fn request<AL, BL, AA, BA, AI, BI>(&self) -> anyhow::Result<Request<AL, BL, AA, BA, AI, BI>>
+    where
+        AL: StoredLedger,
+        BL: StoredLedger,
+        AA: StoredAsset,
+        BA: StoredAsset,
+        AI: StoredIdentity,
+        BI: StoredIdentity,
+    {
+        load_swaps::request(&self.rows)
+    }
+}
+
+impl Database {
+    /// Returns the final status of a swap, `None` if it is still in progress.
+    pub async fn final_status(&self, swap_id: &SwapId) -> anyhow::Result<Option<FinalStatus>> {
+        let key = Text(*swap_id);
+
+        let final_status: Option<Text<FinalStatus>> = self
+            .do_in_transaction(move |connection| {
+                with_connection!(connection => {
+                    swaps::table
+                        .filter(swaps::swap_id.eq(key))
+                        .select(swaps::final_status)
+                        .first(connection)
+                        .optional()
+                })
+            })
+            .await?
+            .ok_or(Error::SwapNotFound)?;
+
+        Ok(final_status.map(|status| *status))
+    }
+
+    /// Records that the request of a swap could not be sent or was not
+    /// answered.
+    pub async fn mark_failed(&self, swap_id: &SwapId) -> anyhow::Result<()> {
+        let key = Text(*swap_id);
+
+        self.do_in_write_transaction(move |connection| {
+            with_connection!(connection => {
+                diesel::update(
+                    swaps::table
+                        .filter(swaps::swap_id.eq(key))
+                        .filter(swaps::final_status.is_null()),
+                )
+                .set((
+                    swaps::final_status.eq(Text(FinalStatus::Failed)),
+                    swaps::finished_at.eq(Utc::now().naive_utc()),
+                ))
+                .execute(connection)
+            })
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Sets the final status of the swaps that finished before their final
+    /// status was recorded, returns how many there were.
+    pub async fn mark_finished_swaps(&self) -> anyhow::Result<usize> {
+        self.do_in_write_transaction(move |connection| {
+            let unfinished: Vec<Text<SwapId>> = with_connection!(connection => {
+                swaps::table
+                    .filter(swaps::final_status.is_null())
+                    .select(swaps::swap_id)
+                    .load(connection)
+            })?;
+
+            let mut marked = 0;
+            for swap_id in &unfinished {
+                if mark_if_finished(connection, swap_id)?.is_some() {
+                    marked += 1;
+                }
+            }
+
+            Ok::<_, diesel::result::Error>(marked)
+        })
+        .await
+    }
+
+    /// Moves the swaps that finished before `finished_before` to the archive
+    /// tables, returns how many were moved.
+    pub async fn archive_finished_swaps(
+        &self,
+        finished_before: NaiveDateTime,
+    ) -> anyhow::Result<usize> {
+        self.do_in_write_transaction(move |connection| {
+            let finished: Vec<(
+                Text<SwapId>,
+                Option<Text<FinalStatus>>,
+                Option<NaiveDateTime>,
+            )> = with_connection!(connection => {
+                swaps::table
+                    .filter(swaps::final_status.is_not_null())
+                    .filter(swaps::finished_at.lt(finished_before))
+                    .select((swaps::swap_id, swaps::final_status, swaps::finished_at))
+                    .load(connection)
+            })?;
+
+            let mut archived = 0;
+            for (swap_id, final_status, finished_at) in finished {
+                let (final_status, finished_at) = match (final_status, finished_at) {
+                    (Some(final_status), Some(finished_at)) => (final_status, finished_at),
+                    _ => continue,
+                };
+                let rows = match load_swap_rows(connection, &swap_id)? {
+                    Some(rows) => rows,
+                    None => continue,
+                };
+
+                archive_swap_rows(connection, &rows, final_status, finished_at)?;
+                archived += 1;
+            }
+
+            Ok::<_, diesel::result::Error>(archived)
+        })
+        .await
+    }
+
+    /// Loads all swaps from the archive tables.
+    pub async fn archived_swaps(&self) -> anyhow::Result<Vec<ArchivedSwap>> {
+        let archived = self
+            .do_in_transaction(move |connection| {
+                with_connection!(connection => {
+                    let swaps = archived_swaps::table
+                        .order(archived_swaps::id.asc())
+                        .select((
+                            (
+                                archived_swaps::swap_id,
+                                archived_swaps::role,
+                                archived_swaps::counterparty,
+                                archived_swaps::hash_function,
+                                archived_swaps::secret_hash,
+                                archived_swaps::accepted_at,
+                            ),
+                            archived_swaps::final_status,
+                            archived_swaps::finished_at,
+                        ))
+                        .load::<(SwapRow, Text<FinalStatus>, NaiveDateTime)>(connection)?;
+
+                    let ledgers = archived_swap_ledgers::table
+                        .select((
+                            archived_swap_ledgers::swap_id,
+                            archived_swap_ledgers::side,
+                            archived_swap_ledgers::ledger,
+                            archived_swap_ledgers::bitcoin_network,
+                            archived_swap_ledgers::ethereum_chain_id,
+                            archived_swap_ledgers::expiry,
+                            archived_swap_ledgers::redeem_identity,
+                            archived_swap_ledgers::refund_identity,
+                        ))
+                        .load::<LedgerRow>(connection)?;
+
+                    let assets = archived_swap_assets::table
+                        .select((
+                            archived_swap_assets::swap_id,
+                            archived_swap_assets::side,
+                            archived_swap_assets::asset,
+                            archived_swap_assets::quantity,
+                            archived_swap_assets::token_contract,
+                        ))
+                        .load::<AssetRow>(connection)?;
+
+                    Ok::<_, diesel::result::Error>((swaps, ledgers, assets))
+                })
+            })
+            .await?;
+
+        let (swaps, ledgers, assets) = archived;
+
+        swaps
+            .into_iter()
+            .map(|(swap, final_status, finished_at)| {
+                let rows = SwapRows {
+                    ledgers: ledgers
+                        .iter()
+                        .filter(|row| row.swap_id == swap.swap_id)
+                        .cloned()
+                        .collect(),
+                    assets: assets
+                        .iter()
+                        .filter(|row| row.swap_id == swap.swap_id)
+                        .cloned()
+                        .collect(),
+                    swap,
+                };
+
+                Ok(ArchivedSwap {
+                    swap: Swap::new(
+                        *rows.swap.swap_id,
+                        *rows.swap.role,
+                        rows.swap.counterparty.0.clone(),
+                    ),
+                    types: rows.swap_types()?,
+                    final_status: *final_status,
+                    finished_at,
+                    rows,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Records the final status of a swap if it has finished, returns the final
+/// status.
+///
+/// A swap has finished if it was declined or if no more events can happen on
+/// its ledgers, e.g. because both HTLCs expired without being funded. The
+/// final status of a swap never changes once it is set.
+pub fn mark_if_finished(
+    connection: &Connection,
+    swap_id: &Text<SwapId>,
+) -> QueryResult<Option<FinalStatus>> {
+    with_connection!(connection => {
+        let declined = swap_declines::table
+            .filter(swap_declines::swap_id.eq(swap_id))
+            .count()
+            .get_result::<i64>(connection)?
+            > 0;
+
+        let events = rfc003_ledger_events::table
+            .filter(rfc003_ledger_events::swap_id.eq(swap_id))
+            .select((
+                rfc003_ledger_events::ledger,
+                rfc003_ledger_events::event,
+                rfc003_ledger_events::at,
+            ))
+            .load::<(Text<LedgerSide>, String, NaiveDateTime)>(connection)?;
+
+        let names = |side| {
+            events
+                .iter()
+                .filter(|(ledger, ..)| **ledger == side)
+                .map(|(_, event, _)| event.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let expiries = swap_ledgers::table
+            .filter(swap_ledgers::swap_id.eq(swap_id))
+            .select(swap_ledgers::expiry)
+            .load::<i64>(connection)?;
+        let now = Utc::now().timestamp();
+        let expired = expiries.len() == 2 && expiries.iter().all(|expiry| *expiry < now);
+
+        let final_status = if declined {
+            Some(FinalStatus::NotSwapped)
+        } else {
+            FinalStatus::from_events(
+                &names(LedgerSide::Alpha),
+                &names(LedgerSide::Beta),
+                expired,
+            )
+        };
+
+        let final_status = match final_status {
+            Some(final_status) => final_status,
+            None => return Ok(None),
+        };
+        let finished_at = events
+            .iter()
+            .map(|(.., at)| *at)
+            .max()
+            .unwrap_or_else(|| Utc::now().naive_utc());
+
+        diesel::update(
+            swaps::table
+                .filter(swaps::swap_id.eq(swap_id))
+                .filter(swaps::final_status.is_null()),
+        )
+        .set((
+            swaps::final_status.eq(Text(final_status)),
+            swaps::finished_at.eq(finished_at),
+        ))
+        .execute(connection)?;
+
+        Ok(Some(final_status))
+    })
+}
+
+fn archive_swap_rows(
+    connection: &Connection,
+    rows: &SwapRows,
+    final_status: Text<FinalStatus>,
+    finished_at: NaiveDateTime,
+) -> QueryResult<()> {
+    let swap = &rows.swap;
+    let swap_id = &swap.swap_id;
+
+    with_connection!(connection => {
+        diesel::insert_into(archived_swaps::table)
+            .values((
+                archived_swaps::swap_id.eq(&swap.swap_id),
+                archived_swaps::role.eq(&swap.role),
+                archived_swaps::counterparty.eq(&swap.counterparty),
+                archived_swaps::hash_function.eq(&swap.hash_function),
+                archived_swaps::secret_hash.eq(&swap.secret_hash),
+                archived_swaps::accepted_at.eq(&swap.accepted_at),
+                archived_swaps::final_status.eq(final_status),
+                archived_swaps::finished_at.eq(finished_at),
+            ))
+            .execute(connection)?;
+
+        for ledger in &rows.ledgers {
+            diesel::insert_into(archived_swap_ledgers::table)
+                .values((
+                    archived_swap_ledgers::swap_id.eq(&ledger.swap_id),
+                    archived_swap_ledgers::side.eq(&ledger.side),
+                    archived_swap_ledgers::ledger.eq(&ledger.ledger),
+                    archived_swap_ledgers::bitcoin_network.eq(&ledger.bitcoin_network),
+                    archived_swap_ledgers::ethereum_chain_id.eq(&ledger.ethereum_chain_id),
+                    archived_swap_ledgers::expiry.eq(&ledger.expiry),
+                    archived_swap_ledgers::redeem_identity.eq(&ledger.redeem_identity),
+                    archived_swap_ledgers::refund_identity.eq(&ledger.refund_identity),
+                ))
+                .execute(connection)?;
+        }
+
+        for asset in &rows.assets {
+            diesel::insert_into(archived_swap_assets::table)
+                .values((
+                    archived_swap_assets::swap_id.eq(&asset.swap_id),
+                    archived_swap_assets::side.eq(&asset.side),
+                    archived_swap_assets::asset.eq(&asset.asset),
+                    archived_swap_assets::quantity.eq(&asset.quantity),
+                    archived_swap_assets::token_contract.eq(&asset.token_contract),
+                ))
+                .execute(connection)?;
+        }
+
+        let declines = swap_declines::table
+            .filter(swap_declines::swap_id.eq(swap_id))
+            .select(swap_declines::reason)
+            .load::<Option<String>>(connection)?;
+        for reason in declines {
+            diesel::insert_into(archived_swap_declines::table)
+                .values((
+                    archived_swap_declines::swap_id.eq(swap_id),
+                    archived_swap_declines::reason.eq(reason),
+                ))
+                .execute(connection)?;
+        }
+
+        let events = rfc003_ledger_events::table
+            .filter(rfc003_ledger_events::swap_id.eq(swap_id))
+            .order(rfc003_ledger_events::id.asc())
+            .select((
+                rfc003_ledger_events::ledger,
+                rfc003_ledger_events::event,
+                rfc003_ledger_events::transaction_id,
+                rfc003_ledger_events::data,
+                rfc003_ledger_events::at,
+            ))
+            .load::<(String, String, String, String, NaiveDateTime)>(connection)?;
+        for (ledger, event, transaction_id, data, at) in events {
+            diesel::insert_into(archived_ledger_events::table)
+                .values((
+                    archived_ledger_events::swap_id.eq(swap_id),
+                    archived_ledger_events::ledger.eq(ledger),
+                    archived_ledger_events::event.eq(event),
+                    archived_ledger_events::transaction_id.eq(transaction_id),
+                    archived_ledger_events::data.eq(data),
+                    archived_ledger_events::at.eq(at),
+                ))
+                .execute(connection)?;
+        }
+
+        diesel::delete(swap_declines::table.filter(swap_declines::swap_id.eq(swap_id)))
+            .execute(connection)?;
+        diesel::delete(
+            rfc003_ledger_events::table.filter(rfc003_ledger_events::swap_id.eq(swap_id)),
+        )
+        .execute(connection)?;
+        diesel::delete(swap_assets::table.filter(swap_assets::swap_id.eq(swap_id)))
+            .execute(connection)?;
+        diesel::delete(swap_ledgers::table.filter(swap_ledgers::swap_id.eq(swap_id)))
+            .execute(connection)?;
+        diesel::delete(swaps::table.filter(swaps::swap_id.eq(swap_id))).execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn swap_is_finished_once_no_more_events_can_happen() {
+        let funded = ["Deployed", "Funded"];
+
+        assert_that(&FinalStatus::from_events(
+            &["Deployed", "Funded", "Redeemed"],
+            &["Deployed", "Funded", "Redeemed"],
+            false,
+        ))
+        .is_equal_to(Some(FinalStatus::Swapped));
+        assert_that(&FinalStatus::from_events(
+            &["Deployed", "Funded", "Refunded"],
+            &["Deployed", "Funded", "Refunded"],
+            false,
+        ))
+        .is_equal_to(Some(FinalStatus::Refunded));
+        assert_that(&FinalStatus::from_events(
+            &["Deployed", "Funded", "Refunded"],
+            &[],
+            false,
+        ))
+        .is_equal_to(Some(FinalStatus::Refunded));
+        assert_that(&FinalStatus::from_events(
+            &["Deployed", "Funded", "Redeemed"],
+            &funded,
+            false,
+        ))
+        .is_none();
+        assert_that(&FinalStatus::from_events(&funded, &[], false)).is_none();
+    }
+
+    #[test]
+    fn expired_swap_without_funding_is_not_swapped() {
+        assert_that(&FinalStatus::from_events(&["Deployed"], &[], true))
+            .is_equal_to(Some(FinalStatus::NotSwapped));
+        assert_that(&FinalStatus::from_events(&[], &[], false)).is_none();
+        assert_that(&FinalStatus::from_events(
+            &["Deployed", "Funded"],
+            &[],
+            true,
+        ))
+        .is_none();
+    }
+}
@@ -0,0 +1,138 @@
+use diesel::{
+    connection::SimpleConnection,
+    pg::PgConnection,
+    r2d2::{self, ConnectionManager, CustomizeConnection, PooledConnection},
+    sqlite::SqliteConnection,
+    Connection as _,
+};
+use std::time::Duration;
+
+mod sqlite {
+    embed_migrations!("./migrations/sqlite");
+
+    pub use self::embedded_migrations::run as run_migrations;
+}
+
+mod postgres {
+    embed_migrations!("./migrations/postgres");
+
+    pub use self::embedded_migrations::run as run_migrations;
+}
+
+/// How long an SQLite connection waits for another one to finish writing.
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A pool of connections to one of the database backends we support.
+#[derive(Clone)]
+pub enum Pool {
+    Sqlite(r2d2::Pool<ConnectionManager<SqliteConnection>>),
+    Postgres(r2d2::Pool<ConnectionManager<PgConnection>>),
+}
+
+/// A connection taken from a [`Pool`].
+pub enum Connection {
+    Sqlite(PooledConnection<ConnectionManager<SqliteConnection>>),
+    Postgres(PooledConnection<ConnectionManager<PgConnection>>),
+}
+
+/// Evaluates `$body` with `$connection` bound to the connection of the
+/// backend in use.
+///
+/// Diesel queries are typed by the backend they run against, hence `$body` is
+/// expanded once per backend.
+macro_rules! with_connection {
+    ($connection:ident => $body:expr) => {
+        match $connection {
+            $crate::db::Connection::Sqlite($connection) => {
+                let $connection: &diesel::sqlite::SqliteConnection = &*$connection;
+                $body
+            }
+            $crate::db::Connection::Postgres($connection) => {
+                let $connection: &diesel::pg::PgConnection = &*$connection;
+                $body
+            }
+        }
+    };
+}
+
+impl Pool {
+    /// Opens a pool of connections to the SQLite database at `url`.
+    ///
+    /// In-memory databases only live as long as their connection, hence the
+    /// pool keeps a single connection open forever for them.
+    pub fn sqlite(url: &str, in_memory: bool) -> anyhow::Result<Self> {
+        let builder = r2d2::Pool::builder().connection_customizer(Box::new(SqlitePragmas));
+        let builder = if in_memory {
+            builder.max_size(1).idle_timeout(None).max_lifetime(None)
+        } else {
+            builder
+        };
+
+        let pool = builder.build(ConnectionManager::<SqliteConnection>::new(url))?;
+        sqlite::run_migrations(&*pool.get()?)?;
+
+        Ok(Pool::Sqlite(pool))
+    }
+
+    /// Opens a pool of connections to the PostgreSQL database at `url`.
+    pub fn postgres(url: &str) -> anyhow::Result<Self> {
+        let pool = r2d2::Pool::builder().build(ConnectionManager::<PgConnection>::new(url))?;
+        postgres::run_migrations(&*pool.get()?)?;
+
+        Ok(Pool::Postgres(pool))
+    }
+
+    /// Takes a connection from the pool, blocking until one is available.
+    pub fn get(&self) -> Result<Connection, r2d2::PoolError> {
+        match self {
+            Pool::Sqlite(pool) => pool.get().map(Connection::Sqlite),
+            Pool::Postgres(pool) => pool.get().map(Connection::Postgres),
+        }
+    }
+}
+
+impl Connection {
+    pub fn transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        match self {
+            Connection::Sqlite(connection) => connection.transaction(f),
+            Connection::Postgres(connection) => connection.transaction(f),
+        }
+    }
+
+    /// Runs `f` in a transaction that intends to write.
+    ///
+    /// A deferred SQLite transaction only asks for the write lock on its first
+    /// write and fails with `SQLITE_BUSY` without waiting if another
+    /// connection wrote in the meantime. `BEGIN IMMEDIATE` takes the lock up
+    /// front, where the busy timeout applies.
+    pub fn write_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        match self {
+            Connection::Sqlite(connection) => connection.immediate_transaction(f),
+            Connection::Postgres(connection) => connection.transaction(f),
+        }
+    }
+}
+
+/// Lets readers proceed while another connection writes and makes writers
+/// wait for each other instead of failing with `SQLITE_BUSY`.
+#[derive(Clone, Copy, Debug)]
+struct SqlitePragmas;
+
+impl CustomizeConnection<SqliteConnection, r2d2::Error> for SqlitePragmas {
+    fn on_acquire(&self, connection: &mut SqliteConnection) -> Result<(), r2d2::Error> {
+        connection
+            .batch_execute(&format!(
+                "PRAGMA busy_timeout = {}; PRAGMA journal_mode = WAL;",
+                SQLITE_BUSY_TIMEOUT.as_millis()
+            ))
+            .map_err(r2d2::Error::QueryError)
+    }
+}
@@ -0,0 +1,745 @@
+use crate::{
+    db::{
+        rows::{insert_swap_rows, load_swap_rows, AssetRow, Ledger, LedgerRow, SwapRow, SwapRows},
+        schema::*,
+        wrapper_types::{
+            custom_sql_types::{Text, U32},
+            BitcoinNetwork, Erc20Amount, Ether, EthereumAddress, Satoshis,
+        },
+        AssetKind, Connection, Database, Error,
+    },
+    swap_protocols::{rfc003::SecretHash, HashFunction, LedgerSide, Role, SwapId},
+};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The version of the export format.
+///
+/// Bump this whenever the format changes and teach `import_swaps` to read the
+/// older versions.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Everything we know about a swap, as a self-contained JSON document.
+///
+/// All values are serialized the same way they are stored in the database.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SwapDocument {
+    version: u32,
+    swap_id: Text<SwapId>,
+    role: Text<Role>,
+    counterparty: Text<PeerId>,
+    request: Option<RequestDocument>,
+    response: Option<ResponseDocument>,
+    #[serde(default)]
+    alpha_ledger_transactions: LedgerTransactions,
+    #[serde(default)]
+    beta_ledger_transactions: LedgerTransactions,
+}
+
+impl SwapDocument {
+    pub fn swap_id(&self) -> SwapId {
+        *self.swap_id
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RequestDocument {
+    BitcoinEthereumBitcoinEther(BitcoinEthereumBitcoinEtherRequest),
+    EthereumBitcoinEtherBitcoin(EthereumBitcoinEtherBitcoinRequest),
+    BitcoinEthereumBitcoinErc20(BitcoinEthereumBitcoinErc20Request),
+    EthereumBitcoinErc20Bitcoin(EthereumBitcoinErc20BitcoinRequest),
+    LitecoinEthereumBitcoinEther(BitcoinEthereumBitcoinEtherRequest),
+    EthereumLitecoinEtherBitcoin(EthereumBitcoinEtherBitcoinRequest),
+    LitecoinEthereumBitcoinErc20(BitcoinEthereumBitcoinErc20Request),
+    EthereumLitecoinErc20Bitcoin(EthereumBitcoinErc20BitcoinRequest),
+}
+
+impl RequestDocument {
+    /// The chain derived from Bitcoin the swap is on, the requests of Bitcoin
+    /// and Litecoin swaps only differ in it.
+    fn utxo_chain(&self) -> Ledger {
+        match self {
+            RequestDocument::BitcoinEthereumBitcoinEther(_)
+            | RequestDocument::EthereumBitcoinEtherBitcoin(_)
+            | RequestDocument::BitcoinEthereumBitcoinErc20(_)
+            | RequestDocument::EthereumBitcoinErc20Bitcoin(_) => Ledger::Bitcoin,
+            RequestDocument::LitecoinEthereumBitcoinEther(_)
+            | RequestDocument::EthereumLitecoinEtherBitcoin(_)
+            | RequestDocument::LitecoinEthereumBitcoinErc20(_)
+            | RequestDocument::EthereumLitecoinErc20Bitcoin(_) => Ledger::Litecoin,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BitcoinEthereumBitcoinEtherRequest {
+    bitcoin_network: Text<BitcoinNetwork>,
+    ethereum_chain_id: U32,
+    bitcoin_amount: Text<Satoshis>,
+    ether_amount: Text<Ether>,
+    hash_function: Text<HashFunction>,
+    bitcoin_refund_identity: Text<::bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EthereumBitcoinEtherBitcoinRequest {
+    ethereum_chain_id: U32,
+    bitcoin_network: Text<BitcoinNetwork>,
+    ether_amount: Text<Ether>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<::bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BitcoinEthereumBitcoinErc20Request {
+    bitcoin_network: Text<BitcoinNetwork>,
+    ethereum_chain_id: U32,
+    bitcoin_amount: Text<Satoshis>,
+    erc20_amount: Text<Erc20Amount>,
+    erc20_token_contract: Text<EthereumAddress>,
+    hash_function: Text<HashFunction>,
+    bitcoin_refund_identity: Text<::bitcoin::PublicKey>,
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_expiry: U32,
+    ethereum_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EthereumBitcoinErc20BitcoinRequest {
+    ethereum_chain_id: U32,
+    bitcoin_network: Text<BitcoinNetwork>,
+    erc20_amount: Text<Erc20Amount>,
+    erc20_token_contract: Text<EthereumAddress>,
+    bitcoin_amount: Text<Satoshis>,
+    hash_function: Text<HashFunction>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    bitcoin_redeem_identity: Text<::bitcoin::PublicKey>,
+    ethereum_expiry: U32,
+    bitcoin_expiry: U32,
+    secret_hash: Text<SecretHash>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResponseDocument {
+    BitcoinEthereumAccept(BitcoinEthereumAccept),
+    EthereumBitcoinAccept(EthereumBitcoinAccept),
+    Decline(Decline),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BitcoinEthereumAccept {
+    bitcoin_redeem_identity: Text<::bitcoin::PublicKey>,
+    ethereum_refund_identity: Text<EthereumAddress>,
+    at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EthereumBitcoinAccept {
+    ethereum_redeem_identity: Text<EthereumAddress>,
+    bitcoin_refund_identity: Text<::bitcoin::PublicKey>,
+    at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Queryable, Insertable)]
+#[table_name = "swap_declines"]
+pub struct Decline {
+    reason: Option<String>,
+}
+
+/// The ids of the transactions we observed for the HTLC on one ledger.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LedgerTransactions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deploy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fund: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redeem: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("export version {0} is not supported, expected {}", EXPORT_VERSION)]
+pub struct UnsupportedVersion(u32);
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("swap {0} cannot be represented in export version {}", EXPORT_VERSION)]
+pub struct UnsupportedSwap(SwapId);
+
+impl Database {
+    /// Export all swaps in the database.
+    pub async fn export_swaps(&self) -> anyhow::Result<Vec<SwapDocument>> {
+        self.do_in_transaction(|connection| {
+            let swap_ids = with_connection!(connection => {
+                swaps::table
+                    .select(swaps::swap_id)
+                    .load::<Text<SwapId>>(connection)
+            })?;
+
+            swap_ids
+                .iter()
+                .map(|swap_id| {
+                    let rows = load_swap_rows(connection, swap_id)?.ok_or(Error::SwapNotFound)?;
+
+                    Ok(SwapDocument {
+                        version: EXPORT_VERSION,
+                        request: request_document(&rows)?,
+                        response: load_response(connection, &rows)?,
+                        alpha_ledger_transactions: load_ledger_transactions(
+                            connection,
+                            swap_id,
+                            LedgerSide::Alpha,
+                        )?,
+                        beta_ledger_transactions: load_ledger_transactions(
+                            connection,
+                            swap_id,
+                            LedgerSide::Beta,
+                        )?,
+                        swap_id: rows.swap.swap_id,
+                        role: rows.swap.role,
+                        counterparty: rows.swap.counterparty,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await
+    }
+
+    /// Import swaps that were exported with `export_swaps`.
+    ///
+    /// Swaps that are already in the database are skipped. Returns the number
+    /// of imported swaps. The ledger transactions are not imported, the
+    /// watchers find them again when the swaps are loaded.
+    pub async fn import_swaps(&self, documents: Vec<SwapDocument>) -> anyhow::Result<usize> {
+        if let Some(document) = documents
+            .iter()
+            .find(|document| document.version != EXPORT_VERSION)
+        {
+            return Err(UnsupportedVersion(document.version).into());
+        }
+
+        self.do_in_write_transaction(move |connection| {
+            let mut imported = 0;
+
+            for document in &documents {
+                let existing = with_connection!(connection => {
+                    swaps::table
+                        .filter(swaps::swap_id.eq(&document.swap_id))
+                        .select(swaps::id)
+                        .first::<i32>(connection)
+                        .optional()
+                })?;
+
+                if existing.is_some() {
+                    tracing::info!("swap {} already exists, skipping", document.swap_id());
+                    continue;
+                }
+
+                import_swap(connection, document)?;
+                imported += 1;
+            }
+
+            Ok::<_, anyhow::Error>(imported)
+        })
+        .await
+    }
+}
+
+fn request_document(rows: &SwapRows) -> anyhow::Result<Option<RequestDocument>> {
+    if rows.ledgers.is_empty() {
+        return Ok(None);
+    }
+
+    let document = try_request_document(rows).ok_or(UnsupportedSwap(*rows.swap.swap_id))?;
+
+    Ok(Some(document))
+}
+
+fn try_request_document(rows: &SwapRows) -> Option<RequestDocument> {
+    let alpha_ledger = rows.ledger(LedgerSide::Alpha)?;
+    let beta_ledger = rows.ledger(LedgerSide::Beta)?;
+    let alpha_asset = rows.asset(LedgerSide::Alpha)?;
+    let beta_asset = rows.asset(LedgerSide::Beta)?;
+    let hash_function = rows.swap.hash_function?;
+    let secret_hash = rows.swap.secret_hash?;
+
+    let document = match (
+        *alpha_ledger.ledger,
+        *beta_ledger.ledger,
+        *alpha_asset.asset,
+        *beta_asset.asset,
+    ) {
+        (Ledger::Bitcoin, Ledger::Ethereum, AssetKind::Bitcoin, AssetKind::Ether)
+        | (Ledger::Litecoin, Ledger::Ethereum, AssetKind::Bitcoin, AssetKind::Ether) => {
+            let request = BitcoinEthereumBitcoinEtherRequest {
+                bitcoin_network: alpha_ledger.bitcoin_network?,
+                ethereum_chain_id: beta_ledger.ethereum_chain_id?,
+                bitcoin_amount: text(Some(&alpha_asset.quantity))?,
+                ether_amount: text(Some(&beta_asset.quantity))?,
+                hash_function,
+                bitcoin_refund_identity: text(alpha_ledger.refund_identity.as_deref())?,
+                ethereum_redeem_identity: text(beta_ledger.redeem_identity.as_deref())?,
+                bitcoin_expiry: alpha_ledger.expiry,
+                ethereum_expiry: beta_ledger.expiry,
+                secret_hash,
+            };
+
+            match *alpha_ledger.ledger {
+                Ledger::Litecoin => RequestDocument::LitecoinEthereumBitcoinEther(request),
+                _ => RequestDocument::BitcoinEthereumBitcoinEther(request),
+            }
+        }
+        (Ledger::Ethereum, Ledger::Bitcoin, AssetKind::Ether, AssetKind::Bitcoin)
+        | (Ledger::Ethereum, Ledger::Litecoin, AssetKind::Ether, AssetKind::Bitcoin) => {
+            let request = EthereumBitcoinEtherBitcoinRequest {
+                ethereum_chain_id: alpha_ledger.ethereum_chain_id?,
+                bitcoin_network: beta_ledger.bitcoin_network?,
+                ether_amount: text(Some(&alpha_asset.quantity))?,
+                bitcoin_amount: text(Some(&beta_asset.quantity))?,
+                hash_function,
+                ethereum_refund_identity: text(alpha_ledger.refund_identity.as_deref())?,
+                bitcoin_redeem_identity: text(beta_ledger.redeem_identity.as_deref())?,
+                ethereum_expiry: alpha_ledger.expiry,
+                bitcoin_expiry: beta_ledger.expiry,
+                secret_hash,
+            };
+
+            match *beta_ledger.ledger {
+                Ledger::Litecoin => RequestDocument::EthereumLitecoinEtherBitcoin(request),
+                _ => RequestDocument::EthereumBitcoinEtherBitcoin(request),
+            }
+        }
+        (Ledger::Bitcoin, Ledger::Ethereum, AssetKind::Bitcoin, AssetKind::Erc20)
+        | (Ledger::Litecoin, Ledger::Ethereum, AssetKind::Bitcoin, AssetKind::Erc20) => {
+            let request = BitcoinEthereumBitcoinErc20Request {
+                bitcoin_network: alpha_ledger.bitcoin_network?,
+                ethereum_chain_id: beta_ledger.ethereum_chain_id?,
+                bitcoin_amount: text(Some(&alpha_asset.quantity))?,
+                erc20_amount: text(Some(&beta_asset.quantity))?,
+                erc20_token_contract: beta_asset.token_contract?,
+                hash_function,
+                bitcoin_refund_identity: text(alpha_ledger.refund_identity.as_deref())?,
+                ethereum_redeem_identity: text(beta_ledger.redeem_identity.as_deref())?,
+                bitcoin_expiry: alpha_ledger.expiry,
+                ethereum_expiry: beta_ledger.expiry,
+                secret_hash,
+            };
+
+            match *alpha_ledger.ledger {
+                Ledger::Litecoin => RequestDocument::LitecoinEthereumBitcoinErc20(request),
+                _ => RequestDocument::BitcoinEthereumBitcoinErc20(request),
+            }
+        }
+        (Ledger::Ethereum, Ledger::Bitcoin, AssetKind::Erc20, AssetKind::Bitcoin)
+        | (Ledger::Ethereum, Ledger::Litecoin, AssetKind::Erc20, AssetKind::Bitcoin) => {
+            let request = EthereumBitcoinErc20BitcoinRequest {
+                ethereum_chain_id: alpha_ledger.ethereum_chain_id?,
+                bitcoin_network: beta_ledger.bitcoin_network?,
+                erc20_amount: text(Some(&alpha_asset.quantity))?,
+                erc20_token_contract: alpha_asset.token_contract?,
+                bitcoin_amount: text(Some(&beta_asset.quantity))?,
+                hash_function,
+                ethereum_refund_identity: text(alpha_ledger.refund_identity.as_deref())?,
+                bitcoin_redeem_identity: text(beta_ledger.redeem_identity.as_deref())?,
+                ethereum_expiry: alpha_ledger.expiry,
+                bitcoin_expiry: beta_ledger.expiry,
+                secret_hash,
+            };
+
+            match *beta_ledger.ledger {
+                Ledger::Litecoin => RequestDocument::EthereumLitecoinErc20Bitcoin(request),
+                _ => RequestDocument::EthereumBitcoinErc20Bitcoin(request),
+            }
+        }
+        _ => return None,
+    };
+
+    Some(document)
+}
+
+fn load_response(
+    connection: &Connection,
+    rows: &SwapRows,
+) -> anyhow::Result<Option<ResponseDocument>> {
+    if let Some(at) = rows.swap.accepted_at {
+        let document = try_accept_document(rows, at).ok_or(UnsupportedSwap(*rows.swap.swap_id))?;
+
+        return Ok(Some(document));
+    }
+
+    let swap_id = &rows.swap.swap_id;
+    let decline = with_connection!(connection => {
+        swap_declines::table
+            .filter(swap_declines::swap_id.eq(swap_id))
+            .select((swap_declines::reason,))
+            .first(connection)
+            .optional()
+    })?;
+
+    Ok(decline.map(ResponseDocument::Decline))
+}
+
+fn try_accept_document(rows: &SwapRows, at: NaiveDateTime) -> Option<ResponseDocument> {
+    let alpha_ledger = rows.ledger(LedgerSide::Alpha)?;
+    let beta_ledger = rows.ledger(LedgerSide::Beta)?;
+    let redeem_identity = alpha_ledger.redeem_identity.as_deref();
+    let refund_identity = beta_ledger.refund_identity.as_deref();
+
+    match (*alpha_ledger.ledger, *beta_ledger.ledger) {
+        (Ledger::Bitcoin, Ledger::Ethereum) | (Ledger::Litecoin, Ledger::Ethereum) => Some(
+            ResponseDocument::BitcoinEthereumAccept(BitcoinEthereumAccept {
+                bitcoin_redeem_identity: text(redeem_identity)?,
+                ethereum_refund_identity: text(refund_identity)?,
+                at,
+            }),
+        ),
+        (Ledger::Ethereum, Ledger::Bitcoin) | (Ledger::Ethereum, Ledger::Litecoin) => Some(
+            ResponseDocument::EthereumBitcoinAccept(EthereumBitcoinAccept {
+                ethereum_redeem_identity: text(redeem_identity)?,
+                bitcoin_refund_identity: text(refund_identity)?,
+                at,
+            }),
+        ),
+        _ => None,
+    }
+}
+
+fn text<T>(column: Option<&str>) -> Option<Text<T>>
+where
+    T: FromStr,
+{
+    column?.parse().ok().map(Text)
+}
+
+fn load_ledger_transactions(
+    connection: &Connection,
+    swap_id: &Text<SwapId>,
+    side: LedgerSide,
+) -> QueryResult<LedgerTransactions> {
+    with_connection!(connection => {
+        let events = rfc003_ledger_events::table
+            .filter(rfc003_ledger_events::swap_id.eq(swap_id))
+            .filter(rfc003_ledger_events::ledger.eq(Text(side)))
+            .select((
+                rfc003_ledger_events::event,
+                rfc003_ledger_events::transaction_id,
+            ))
+            .load::<(String, String)>(connection)?;
+
+        let mut transactions = LedgerTransactions::default();
+        for (event, transaction_id) in events {
+            match event.as_str() {
+                "Deployed" => transactions.deploy = Some(transaction_id),
+                "Funded" => transactions.fund = Some(transaction_id),
+                "Redeemed" => transactions.redeem = Some(transaction_id),
+                "Refunded" => transactions.refund = Some(transaction_id),
+                _ => tracing::warn!("unknown ledger event {} for swap {}", event, **swap_id),
+            }
+        }
+
+        Ok(transactions)
+    })
+}
+
+fn import_swap(connection: &Connection, document: &SwapDocument) -> anyhow::Result<()> {
+    let swap_id = *document.swap_id;
+
+    let mut rows = SwapRows {
+        swap: SwapRow {
+            swap_id: document.swap_id,
+            role: document.role,
+            counterparty: document.counterparty.clone(),
+            hash_function: None,
+            secret_hash: None,
+            accepted_at: None,
+        },
+        ledgers: Vec::new(),
+        assets: Vec::new(),
+    };
+
+    let utxo_chain = document
+        .request
+        .as_ref()
+        .map_or(Ledger::Bitcoin, RequestDocument::utxo_chain);
+
+    match &document.request {
+        Some(RequestDocument::BitcoinEthereumBitcoinEther(request))
+        | Some(RequestDocument::LitecoinEthereumBitcoinEther(request)) => {
+            rows.swap.hash_function = Some(request.hash_function);
+            rows.swap.secret_hash = Some(request.secret_hash);
+            rows.ledgers = vec![
+                LedgerRow {
+                    refund_identity: Some(request.bitcoin_refund_identity.to_string()),
+                    ..utxo_chain_ledger(
+                        utxo_chain,
+                        swap_id,
+                        LedgerSide::Alpha,
+                        request.bitcoin_network,
+                        request.bitcoin_expiry,
+                    )
+                },
+                LedgerRow {
+                    redeem_identity: Some(request.ethereum_redeem_identity.to_string()),
+                    ..ethereum_ledger(
+                        swap_id,
+                        LedgerSide::Beta,
+                        request.ethereum_chain_id,
+                        request.ethereum_expiry,
+                    )
+                },
+            ];
+            rows.assets = vec![
+                asset_row(
+                    swap_id,
+                    LedgerSide::Alpha,
+                    AssetKind::Bitcoin,
+                    request.bitcoin_amount.to_string(),
+                    None,
+                ),
+                asset_row(
+                    swap_id,
+                    LedgerSide::Beta,
+                    AssetKind::Ether,
+                    request.ether_amount.to_string(),
+                    None,
+                ),
+            ];
+        }
+        Some(RequestDocument::EthereumBitcoinEtherBitcoin(request))
+        | Some(RequestDocument::EthereumLitecoinEtherBitcoin(request)) => {
+            rows.swap.hash_function = Some(request.hash_function);
+            rows.swap.secret_hash = Some(request.secret_hash);
+            rows.ledgers = vec![
+                LedgerRow {
+                    refund_identity: Some(request.ethereum_refund_identity.to_string()),
+                    ..ethereum_ledger(
+                        swap_id,
+                        LedgerSide::Alpha,
+                        request.ethereum_chain_id,
+                        request.ethereum_expiry,
+                    )
+                },
+                LedgerRow {
+                    redeem_identity: Some(request.bitcoin_redeem_identity.to_string()),
+                    ..utxo_chain_ledger(
+                        utxo_chain,
+                        swap_id,
+                        LedgerSide::Beta,
+                        request.bitcoin_network,
+                        request.bitcoin_expiry,
+                    )
+                },
+            ];
+            rows.assets = vec![
+                asset_row(
+                    swap_id,
+                    LedgerSide::Alpha,
+                    AssetKind::Ether,
+                    request.ether_amount.to_string(),
+                    None,
+                ),
+                asset_row(
+                    swap_id,
+                    LedgerSide::Beta,
+                    AssetKind::Bitcoin,
+                    request.bitcoin_amount.to_string(),
+                    None,
+                ),
+            ];
+        }
+        Some(RequestDocument::BitcoinEthereumBitcoinErc20(request))
+        | Some(RequestDocument::LitecoinEthereumBitcoinErc20(request)) => {
+            rows.swap.hash_function = Some(request.hash_function);
+            rows.swap.secret_hash = Some(request.secret_hash);
+            rows.ledgers = vec![
+                LedgerRow {
+                    refund_identity: Some(request.bitcoin_refund_identity.to_string()),
+                    ..utxo_chain_ledger(
+                        utxo_chain,
+                        swap_id,
+                        LedgerSide::Alpha,
+                        request.bitcoin_network,
+                        request.bitcoin_expiry,
+                    )
+                },
+                LedgerRow {
+                    redeem_identity: Some(request.ethereum_redeem_identity.to_string()),
+                    ..ethereum_ledger(
+                        swap_id,
+                        LedgerSide::Beta,
+                        request.ethereum_chain_id,
+                        request.ethereum_expiry,
+                    )
+                },
+            ];
+            rows.assets = vec![
+                asset_row(
+                    swap_id,
+                    LedgerSide::Alpha,
+                    AssetKind::Bitcoin,
+                    request.bitcoin_amount.to_string(),
+                    None,
+                ),
+                asset_row(
+                    swap_id,
+                    LedgerSide::Beta,
+                    AssetKind::Erc20,
+                    request.erc20_amount.to_string(),
+                    Some(request.erc20_token_contract),
+                ),
+            ];
+        }
+        Some(RequestDocument::EthereumBitcoinErc20Bitcoin(request))
+        | Some(RequestDocument::EthereumLitecoinErc20Bitcoin(request)) => {
+            rows.swap.hash_function = Some(request.hash_function);
+            rows.swap.secret_hash = Some(request.secret_hash);
+            rows.ledgers = vec![
+                LedgerRow {
+                    refund_identity: Some(request.ethereum_refund_identity.to_string()),
+                    ..ethereum_ledger(
+                        swap_id,
+                        LedgerSide::Alpha,
+                        request.ethereum_chain_id,
+                        request.ethereum_expiry,
+                    )
+                },
+                LedgerRow {
+                    redeem_identity: Some(request.bitcoin_redeem_identity.to_string()),
+                    ..utxo_chain_ledger(
+                        utxo_chain,
+                        swap_id,
+                        LedgerSide::Beta,
+                        request.bitcoin_network,
+                        request.bitcoin_expiry,
+                    )
+                },
+            ];
+            rows.assets = vec![
+                asset_row(
+                    swap_id,
+                    LedgerSide::Alpha,
+                    AssetKind::Erc20,
+                    request.erc20_amount.to_string(),
+                    Some(request.erc20_token_contract),
+                ),
+                asset_row(
+                    swap_id,
+                    LedgerSide::Beta,
+                    AssetKind::Bitcoin,
+                    request.bitcoin_amount.to_string(),
+                    None,
+                ),
+            ];
+        }
+        None => {}
+    }
+
+    let (redeem_identity, refund_identity, at) = match &document.response {
+        Some(ResponseDocument::BitcoinEthereumAccept(accept)) => (
+            accept.bitcoin_redeem_identity.to_string(),
+            accept.ethereum_refund_identity.to_string(),
+            accept.at,
+        ),
+        Some(ResponseDocument::EthereumBitcoinAccept(accept)) => (
+            accept.ethereum_redeem_identity.to_string(),
+            accept.bitcoin_refund_identity.to_string(),
+            accept.at,
+        ),
+        Some(ResponseDocument::Decline(decline)) => {
+            insert_swap_rows(connection, &rows)?;
+
+            with_connection!(connection => {
+                diesel::insert_into(swap_declines::table)
+                    .values((swap_declines::swap_id.eq(&document.swap_id), decline))
+                    .execute(connection)
+            })?;
+
+            return Ok(());
+        }
+        None => {
+            insert_swap_rows(connection, &rows)?;
+
+            return Ok(());
+        }
+    };
+
+    if rows.ledgers.is_empty() {
+        return Err(Error::RequestNotFound.into());
+    }
+
+    for ledger in &mut rows.ledgers {
+        match *ledger.side {
+            LedgerSide::Alpha => ledger.redeem_identity = Some(redeem_identity.clone()),
+            LedgerSide::Beta => ledger.refund_identity = Some(refund_identity.clone()),
+        }
+    }
+    rows.swap.accepted_at = Some(at);
+
+    insert_swap_rows(connection, &rows)?;
+
+    Ok(())
+}
+
+fn utxo_chain_ledger(
+    ledger: Ledger,
+    swap_id: SwapId,
+    side: LedgerSide,
+    network: Text<BitcoinNetwork>,
+    expiry: U32,
+) -> LedgerRow {
+    LedgerRow {
+        swap_id: Text(swap_id),
+        side: Text(side),
+        ledger: Text(ledger),
+        bitcoin_network: Some(network),
+        ethereum_chain_id: None,
+        expiry,
+        redeem_identity: None,
+        refund_identity: None,
+    }
+}
+
+fn ethereum_ledger(swap_id: SwapId, side: LedgerSide, chain_id: U32, expiry: U32) -> LedgerRow {
+    LedgerRow {
+        swap_id: Text(swap_id),
+        side: Text(side),
+        ledger: Text(Ledger::Ethereum),
+        bitcoin_network: None,
+        ethereum_chain_id: Some(chain_id),
+        expiry,
+        redeem_identity: None,
+        refund_identity: None,
+    }
+}
+
+fn asset_row(
+    swap_id: SwapId,
+    side: LedgerSide,
+    asset: AssetKind,
+    quantity: String,
+    token_contract: Option<Text<EthereumAddress>>,
+) -> AssetRow {
+    AssetRow {
+        swap_id: Text(swap_id),
+        side: Text(side),
+        asset: Text(asset),
+        quantity,
+        token_contract,
+    }
+}
@@ -3,10 +3,14 @@
 /// activity that involves migration scripts to migrate old data. These tests
 /// make sure we don't change the format accidentally!
 use crate::{
-    db::wrapper_types::{Erc20Amount, Ether, EthereumAddress, Satoshis},
+    db::{
+        wrapper_types::{Erc20Amount, Ether, EthereumAddress, Satoshis},
+        Database, SwapDocument,
+    },
     swap_protocols::{rfc003::SecretHash, HashFunction, SwapId},
 };
-use std::{fmt, str::FromStr};
+use spectral::prelude::*;
+use std::{fmt, path::Path, str::FromStr};
 
 #[test]
 fn swap_id() {
@@ -38,7 +42,9 @@ fn bitcoin_amount() {
 #[test]
 fn hash_function() {
     roundtrip_test::<HashFunction>("SHA-256");
-    assert_num_variants::<HashFunction>(1)
+    roundtrip_test::<HashFunction>("SHA3-256");
+    roundtrip_test::<HashFunction>("RIPEMD160-SHA256");
+    assert_num_variants::<HashFunction>(3)
 }
 
 #[test]
@@ -58,6 +64,46 @@ fn secrethash() {
     roundtrip_test::<SecretHash>(
         "68917b35bacf71dbadf37628b3b7f290f6d88877d7b2269008d893ae7bd4f9ee",
     );
+    roundtrip_test::<SecretHash>("ce4be827894106ecf7bedfe6c0343a81e1e7814a");
+}
+
+const EXPORTED_SWAP: &str = r#"{"version":1,"swap_id":"7f3a105d-ecf2-4cc6-b35c-b4351ac28a34","role":"Alice","counterparty":"QmfUfpC2frwFvcDzpspnfZitHt5wct6n4kpG5jzgRdsxkY","request":{"kind":"bitcoin_ethereum_bitcoin_erc20","bitcoin_network":"regtest","ethereum_chain_id":17,"bitcoin_amount":"100000000","erc20_amount":"1000000000000000","erc20_token_contract":"b97048628db6b661d4c2aa833e95dbe1a905b280","hash_function":"SHA-256","bitcoin_refund_identity":"02ef606e64a51b07373f81e042887e8e9c3806f0ff3fe3711df18beba8b82d82e6","ethereum_redeem_identity":"68917b35bacf71dbadf37628b3b7f290f6d88877","bitcoin_expiry":1591200000,"ethereum_expiry":1591100000,"secret_hash":"68917b35bacf71dbadf37628b3b7f290f6d88877d7b2269008d893ae7bd4f9ee"},"response":{"kind":"bitcoin_ethereum_accept","bitcoin_redeem_identity":"0216867374f539badfd90d7b2269008d893ae7bd4f9ee7c695c967d01d6953c401","ethereum_refund_identity":"8457037fcd80a8650c4692d7fcfc1d0a96b92867","at":"2020-06-03T10:00:00"},"alpha_ledger_transactions":{},"beta_ledger_transactions":{}}"#;
+
+#[test]
+fn swap_document() {
+    let document = serde_json::from_str::<SwapDocument>(EXPORTED_SWAP).unwrap();
+
+    let written = serde_json::to_string(&document);
+
+    assert_that(&written).is_ok_containing(EXPORTED_SWAP.to_owned());
+}
+
+#[tokio::test]
+async fn swap_document_survives_import_and_export() {
+    let document = serde_json::from_str::<SwapDocument>(EXPORTED_SWAP).unwrap();
+    let db = Database::new(&Path::new(":memory:")).unwrap();
+
+    let imported = db.import_swaps(vec![document.clone()]).await;
+    assert_that(&imported).is_ok_containing(1);
+
+    let imported_again = db.import_swaps(vec![document]).await;
+    assert_that(&imported_again).is_ok_containing(0);
+
+    let exported = db.export_swaps().await.unwrap();
+    let written = serde_json::to_string(&exported[0]);
+
+    assert_that(&written).is_ok_containing(EXPORTED_SWAP.to_owned());
+}
+
+#[tokio::test]
+async fn swap_document_of_unknown_version_is_refused() {
+    let document = EXPORTED_SWAP.replace(r#""version":1"#, r#""version":2"#);
+    let document = serde_json::from_str::<SwapDocument>(&document).unwrap();
+    let db = Database::new(&Path::new(":memory:")).unwrap();
+
+    let imported = db.import_swaps(vec![document]).await;
+
+    assert_that(&imported).is_err();
 }
 
 /// Given a string representation of a value T, this function will assert
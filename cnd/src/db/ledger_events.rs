@@ -0,0 +1,189 @@
+use crate::{
+    db::{
+        archive::mark_if_finished,
+        schema::{self, *},
+        wrapper_types::custom_sql_types::Text,
+        Database, Save,
+    },
+    swap_protocols::{rfc003::create_swap::SwapEvent, LedgerSide, SwapId},
+    transaction,
+};
+use async_trait::async_trait;
+use diesel::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// An event that was observed on one of the two ledgers of a swap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerEvent<A, H, T> {
+    pub swap_id: SwapId,
+    pub side: LedgerSide,
+    pub event: SwapEvent<A, H, T>,
+}
+
+/// Load the events that were observed on one of the ledgers of a swap, in the
+/// order they happened.
+#[async_trait]
+pub trait LoadLedgerEvents<A, H, T>: Send + Sync + 'static {
+    async fn load_ledger_events(
+        &self,
+        swap_id: &SwapId,
+        side: LedgerSide,
+    ) -> anyhow::Result<Vec<SwapEvent<A, H, T>>>;
+}
+
+pub trait TransactionId {
+    fn transaction_id(&self) -> String;
+}
+
+impl TransactionId for transaction::Bitcoin {
+    fn transaction_id(&self) -> String {
+        self.txid().to_string()
+    }
+}
+
+impl TransactionId for transaction::Ethereum {
+    fn transaction_id(&self) -> String {
+        format!("{:#x}", self.hash)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "rfc003_ledger_events"]
+struct InsertableLedgerEvent {
+    swap_id: Text<SwapId>,
+    ledger: Text<LedgerSide>,
+    event: String,
+    transaction_id: String,
+    data: String,
+}
+
+#[async_trait]
+impl<A, H, T> Save<LedgerEvent<A, H, T>> for Database
+where
+    SwapEvent<A, H, T>: Serialize,
+    A: Send + 'static,
+    H: Send + 'static,
+    T: TransactionId + Send + 'static,
+{
+    async fn save(&self, ledger_event: LedgerEvent<A, H, T>) -> anyhow::Result<()> {
+        let LedgerEvent {
+            swap_id,
+            side,
+            event,
+        } = ledger_event;
+
+        let transaction_id = match &event {
+            SwapEvent::Deployed(deployed) => &deployed.transaction,
+            SwapEvent::Funded(funded) => funded.transaction(),
+            SwapEvent::Redeemed(redeemed) => &redeemed.transaction,
+            SwapEvent::Refunded(refunded) => &refunded.transaction,
+        }
+        .transaction_id();
+
+        let insertable = InsertableLedgerEvent {
+            swap_id: Text(swap_id),
+            ledger: Text(side),
+            event: event.to_string(),
+            transaction_id,
+            data: serde_json::to_string(&event)?,
+        };
+
+        self.do_in_write_transaction(move |connection| {
+            with_connection!(connection => {
+                diesel::insert_into(schema::rfc003_ledger_events::table)
+                    .values(&insertable)
+                    .execute(connection)
+            })?;
+
+            mark_if_finished(connection, &insertable.swap_id)
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<A, H, T> LoadLedgerEvents<A, H, T> for Database
+where
+    SwapEvent<A, H, T>: DeserializeOwned + Send + 'static,
+{
+    async fn load_ledger_events(
+        &self,
+        swap_id: &SwapId,
+        side: LedgerSide,
+    ) -> anyhow::Result<Vec<SwapEvent<A, H, T>>> {
+        let swap_id = *swap_id;
+
+        let records: Vec<String> = self
+            .do_in_transaction(move |connection| {
+                let key = Text(swap_id);
+
+                with_connection!(connection => {
+                    rfc003_ledger_events::table
+                        .filter(rfc003_ledger_events::swap_id.eq(key))
+                        .filter(rfc003_ledger_events::ledger.eq(Text(side)))
+                        .order(rfc003_ledger_events::id.asc())
+                        .select(rfc003_ledger_events::data)
+                        .load(connection)
+                })
+            })
+            .await?;
+
+        records
+            .iter()
+            .map(|data| Ok(serde_json::from_str(data)?))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asset::{self, ethereum::FromWei},
+        htlc_location,
+        swap_protocols::rfc003::events::{Deployed, Funded},
+    };
+    use spectral::prelude::*;
+    use std::path::Path;
+
+    type Event = SwapEvent<asset::Ether, htlc_location::Ethereum, transaction::Ethereum>;
+
+    #[tokio::test]
+    async fn saved_events_are_loaded_in_order_per_ledger() {
+        let db = Database::new(&Path::new(":memory:")).unwrap();
+        let swap_id = SwapId::default();
+
+        let deployed = Event::Deployed(Deployed {
+            location: htlc_location::Ethereum::default(),
+            transaction: transaction::Ethereum::default(),
+        });
+        let funded = Event::Funded(Funded::Correctly {
+            asset: asset::Ether::from_wei(1_000u32),
+            transaction: transaction::Ethereum::default(),
+        });
+
+        for event in vec![deployed.clone(), funded.clone()] {
+            db.save(LedgerEvent {
+                swap_id,
+                side: LedgerSide::Beta,
+                event,
+            })
+            .await
+            .unwrap();
+        }
+
+        let beta: Vec<Event> = db
+            .load_ledger_events(&swap_id, LedgerSide::Beta)
+            .await
+            .unwrap();
+        let alpha: Vec<Event> = db
+            .load_ledger_events(&swap_id, LedgerSide::Alpha)
+            .await
+            .unwrap();
+
+        assert_that(&beta).is_equal_to(vec![deployed, funded]);
+        assert_that(&alpha).is_empty();
+    }
+}
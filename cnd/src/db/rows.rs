@@ -0,0 +1,423 @@
+use crate::{
+    asset,
+    db::{
+        schema::{swap_assets, swap_ledgers, swaps},
+        wrapper_types::{
+            custom_sql_types::{Text, U32},
+            BitcoinNetwork, Erc20Amount, Ether, EthereumAddress, Satoshis,
+        },
+        AssetKind, Connection, Error, LedgerKind, SwapTypes,
+    },
+    identity,
+    swap_protocols::{
+        ledger::{bitcoin, litecoin, Ethereum},
+        rfc003::SecretHash,
+        HashFunction, LedgerSide, Role, SwapId,
+    },
+    timestamp::Timestamp,
+};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use libp2p::PeerId;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+/// The value of the `ledger` column of `swap_ledgers`.
+#[derive(Clone, Copy, Debug, Display, EnumString, PartialEq)]
+pub enum Ledger {
+    Bitcoin,
+    Ethereum,
+    /// Stored with the network in the `bitcoin_network` column, like Bitcoin.
+    Litecoin,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("the stored {0} does not match the type it is loaded as")]
+pub struct TypeMismatch(&'static str);
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[table_name = "swaps"]
+pub struct SwapRow {
+    pub swap_id: Text<SwapId>,
+    pub role: Text<Role>,
+    pub counterparty: Text<PeerId>,
+    pub hash_function: Option<Text<HashFunction>>,
+    pub secret_hash: Option<Text<SecretHash>>,
+    pub accepted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[table_name = "swap_ledgers"]
+pub struct LedgerRow {
+    pub swap_id: Text<SwapId>,
+    pub side: Text<LedgerSide>,
+    pub ledger: Text<Ledger>,
+    pub bitcoin_network: Option<Text<BitcoinNetwork>>,
+    pub ethereum_chain_id: Option<U32>,
+    pub expiry: U32,
+    pub redeem_identity: Option<String>,
+    pub refund_identity: Option<String>,
+}
+
+impl LedgerRow {
+    /// Constructs the row of `ledger` without any identities.
+    pub fn new<L>(swap_id: SwapId, side: LedgerSide, ledger: &L, expiry: Timestamp) -> Self
+    where
+        L: StoredLedger,
+    {
+        let (kind, bitcoin_network, ethereum_chain_id) = ledger.to_columns();
+
+        LedgerRow {
+            swap_id: Text(swap_id),
+            side: Text(side),
+            ledger: Text(kind),
+            bitcoin_network: bitcoin_network.map(Text),
+            ethereum_chain_id: ethereum_chain_id.map(U32),
+            expiry: U32(expiry.into()),
+            redeem_identity: None,
+            refund_identity: None,
+        }
+    }
+
+    pub fn ledger_kind(&self) -> anyhow::Result<LedgerKind> {
+        match (*self.ledger, self.bitcoin_network) {
+            (Ledger::Bitcoin, Some(network)) => Ok(LedgerKind::Bitcoin((*network).into())),
+            (Ledger::Litecoin, Some(network)) => Ok(LedgerKind::Litecoin((*network).into())),
+            (Ledger::Ethereum, _) => Ok(LedgerKind::Ethereum),
+            (Ledger::Bitcoin, None) | (Ledger::Litecoin, None) => {
+                Err(TypeMismatch("ledger").into())
+            }
+        }
+    }
+
+    pub fn redeem_identity<I>(&self) -> anyhow::Result<Option<I>>
+    where
+        I: StoredIdentity,
+    {
+        self.redeem_identity
+            .as_deref()
+            .map(I::from_column)
+            .transpose()
+    }
+
+    pub fn refund_identity<I>(&self) -> anyhow::Result<Option<I>>
+    where
+        I: StoredIdentity,
+    {
+        self.refund_identity
+            .as_deref()
+            .map(I::from_column)
+            .transpose()
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq)]
+#[table_name = "swap_assets"]
+pub struct AssetRow {
+    pub swap_id: Text<SwapId>,
+    pub side: Text<LedgerSide>,
+    pub asset: Text<AssetKind>,
+    pub quantity: String,
+    pub token_contract: Option<Text<EthereumAddress>>,
+}
+
+impl AssetRow {
+    pub fn new<A>(swap_id: SwapId, side: LedgerSide, asset: &A) -> Self
+    where
+        A: StoredAsset,
+    {
+        let (kind, quantity, token_contract) = asset.to_columns();
+
+        AssetRow {
+            swap_id: Text(swap_id),
+            side: Text(side),
+            asset: Text(kind),
+            quantity,
+            token_contract: token_contract.map(Text),
+        }
+    }
+}
+
+/// Everything that is stored about a swap in the `swaps`, `swap_ledgers` and
+/// `swap_assets` tables.
+///
+/// The ledgers and assets are empty until the request has been saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapRows {
+    pub swap: SwapRow,
+    pub ledgers: Vec<LedgerRow>,
+    pub assets: Vec<AssetRow>,
+}
+
+impl SwapRows {
+    pub fn ledger(&self, side: LedgerSide) -> Option<&LedgerRow> {
+        self.ledgers.iter().find(|row| *row.side == side)
+    }
+
+    pub fn asset(&self, side: LedgerSide) -> Option<&AssetRow> {
+        self.assets.iter().find(|row| *row.side == side)
+    }
+
+    pub fn swap_types(&self) -> anyhow::Result<SwapTypes> {
+        let ledger_kind = |side| match self.ledger(side) {
+            Some(ledger) => ledger.ledger_kind(),
+            None => Err(Error::RequestNotFound.into()),
+        };
+        let asset_kind = |side| match self.asset(side) {
+            Some(asset) => Ok(*asset.asset),
+            None => Err(Error::RequestNotFound),
+        };
+
+        Ok(SwapTypes {
+            alpha_ledger: ledger_kind(LedgerSide::Alpha)?,
+            beta_ledger: ledger_kind(LedgerSide::Beta)?,
+            alpha_asset: asset_kind(LedgerSide::Alpha)?,
+            beta_asset: asset_kind(LedgerSide::Beta)?,
+            role: *self.swap.role,
+        })
+    }
+}
+
+pub fn load_swap_rows(
+    connection: &Connection,
+    swap_id: &Text<SwapId>,
+) -> QueryResult<Option<SwapRows>> {
+    with_connection!(connection => {
+        let swap = swaps::table
+            .filter(swaps::swap_id.eq(swap_id))
+            .select((
+                swaps::swap_id,
+                swaps::role,
+                swaps::counterparty,
+                swaps::hash_function,
+                swaps::secret_hash,
+                swaps::accepted_at,
+            ))
+            .first::<SwapRow>(connection)
+            .optional()?;
+
+        let swap = match swap {
+            Some(swap) => swap,
+            None => return Ok(None),
+        };
+
+        let ledgers = swap_ledgers::table
+            .filter(swap_ledgers::swap_id.eq(swap_id))
+            .select((
+                swap_ledgers::swap_id,
+                swap_ledgers::side,
+                swap_ledgers::ledger,
+                swap_ledgers::bitcoin_network,
+                swap_ledgers::ethereum_chain_id,
+                swap_ledgers::expiry,
+                swap_ledgers::redeem_identity,
+                swap_ledgers::refund_identity,
+            ))
+            .load::<LedgerRow>(connection)?;
+
+        let assets = swap_assets::table
+            .filter(swap_assets::swap_id.eq(swap_id))
+            .select((
+                swap_assets::swap_id,
+                swap_assets::side,
+                swap_assets::asset,
+                swap_assets::quantity,
+                swap_assets::token_contract,
+            ))
+            .load::<AssetRow>(connection)?;
+
+        Ok(Some(SwapRows {
+            swap,
+            ledgers,
+            assets,
+        }))
+    })
+}
+
+pub fn insert_swap_rows(connection: &Connection, rows: &SwapRows) -> QueryResult<()> {
+    with_connection!(connection => {
+        diesel::insert_into(swaps::table)
+            .values(&rows.swap)
+            .execute(connection)?;
+
+        for ledger in &rows.ledgers {
+            diesel::insert_into(swap_ledgers::table)
+                .values(ledger)
+                .execute(connection)?;
+        }
+
+        for asset in &rows.assets {
+            diesel::insert_into(swap_assets::table)
+                .values(asset)
+                .execute(connection)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// A ledger that can be stored in a row of `swap_ledgers`.
+pub trait StoredLedger: Sized {
+    /// Returns the values of the `ledger`, `bitcoin_network` and
+    /// `ethereum_chain_id` columns.
+    fn to_columns(&self) -> (Ledger, Option<BitcoinNetwork>, Option<u32>);
+    fn from_row(row: &LedgerRow) -> anyhow::Result<Self>;
+}
+
+macro_rules! impl_stored_ledger_for_utxo_chain {
+    ($chain:ident, $ledger:ident, $network:ident) => {
+        impl StoredLedger for $chain::$network {
+            fn to_columns(&self) -> (Ledger, Option<BitcoinNetwork>, Option<u32>) {
+                (Ledger::$ledger, Some(BitcoinNetwork::$network), None)
+            }
+
+            fn from_row(row: &LedgerRow) -> anyhow::Result<Self> {
+                match (*row.ledger, row.bitcoin_network) {
+                    (Ledger::$ledger, Some(Text(BitcoinNetwork::$network))) => Ok($chain::$network),
+                    _ => Err(TypeMismatch("ledger").into()),
+                }
+            }
+        }
+    };
+}
+
+impl_stored_ledger_for_utxo_chain!(bitcoin, Bitcoin, Mainnet);
+impl_stored_ledger_for_utxo_chain!(bitcoin, Bitcoin, Testnet);
+impl_stored_ledger_for_utxo_chain!(bitcoin, Bitcoin, Regtest);
+impl_stored_ledger_for_utxo_chain!(litecoin, Litecoin, Mainnet);
+impl_stored_ledger_for_utxo_chain!(litecoin, Litecoin, Testnet);
+impl_stored_ledger_for_utxo_chain!(litecoin, Litecoin, Regtest);
+
+impl StoredLedger for Ethereum {
+    fn to_columns(&self) -> (Ledger, Option<BitcoinNetwork>, Option<u32>) {
+        (Ledger::Ethereum, None, Some(self.chain_id.into()))
+    }
+
+    fn from_row(row: &LedgerRow) -> anyhow::Result<Self> {
+        match (*row.ledger, row.ethereum_chain_id) {
+            (Ledger::Ethereum, Some(chain_id)) => Ok(Ethereum {
+                chain_id: chain_id.0.into(),
+            }),
+            _ => Err(TypeMismatch("ledger").into()),
+        }
+    }
+}
+
+/// An asset that can be stored in a row of `swap_assets`.
+pub trait StoredAsset: Sized {
+    /// Returns the values of the `asset`, `quantity` and `token_contract`
+    /// columns.
+    fn to_columns(&self) -> (AssetKind, String, Option<EthereumAddress>);
+    fn from_row(row: &AssetRow) -> anyhow::Result<Self>;
+}
+
+impl StoredAsset for asset::Bitcoin {
+    fn to_columns(&self) -> (AssetKind, String, Option<EthereumAddress>) {
+        (AssetKind::Bitcoin, Satoshis::from(*self).to_string(), None)
+    }
+
+    fn from_row(row: &AssetRow) -> anyhow::Result<Self> {
+        match *row.asset {
+            AssetKind::Bitcoin => Ok(Satoshis::from_str(&row.quantity)?.into()),
+            _ => Err(TypeMismatch("asset").into()),
+        }
+    }
+}
+
+impl StoredAsset for asset::Ether {
+    fn to_columns(&self) -> (AssetKind, String, Option<EthereumAddress>) {
+        (
+            AssetKind::Ether,
+            Ether::from(self.clone()).to_string(),
+            None,
+        )
+    }
+
+    fn from_row(row: &AssetRow) -> anyhow::Result<Self> {
+        match *row.asset {
+            AssetKind::Ether => Ok(Ether::from_str(&row.quantity)?.into()),
+            _ => Err(TypeMismatch("asset").into()),
+        }
+    }
+}
+
+impl StoredAsset for asset::Erc20 {
+    fn to_columns(&self) -> (AssetKind, String, Option<EthereumAddress>) {
+        (
+            AssetKind::Erc20,
+            Erc20Amount::from(self.quantity.clone()).to_string(),
+            Some(self.token_contract.into()),
+        )
+    }
+
+    fn from_row(row: &AssetRow) -> anyhow::Result<Self> {
+        match (*row.asset, row.token_contract) {
+            (AssetKind::Erc20, Some(token_contract)) => Ok(asset::Erc20::new(
+                token_contract.0.into(),
+                Erc20Amount::from_str(&row.quantity)?.into(),
+            )),
+            _ => Err(TypeMismatch("asset").into()),
+        }
+    }
+}
+
+/// An identity that can be stored in the identity columns of `swap_ledgers`.
+pub trait StoredIdentity: Sized {
+    fn to_column(&self) -> String;
+    fn from_column(column: &str) -> anyhow::Result<Self>;
+}
+
+impl StoredIdentity for identity::Bitcoin {
+    fn to_column(&self) -> String {
+        ::bitcoin::PublicKey::from(*self).to_string()
+    }
+
+    fn from_column(column: &str) -> anyhow::Result<Self> {
+        Ok(::bitcoin::PublicKey::from_str(column)?.into())
+    }
+}
+
+impl StoredIdentity for identity::Ethereum {
+    fn to_column(&self) -> String {
+        EthereumAddress::from(*self).to_string()
+    }
+
+    fn from_column(column: &str) -> anyhow::Result<Self> {
+        Ok(EthereumAddress::from_str(column)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset::ethereum::FromWei;
+    use spectral::prelude::*;
+
+    #[test]
+    fn ledger_row_roundtrips_to_the_same_ledger() {
+        let ledger = Ethereum {
+            chain_id: 17.into(),
+        };
+
+        let row = LedgerRow::new(
+            SwapId::default(),
+            LedgerSide::Alpha,
+            &ledger,
+            Timestamp::from(42),
+        );
+
+        assert_that(&Ethereum::from_row(&row).unwrap()).is_equal_to(&ledger);
+        assert_that(&bitcoin::Regtest::from_row(&row)).is_err();
+    }
+
+    #[test]
+    fn asset_row_roundtrips_to_the_same_asset() {
+        let asset = asset::Ether::from_wei(1_000_000_000_000u64);
+
+        let row = AssetRow::new(SwapId::default(), LedgerSide::Beta, &asset);
+
+        assert_that(&row.quantity.as_str()).is_equal_to("1000000000000");
+        assert_that(&asset::Ether::from_row(&row).unwrap()).is_equal_to(&asset);
+        assert_that(&asset::Bitcoin::from_row(&row)).is_err();
+    }
+}
@@ -1,23 +1,19 @@
 use crate::{
-    asset,
     db::{
+        archive::mark_if_finished,
+        rows::{AssetRow, LedgerRow, StoredAsset, StoredIdentity, StoredLedger},
         schema::{self, *},
-        wrapper_types::{
-            custom_sql_types::{Text, U32},
-            BitcoinNetwork, Erc20Amount, Ether, EthereumAddress, Satoshis,
-        },
-        Sqlite, Swap,
+        wrapper_types::custom_sql_types::Text,
+        Database, Swap,
     },
-    identity,
     swap_protocols::{
-        ledger::{self, Ethereum},
-        rfc003::{Accept, Decline, Request, SecretHash},
-        HashFunction, Role, SwapId,
+        rfc003::{Accept, Decline, Request},
+        LedgerSide, Role, SwapId,
     },
 };
 use async_trait::async_trait;
-use diesel::RunQueryDsl;
-use impl_template::impl_template;
+use chrono::Utc;
+use diesel::prelude::*;
 use libp2p::{self, PeerId};
 
 /// Save swap to database.
@@ -27,14 +23,16 @@ pub trait Save<T>: Send + Sync + 'static {
 }
 
 #[async_trait]
-impl Save<Swap> for Sqlite {
+impl Save<Swap> for Database {
     async fn save(&self, swap: Swap) -> anyhow::Result<()> {
         let insertable = InsertableSwap::from(swap);
 
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(schema::rfc003_swaps::dsl::rfc003_swaps)
-                .values(&insertable)
-                .execute(&*connection)
+        self.do_in_write_transaction(move |connection| {
+            with_connection!(connection => {
+                diesel::insert_into(schema::swaps::table)
+                    .values(&insertable)
+                    .execute(&*connection)
+            })
         })
         .await?;
 
@@ -43,7 +41,7 @@ impl Save<Swap> for Sqlite {
 }
 
 #[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_swaps"]
+#[table_name = "swaps"]
 struct InsertableSwap {
     pub swap_id: Text<SwapId>,
     pub role: Text<Role>,
@@ -60,224 +58,21 @@ impl From<Swap> for InsertableSwap {
     }
 }
 
-#[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_bitcoin_ethereum_bitcoin_ether_request_messages"]
-struct InsertableBitcoinEthereumBitcoinEtherRequestMessage {
-    swap_id: Text<SwapId>,
-    bitcoin_network: Text<BitcoinNetwork>,
-    ethereum_chain_id: U32,
-    bitcoin_amount: Text<Satoshis>,
-    ether_amount: Text<Ether>,
-    hash_function: Text<HashFunction>,
-    bitcoin_refund_identity: Text<::bitcoin::PublicKey>,
-    ethereum_redeem_identity: Text<EthereumAddress>,
-    bitcoin_expiry: U32,
-    ethereum_expiry: U32,
-    secret_hash: Text<SecretHash>,
-}
-
-#[impl_template]
 #[async_trait]
-impl
-    Save<
-        Request<
-            ((
-                ledger::bitcoin::Mainnet,
-                ledger::bitcoin::Testnet,
-                ledger::bitcoin::Regtest,
-            )),
-            Ethereum,
-            asset::Bitcoin,
-            asset::Ether,
-            identity::Bitcoin,
-            identity::Ethereum,
-        >,
-    > for Sqlite
+impl<AL, BL, AA, BA, AI, BI> Save<Request<AL, BL, AA, BA, AI, BI>> for Database
+where
+    AL: StoredLedger + Send + 'static,
+    BL: StoredLedger + Send + 'static,
+    AA: StoredAsset + Send + 'static,
+    BA: StoredAsset + Send + 'static,
+    AI: StoredIdentity + Send + 'static,
+    BI: StoredIdentity + Send + 'static,
 {
-    async fn save(
-        &self,
-        message: Request<
-            __TYPE0__,
-            Ethereum,
-            asset::Bitcoin,
-            asset::Ether,
-            identity::Bitcoin,
-            identity::Ethereum,
-        >,
-    ) -> anyhow::Result<()> {
-        let Request {
-            swap_id,
-            alpha_asset,
-            beta_ledger,
-            beta_asset,
-            hash_function,
-            alpha_ledger_refund_identity,
-            beta_ledger_redeem_identity,
-            alpha_expiry,
-            beta_expiry,
-            secret_hash,
-            ..
-        } = message;
-
-        let insertable = InsertableBitcoinEthereumBitcoinEtherRequestMessage {
-            swap_id: Text(swap_id),
-            bitcoin_network: Text(BitcoinNetwork::from(__TYPE0__)),
-            ethereum_chain_id: U32(beta_ledger.chain_id.into()),
-            bitcoin_amount: Text(alpha_asset.into()),
-            ether_amount: Text(beta_asset.into()),
-            hash_function: Text(hash_function),
-            bitcoin_refund_identity: Text(alpha_ledger_refund_identity.into()),
-            ethereum_redeem_identity: Text(beta_ledger_redeem_identity.into()),
-            bitcoin_expiry: U32(alpha_expiry.into()),
-            ethereum_expiry: U32(beta_expiry.into()),
-            secret_hash: Text(secret_hash),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_bitcoin_ethereum_bitcoin_ether_request_messages::table)
-                .values(&insertable)
-                .execute(connection)
-        })
-        .await?;
-
-        Ok(())
-    }
-}
-
-#[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages"]
-struct InsertableBitcoinEthereumBitcoinErc20RequestMessage {
-    swap_id: Text<SwapId>,
-    bitcoin_network: Text<BitcoinNetwork>,
-    ethereum_chain_id: U32,
-    bitcoin_amount: Text<Satoshis>,
-    erc20_amount: Text<Erc20Amount>,
-    erc20_token_contract: Text<EthereumAddress>,
-    hash_function: Text<HashFunction>,
-    bitcoin_refund_identity: Text<::bitcoin::PublicKey>,
-    ethereum_redeem_identity: Text<EthereumAddress>,
-    bitcoin_expiry: U32,
-    ethereum_expiry: U32,
-    secret_hash: Text<SecretHash>,
-}
-
-#[impl_template]
-#[async_trait]
-impl
-    Save<
-        Request<
-            ((
-                ledger::bitcoin::Mainnet,
-                ledger::bitcoin::Testnet,
-                ledger::bitcoin::Regtest,
-            )),
-            Ethereum,
-            asset::Bitcoin,
-            asset::Erc20,
-            identity::Bitcoin,
-            identity::Ethereum,
-        >,
-    > for Sqlite
-{
-    async fn save(
-        &self,
-        message: Request<
-            __TYPE0__,
-            Ethereum,
-            asset::Bitcoin,
-            asset::Erc20,
-            identity::Bitcoin,
-            identity::Ethereum,
-        >,
-    ) -> anyhow::Result<()> {
-        let Request {
-            swap_id,
-            alpha_asset,
-            beta_ledger,
-            beta_asset,
-            hash_function,
-            alpha_ledger_refund_identity,
-            beta_ledger_redeem_identity,
-            alpha_expiry,
-            beta_expiry,
-            secret_hash,
-            ..
-        } = message;
-
-        let insertable = InsertableBitcoinEthereumBitcoinErc20RequestMessage {
-            swap_id: Text(swap_id),
-            bitcoin_network: Text(BitcoinNetwork::from(__TYPE0__)),
-            ethereum_chain_id: U32(beta_ledger.chain_id.into()),
-            bitcoin_amount: Text(alpha_asset.into()),
-            erc20_amount: Text(beta_asset.quantity.into()),
-            erc20_token_contract: Text(beta_asset.token_contract.into()),
-            hash_function: Text(hash_function),
-            bitcoin_refund_identity: Text(alpha_ledger_refund_identity.into()),
-            ethereum_redeem_identity: Text(beta_ledger_redeem_identity.into()),
-            bitcoin_expiry: U32(alpha_expiry.into()),
-            ethereum_expiry: U32(beta_expiry.into()),
-            secret_hash: Text(secret_hash),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages::table)
-                .values(&insertable)
-                .execute(connection)
-        })
-        .await?;
-
-        Ok(())
-    }
-}
-
-#[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_ethereum_bitcoin_ether_bitcoin_request_messages"]
-struct InsertableEthereumBitcoinEtherBitcoinRequestMessage {
-    swap_id: Text<SwapId>,
-    ethereum_chain_id: U32,
-    bitcoin_network: Text<BitcoinNetwork>,
-    ether_amount: Text<Ether>,
-    bitcoin_amount: Text<Satoshis>,
-    hash_function: Text<HashFunction>,
-    ethereum_refund_identity: Text<EthereumAddress>,
-    bitcoin_redeem_identity: Text<::bitcoin::PublicKey>,
-    ethereum_expiry: U32,
-    bitcoin_expiry: U32,
-    secret_hash: Text<SecretHash>,
-}
-
-#[impl_template]
-#[async_trait]
-impl
-    Save<
-        Request<
-            Ethereum,
-            ((
-                ledger::bitcoin::Mainnet,
-                ledger::bitcoin::Testnet,
-                ledger::bitcoin::Regtest,
-            )),
-            asset::Ether,
-            asset::Bitcoin,
-            identity::Ethereum,
-            identity::Bitcoin,
-        >,
-    > for Sqlite
-{
-    async fn save(
-        &self,
-        message: Request<
-            Ethereum,
-            __TYPE0__,
-            asset::Ether,
-            asset::Bitcoin,
-            identity::Ethereum,
-            identity::Bitcoin,
-        >,
-    ) -> anyhow::Result<()> {
+    async fn save(&self, message: Request<AL, BL, AA, BA, AI, BI>) -> anyhow::Result<()> {
         let Request {
             swap_id,
             alpha_ledger,
+            beta_ledger,
             alpha_asset,
             beta_asset,
             hash_function,
@@ -286,27 +81,48 @@ impl
             alpha_expiry,
             beta_expiry,
             secret_hash,
-            ..
         } = message;
 
-        let insertable = InsertableEthereumBitcoinEtherBitcoinRequestMessage {
-            swap_id: Text(swap_id),
-            bitcoin_network: Text(BitcoinNetwork::from(__TYPE0__)),
-            ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
-            ether_amount: Text(alpha_asset.into()),
-            bitcoin_amount: Text(beta_asset.into()),
-            hash_function: Text(hash_function),
-            ethereum_refund_identity: Text(alpha_ledger_refund_identity.into()),
-            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into()),
-            ethereum_expiry: U32(alpha_expiry.into()),
-            bitcoin_expiry: U32(beta_expiry.into()),
-            secret_hash: Text(secret_hash),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_ethereum_bitcoin_ether_bitcoin_request_messages::table)
-                .values(&insertable)
-                .execute(connection)
+        let ledgers = vec![
+            LedgerRow {
+                refund_identity: Some(alpha_ledger_refund_identity.to_column()),
+                ..LedgerRow::new(swap_id, LedgerSide::Alpha, &alpha_ledger, alpha_expiry)
+            },
+            LedgerRow {
+                redeem_identity: Some(beta_ledger_redeem_identity.to_column()),
+                ..LedgerRow::new(swap_id, LedgerSide::Beta, &beta_ledger, beta_expiry)
+            },
+        ];
+        let assets = vec![
+            AssetRow::new(swap_id, LedgerSide::Alpha, &alpha_asset),
+            AssetRow::new(swap_id, LedgerSide::Beta, &beta_asset),
+        ];
+
+        self.do_in_write_transaction(move |connection| {
+            let key = Text(swap_id);
+
+            with_connection!(connection => {
+                diesel::update(swaps::table.filter(swaps::swap_id.eq(&key)))
+                    .set((
+                        swaps::hash_function.eq(Text(hash_function)),
+                        swaps::secret_hash.eq(Text(secret_hash)),
+                    ))
+                    .execute(connection)?;
+
+                for ledger in &ledgers {
+                    diesel::insert_into(swap_ledgers::table)
+                        .values(ledger)
+                        .execute(connection)?;
+                }
+
+                for asset in &assets {
+                    diesel::insert_into(swap_assets::table)
+                        .values(asset)
+                        .execute(connection)?;
+                }
+
+                Ok::<_, diesel::result::Error>(())
+            })
         })
         .await?;
 
@@ -314,159 +130,47 @@ impl
     }
 }
 
-#[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages"]
-struct InsertableEthereumBitcoinErc20BitcoinRequestMessage {
-    swap_id: Text<SwapId>,
-    ethereum_chain_id: U32,
-    bitcoin_network: Text<BitcoinNetwork>,
-    erc20_amount: Text<Erc20Amount>,
-    erc20_token_contract: Text<EthereumAddress>,
-    bitcoin_amount: Text<Satoshis>,
-    hash_function: Text<HashFunction>,
-    ethereum_refund_identity: Text<EthereumAddress>,
-    bitcoin_redeem_identity: Text<::bitcoin::PublicKey>,
-    ethereum_expiry: U32,
-    bitcoin_expiry: U32,
-    secret_hash: Text<SecretHash>,
-}
-
-#[impl_template]
 #[async_trait]
-impl
-    Save<
-        Request<
-            Ethereum,
-            ((
-                ledger::bitcoin::Mainnet,
-                ledger::bitcoin::Testnet,
-                ledger::bitcoin::Regtest,
-            )),
-            asset::Erc20,
-            asset::Bitcoin,
-            identity::Ethereum,
-            identity::Bitcoin,
-        >,
-    > for Sqlite
+impl<AI, BI> Save<Accept<AI, BI>> for Database
+where
+    AI: StoredIdentity + Send + 'static,
+    BI: StoredIdentity + Send + 'static,
 {
-    async fn save(
-        &self,
-        message: Request<
-            Ethereum,
-            __TYPE0__,
-            asset::Erc20,
-            asset::Bitcoin,
-            identity::Ethereum,
-            identity::Bitcoin,
-        >,
-    ) -> anyhow::Result<()> {
-        let Request {
-            swap_id,
-            alpha_ledger,
-            alpha_asset,
-            beta_asset,
-            hash_function,
-            alpha_ledger_refund_identity,
-            beta_ledger_redeem_identity,
-            alpha_expiry,
-            beta_expiry,
-            secret_hash,
-            ..
-        } = message;
-
-        let insertable = InsertableEthereumBitcoinErc20BitcoinRequestMessage {
-            swap_id: Text(swap_id),
-            ethereum_chain_id: U32(alpha_ledger.chain_id.into()),
-            bitcoin_network: Text(BitcoinNetwork::from(__TYPE0__)),
-            erc20_amount: Text(alpha_asset.quantity.into()),
-            erc20_token_contract: Text(alpha_asset.token_contract.into()),
-            bitcoin_amount: Text(beta_asset.into()),
-            hash_function: Text(hash_function),
-            ethereum_refund_identity: Text(alpha_ledger_refund_identity.into()),
-            bitcoin_redeem_identity: Text(beta_ledger_redeem_identity.into()),
-            ethereum_expiry: U32(alpha_expiry.into()),
-            bitcoin_expiry: U32(beta_expiry.into()),
-            secret_hash: Text(secret_hash),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages::table)
-                .values(&insertable)
-                .execute(connection)
-        })
-        .await?;
-
-        Ok(())
-    }
-}
-
-#[derive(Insertable, Debug, Copy, Clone)]
-#[table_name = "rfc003_ethereum_bitcoin_accept_messages"]
-struct InsertableEthereumBitcoinAcceptMessage {
-    swap_id: Text<SwapId>,
-    ethereum_redeem_identity: Text<EthereumAddress>,
-    bitcoin_refund_identity: Text<bitcoin::PublicKey>,
-}
-
-#[async_trait]
-impl Save<Accept<identity::Ethereum, identity::Bitcoin>> for Sqlite {
-    async fn save(
-        &self,
-        message: Accept<identity::Ethereum, identity::Bitcoin>,
-    ) -> anyhow::Result<()> {
-        let Accept {
-            swap_id,
-            alpha_ledger_redeem_identity,
-            beta_ledger_refund_identity,
-        } = message;
-
-        let insertable = InsertableEthereumBitcoinAcceptMessage {
-            swap_id: Text(swap_id),
-            ethereum_redeem_identity: Text(alpha_ledger_redeem_identity.into()),
-            bitcoin_refund_identity: Text(beta_ledger_refund_identity.into()),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_ethereum_bitcoin_accept_messages::table)
-                .values(&insertable)
-                .execute(&*connection)
-        })
-        .await?;
-
-        Ok(())
-    }
-}
-
-#[derive(Insertable, Debug, Copy, Clone)]
-#[table_name = "rfc003_bitcoin_ethereum_accept_messages"]
-struct InsertableBitcoinEthereumAcceptMessage {
-    swap_id: Text<SwapId>,
-    bitcoin_redeem_identity: Text<bitcoin::PublicKey>,
-    ethereum_refund_identity: Text<EthereumAddress>,
-}
-
-#[async_trait]
-impl Save<Accept<identity::Bitcoin, identity::Ethereum>> for Sqlite {
-    async fn save(
-        &self,
-        message: Accept<identity::Bitcoin, identity::Ethereum>,
-    ) -> anyhow::Result<()> {
+    async fn save(&self, message: Accept<AI, BI>) -> anyhow::Result<()> {
         let Accept {
             swap_id,
             alpha_ledger_redeem_identity,
             beta_ledger_refund_identity,
         } = message;
 
-        let insertable = InsertableBitcoinEthereumAcceptMessage {
-            swap_id: Text(swap_id),
-            bitcoin_redeem_identity: Text(alpha_ledger_redeem_identity.into()),
-            ethereum_refund_identity: Text(beta_ledger_refund_identity.into()),
-        };
-
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_bitcoin_ethereum_accept_messages::table)
-                .values(&insertable)
-                .execute(&*connection)
+        let alpha_ledger_redeem_identity = alpha_ledger_redeem_identity.to_column();
+        let beta_ledger_refund_identity = beta_ledger_refund_identity.to_column();
+        let accepted_at = Utc::now().naive_utc();
+
+        self.do_in_write_transaction(move |connection| {
+            let key = Text(swap_id);
+
+            with_connection!(connection => {
+                diesel::update(
+                    swap_ledgers::table
+                        .filter(swap_ledgers::swap_id.eq(&key))
+                        .filter(swap_ledgers::side.eq(Text(LedgerSide::Alpha))),
+                )
+                .set(swap_ledgers::redeem_identity.eq(&alpha_ledger_redeem_identity))
+                .execute(connection)?;
+
+                diesel::update(
+                    swap_ledgers::table
+                        .filter(swap_ledgers::swap_id.eq(&key))
+                        .filter(swap_ledgers::side.eq(Text(LedgerSide::Beta))),
+                )
+                .set(swap_ledgers::refund_identity.eq(&beta_ledger_refund_identity))
+                .execute(connection)?;
+
+                diesel::update(swaps::table.filter(swaps::swap_id.eq(&key)))
+                    .set(swaps::accepted_at.eq(accepted_at))
+                    .execute(connection)
+            })
         })
         .await?;
 
@@ -475,14 +179,14 @@ impl Save<Accept<identity::Bitcoin, identity::Ethereum>> for Sqlite {
 }
 
 #[derive(Insertable, Debug, Clone)]
-#[table_name = "rfc003_decline_messages"]
+#[table_name = "swap_declines"]
 struct InsertableDeclineMessage {
     swap_id: Text<SwapId>,
     reason: Option<String>,
 }
 
 #[async_trait]
-impl Save<Decline> for Sqlite {
+impl Save<Decline> for Database {
     async fn save(&self, message: Decline) -> anyhow::Result<()> {
         let Decline {
             swap_id,
@@ -495,10 +199,14 @@ impl Save<Decline> for Sqlite {
             reason: None,
         };
 
-        self.do_in_transaction(|connection| {
-            diesel::insert_into(rfc003_decline_messages::table)
-                .values(&insertable)
-                .execute(&*connection)
+        self.do_in_write_transaction(move |connection| {
+            with_connection!(connection => {
+                diesel::insert_into(swap_declines::table)
+                    .values(&insertable)
+                    .execute(&*connection)
+            })?;
+
+            mark_if_finished(connection, &insertable.swap_id)
         })
         .await?;
 
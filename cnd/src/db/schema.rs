@@ -1,95 +1,110 @@
 table! {
-   rfc003_bitcoin_ethereum_bitcoin_ether_request_messages {
+   swap_declines {
        id -> Integer,
        swap_id -> Text,
-       bitcoin_network -> Text,
-       ethereum_chain_id -> BigInt,
-       bitcoin_amount -> Text,
-       ether_amount -> Text,
-       hash_function -> Text,
-       bitcoin_refund_identity -> Text,
-       ethereum_redeem_identity -> Text,
-       bitcoin_expiry -> BigInt,
-       ethereum_expiry -> BigInt,
-       secret_hash -> Text,
+       reason -> Nullable<Text>,
    }
 }
 
 table! {
-   rfc003_ethereum_bitcoin_ether_bitcoin_request_messages {
+   swaps {
        id -> Integer,
        swap_id -> Text,
-       ethereum_chain_id -> BigInt,
-       bitcoin_network -> Text,
-       ether_amount -> Text,
-       bitcoin_amount -> Text,
-       hash_function -> Text,
-       ethereum_refund_identity -> Text,
-       bitcoin_redeem_identity -> Text,
-       ethereum_expiry -> BigInt,
-       bitcoin_expiry -> BigInt,
-       secret_hash -> Text,
+       role -> Text,
+       counterparty -> Text,
+       hash_function -> Nullable<Text>,
+       secret_hash -> Nullable<Text>,
+       accepted_at -> Nullable<Timestamp>,
+       final_status -> Nullable<Text>,
+       finished_at -> Nullable<Timestamp>,
    }
 }
 
 table! {
-   rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages {
+   swap_ledgers {
        id -> Integer,
        swap_id -> Text,
-       bitcoin_network -> Text,
-       ethereum_chain_id -> BigInt,
-       bitcoin_amount -> Text,
-       erc20_amount -> Text,
-       erc20_token_contract -> Text,
-       hash_function -> Text,
-       bitcoin_refund_identity -> Text,
-       ethereum_redeem_identity -> Text,
-       bitcoin_expiry -> BigInt,
-       ethereum_expiry -> BigInt,
-       secret_hash -> Text,
+       side -> Text,
+       ledger -> Text,
+       bitcoin_network -> Nullable<Text>,
+       ethereum_chain_id -> Nullable<BigInt>,
+       expiry -> BigInt,
+       redeem_identity -> Nullable<Text>,
+       refund_identity -> Nullable<Text>,
    }
 }
 
 table! {
-   rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages {
+   swap_assets {
        id -> Integer,
        swap_id -> Text,
-       ethereum_chain_id -> BigInt,
-       bitcoin_network -> Text,
-       erc20_amount -> Text,
-       erc20_token_contract -> Text,
-       bitcoin_amount -> Text,
-       hash_function -> Text,
-       ethereum_refund_identity -> Text,
-       bitcoin_redeem_identity -> Text,
-       ethereum_expiry -> BigInt,
-       bitcoin_expiry -> BigInt,
-       secret_hash -> Text,
+       side -> Text,
+       asset -> Text,
+       quantity -> Text,
+       token_contract -> Nullable<Text>,
    }
 }
 
 table! {
-   rfc003_ethereum_bitcoin_accept_messages {
+   seed_fingerprint {
+       id -> Integer,
+       fingerprint -> Text,
+   }
+}
+
+table! {
+   rfc003_ledger_events {
        id -> Integer,
        swap_id -> Text,
-       ethereum_redeem_identity -> Text,
-       bitcoin_refund_identity -> Text,
+       ledger -> Text,
+       event -> Text,
+       transaction_id -> Text,
+       data -> Text,
        at -> Timestamp,
    }
 }
 
 table! {
-   rfc003_bitcoin_ethereum_accept_messages {
+   archived_swaps {
        id -> Integer,
        swap_id -> Text,
-       bitcoin_redeem_identity -> Text,
-       ethereum_refund_identity -> Text,
-       at -> Timestamp,
+       role -> Text,
+       counterparty -> Text,
+       hash_function -> Nullable<Text>,
+       secret_hash -> Nullable<Text>,
+       accepted_at -> Nullable<Timestamp>,
+       final_status -> Text,
+       finished_at -> Timestamp,
+   }
+}
+
+table! {
+   archived_swap_ledgers {
+       id -> Integer,
+       swap_id -> Text,
+       side -> Text,
+       ledger -> Text,
+       bitcoin_network -> Nullable<Text>,
+       ethereum_chain_id -> Nullable<BigInt>,
+       expiry -> BigInt,
+       redeem_identity -> Nullable<Text>,
+       refund_identity -> Nullable<Text>,
    }
 }
 
 table! {
-   rfc003_decline_messages {
+   archived_swap_assets {
+       id -> Integer,
+       swap_id -> Text,
+       side -> Text,
+       asset -> Text,
+       quantity -> Text,
+       token_contract -> Nullable<Text>,
+   }
+}
+
+table! {
+   archived_swap_declines {
        id -> Integer,
        swap_id -> Text,
        reason -> Nullable<Text>,
@@ -97,10 +112,13 @@ table! {
 }
 
 table! {
-   rfc003_swaps {
+   archived_ledger_events {
        id -> Integer,
        swap_id -> Text,
-       role -> Text,
-       counterparty -> Text,
+       ledger -> Text,
+       event -> Text,
+       transaction_id -> Text,
+       data -> Text,
+       at -> Timestamp,
    }
 }
@@ -0,0 +1,127 @@
+use crate::{
+    db::{
+        schema::{self, *},
+        Database, Error,
+    },
+    seed::RootSeed,
+};
+use diesel::prelude::*;
+
+impl Database {
+    /// Make sure the swaps in this database were created with the given seed.
+    ///
+    /// The fingerprint of the seed is recorded the first time this is called,
+    /// subsequent calls fail if the fingerprint of the given seed differs.
+    /// This prevents starting with a restored seed that cannot derive the
+    /// keys of the swaps that are already in the database.
+    ///
+    /// Databases created before fingerprints were recorded may already hold
+    /// swaps. Their seed cannot be checked, so the fingerprint is only
+    /// recorded for them if `trust_existing_swaps` is set.
+    pub async fn verify_seed(
+        &self,
+        seed: &RootSeed,
+        trust_existing_swaps: bool,
+    ) -> anyhow::Result<()> {
+        let fingerprint = seed.fingerprint();
+
+        self.do_in_write_transaction(move |connection| {
+            let stored = with_connection!(connection => {
+                schema::seed_fingerprint::table
+                    .select(schema::seed_fingerprint::fingerprint)
+                    .first::<String>(connection)
+                    .optional()
+            })?;
+
+            match stored {
+                Some(stored) if stored == fingerprint => Ok(()),
+                Some(_) => Err(anyhow::Error::from(Error::SeedMismatch)),
+                None => {
+                    let existing_swaps = with_connection!(connection => {
+                        let live = schema::swaps::table
+                            .count()
+                            .get_result::<i64>(connection)?;
+                        let archived = schema::archived_swaps::table
+                            .count()
+                            .get_result::<i64>(connection)?;
+
+                        live + archived
+                    });
+
+                    if existing_swaps > 0 && !trust_existing_swaps {
+                        return Err(anyhow::Error::from(Error::UnverifiableSeed));
+                    }
+
+                    with_connection!(connection => {
+                        diesel::insert_into(schema::seed_fingerprint::table)
+                            .values(&InsertableSeedFingerprint {
+                                fingerprint: fingerprint.clone(),
+                            })
+                            .execute(connection)
+                    })?;
+
+                    Ok(())
+                }
+            }
+        })
+        .await
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[table_name = "seed_fingerprint"]
+struct InsertableSeedFingerprint {
+    pub fingerprint: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{Save, Swap},
+        swap_protocols::{Role, SwapId},
+    };
+    use libp2p::PeerId;
+    use rand::rngs::OsRng;
+    use spectral::prelude::*;
+
+    #[tokio::test]
+    async fn database_refuses_a_different_seed() {
+        let path = tempfile::Builder::new()
+            .suffix(".sqlite")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        let db = Database::new(&path).unwrap();
+
+        let seed = RootSeed::new_random(OsRng).unwrap();
+        let other = RootSeed::new_random(OsRng).unwrap();
+
+        assert_that(&db.verify_seed(&seed, false).await).is_ok();
+        assert_that(&db.verify_seed(&seed, false).await).is_ok();
+        assert_that(&db.verify_seed(&other, false).await).is_err();
+        assert_that(&db.verify_seed(&other, true).await).is_err();
+    }
+
+    #[tokio::test]
+    async fn database_with_swaps_only_records_a_seed_when_trusted() {
+        let path = tempfile::Builder::new()
+            .suffix(".sqlite")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        let db = Database::new(&path).unwrap();
+        Save::save(
+            &db,
+            Swap::new(SwapId::default(), Role::Alice, PeerId::random()),
+        )
+        .await
+        .unwrap();
+
+        let seed = RootSeed::new_random(OsRng).unwrap();
+
+        assert_that(&db.verify_seed(&seed, false).await).is_err();
+        assert_that(&db.verify_seed(&seed, true).await).is_ok();
+        assert_that(&db.verify_seed(&seed, false).await).is_ok();
+    }
+}
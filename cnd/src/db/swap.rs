@@ -1,5 +1,5 @@
 use crate::{
-    db::{schema, wrapper_types::custom_sql_types::Text, Error, Sqlite},
+    db::{schema, wrapper_types::custom_sql_types::Text, Database, Error},
     diesel::{ExpressionMethods, OptionalExtension, QueryDsl},
     swap_protocols::{Role, SwapId},
 };
@@ -33,18 +33,23 @@ impl Swap {
 }
 
 #[async_trait]
-impl Retrieve for Sqlite {
+impl Retrieve for Database {
     async fn get(&self, key: &SwapId) -> anyhow::Result<Swap> {
-        use self::schema::rfc003_swaps::dsl::*;
+        use self::schema::swaps::dsl::*;
+
+        let key = *key;
 
         let record: QueryableSwap = self
-            .do_in_transaction(|connection| {
+            .do_in_transaction(move |connection| {
                 let key = Text(key);
 
-                rfc003_swaps
-                    .filter(swap_id.eq(key))
-                    .first(&*connection)
-                    .optional()
+                with_connection!(connection => {
+                    swaps
+                        .filter(swap_id.eq(key))
+                        .select((swap_id, role, counterparty))
+                        .first(connection)
+                        .optional()
+                })
             })
             .await?
             .ok_or(Error::SwapNotFound)?;
@@ -53,10 +58,16 @@ impl Retrieve for Sqlite {
     }
 
     async fn all(&self) -> anyhow::Result<Vec<Swap>> {
-        use self::schema::rfc003_swaps::dsl::*;
+        use self::schema::swaps::dsl::*;
 
         let records: Vec<QueryableSwap> = self
-            .do_in_transaction(|connection| rfc003_swaps.load(&*connection))
+            .do_in_transaction(move |connection| {
+                with_connection!(connection => {
+                    swaps
+                        .select((swap_id, role, counterparty))
+                        .load(connection)
+                })
+            })
             .await?;
 
         Ok(records.into_iter().map(|q| q.into()).collect())
@@ -65,7 +76,6 @@ impl Retrieve for Sqlite {
 
 #[derive(Queryable, Debug, Clone, PartialEq)]
 struct QueryableSwap {
-    pub id: i32,
     pub swap_id: Text<SwapId>,
     pub role: Text<Role>,
     pub counterparty: Text<PeerId>,
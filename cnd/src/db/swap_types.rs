@@ -1,11 +1,6 @@
 use crate::{
     asset, comit_api,
-    db::{
-        schema,
-        wrapper_types::{custom_sql_types::Text, BitcoinNetwork},
-        Sqlite,
-    },
-    diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl},
+    db::{rows::load_swap_rows, wrapper_types::custom_sql_types::Text, Database, Error},
     swap_protocols::{Role, SwapId},
 };
 use async_trait::async_trait;
@@ -22,107 +17,19 @@ pub trait DetermineTypes: Send + Sync + 'static {
 }
 
 #[async_trait]
-impl DetermineTypes for Sqlite {
+impl DetermineTypes for Database {
     async fn determine_types(&self, key: &SwapId) -> anyhow::Result<SwapTypes> {
-        let role = self.role(key).await?;
+        let key = *key;
 
-        if let Some(bitcoin_network) = self
-            .rfc003_bitcoin_ethereum_bitcoin_ether_request_messages_has_swap(key)
+        let rows = self
+            .do_in_transaction(move |connection| load_swap_rows(connection, &Text(key)))
             .await?
-        {
-            return Ok(SwapTypes {
-                alpha_ledger: bitcoin_network.into(),
-                beta_ledger: LedgerKind::Ethereum,
-                alpha_asset: AssetKind::Bitcoin,
-                beta_asset: AssetKind::Ether,
-                role,
-            });
-        }
-
-        if let Some(bitcoin_network) = self
-            .rfc003_ethereum_bitcoin_ether_bitcoin_request_messages_has_swap(key)
-            .await?
-        {
-            return Ok(SwapTypes {
-                alpha_ledger: LedgerKind::Ethereum,
-                beta_ledger: bitcoin_network.into(),
-                alpha_asset: AssetKind::Ether,
-                beta_asset: AssetKind::Bitcoin,
-                role,
-            });
-        }
-
-        if let Some(bitcoin_network) = self
-            .rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages_has_swap(key)
-            .await?
-        {
-            return Ok(SwapTypes {
-                alpha_ledger: bitcoin_network.into(),
-                beta_ledger: LedgerKind::Ethereum,
-                alpha_asset: AssetKind::Bitcoin,
-                beta_asset: AssetKind::Erc20,
-                role,
-            });
-        }
-
-        if let Some(bitcoin_network) = self
-            .rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages_has_swap(key)
-            .await?
-        {
-            return Ok(SwapTypes {
-                alpha_ledger: LedgerKind::Ethereum,
-                beta_ledger: bitcoin_network.into(),
-                alpha_asset: AssetKind::Erc20,
-                beta_asset: AssetKind::Bitcoin,
-                role,
-            });
-        }
+            .ok_or(Error::SwapNotFound)?;
 
-        unreachable!("we got role for swap so the swap_id must exist")
+        rows.swap_types()
     }
 }
 
-macro_rules! impl_has_swap {
-    ($table:ident) => {
-        paste::item! {
-            async fn [<$table _has_swap>](&self, key: &SwapId) -> anyhow::Result<Option<BitcoinNetwork>> {
-                use schema::$table as swaps;
-
-                let record: Option<QueryableSwap> = self.do_in_transaction(|connection| {
-                    let key = Text(key);
-                    swaps::table
-                        .filter(swaps::swap_id.eq(key))
-                        .select((swaps::swap_id,swaps::bitcoin_network))
-                        .first(&*connection)
-                        .optional()
-                })
-                .await?;
-
-                match record {
-                    None => Ok(None),
-                    Some(swap) => {
-                        let network: BitcoinNetwork = *swap.bitcoin_network;
-                        Ok(Some(network))
-                    }
-                }
-            }
-        }
-    };
-}
-
-impl Sqlite {
-    impl_has_swap!(rfc003_bitcoin_ethereum_bitcoin_ether_request_messages);
-    impl_has_swap!(rfc003_ethereum_bitcoin_ether_bitcoin_request_messages);
-    impl_has_swap!(rfc003_bitcoin_ethereum_bitcoin_erc20_request_messages);
-    impl_has_swap!(rfc003_ethereum_bitcoin_erc20_bitcoin_request_messages);
-}
-
-#[derive(Queryable, Debug, Clone, PartialEq)]
-struct QueryableSwap {
-    swap_id: Text<SwapId>,
-    bitcoin_network: Text<BitcoinNetwork>,
-}
-
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SwapTypes {
     pub alpha_ledger: LedgerKind,
@@ -142,6 +49,8 @@ pub enum BitcoinLedgerKind {
 #[derive(Debug, Clone, Copy, Display, PartialEq)]
 pub enum LedgerKind {
     Bitcoin(BitcoinLedgerKind),
+    /// Litecoin has the same networks as Bitcoin.
+    Litecoin(BitcoinLedgerKind),
     Ethereum,
 }
 
@@ -157,6 +66,15 @@ impl From<comit_api::LedgerKind> for LedgerKind {
             comit_api::LedgerKind::BitcoinRegtest => {
                 LedgerKind::Bitcoin(BitcoinLedgerKind::Regtest)
             }
+            comit_api::LedgerKind::LitecoinMainnet => {
+                LedgerKind::Litecoin(BitcoinLedgerKind::Mainnet)
+            }
+            comit_api::LedgerKind::LitecoinTestnet => {
+                LedgerKind::Litecoin(BitcoinLedgerKind::Testnet)
+            }
+            comit_api::LedgerKind::LitecoinRegtest => {
+                LedgerKind::Litecoin(BitcoinLedgerKind::Regtest)
+            }
             comit_api::LedgerKind::Ethereum(_) => LedgerKind::Ethereum,
         }
     }
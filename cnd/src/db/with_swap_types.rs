@@ -28,7 +28,7 @@ macro_rules! with_swap_types {
             asset,
             db::{AssetKind, BitcoinLedgerKind, LedgerKind, SwapTypes},
             htlc_location, identity,
-            swap_protocols::ledger::{bitcoin, Ethereum},
+            swap_protocols::ledger::{bitcoin, litecoin, Ethereum},
             transaction,
         };
         let swap_types: SwapTypes = $swap_types;
@@ -392,6 +392,484 @@ macro_rules! with_swap_types {
                     _match_role!(role, $fn)
                 }
             },
+            SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin(litecoin_ledger_kind),
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Bitcoin,
+                beta_asset: AssetKind::Ether,
+                ..
+            } => match litecoin_ledger_kind {
+                BitcoinLedgerKind::Mainnet => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Mainnet;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Ether;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Testnet => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Testnet;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Ether;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Regtest => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Regtest;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Ether;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+            },
+            SwapTypes {
+                alpha_ledger: LedgerKind::Litecoin(litecoin_ledger_kind),
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset: AssetKind::Bitcoin,
+                beta_asset: AssetKind::Erc20,
+                ..
+            } => match litecoin_ledger_kind {
+                BitcoinLedgerKind::Testnet => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Testnet;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Regtest => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Regtest;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Mainnet => {
+                    #[allow(dead_code)]
+                    type AL = litecoin::Mainnet;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type BA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRefund<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+            },
+
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin(litecoin_ledger_kind),
+                alpha_asset: AssetKind::Ether,
+                beta_asset: AssetKind::Bitcoin,
+                ..
+            } => match litecoin_ledger_kind {
+                BitcoinLedgerKind::Mainnet => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Mainnet;
+                    #[allow(dead_code)]
+                    type AA = asset::Ether;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Testnet => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Testnet;
+                    #[allow(dead_code)]
+                    type AA = asset::Ether;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Regtest => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Regtest;
+                    #[allow(dead_code)]
+                    type AA = asset::Ether;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+            },
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Litecoin(litecoin_ledger_kind),
+                alpha_asset: AssetKind::Erc20,
+                beta_asset: AssetKind::Bitcoin,
+                ..
+            } => match litecoin_ledger_kind {
+                BitcoinLedgerKind::Mainnet => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Mainnet;
+                    #[allow(dead_code)]
+                    type AA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Testnet => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Testnet;
+                    #[allow(dead_code)]
+                    type AA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+                BitcoinLedgerKind::Regtest => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = litecoin::Regtest;
+                    #[allow(dead_code)]
+                    type AA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type BA = asset::Bitcoin;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Bitcoin;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Bitcoin;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Bitcoin;
+                    #[allow(dead_code)]
+                    type AcceptBody =
+                        crate::http_api::routes::rfc003::accept::OnlyRedeem<identity::Ethereum>;
+
+                    _match_role!(role, $fn)
+                }
+            },
+            SwapTypes {
+                alpha_ledger: LedgerKind::Ethereum,
+                beta_ledger: LedgerKind::Ethereum,
+                alpha_asset,
+                beta_asset,
+                ..
+            } => match (alpha_asset, beta_asset) {
+                (AssetKind::Ether, AssetKind::Erc20) => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Ether;
+                    #[allow(dead_code)]
+                    type BA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<
+                        identity::Ethereum,
+                    >;
+
+                    _match_role!(role, $fn)
+                }
+                (AssetKind::Erc20, AssetKind::Ether) => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type BA = asset::Ether;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<
+                        identity::Ethereum,
+                    >;
+
+                    _match_role!(role, $fn)
+                }
+                (AssetKind::Erc20, AssetKind::Erc20) => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type BA = asset::Erc20;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<
+                        identity::Ethereum,
+                    >;
+
+                    _match_role!(role, $fn)
+                }
+                (AssetKind::Ether, AssetKind::Ether) => {
+                    #[allow(dead_code)]
+                    type AL = Ethereum;
+                    #[allow(dead_code)]
+                    type BL = Ethereum;
+                    #[allow(dead_code)]
+                    type AA = asset::Ether;
+                    #[allow(dead_code)]
+                    type BA = asset::Ether;
+                    #[allow(dead_code)]
+                    type AH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type BH = htlc_location::Ethereum;
+                    #[allow(dead_code)]
+                    type AI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type BI = identity::Ethereum;
+                    #[allow(dead_code)]
+                    type AT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type BT = transaction::Ethereum;
+                    #[allow(dead_code)]
+                    type AcceptBody = crate::http_api::routes::rfc003::accept::RedeemAndRefund<
+                        identity::Ethereum,
+                    >;
+
+                    _match_role!(role, $fn)
+                }
+                _ => unimplemented!(),
+            },
             _ => unimplemented!(),
         }
     }};
@@ -1,4 +1,4 @@
-use crate::{asset, asset::Bitcoin, db::LedgerKind, identity, swap_protocols::ledger};
+use crate::{asset, asset::Bitcoin, identity, swap_protocols::ledger};
 use std::{fmt, str::FromStr};
 
 pub mod custom_sql_types;
@@ -183,12 +183,12 @@ impl_from_for_bitcoinnetwork!(Mainnet);
 impl_from_for_bitcoinnetwork!(Testnet);
 impl_from_for_bitcoinnetwork!(Regtest);
 
-impl From<BitcoinNetwork> for LedgerKind {
+impl From<BitcoinNetwork> for BitcoinLedgerKind {
     fn from(network: BitcoinNetwork) -> Self {
         match network {
-            BitcoinNetwork::Mainnet => LedgerKind::Bitcoin(BitcoinLedgerKind::Mainnet),
-            BitcoinNetwork::Testnet => LedgerKind::Bitcoin(BitcoinLedgerKind::Testnet),
-            BitcoinNetwork::Regtest => LedgerKind::Bitcoin(BitcoinLedgerKind::Regtest),
+            BitcoinNetwork::Mainnet => BitcoinLedgerKind::Mainnet,
+            BitcoinNetwork::Testnet => BitcoinLedgerKind::Testnet,
+            BitcoinNetwork::Regtest => BitcoinLedgerKind::Regtest,
         }
     }
 }
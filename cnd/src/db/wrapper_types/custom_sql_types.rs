@@ -5,6 +5,7 @@ use diesel::{
     serialize::{self, Output, ToSql},
     sql_types,
 };
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::TryFrom, fmt, ops::Deref, str::FromStr};
 
 /// Custom diesel new-type that works as long as T implements `Display` and
@@ -51,8 +52,38 @@ where
     }
 }
 
+/// Serializes to the same string that is stored in the database.
+impl<T> Serialize for Text<T>
+where
+    T: fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Text<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: fmt::Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let parsed = T::from_str(&s).map_err(D::Error::custom)?;
+
+        Ok(Text(parsed))
+    }
+}
+
 // Custom diesel new type for enforcing storage of a u32
-#[derive(Debug, Clone, Copy, PartialEq, FromSqlRow, AsExpression)]
+#[derive(Debug, Clone, Copy, PartialEq, FromSqlRow, AsExpression, Serialize, Deserialize)]
+#[serde(transparent)]
 #[sql_type = "sql_types::BigInt"]
 pub struct U32(pub u32);
 
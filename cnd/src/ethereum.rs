@@ -151,7 +151,7 @@ impl TransactionReceipt {
 }
 
 /// Description of a Transaction, pending or in the chain.
-#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Transaction {
     /// Hash
     pub hash: Hash,
@@ -18,7 +18,7 @@ use crate::{
     asset, htlc_location, identity,
     network::DialInformation,
     swap_protocols::{
-        ledger::{self, bitcoin::Network, ethereum::ChainId, Bitcoin},
+        ledger::{self, bitcoin::Network, ethereum::ChainId},
         Role, SwapId, SwapProtocol,
     },
     transaction,
@@ -306,6 +306,9 @@ pub enum HttpLedger {
     BitcoinMainnet(ledger::bitcoin::Mainnet),
     BitcoinTestnet(ledger::bitcoin::Testnet),
     BitcoinRegtest(ledger::bitcoin::Regtest),
+    LitecoinMainnet(ledger::litecoin::Mainnet),
+    LitecoinTestnet(ledger::litecoin::Testnet),
+    LitecoinRegtest(ledger::litecoin::Regtest),
     Ethereum(ledger::Ethereum),
 }
 
@@ -333,6 +336,7 @@ pub enum HttpAsset {
 #[serde(rename_all = "lowercase")]
 pub enum HttpLedgerParams {
     Bitcoin(BitcoinLedgerParams),
+    Litecoin(BitcoinLedgerParams),
     Ethereum(EthereumLedgerParams),
 }
 
@@ -341,7 +345,9 @@ pub struct BitcoinLedgerParams {
     network: Http<bitcoin::Network>,
 }
 
-impl<B: Bitcoin + Network> From<B> for BitcoinLedgerParams {
+/// Litecoin has the same networks as Bitcoin, hence it is described by the same
+/// parameters.
+impl<B: Network> From<B> for BitcoinLedgerParams {
     fn from(_: B) -> Self {
         BitcoinLedgerParams {
             network: Http(B::network()),
@@ -395,6 +401,11 @@ impl TryFrom<HttpLedgerParams> for HttpLedger {
                 bitcoin::Network::Testnet => HttpLedger::BitcoinTestnet(ledger::bitcoin::Testnet),
                 bitcoin::Network::Regtest => HttpLedger::BitcoinRegtest(ledger::bitcoin::Regtest),
             },
+            HttpLedgerParams::Litecoin(BitcoinLedgerParams { network }) => match *network {
+                bitcoin::Network::Bitcoin => HttpLedger::LitecoinMainnet(ledger::litecoin::Mainnet),
+                bitcoin::Network::Testnet => HttpLedger::LitecoinTestnet(ledger::litecoin::Testnet),
+                bitcoin::Network::Regtest => HttpLedger::LitecoinRegtest(ledger::litecoin::Regtest),
+            },
             HttpLedgerParams::Ethereum(params) => HttpLedger::Ethereum(params.try_into()?),
         })
     }
@@ -406,6 +417,9 @@ impl From<HttpLedger> for HttpLedgerParams {
             HttpLedger::BitcoinMainnet(ledger) => HttpLedgerParams::Bitcoin(ledger.into()),
             HttpLedger::BitcoinTestnet(ledger) => HttpLedgerParams::Bitcoin(ledger.into()),
             HttpLedger::BitcoinRegtest(ledger) => HttpLedgerParams::Bitcoin(ledger.into()),
+            HttpLedger::LitecoinMainnet(ledger) => HttpLedgerParams::Litecoin(ledger.into()),
+            HttpLedger::LitecoinTestnet(ledger) => HttpLedgerParams::Litecoin(ledger.into()),
+            HttpLedger::LitecoinRegtest(ledger) => HttpLedgerParams::Litecoin(ledger.into()),
             HttpLedger::Ethereum(ledger) => HttpLedgerParams::Ethereum(ledger.into()),
         }
     }
@@ -522,6 +536,24 @@ impl From<ledger::bitcoin::Regtest> for HttpLedger {
     }
 }
 
+impl From<ledger::litecoin::Mainnet> for HttpLedger {
+    fn from(ledger: ledger::litecoin::Mainnet) -> Self {
+        HttpLedger::LitecoinMainnet(ledger)
+    }
+}
+
+impl From<ledger::litecoin::Testnet> for HttpLedger {
+    fn from(ledger: ledger::litecoin::Testnet) -> Self {
+        HttpLedger::LitecoinTestnet(ledger)
+    }
+}
+
+impl From<ledger::litecoin::Regtest> for HttpLedger {
+    fn from(ledger: ledger::litecoin::Regtest) -> Self {
+        HttpLedger::LitecoinRegtest(ledger)
+    }
+}
+
 impl From<ledger::Ethereum> for HttpLedger {
     fn from(ethereum: ledger::Ethereum) -> Self {
         HttpLedger::Ethereum(ethereum)
@@ -555,7 +587,7 @@ mod tests {
         ethereum::{Address, Hash, U256},
         http_api::{Http, HttpAsset, HttpLedger},
         swap_protocols::{
-            ledger::{bitcoin, ethereum, Ethereum},
+            ledger::{bitcoin, ethereum, litecoin, Ethereum},
             HashFunction, SwapId, SwapProtocol,
         },
         transaction,
@@ -617,6 +649,38 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn litecoin_http_ledger_serializes_correctly_to_json() {
+        let input = &[
+            HttpLedger::from(litecoin::Mainnet),
+            HttpLedger::from(litecoin::Testnet),
+            HttpLedger::from(litecoin::Regtest),
+        ];
+
+        let expected = &[
+            r#"{"name":"litecoin","network":"mainnet"}"#,
+            r#"{"name":"litecoin","network":"testnet"}"#,
+            r#"{"name":"litecoin","network":"regtest"}"#,
+        ];
+
+        let actual = input
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<String>, serde_json::Error>>()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn litecoin_http_ledger_deserializes_from_json() {
+        let ledger =
+            serde_json::from_str::<HttpLedger>(r#"{"name":"litecoin","network":"testnet"}"#)
+                .unwrap();
+
+        assert_eq!(ledger, HttpLedger::LitecoinTestnet(litecoin::Testnet));
+    }
+
     #[test]
     fn ethereum_http_ledger_regtest_serializes_correctly_to_json() {
         let input = &[
@@ -5,7 +5,7 @@ use crate::{
     swap_protocols::{
         actions::{
             bitcoin::{SendToAddress, SpendOutput},
-            ethereum, lnd,
+            ethereum, litecoin, lnd,
             lnd::Chain,
         },
         ledger,
@@ -16,7 +16,7 @@ use crate::{
     transaction,
 };
 use anyhow::Context;
-use blockchain_contracts::bitcoin::witness;
+use blockchain_contracts::bitcoin::witness::{self, PrimedTransaction};
 use http_api_problem::HttpApiProblem;
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
@@ -37,6 +37,12 @@ pub enum ActionExecutionParameters {
         address: bitcoin::Address,
         fee_per_wu: String,
     },
+    /// An address of a chain derived from Bitcoin, decoded with the
+    /// parameters of the chain the action is for.
+    UtxoChainAddressAndFee {
+        address: String,
+        fee_per_wu: String,
+    },
     None {},
 }
 
@@ -55,6 +61,17 @@ pub enum ActionResponseBody {
         #[serde(skip_serializing_if = "Option::is_none")]
         min_median_block_time: Option<Timestamp>,
     },
+    LitecoinSendAmountToAddress {
+        to: String,
+        amount: String,
+        network: Http<bitcoin::Network>,
+    },
+    LitecoinBroadcastSignedTransaction {
+        hex: String,
+        network: Http<bitcoin::Network>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min_median_block_time: Option<Timestamp>,
+    },
     EthereumDeployContract {
         data: crate::ethereum::Bytes,
         amount: asset::Ether,
@@ -102,21 +119,69 @@ impl ActionResponseBody {
         transaction: &transaction::Bitcoin,
         network: bitcoin::Network,
     ) -> Self {
-        let min_median_block_time = if transaction.lock_time == 0 {
-            None
-        } else {
-            // The first time a tx with lock_time can be broadcasted is when
-            // mediantime == locktime + 1
-            let min_median_block_time = transaction.lock_time + 1;
-            Some(Timestamp::from(min_median_block_time))
-        };
-
         ActionResponseBody::BitcoinBroadcastSignedTransaction {
             hex: bitcoin::consensus::encode::serialize_hex(transaction),
             network: Http(network),
-            min_median_block_time,
+            min_median_block_time: min_median_block_time(transaction),
         }
     }
+
+    fn litecoin_broadcast_signed_transaction(
+        transaction: &transaction::Bitcoin,
+        network: bitcoin::Network,
+    ) -> Self {
+        ActionResponseBody::LitecoinBroadcastSignedTransaction {
+            hex: bitcoin::consensus::encode::serialize_hex(transaction),
+            network: Http(network),
+            min_median_block_time: min_median_block_time(transaction),
+        }
+    }
+}
+
+fn min_median_block_time(transaction: &transaction::Bitcoin) -> Option<Timestamp> {
+    if transaction.lock_time == 0 {
+        None
+    } else {
+        // The first time a tx with lock_time can be broadcasted is when
+        // mediantime == locktime + 1
+        let min_median_block_time = transaction.lock_time + 1;
+        Some(Timestamp::from(min_median_block_time))
+    }
+}
+
+fn sign_with_rate(
+    transaction: PrimedTransaction,
+    fee_per_wu: String,
+) -> anyhow::Result<transaction::Bitcoin> {
+    let fee_per_wu = fee_per_wu.parse::<usize>().with_context(|| {
+        HttpApiProblem::new("Invalid query parameter.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail("Query parameter fee-per-byte is not a valid unsigned integer.")
+    })?;
+
+    let transaction = transaction
+        .sign_with_rate(&*crate::SECP, fee_per_wu)
+        .map_err(|e| {
+            tracing::error!("Could not sign Bitcoin transaction: {:?}", e);
+            match e {
+                witness::Error::FeeHigherThanInputValue => HttpApiProblem::new(
+                    "Fee is too high.",
+                )
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail(
+                    "The Fee per byte/WU provided makes the total fee higher than the spendable input value.",
+                ),
+                witness::Error::OverflowingFee => HttpApiProblem::new(
+                    "Fee is too high.",
+                )
+                    .set_status(StatusCode::BAD_REQUEST)
+                    .set_detail(
+                        "The Fee per byte/WU provided makes the total fee higher than the system supports.",
+                    )
+            }
+        })?;
+
+    Ok(transaction)
 }
 
 pub trait IntoResponsePayload {
@@ -276,35 +341,8 @@ impl IntoResponsePayload for SpendOutput {
                 address,
                 fee_per_wu,
             } => {
-                let fee_per_wu = fee_per_wu.parse::<usize>().with_context(|| {
-                    HttpApiProblem::new("Invalid query parameter.")
-                        .set_status(StatusCode::BAD_REQUEST)
-                        .set_detail("Query parameter fee-per-byte is not a valid unsigned integer.")
-                })?;
-
                 let network = self.network;
-                let transaction =
-                    self.spend_to(address)
-                        .sign_with_rate(&*crate::SECP, fee_per_wu)
-                        .map_err(|e| {
-                            tracing::error!("Could not sign Bitcoin transaction: {:?}", e);
-                            match e {
-                                witness::Error::FeeHigherThanInputValue => HttpApiProblem::new(
-                                    "Fee is too high.",
-                                )
-                                .set_status(StatusCode::BAD_REQUEST)
-                                .set_detail(
-                                    "The Fee per byte/WU provided makes the total fee higher than the spendable input value.",
-                                ),
-                                witness::Error::OverflowingFee => HttpApiProblem::new(
-                                    "Fee is too high.",
-                                )
-                                    .set_status(StatusCode::BAD_REQUEST)
-                                    .set_detail(
-                                        "The Fee per byte/WU provided makes the total fee higher than the system supports.",
-                                    )
-                            }
-                        })?;
+                let transaction = sign_with_rate(self.spend_to(address), fee_per_wu)?;
 
                 Ok(ActionResponseBody::bitcoin_broadcast_signed_transaction(
                     &transaction,
@@ -358,6 +396,114 @@ impl ListRequiredFields for SpendOutput {
     }
 }
 
+impl IntoResponsePayload for litecoin::SendToAddress {
+    fn into_response_payload(
+        self,
+        query_params: ActionExecutionParameters,
+    ) -> anyhow::Result<ActionResponseBody> {
+        match query_params {
+            ActionExecutionParameters::None {} => Ok(self.into()),
+            _ => Err(anyhow::Error::from(UnexpectedQueryParameters {
+                action: "litecoin::SendToAddress",
+                parameters: &["address", "fee_per_wu"],
+            })),
+        }
+    }
+}
+
+impl From<litecoin::SendToAddress> for ActionResponseBody {
+    fn from(action: litecoin::SendToAddress) -> Self {
+        let litecoin::SendToAddress {
+            to,
+            amount,
+            network,
+        } = action;
+        ActionResponseBody::LitecoinSendAmountToAddress {
+            to,
+            amount: amount.as_sat().to_string(),
+            network: Http(network),
+        }
+    }
+}
+
+impl ListRequiredFields for litecoin::SendToAddress {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![]
+    }
+}
+
+impl IntoResponsePayload for litecoin::SpendOutput {
+    fn into_response_payload(
+        self,
+        query_params: ActionExecutionParameters,
+    ) -> anyhow::Result<ActionResponseBody> {
+        // Testnet and regtest addresses of Litecoin and Bitcoin may share
+        // their encoding, hence both kinds of parameters are accepted.
+        let (address, fee_per_wu) = match query_params {
+            ActionExecutionParameters::BitcoinAddressAndFee {
+                address,
+                fee_per_wu,
+            } => (address.to_string(), fee_per_wu),
+            ActionExecutionParameters::UtxoChainAddressAndFee {
+                address,
+                fee_per_wu,
+            } => (address, fee_per_wu),
+            ActionExecutionParameters::None {} => {
+                return Err(anyhow::Error::from(MissingQueryParameters {
+                    action: "litecoin::SpendOutput",
+                    parameters: &[
+                        problem::MissingQueryParameter {
+                            name: "address",
+                            data_type: "string",
+                            description: "The litecoin address to where the funds should be sent.",
+                        },
+                        problem::MissingQueryParameter {
+                            name: "fee_per_wu",
+                            data_type: "uint",
+                            description:
+                                "The fee per weight unit you want to pay for the transaction in litoshis.",
+                        },
+                    ],
+                }))
+            }
+        };
+
+        let network = self.network;
+        let transaction = self.spend_to(&address).ok_or_else(|| {
+            HttpApiProblem::new("Invalid query parameter.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail("Query parameter address is not a valid litecoin address.")
+        })?;
+        let transaction = sign_with_rate(transaction, fee_per_wu)?;
+
+        Ok(ActionResponseBody::litecoin_broadcast_signed_transaction(
+            &transaction,
+            network,
+        ))
+    }
+}
+
+impl ListRequiredFields for litecoin::SpendOutput {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![
+            siren::Field {
+                name: "address".to_owned(),
+                class: vec!["litecoin".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: None,
+            },
+            siren::Field {
+                name: "fee_per_wu".to_owned(),
+                class: vec!["litecoin".to_owned(), "feePerWU".to_owned()],
+                _type: Some("number".to_owned()),
+                value: None,
+                title: None,
+            },
+        ]
+    }
+}
+
 impl IntoResponsePayload for ethereum::DeployContract {
     fn into_response_payload(
         self,
@@ -444,6 +590,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn given_litecoin_address_and_fee_deserialize_to_utxo_chain_address() {
+        let s = "address=LKKHMBjCU89fyFNgSRprDoD8Jb25N8uWvd&fee_per_wu=10";
+
+        let res = serde_urlencoded::from_str::<ActionExecutionParameters>(s);
+        assert_eq!(
+            res,
+            Ok(ActionExecutionParameters::UtxoChainAddressAndFee {
+                address: "LKKHMBjCU89fyFNgSRprDoD8Jb25N8uWvd".to_string(),
+                fee_per_wu: "10".to_string(),
+            })
+        );
+    }
+
     #[test]
     fn call_contract_serializes_correctly_to_json_with_none() {
         let addr =
@@ -500,4 +660,19 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn litecoin_send_amount_to_address_serializes_correctly_to_json() {
+        let action = ActionResponseBody::from(litecoin::SendToAddress {
+            to: "LKKHMBjCU89fyFNgSRprDoD8Jb25N8uWvd".to_string(),
+            amount: asset::Bitcoin::from_sat(100_000_000),
+            network: bitcoin::Network::Bitcoin,
+        });
+
+        let serialized = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"litecoin-send-amount-to-address","payload":{"to":"LKKHMBjCU89fyFNgSRprDoD8Jb25N8uWvd","amount":"100000000","network":"mainnet"}}"#
+        );
+    }
 }
@@ -1,9 +1,14 @@
 use crate::{
     db,
     http_api::routes::{
-        rfc003::handlers::{post_swap::UnsupportedSwap, InvalidAction, InvalidActionInvocation},
+        orders::{InvalidOrderBody, OrderNotFound},
+        rfc003::handlers::{
+            post_swap::{PeerDoesNotSupportSwap, UnsupportedHashFunction, UnsupportedSwap},
+            InvalidAction, InvalidActionInvocation,
+        },
         LndActionError,
     },
+    swap_protocols::{rfc003::expiries::UnsafeExpiries, NoLitecoinConnector},
 };
 use http_api_problem::HttpApiProblem;
 use warp::{
@@ -103,6 +108,50 @@ pub fn from_anyhow(e: anyhow::Error) -> HttpApiProblem {
             .set_detail("The requested combination of ledgers and assets is not supported.");
     }
 
+    if let Some(e) = e.downcast_ref::<NoLitecoinConnector>() {
+        tracing::warn!("{}", e);
+
+        return HttpApiProblem::new("Swap not supported.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string());
+    }
+
+    if let Some(e) = e.downcast_ref::<UnsupportedHashFunction>() {
+        tracing::warn!("{}", e);
+
+        return HttpApiProblem::new("Hash function not supported.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string());
+    }
+
+    if let Some(e) = e.downcast_ref::<UnsafeExpiries>() {
+        tracing::warn!("{}", e);
+
+        return HttpApiProblem::new("Unsafe expiries.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string());
+    }
+
+    if e.is::<PeerDoesNotSupportSwap>() {
+        tracing::warn!("{}", e);
+
+        return HttpApiProblem::new("Swap not supported by peer.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(format!("{}", e));
+    }
+
+    if e.is::<OrderNotFound>() {
+        return HttpApiProblem::new("Order not found.").set_status(StatusCode::NOT_FOUND);
+    }
+
+    if let Some(e) = e.downcast_ref::<InvalidOrderBody>() {
+        tracing::warn!("{}", e);
+
+        return HttpApiProblem::new("Invalid order.")
+            .set_status(StatusCode::BAD_REQUEST)
+            .set_detail(e.to_string());
+    }
+
     if e.is::<LndActionError>() {
         return HttpApiProblem::new("Action not found.").set_status(StatusCode::NOT_FOUND);
     }
@@ -59,9 +59,17 @@ pub fn create(
     let get_swaps = swaps
         .and(warp::get())
         .and(warp::path::end())
+        .and(warp::query::<http_api::routes::index::GetSwapsQuery>())
         .and(dependencies.clone())
         .and_then(http_api::routes::index::get_swaps);
 
+    let export_swaps = swaps
+        .and(warp::path("export"))
+        .and(warp::get())
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::index::export_swaps);
+
     let rfc003_action = warp::method()
         .and(rfc003)
         .and(warp::path::param::<SwapId>())
@@ -80,6 +88,26 @@ pub fn create(
         .and(dependencies.clone())
         .and_then(http_api::routes::peers::get_peers);
 
+    let get_orders = warp::get()
+        .and(warp::path(http_api::routes::orders::PATH))
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and_then(http_api::routes::orders::get_orders);
+
+    let get_order = warp::get()
+        .and(warp::path(http_api::routes::orders::PATH))
+        .and(dependencies.clone())
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and_then(http_api::routes::orders::get_order);
+
+    let post_order = warp::post()
+        .and(warp::path(http_api::routes::orders::PATH))
+        .and(warp::path::end())
+        .and(dependencies.clone())
+        .and(warp::body::json())
+        .and_then(http_api::routes::orders::post_order);
+
     let get_info_siren = warp::get()
         .and(warp::path::end())
         .and(warp::header::exact("accept", "application/vnd.siren+json"))
@@ -102,6 +130,15 @@ pub fn create(
         .and(facade2.clone())
         .and_then(http_api::routes::index::post_lightning_route_new);
 
+    let hbit_bitcoin_halight_bitcoin = warp::post()
+        .and(warp::path!(
+            "swaps" / "hbit" / "bitcoin" / "bitcoin" / "halight" / "lightning" / "bitcoin"
+        ))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(facade2.clone())
+        .and_then(http_api::routes::index::post_hbit_halight_route);
+
     let herc20_erc20_halight_bitcoin = swaps
         .and(warp::path!(
             "herc20" / "ethereum" / "erc20" / "halight" / "lightning" / "bitcoin"
@@ -154,6 +191,7 @@ pub fn create(
         .and(warp::path::param::<NodeLocalSwapId>())
         .and(warp::path("redeem"))
         .and(warp::path::end())
+        .and(warp::query::<http_api::action::ActionExecutionParameters>())
         .and(facade2.clone())
         .and_then(http_api::routes::action_redeem);
 
@@ -162,6 +200,7 @@ pub fn create(
         .and(warp::path::param::<NodeLocalSwapId>())
         .and(warp::path("refund"))
         .and(warp::path::end())
+        .and(warp::query::<http_api::action::ActionExecutionParameters>())
         .and(facade2)
         .and_then(http_api::routes::action_refund);
 
@@ -170,10 +209,15 @@ pub fn create(
         .or(rfc003_post_swap)
         .or(rfc003_action)
         .or(get_swaps)
+        .or(export_swaps)
         .or(get_peers)
+        .or(get_orders)
+        .or(get_order)
+        .or(post_order)
         .or(get_info_siren)
         .or(get_info)
         .or(han_ether_halight_bitcoin)
+        .or(hbit_bitcoin_halight_bitcoin)
         .or(herc20_erc20_halight_bitcoin)
         .or(halight_bitcoin_han_ether)
         .or(halight_bitcoin_herc20_erc20)
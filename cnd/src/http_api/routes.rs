@@ -1,4 +1,6 @@
+pub mod hbit_halight;
 pub mod index;
+pub mod orders;
 pub mod peers;
 pub mod rfc003;
 
@@ -6,7 +8,10 @@ use crate::{
     asset,
     ethereum::Bytes,
     htlc_location,
-    http_api::{action::ActionResponseBody, problem},
+    http_api::{
+        action::{ActionExecutionParameters, ActionResponseBody},
+        problem,
+    },
     network::comit_ln,
     swap_protocols::{
         actions::{
@@ -45,6 +50,10 @@ pub async fn handle_get_han_halight_swap(
     facade: Facade2,
     local_id: NodeLocalSwapId,
 ) -> anyhow::Result<siren::Entity> {
+    if let Some(finalized_swap) = facade.get_finalized_hbit_swap(local_id).await {
+        return hbit_halight::handle_get_swap(facade, finalized_swap).await;
+    }
+
     let swap_id = SwapId(local_id.0);
 
     // This is ok, we use a new create_watcher in han.rs and call it with local id.
@@ -323,6 +332,10 @@ async fn handle_action_init(
     local_id: NodeLocalSwapId,
     facade: Facade2,
 ) -> anyhow::Result<ActionResponseBody> {
+    if let Some(finalized_swap) = facade.get_finalized_hbit_swap(local_id).await {
+        return hbit_halight::handle_action_init(facade, finalized_swap).await;
+    }
+
     let id = SwapId(local_id.0);
 
     let alpha_ledger_state: LedgerState<
@@ -378,6 +391,10 @@ async fn handle_action_fund(
     local_id: NodeLocalSwapId,
     facade: Facade2,
 ) -> anyhow::Result<ActionResponseBody> {
+    if let Some(finalized_swap) = facade.get_finalized_hbit_swap(local_id).await {
+        return hbit_halight::handle_action_fund(facade, finalized_swap).await;
+    }
+
     let id = SwapId(local_id.0);
     let alpha_ledger_state: LedgerState<
         asset::Ether,
@@ -427,8 +444,12 @@ async fn handle_action_fund(
 }
 
 #[allow(clippy::needless_pass_by_value)]
-pub async fn action_redeem(id: NodeLocalSwapId, facade: Facade2) -> Result<impl Reply, Rejection> {
-    handle_action_redeem(id, facade)
+pub async fn action_redeem(
+    id: NodeLocalSwapId,
+    query_params: ActionExecutionParameters,
+    facade: Facade2,
+) -> Result<impl Reply, Rejection> {
+    handle_action_redeem(id, query_params, facade)
         .await
         .map(|body| warp::reply::json(&body))
         .map_err(problem::from_anyhow)
@@ -438,8 +459,13 @@ pub async fn action_redeem(id: NodeLocalSwapId, facade: Facade2) -> Result<impl
 #[allow(clippy::unit_arg, clippy::let_unit_value, clippy::cognitive_complexity)]
 async fn handle_action_redeem(
     local_id: NodeLocalSwapId,
+    query_params: ActionExecutionParameters,
     facade: Facade2,
 ) -> anyhow::Result<ActionResponseBody> {
+    if let Some(finalized_swap) = facade.get_finalized_hbit_swap(local_id).await {
+        return hbit_halight::handle_action_redeem(facade, finalized_swap, query_params).await;
+    }
+
     let id = SwapId(local_id.0);
     let alpha_ledger_state: LedgerState<
         asset::Ether,
@@ -489,8 +515,12 @@ async fn handle_action_redeem(
 }
 
 #[allow(clippy::needless_pass_by_value)]
-pub async fn action_refund(id: NodeLocalSwapId, facade: Facade2) -> Result<impl Reply, Rejection> {
-    handle_action_refund(id, facade)
+pub async fn action_refund(
+    id: NodeLocalSwapId,
+    query_params: ActionExecutionParameters,
+    facade: Facade2,
+) -> Result<impl Reply, Rejection> {
+    handle_action_refund(id, query_params, facade)
         .await
         .map(|body| warp::reply::json(&body))
         .map_err(problem::from_anyhow)
@@ -500,8 +530,13 @@ pub async fn action_refund(id: NodeLocalSwapId, facade: Facade2) -> Result<impl
 #[allow(clippy::unit_arg, clippy::let_unit_value, clippy::cognitive_complexity)]
 async fn handle_action_refund(
     local_id: NodeLocalSwapId,
+    query_params: ActionExecutionParameters,
     facade: Facade2,
 ) -> anyhow::Result<ActionResponseBody> {
+    if let Some(finalized_swap) = facade.get_finalized_hbit_swap(local_id).await {
+        return hbit_halight::handle_action_refund(facade, finalized_swap, query_params).await;
+    }
+
     let id = SwapId(local_id.0);
     let alpha_ledger_state: LedgerState<
         asset::Ether,
@@ -0,0 +1,411 @@
+use crate::{
+    asset, htlc_location,
+    http_api::{
+        action::{
+            ActionExecutionParameters, ActionResponseBody, IntoResponsePayload, ListRequiredFields,
+        },
+        routes::{make_siren_action, LndActionError},
+    },
+    network::comit_ln::FinalizedHbitSwap,
+    swap_protocols::{
+        actions::{
+            bitcoin::{SendToAddress, SpendOutput},
+            lnd::{self, Chain},
+        },
+        halight::{self, data},
+        ledger,
+        rfc003::{
+            actions::{MakeFundAction, MakeRedeemAction, MakeRefundAction},
+            LedgerState,
+        },
+        state::Get,
+        Facade2, FundAction, InitAction, RedeemAction, RefundAction, Role, SwapId,
+    },
+    transaction,
+};
+
+type BitcoinLedgerState = LedgerState<asset::Bitcoin, htlc_location::Bitcoin, transaction::Bitcoin>;
+
+/// The hbit HTLC of a swap, this is where the alpha asset is locked.
+type Hbit = (ledger::bitcoin::Regtest, asset::Bitcoin);
+
+pub async fn handle_get_swap(
+    facade: Facade2,
+    finalized_swap: FinalizedHbitSwap,
+) -> anyhow::Result<siren::Entity> {
+    let swap_id = SwapId(finalized_swap.local_id.0);
+    let swap = siren::Entity::default().with_class_member("swap");
+
+    let alpha_ledger_state: Option<BitcoinLedgerState> =
+        facade.alpha_ledger_state.get(&swap_id).await?;
+    let beta_ledger_state = facade.beta_ledger_state.get(&swap_id).await?;
+
+    let (alpha_ledger_state, beta_ledger_state) = match (alpha_ledger_state, beta_ledger_state) {
+        (Some(alpha_ledger_state), Some(beta_ledger_state)) => {
+            (alpha_ledger_state, beta_ledger_state)
+        }
+        _ => {
+            tracing::debug!("returning empty siren document because states are not yet completed");
+
+            return Ok(swap);
+        }
+    };
+
+    // Spending the HTLC output requires an address and a fee from the user.
+    let spend_output_action = |action_name| siren::Action {
+        fields: SpendOutput::list_required_fields(),
+        ..make_siren_action(swap_id, action_name)
+    };
+
+    let maybe_actions = match finalized_swap.role {
+        Role::Alice => {
+            let state = AliceBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            vec![
+                state
+                    .init_action()
+                    .map(|_| make_siren_action(swap_id, "init")),
+                state
+                    .fund_action()
+                    .map(|_| make_siren_action(swap_id, "fund")),
+                state
+                    .redeem_action()
+                    .map(|_| make_siren_action(swap_id, "redeem")),
+                state.refund_action().map(|_| spend_output_action("refund")),
+            ]
+        }
+        Role::Bob => {
+            let state = BobBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            // Bob cannot init and refund in this swap combination
+            vec![
+                state
+                    .fund_action()
+                    .map(|_| make_siren_action(swap_id, "fund")),
+                state.redeem_action().map(|_| spend_output_action("redeem")),
+            ]
+        }
+    };
+
+    Ok(maybe_actions
+        .into_iter()
+        .filter_map(|action| action)
+        .fold(swap, |acc, action| acc.with_action(action)))
+}
+
+pub async fn handle_action_init(
+    facade: Facade2,
+    finalized_swap: FinalizedHbitSwap,
+) -> anyhow::Result<ActionResponseBody> {
+    let (alpha_ledger_state, beta_ledger_state) = ledger_states(&facade, &finalized_swap).await?;
+
+    let maybe_response = match finalized_swap.role {
+        Role::Alice => {
+            let state = AliceBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state.init_action().map(ActionResponseBody::from)
+        }
+        Role::Bob => None,
+    };
+
+    let response = maybe_response.ok_or(LndActionError::NotFound)?;
+
+    Ok(response)
+}
+
+pub async fn handle_action_fund(
+    facade: Facade2,
+    finalized_swap: FinalizedHbitSwap,
+) -> anyhow::Result<ActionResponseBody> {
+    let (alpha_ledger_state, beta_ledger_state) = ledger_states(&facade, &finalized_swap).await?;
+
+    let maybe_response = match finalized_swap.role {
+        Role::Alice => {
+            let state = AliceBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state.fund_action().map(ActionResponseBody::from)
+        }
+        Role::Bob => {
+            let state = BobBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state.fund_action().map(ActionResponseBody::from)
+        }
+    };
+
+    let response = maybe_response.ok_or(LndActionError::NotFound)?;
+
+    Ok(response)
+}
+
+pub async fn handle_action_redeem(
+    facade: Facade2,
+    finalized_swap: FinalizedHbitSwap,
+    query_params: ActionExecutionParameters,
+) -> anyhow::Result<ActionResponseBody> {
+    let (alpha_ledger_state, beta_ledger_state) = ledger_states(&facade, &finalized_swap).await?;
+
+    let maybe_response = match finalized_swap.role {
+        Role::Alice => {
+            let state = AliceBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state.redeem_action().map(ActionResponseBody::from)
+        }
+        Role::Bob => {
+            let state = BobBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state
+                .redeem_action()
+                .map(|action| action.into_response_payload(query_params))
+                .transpose()?
+        }
+    };
+
+    let response = maybe_response.ok_or(LndActionError::NotFound)?;
+
+    Ok(response)
+}
+
+pub async fn handle_action_refund(
+    facade: Facade2,
+    finalized_swap: FinalizedHbitSwap,
+    query_params: ActionExecutionParameters,
+) -> anyhow::Result<ActionResponseBody> {
+    let (alpha_ledger_state, beta_ledger_state) = ledger_states(&facade, &finalized_swap).await?;
+
+    let maybe_response = match finalized_swap.role {
+        Role::Alice => {
+            let state = AliceBtcLnState {
+                alpha_ledger_state,
+                beta_ledger_state,
+                finalized_swap,
+            };
+
+            state
+                .refund_action()
+                .map(|action| action.into_response_payload(query_params))
+                .transpose()?
+        }
+        Role::Bob => None,
+    };
+
+    let response = maybe_response.ok_or(LndActionError::NotFound)?;
+
+    Ok(response)
+}
+
+async fn ledger_states(
+    facade: &Facade2,
+    finalized_swap: &FinalizedHbitSwap,
+) -> anyhow::Result<(BitcoinLedgerState, halight::State)> {
+    let id = SwapId(finalized_swap.local_id.0);
+
+    let alpha_ledger_state: BitcoinLedgerState = facade
+        .alpha_ledger_state
+        .get(&id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("alpha ledger state not found for {}", id))?;
+
+    let beta_ledger_state: halight::State = facade
+        .beta_ledger_state
+        .get(&id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("beta ledger state not found for {}", id))?;
+
+    Ok((alpha_ledger_state, beta_ledger_state))
+}
+
+#[derive(Debug)]
+pub struct AliceBtcLnState {
+    pub alpha_ledger_state: BitcoinLedgerState,
+    pub beta_ledger_state: halight::State,
+    pub finalized_swap: FinalizedHbitSwap,
+}
+
+#[derive(Debug)]
+pub struct BobBtcLnState {
+    pub alpha_ledger_state: BitcoinLedgerState,
+    pub beta_ledger_state: halight::State,
+    pub finalized_swap: FinalizedHbitSwap,
+}
+
+impl InitAction for AliceBtcLnState {
+    type Output = lnd::AddHoldInvoice;
+
+    fn init_action(&self) -> Option<Self::Output> {
+        match self.beta_ledger_state {
+            halight::State::Unknown => {
+                let amount = self.finalized_swap.beta_asset;
+                let secret_hash = self.finalized_swap.secret_hash;
+                let expiry = 3600;
+                let cltv_expiry = self.finalized_swap.beta_expiry.into();
+                let chain = Chain::Bitcoin;
+                let network = bitcoin::Network::Regtest;
+                let self_public_key = self.finalized_swap.beta_ledger_redeem_identity;
+
+                Some(lnd::AddHoldInvoice {
+                    amount,
+                    secret_hash,
+                    expiry,
+                    cltv_expiry,
+                    chain,
+                    network,
+                    self_public_key,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FundAction for AliceBtcLnState {
+    type Output = SendToAddress;
+
+    fn fund_action(&self) -> Option<Self::Output> {
+        match (&self.alpha_ledger_state, &self.beta_ledger_state) {
+            (LedgerState::NotDeployed, halight::State::Opened(_)) => {
+                let htlc_params = self.finalized_swap.hbit_params();
+
+                <Hbit as MakeFundAction>::make_fund_action(htlc_params)
+                    .map_err(|e| tracing::error!("failed to build fund action: {}", e))
+                    .ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RedeemAction for AliceBtcLnState {
+    type Output = lnd::SettleInvoice;
+
+    fn redeem_action(&self) -> Option<Self::Output> {
+        match self.beta_ledger_state {
+            halight::State::Accepted(_) => {
+                let secret = self.finalized_swap.secret.unwrap(); // unwrap ok since only Alice calls this.
+                let chain = Chain::Bitcoin;
+                let network = bitcoin::Network::Regtest;
+                let self_public_key = self.finalized_swap.beta_ledger_redeem_identity;
+
+                Some(lnd::SettleInvoice {
+                    secret,
+                    chain,
+                    network,
+                    self_public_key,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RefundAction for AliceBtcLnState {
+    type Output = SpendOutput;
+
+    fn refund_action(&self) -> Option<Self::Output> {
+        match &self.alpha_ledger_state {
+            LedgerState::Funded {
+                htlc_location,
+                fund_transaction,
+                ..
+            } => {
+                let htlc_params = self.finalized_swap.hbit_params();
+
+                <Hbit as MakeRefundAction>::make_refund_action(
+                    htlc_params,
+                    *htlc_location,
+                    &self.finalized_swap.swap_seed,
+                    fund_transaction,
+                )
+                .map_err(|e| tracing::error!("failed to build refund action: {}", e))
+                .ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FundAction for BobBtcLnState {
+    type Output = lnd::SendPayment;
+
+    fn fund_action(&self) -> Option<Self::Output> {
+        match (&self.alpha_ledger_state, &self.beta_ledger_state) {
+            (LedgerState::Funded { .. }, halight::State::Opened(_)) => {
+                let to_public_key = self.finalized_swap.beta_ledger_redeem_identity;
+                let amount = self.finalized_swap.beta_asset;
+                let secret_hash = self.finalized_swap.secret_hash;
+                let final_cltv_delta = self.finalized_swap.beta_expiry.into();
+                let chain = Chain::Bitcoin;
+                let network = bitcoin::Network::Regtest;
+                let self_public_key = self.finalized_swap.beta_ledger_refund_identity;
+
+                Some(lnd::SendPayment {
+                    to_public_key,
+                    amount,
+                    secret_hash,
+                    final_cltv_delta,
+                    chain,
+                    network,
+                    self_public_key,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl RedeemAction for BobBtcLnState {
+    type Output = SpendOutput;
+
+    fn redeem_action(&self) -> Option<Self::Output> {
+        match (&self.alpha_ledger_state, &self.beta_ledger_state) {
+            (
+                LedgerState::Funded {
+                    htlc_location,
+                    fund_transaction,
+                    ..
+                },
+                halight::State::Settled(data::Settled { secret }),
+            ) => {
+                let htlc_params = self.finalized_swap.hbit_params();
+
+                <Hbit as MakeRedeemAction>::make_redeem_action(
+                    htlc_params,
+                    *htlc_location,
+                    &self.finalized_swap.swap_seed,
+                    fund_transaction,
+                    *secret,
+                )
+                .map_err(|e| tracing::error!("failed to build redeem action: {}", e))
+                .ok()
+            }
+            _ => None,
+        }
+    }
+}
@@ -6,11 +6,14 @@ use crate::{
     http_api::{problem, routes::into_rejection, Http},
     identity,
     network::{DialInformation, ListenAddresses},
-    swap_protocols::{CreateSwapParams, Facade, Facade2, NodeLocalSwapId, Role},
+    swap_protocols::{
+        CreateHbitSwapParams, CreateSwapParams, Facade, Facade2, NodeLocalSwapId, Role, SwapParams,
+    },
 };
 use http_api_problem::HttpApiProblem;
 use libp2p::{Multiaddr, PeerId};
 use serde::{Deserialize, Serialize};
+use tracing_futures::Instrument;
 use warp::{http::StatusCode, Rejection, Reply};
 
 #[derive(Serialize, Debug)]
@@ -53,9 +56,19 @@ pub async fn get_info_siren(id: PeerId, dependencies: Facade) -> Result<impl Rep
     ))
 }
 
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct GetSwapsQuery {
+    /// Whether to include the swaps that have been archived.
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
 #[allow(clippy::needless_pass_by_value)]
-pub async fn get_swaps(dependencies: Facade) -> Result<impl Reply, Rejection> {
-    handle_get_swaps(dependencies)
+pub async fn get_swaps(
+    query: GetSwapsQuery,
+    dependencies: Facade,
+) -> Result<impl Reply, Rejection> {
+    handle_get_swaps(dependencies, query.include_archived)
         .await
         .map(|swaps| {
             Ok(warp::reply::with_header(
@@ -68,6 +81,17 @@ pub async fn get_swaps(dependencies: Facade) -> Result<impl Reply, Rejection> {
         .map_err(into_rejection)
 }
 
+#[allow(clippy::needless_pass_by_value)]
+pub async fn export_swaps(dependencies: Facade) -> Result<impl Reply, Rejection> {
+    dependencies
+        .db
+        .export_swaps()
+        .await
+        .map(|swaps| warp::reply::json(&swaps))
+        .map_err(problem::from_anyhow)
+        .map_err(into_rejection)
+}
+
 // `warp::reply::Json` is used as a return type to please the compiler
 // until proper logic is implemented
 #[allow(clippy::needless_pass_by_value)]
@@ -96,7 +120,10 @@ pub async fn post_lightning_route_new(
 
     facade.save(id, ()).await;
 
-    facade.initiate_communication(id, body.into()).await;
+    facade
+        .initiate_communication(id, CreateSwapParams::from(body).into())
+        .instrument(tracing::info_span!("post_swap", swap_id = %id))
+        .await;
 
     Ok(warp::reply::with_status(
         warp::reply::with_header(reply, "Location", format!("/swaps/{}", id)),
@@ -127,6 +154,63 @@ impl From<Body> for CreateSwapParams {
     }
 }
 
+pub async fn post_hbit_halight_route(
+    body: serde_json::Value,
+    facade: Facade2,
+) -> Result<impl Reply, Rejection> {
+    let body = HbitHalightBody::deserialize(&body)
+        .map_err(anyhow::Error::new)
+        .map_err(problem::from_anyhow)
+        .map_err(warp::reject::custom)?;
+
+    // The HTLC and its actions are only built for regtest so far.
+    if body.alpha.network != "regtest" || body.beta.network != "regtest" {
+        return Err(warp::reject::custom(
+            HttpApiProblem::new("Network not supported.")
+                .set_status(StatusCode::BAD_REQUEST)
+                .set_detail("Bitcoin to Lightning swaps are only supported on regtest."),
+        ));
+    }
+
+    let reply = warp::reply::reply();
+
+    let id = NodeLocalSwapId::default();
+
+    facade.save(id, ()).await;
+
+    facade
+        .initiate_communication(id, SwapParams::from(CreateHbitSwapParams::from(body)))
+        .instrument(tracing::info_span!("post_swap", swap_id = %id))
+        .await;
+
+    Ok(warp::reply::with_status(
+        warp::reply::with_header(reply, "Location", format!("/swaps/{}", id)),
+        StatusCode::CREATED,
+    ))
+}
+
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct HbitHalightBody {
+    pub alpha: HbitBitcoinBitcoin,
+    pub beta: HalightLightningBitcoin,
+    pub peer: DialInformation,
+    pub role: Http<Role>,
+}
+
+impl From<HbitHalightBody> for CreateHbitSwapParams {
+    fn from(body: HbitHalightBody) -> Self {
+        Self {
+            role: body.role.0,
+            peer: body.peer,
+            bitcoin_absolute_expiry: body.alpha.absolute_expiry.into(),
+            bitcoin_amount: body.alpha.amount,
+            lightning_identity: body.beta.identity,
+            lightning_cltv_expiry: body.beta.cltv_expiry.into(),
+            lightning_amount: body.beta.amount.0,
+        }
+    }
+}
+
 #[derive(serde::Deserialize, Clone, Debug)]
 pub struct HanEthereumEther {
     pub amount: asset::Ether,
@@ -135,6 +219,15 @@ pub struct HanEthereumEther {
     pub absolute_expiry: u32,
 }
 
+/// The Bitcoin identity of the HTLC is derived from the swap seed, hence it is
+/// not part of the request.
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct HbitBitcoinBitcoin {
+    pub amount: asset::Bitcoin,
+    pub network: String,
+    pub absolute_expiry: u32,
+}
+
 #[derive(serde::Deserialize, Clone, Debug)]
 pub struct HalightLightningBitcoin {
     pub amount: Http<asset::Lightning>,
@@ -1,10 +1,15 @@
 use crate::{
     db::{DetermineTypes, Retrieve},
-    http_api::swap_resource::{build_rfc003_siren_entity, IncludeState, OnFail},
+    http_api::swap_resource::{
+        build_archived_siren_entity, build_rfc003_siren_entity, IncludeState, OnFail,
+    },
     swap_protocols::Facade,
 };
 
-pub async fn handle_get_swaps(dependencies: Facade) -> anyhow::Result<siren::Entity> {
+pub async fn handle_get_swaps(
+    dependencies: Facade,
+    include_archived: bool,
+) -> anyhow::Result<siren::Entity> {
     let mut entity = siren::Entity::default().with_class_member("swaps");
 
     for swap in Retrieve::all(&dependencies).await?.into_iter() {
@@ -21,5 +26,12 @@ pub async fn handle_get_swaps(dependencies: Facade) -> anyhow::Result<siren::Ent
         entity.push_sub_entity(siren::SubEntity::from_entity(sub_entity, &["item"]));
     }
 
+    if include_archived {
+        for archived in dependencies.db.archived_swaps().await?.into_iter() {
+            let sub_entity = build_archived_siren_entity(archived)?;
+            entity.push_sub_entity(siren::SubEntity::from_entity(sub_entity, &["item"]));
+        }
+    }
+
     Ok(entity)
 }
@@ -0,0 +1,118 @@
+use crate::{
+    http_api::{problem, routes::into_rejection},
+    network::{
+        orderbook::{
+            Maker, Order, OrderAsset, OrderId, OrderLedger, OrderProtocol, Quantity, Rate,
+            SignedOrder,
+        },
+        ListenAddresses, LocalPeerId, Orders,
+    },
+    swap_protocols::Facade,
+    timestamp::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+use warp::{http::header, Rejection, Reply};
+
+pub const PATH: &str = "orders";
+
+pub fn order_path(id: OrderId) -> String {
+    format!("/{}/{}", PATH, id)
+}
+
+#[derive(Serialize, Debug)]
+pub struct OrdersResource {
+    orders: Vec<SignedOrder>,
+}
+
+/// The body of a `POST /orders` request.
+///
+/// The id of the order and the dial information of the maker are filled in
+/// by this node.
+#[derive(Deserialize, Clone, Debug)]
+pub struct OrderBody {
+    pub protocol: OrderProtocol,
+    pub alpha_ledger: OrderLedger,
+    pub beta_ledger: OrderLedger,
+    pub alpha_asset: OrderAsset,
+    pub beta_asset: OrderAsset,
+    pub rate: Rate,
+    pub min_quantity: Quantity,
+    pub max_quantity: Quantity,
+    pub expiry: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum InvalidOrderBody {
+    #[error("min_quantity is bigger than max_quantity")]
+    QuantityRange,
+    #[error("expiry is in the past")]
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("order not found")]
+pub struct OrderNotFound;
+
+#[allow(clippy::needless_pass_by_value)]
+pub async fn get_orders(dependencies: Facade) -> Result<impl Reply, Rejection> {
+    let orders = dependencies.orders().await;
+
+    Ok(warp::reply::json(&OrdersResource { orders }))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub async fn get_order(dependencies: Facade, id: OrderId) -> Result<impl Reply, Rejection> {
+    dependencies
+        .orders()
+        .await
+        .into_iter()
+        .find(|signed_order| signed_order.order.id == id)
+        .map(|signed_order| warp::reply::json(&signed_order))
+        .ok_or_else(|| anyhow::Error::from(OrderNotFound))
+        .map_err(problem::from_anyhow)
+        .map_err(into_rejection)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub async fn post_order(dependencies: Facade, body: OrderBody) -> Result<impl Reply, Rejection> {
+    handle_post_order(dependencies, body)
+        .await
+        .map(|signed_order| {
+            let path = order_path(signed_order.order.id);
+            let body = warp::reply::json(&signed_order);
+            let response = warp::reply::with_header(body, header::LOCATION, path);
+            warp::reply::with_status(response, warp::http::StatusCode::CREATED)
+        })
+        .map_err(problem::from_anyhow)
+        .map_err(into_rejection)
+}
+
+async fn handle_post_order(dependencies: Facade, body: OrderBody) -> anyhow::Result<SignedOrder> {
+    if body.min_quantity.0 > body.max_quantity.0 {
+        return Err(InvalidOrderBody::QuantityRange.into());
+    }
+    if body.expiry <= Timestamp::now() {
+        return Err(InvalidOrderBody::Expired.into());
+    }
+
+    let maker = Maker {
+        peer_id: dependencies.local_peer_id(),
+        addresses: dependencies.listen_addresses().await,
+    };
+
+    let order = Order {
+        id: OrderId::default(),
+        maker,
+        protocol: body.protocol,
+        alpha_ledger: body.alpha_ledger,
+        beta_ledger: body.beta_ledger,
+        alpha_asset: body.alpha_asset,
+        beta_asset: body.beta_asset,
+        rate: body.rate,
+        min_quantity: body.min_quantity,
+        max_quantity: body.max_quantity,
+        expiry: body.expiry,
+    };
+
+    dependencies.publish_order(order).await
+}
@@ -1,4 +1,8 @@
-use crate::{http_api::Http, network::ComitPeers, swap_protocols::Facade};
+use crate::{
+    http_api::Http,
+    network::{protocols::capabilities, ComitPeers, PeerCapabilities},
+    swap_protocols::Facade,
+};
 use libp2p::{Multiaddr, PeerId};
 use serde::Serialize;
 use warp::{Rejection, Reply};
@@ -12,18 +16,24 @@ pub struct PeersResource {
 pub struct Peer {
     id: Http<PeerId>,
     endpoints: Vec<Multiaddr>,
+    /// The version and supported swaps the peer advertised, `null` until the
+    /// peer told us.
+    capabilities: Option<capabilities::Message>,
 }
 
 #[allow(clippy::needless_pass_by_value)]
 pub async fn get_peers(dependencies: Facade) -> Result<impl Reply, Rejection> {
-    let peers = dependencies
-        .comit_peers()
-        .await
-        .map(|(peer, addresses)| Peer {
+    let mut peers = Vec::new();
+
+    for (peer, addresses) in dependencies.comit_peers().await {
+        let capabilities = dependencies.peer_capabilities(&peer).await;
+
+        peers.push(Peer {
             id: Http(peer),
             endpoints: addresses,
-        })
-        .collect();
+            capabilities,
+        });
+    }
 
     Ok(warp::reply::json(&PeersResource { peers }))
 }
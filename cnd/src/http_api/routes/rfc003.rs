@@ -21,13 +21,17 @@ use warp::{
 
 pub use self::swap_state::{LedgerState, SwapCommunication, SwapCommunicationState, SwapState};
 use crate::http_api::problem;
+use tracing_futures::Instrument;
 
 #[allow(clippy::needless_pass_by_value)]
 pub async fn post_swap(
     dependencies: Facade,
     body: serde_json::Value,
 ) -> Result<impl Reply, Rejection> {
-    handle_post_swap(dependencies, body)
+    let id = SwapId::default();
+
+    handle_post_swap(dependencies, id, body)
+        .instrument(tracing::info_span!("post_swap", swap_id = %id))
         .await
         .map(|swap_created| {
             let body = warp::reply::json(&swap_created);
@@ -42,6 +46,7 @@ pub async fn post_swap(
 #[allow(clippy::needless_pass_by_value)]
 pub async fn get_swap(dependencies: Facade, id: SwapId) -> Result<impl Reply, Rejection> {
     handle_get_swap(dependencies, id)
+        .instrument(tracing::debug_span!("get_swap", swap_id = %id))
         .await
         .map(|swap_resource| warp::reply::json(&swap_resource))
         .map_err(problem::from_anyhow)
@@ -58,6 +63,7 @@ pub async fn action(
     body: serde_json::Value,
 ) -> Result<impl Reply, Rejection> {
     handle_action(method, id, action_kind, body, query_params, dependencies)
+        .instrument(tracing::info_span!("action", swap_id = %id, action = %action_kind))
         .await
         .map(|body| warp::reply::json(&body))
         .map_err(problem::from_anyhow)
@@ -2,7 +2,7 @@ use crate::{
     http_api::action::ListRequiredFields,
     identity,
     swap_protocols::{
-        ledger::{bitcoin, Ethereum},
+        ledger::{bitcoin, litecoin, Ethereum},
         rfc003::{
             actions::Accept,
             messages::{self, IntoAcceptMessage},
@@ -36,6 +36,24 @@ impl ListRequiredFields for Accept<Ethereum, bitcoin::Regtest> {
     }
 }
 
+impl ListRequiredFields for Accept<Ethereum, litecoin::Mainnet> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        ethereum_bitcoin_accept_required_fields()
+    }
+}
+
+impl ListRequiredFields for Accept<Ethereum, litecoin::Testnet> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        ethereum_bitcoin_accept_required_fields()
+    }
+}
+
+impl ListRequiredFields for Accept<Ethereum, litecoin::Regtest> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        ethereum_bitcoin_accept_required_fields()
+    }
+}
+
 fn ethereum_bitcoin_accept_required_fields() -> Vec<siren::Field> {
     vec![siren::Field {
         name: "alpha_ledger_redeem_identity".to_owned(),
@@ -87,6 +105,24 @@ impl ListRequiredFields for Accept<bitcoin::Regtest, Ethereum> {
     }
 }
 
+impl ListRequiredFields for Accept<litecoin::Mainnet, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        bitcoin_ethereum_accept_required_fields()
+    }
+}
+
+impl ListRequiredFields for Accept<litecoin::Testnet, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        bitcoin_ethereum_accept_required_fields()
+    }
+}
+
+impl ListRequiredFields for Accept<litecoin::Regtest, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        bitcoin_ethereum_accept_required_fields()
+    }
+}
+
 fn bitcoin_ethereum_accept_required_fields() -> Vec<siren::Field> {
     vec![siren::Field {
         name: "beta_ledger_refund_identity".to_owned(),
@@ -114,3 +150,46 @@ impl IntoAcceptMessage<identity::Bitcoin, identity::Ethereum> for OnlyRefund<ide
         }
     }
 }
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct RedeemAndRefund<I> {
+    pub alpha_ledger_redeem_identity: I,
+    pub beta_ledger_refund_identity: I,
+}
+
+impl ListRequiredFields for Accept<Ethereum, Ethereum> {
+    fn list_required_fields() -> Vec<siren::Field> {
+        vec![
+            siren::Field {
+                name: "alpha_ledger_redeem_identity".to_owned(),
+                class: vec!["ethereum".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: Some("Alpha ledger redeem identity".to_owned()),
+            },
+            siren::Field {
+                name: "beta_ledger_refund_identity".to_owned(),
+                class: vec!["ethereum".to_owned(), "address".to_owned()],
+                _type: Some("text".to_owned()),
+                value: None,
+                title: Some("Beta ledger refund identity".to_owned()),
+            },
+        ]
+    }
+}
+
+impl IntoAcceptMessage<identity::Ethereum, identity::Ethereum>
+    for RedeemAndRefund<identity::Ethereum>
+{
+    fn into_accept_message(
+        self,
+        id: SwapId,
+        _: &dyn DeriveIdentities,
+    ) -> messages::Accept<identity::Ethereum, identity::Ethereum> {
+        messages::Accept {
+            swap_id: id,
+            alpha_ledger_redeem_identity: self.alpha_ledger_redeem_identity,
+            beta_ledger_refund_identity: self.beta_ledger_refund_identity,
+        }
+    }
+}
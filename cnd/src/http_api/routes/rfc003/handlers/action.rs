@@ -18,10 +18,10 @@ use crate::{
             self,
             actions::{Action, ActionKind},
             messages::{Decision, IntoAcceptMessage},
-            LedgerState, SwapCommunication,
+            LedgerState, LedgerTimes, SwapCommunication,
         },
         state::{Get, Insert},
-        Facade, SwapId,
+        Facade, Role, SwapId,
     },
 };
 use anyhow::Context;
@@ -60,11 +60,18 @@ pub async fn handle_action(
             .await?
             .ok_or_else(|| anyhow::anyhow!("beta ledger state not found for {}", swap_id))?;
         let secret_source = dependencies.derive_swap_seed(swap_id);
+        let ledger_times = LedgerTimes::fetch(
+            &dependencies,
+            &swap_communication.request().alpha_ledger,
+            &swap_communication.request().beta_ledger,
+        )
+        .await;
 
         let state = RoleState::new(
             swap_communication,
             alpha_ledger_state,
             beta_ledger_state,
+            ledger_times,
             secret_source,
         );
 
@@ -106,8 +113,12 @@ pub async fn handle_action(
                 )
                 .await?;
 
-                init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(&dependencies, accepted)
-                    .await?;
+                init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(
+                    &dependencies,
+                    Role::Bob,
+                    accepted,
+                )
+                .await?;
 
                 Ok(ActionResponseBody::None)
             }
@@ -1,20 +1,24 @@
 use crate::{
-    db::{LoadAcceptedSwap, Save, Sqlite, Swap},
+    config::ExpiryLedger,
+    db::{Database, LoadAcceptedSwap, Save, Swap},
     htlc_location,
     http_api::{HttpAsset, HttpLedger},
     identity,
     init_swap::init_accepted_swap,
-    network::{DialInformation, SendRequest},
+    network::{
+        protocols::capabilities::{self, SwapCapability},
+        DialInformation, PeerCapabilities, SendRequest,
+    },
     seed::DeriveSwapSeed,
     swap_protocols::{
         rfc003::{
             self,
             events::{HtlcDeployed, HtlcFunded, HtlcRedeemed, HtlcRefunded},
-            Accept, Decline, DeriveIdentities, DeriveSecret, LedgerState, Request, SecretHash,
-            SwapCommunication,
+            expiries, Accept, Decline, DeriveIdentities, DeriveSecret, LedgerState, Request,
+            SecretHash, SwapCommunication,
         },
         state::Insert,
-        Facade, HashFunction, Role, SwapId,
+        Facade, HashFunction, NoLitecoinConnector, Role, SwapId,
     },
     timestamp::Timestamp,
     transaction,
@@ -24,6 +28,7 @@ use futures::future::TryFutureExt;
 use libp2p_comit::frame::OutboundRequest;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{convert::TryInto, fmt::Debug, str::FromStr};
+use tracing_futures::Instrument;
 
 async fn initiate_request<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
     dependencies: Facade,
@@ -32,7 +37,7 @@ async fn initiate_request<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
     swap_request: rfc003::Request<AL, BL, AA, BA, AI, BI>,
 ) -> anyhow::Result<()>
 where
-    Sqlite:
+    Database:
         Save<Request<AL, BL, AA, BA, AI, BI>> + Save<Accept<AI, BI>> + Save<Swap> + Save<Decline>,
     AL: Clone + Send + Sync + 'static,
     BL: Clone + Send + Sync + 'static,
@@ -81,10 +86,25 @@ where
 
     let future = {
         async move {
-            let response = dependencies
+            let response = match dependencies
                 .send_request(peer.clone(), swap_request.clone())
                 .await
-                .with_context(|| format!("Failed to send swap request to {}", peer.clone()))?;
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    dependencies
+                        .insert(id, SwapCommunication::Failed {
+                            request: swap_request,
+                        })
+                        .await;
+                    if let Err(e) = dependencies.db.mark_failed(&id).await {
+                        tracing::error!("failed to record that swap {} failed: {:?}", id, e);
+                    }
+
+                    return Err(e)
+                        .with_context(|| format!("Failed to send swap request to {}", peer));
+                }
+            };
 
             match response {
                 Ok(accept) => {
@@ -95,8 +115,12 @@ where
                     )
                     .await?;
 
-                    init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(&dependencies, accepted)
-                        .await?;
+                    init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(
+                        &dependencies,
+                        Role::Alice,
+                        accepted,
+                    )
+                    .await?;
                 }
                 Err(decline) => {
                     tracing::info!("Swap declined: {}", decline.swap_id);
@@ -111,34 +135,527 @@ where
             };
             Ok(())
         }
-    };
-
-    tokio::task::spawn(future.map_err(|e: anyhow::Error| {
-        tracing::error!("{}", e);
-    }));
-
-    Ok(())
-}
-
-pub async fn handle_post_swap(
-    dependencies: Facade,
-    body: serde_json::Value,
-) -> anyhow::Result<SwapCreated> {
-    let id = SwapId::default();
-    let seed = dependencies.derive_swap_seed(id);
-    let secret_hash = seed.derive_secret().hash();
+    };
+
+    tokio::task::spawn(
+        future
+            .map_err(|e: anyhow::Error| {
+                tracing::error!("{}", e);
+            })
+            .in_current_span(),
+    );
+
+    Ok(())
+}
+
+pub async fn handle_post_swap(
+    dependencies: Facade,
+    id: SwapId,
+    body: serde_json::Value,
+) -> anyhow::Result<SwapCreated> {
+    let seed = dependencies.derive_swap_seed(id);
+
+    let mut body = SwapRequestBody::deserialize(&body)?;
+
+    if !rfc003::HASH_FUNCTIONS.contains(&body.hash_function) {
+        return Err(anyhow::Error::from(UnsupportedHashFunction(
+            body.hash_function,
+        )));
+    }
+    let secret_hash = seed.derive_secret().hash(body.hash_function);
+
+    let uses_litecoin = [&body.alpha_ledger, &body.beta_ledger]
+        .iter()
+        .any(|ledger| capability_ledger(ledger) == capabilities::Ledger::Litecoin);
+    if uses_litecoin && dependencies.litecoin_connector.is_none() {
+        return Err(anyhow::Error::from(NoLitecoinConnector));
+    }
+
+    // Peers that did not tell us their capabilities (yet) get the benefit of
+    // the doubt, they will decline the request if they cannot handle it.
+    if let Some(peer_capabilities) = dependencies.peer_capabilities(&body.peer.peer_id).await {
+        if !peer_capabilities.supports(&body.capability()) {
+            return Err(anyhow::Error::from(PeerDoesNotSupportSwap {
+                peer_version: peer_capabilities.version,
+            }));
+        }
+    }
+
+    let alpha_expiry = *body.alpha_expiry.get_or_insert_with(default_alpha_expiry);
+    let beta_expiry = *body.beta_expiry.get_or_insert_with(default_beta_expiry);
+    let expiry_rules = dependencies.expiries.rules_for(
+        expiry_ledger(&body.alpha_ledger),
+        expiry_ledger(&body.beta_ledger),
+    );
+    expiries::validate(&expiry_rules, alpha_expiry, beta_expiry, Timestamp::now())?;
+
+    match body {
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinMainnet(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinTestnet(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinRegtest(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinMainnet(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinTestnet(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinRegtest(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinMainnet(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinTestnet(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::BitcoinRegtest(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Bitcoin(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Bitcoin,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Bitcoin,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinMainnet(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinTestnet(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::BitcoinRegtest(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Bitcoin(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Bitcoin,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Bitcoin,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
 
-    let body = SwapRequestBody::deserialize(&body)?;
-
-    match body {
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinMainnet(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinMainnet(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Ether(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -151,6 +668,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -168,13 +686,14 @@ pub async fn handle_post_swap(
             .await?;
         }
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinTestnet(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinTestnet(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Ether(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -187,6 +706,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -204,13 +724,14 @@ pub async fn handle_post_swap(
             .await?;
         }
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinRegtest(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinRegtest(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Ether(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -223,6 +744,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -241,12 +763,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinMainnet(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinMainnet(beta_ledger),
             alpha_asset: HttpAsset::Ether(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -259,6 +782,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -277,12 +801,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinTestnet(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinTestnet(beta_ledger),
             alpha_asset: HttpAsset::Ether(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -295,6 +820,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -313,12 +839,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinRegtest(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinRegtest(beta_ledger),
             alpha_asset: HttpAsset::Ether(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -331,6 +858,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -348,13 +876,14 @@ pub async fn handle_post_swap(
             .await?;
         }
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinMainnet(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinMainnet(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Erc20(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -367,6 +896,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -384,13 +914,14 @@ pub async fn handle_post_swap(
             .await?;
         }
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinTestnet(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinTestnet(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Erc20(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -403,6 +934,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -420,13 +952,14 @@ pub async fn handle_post_swap(
             .await?;
         }
         SwapRequestBody {
-            alpha_ledger: HttpLedger::BitcoinRegtest(alpha_ledger),
+            alpha_ledger: HttpLedger::LitecoinRegtest(alpha_ledger),
             beta_ledger: HttpLedger::Ethereum(beta_ledger),
             alpha_asset: HttpAsset::Bitcoin(alpha_asset),
             beta_asset: HttpAsset::Erc20(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_bitcoin_ethereum_identities(&seed)?;
@@ -439,6 +972,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -457,12 +991,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinMainnet(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinMainnet(beta_ledger),
             alpha_asset: HttpAsset::Erc20(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -475,6 +1010,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -493,12 +1029,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinTestnet(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinTestnet(beta_ledger),
             alpha_asset: HttpAsset::Erc20(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -511,6 +1048,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -529,12 +1067,13 @@ pub async fn handle_post_swap(
         }
         SwapRequestBody {
             alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
-            beta_ledger: HttpLedger::BitcoinRegtest(beta_ledger),
+            beta_ledger: HttpLedger::LitecoinRegtest(beta_ledger),
             alpha_asset: HttpAsset::Erc20(alpha_asset),
             beta_asset: HttpAsset::Bitcoin(beta_asset),
             alpha_expiry,
             beta_expiry,
             identities,
+            hash_function,
             peer,
         } => {
             let identities = identities.into_ethereum_bitcoin_identities(&seed)?;
@@ -547,6 +1086,7 @@ pub async fn handle_post_swap(
                 alpha_expiry,
                 beta_expiry,
                 identities,
+                hash_function,
                 secret_hash,
             );
             initiate_request::<
@@ -563,7 +1103,158 @@ pub async fn handle_post_swap(
             >(dependencies, id, peer, request)
             .await?;
         }
-
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_ethereum_identities()?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_ethereum_identities()?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Erc20(alpha_asset),
+            beta_asset: HttpAsset::Erc20(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_ethereum_identities()?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
+        SwapRequestBody {
+            alpha_ledger: HttpLedger::Ethereum(alpha_ledger),
+            beta_ledger: HttpLedger::Ethereum(beta_ledger),
+            alpha_asset: HttpAsset::Ether(alpha_asset),
+            beta_asset: HttpAsset::Ether(beta_asset),
+            alpha_expiry,
+            beta_expiry,
+            identities,
+            hash_function,
+            peer,
+        } => {
+            let identities = identities.into_ethereum_ethereum_identities()?;
+            let request = new_request(
+                id,
+                alpha_ledger,
+                beta_ledger,
+                alpha_asset,
+                beta_asset,
+                alpha_expiry,
+                beta_expiry,
+                identities,
+                hash_function,
+                secret_hash,
+            );
+            initiate_request::<
+                _,
+                _,
+                _,
+                _,
+                htlc_location::Ethereum,
+                htlc_location::Ethereum,
+                _,
+                _,
+                transaction::Ethereum,
+                transaction::Ethereum,
+            >(dependencies, id, peer, request)
+            .await?;
+        }
         _ => {
             return Err(anyhow::Error::from(UnsupportedSwap {
                 alpha_ledger: body.alpha_ledger,
@@ -587,6 +1278,7 @@ fn new_request<AL, BL, AA, BA, AI, BI>(
     alpha_expiry: Option<Timestamp>,
     beta_expiry: Option<Timestamp>,
     identities: Identities<AI, BI>,
+    hash_function: HashFunction,
     secret_hash: SecretHash,
 ) -> rfc003::Request<AL, BL, AA, BA, AI, BI> {
     rfc003::Request {
@@ -595,7 +1287,7 @@ fn new_request<AL, BL, AA, BA, AI, BI>(
         beta_ledger,
         alpha_asset,
         beta_asset,
-        hash_function: HashFunction::Sha256,
+        hash_function,
         alpha_ledger_refund_identity: identities.alpha_ledger_refund_identity,
         beta_ledger_redeem_identity: identities.beta_ledger_redeem_identity,
         alpha_expiry: alpha_expiry.unwrap_or_else(default_alpha_expiry),
@@ -615,6 +1307,20 @@ pub struct UnsupportedSwap {
     beta_ledger: HttpLedger,
 }
 
+/// An error type for describing that our HTLCs can't be locked with the
+/// requested hash function.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("rfc003 swaps using {0} are not supported")]
+pub struct UnsupportedHashFunction(HashFunction);
+
+/// An error type for describing that the peer told us it does not support the
+/// requested combination of assets and ledgers.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("peer running cnd {peer_version} does not support the requested swap")]
+pub struct PeerDoesNotSupportSwap {
+    peer_version: String,
+}
+
 #[derive(Serialize, Clone, Copy, Debug)]
 pub struct SwapCreated {
     pub id: SwapId,
@@ -636,9 +1342,61 @@ struct SwapRequestBody {
     beta_expiry: Option<Timestamp>,
     #[serde(flatten)]
     identities: HttpIdentities,
+    #[serde(default = "default_hash_function")]
+    hash_function: HashFunction,
     peer: DialInformation,
 }
 
+impl SwapRequestBody {
+    fn capability(&self) -> SwapCapability {
+        SwapCapability::new(
+            capabilities::Protocol::Rfc003 {
+                hash_function: self.hash_function,
+            },
+            (
+                capability_ledger(&self.alpha_ledger),
+                capability_ledger(&self.beta_ledger),
+            ),
+            (
+                capability_asset(&self.alpha_asset),
+                capability_asset(&self.beta_asset),
+            ),
+        )
+    }
+}
+
+fn capability_ledger(ledger: &HttpLedger) -> capabilities::Ledger {
+    match ledger {
+        HttpLedger::BitcoinMainnet(_)
+        | HttpLedger::BitcoinTestnet(_)
+        | HttpLedger::BitcoinRegtest(_) => capabilities::Ledger::Bitcoin,
+        HttpLedger::LitecoinMainnet(_)
+        | HttpLedger::LitecoinTestnet(_)
+        | HttpLedger::LitecoinRegtest(_) => capabilities::Ledger::Litecoin,
+        HttpLedger::Ethereum(_) => capabilities::Ledger::Ethereum,
+    }
+}
+
+fn expiry_ledger(ledger: &HttpLedger) -> ExpiryLedger {
+    match ledger {
+        HttpLedger::BitcoinMainnet(_)
+        | HttpLedger::BitcoinTestnet(_)
+        | HttpLedger::BitcoinRegtest(_) => ExpiryLedger::Bitcoin,
+        HttpLedger::LitecoinMainnet(_)
+        | HttpLedger::LitecoinTestnet(_)
+        | HttpLedger::LitecoinRegtest(_) => ExpiryLedger::Litecoin,
+        HttpLedger::Ethereum(_) => ExpiryLedger::Ethereum,
+    }
+}
+
+fn capability_asset(asset: &HttpAsset) -> capabilities::Asset {
+    match asset {
+        HttpAsset::Bitcoin(_) => capabilities::Asset::Bitcoin,
+        HttpAsset::Ether(_) => capabilities::Asset::Ether,
+        HttpAsset::Erc20(_) => capabilities::Asset::Erc20,
+    }
+}
+
 /// The identities a user may have to provide for a given swap.
 ///
 /// To make the implementation easier, this is hardcoded to Ethereum addresses
@@ -719,6 +1477,26 @@ impl HttpIdentities {
             beta_ledger_redeem_identity,
         })
     }
+
+    fn into_ethereum_ethereum_identities(
+        self,
+    ) -> anyhow::Result<Identities<identity::Ethereum, identity::Ethereum>> {
+        let alpha_ledger_refund_identity = self.alpha_ledger_refund_identity.ok_or_else(|| {
+            anyhow::Error::from(MissingIdentity {
+                kind: IdentityKind::AlphaLedgerRefundIdentity,
+            })
+        })?;
+        let beta_ledger_redeem_identity = self.beta_ledger_redeem_identity.ok_or_else(|| {
+            anyhow::Error::from(MissingIdentity {
+                kind: IdentityKind::BetaLedgerRedeemIdentity,
+            })
+        })?;
+
+        Ok(Identities {
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -753,6 +1531,10 @@ pub enum IdentityKind {
     BetaLedgerRedeemIdentity,
 }
 
+fn default_hash_function() -> HashFunction {
+    HashFunction::Sha256
+}
+
 fn default_alpha_expiry() -> Timestamp {
     Timestamp::now().plus(60 * 60 * 24)
 }
@@ -797,7 +1579,42 @@ mod tests {
 
         let body = serde_json::from_str::<SwapRequestBody>(body);
 
-        assert_that(&body).is_ok();
+        assert_that(&body)
+            .is_ok()
+            .map(|b| &b.hash_function)
+            .is_equal_to(&HashFunction::Sha256);
+    }
+
+    #[test]
+    fn can_deserialize_swap_request_body_with_hash_function() {
+        let body = r#"{
+                "alpha_ledger": {
+                    "name": "bitcoin",
+                    "network": "regtest"
+                },
+                "beta_ledger": {
+                    "name": "ethereum",
+                    "chain_id": 17
+                },
+                "alpha_asset": {
+                    "name": "bitcoin",
+                    "quantity": "100000000"
+                },
+                "beta_asset": {
+                    "name": "ether",
+                    "quantity": "10000000000000000000"
+                },
+                "beta_ledger_redeem_identity": "0x00a329c0648769a73afac7f9381e08fb43dbea72",
+                "hash_function": "RIPEMD160-SHA256",
+                "peer": "Qma9T5YraSnpRDZqRR4krcSJabThc8nwZuJV3LercPHufi"
+            }"#;
+
+        let body = serde_json::from_str::<SwapRequestBody>(body);
+
+        assert_that(&body)
+            .is_ok()
+            .map(|b| &b.hash_function)
+            .is_equal_to(&HashFunction::Ripemd160Sha256);
     }
 
     #[test]
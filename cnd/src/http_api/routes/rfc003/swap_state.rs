@@ -48,6 +48,7 @@ pub enum SwapCommunicationState {
     Sent,
     Accepted,
     Declined,
+    Failed,
 }
 
 impl<AL, BL, AA, BA, AI, BI> From<rfc003::SwapCommunication<AL, BL, AA, BA, AI, BI>>
@@ -86,6 +87,16 @@ impl<AL, BL, AA, BA, AI, BI> From<rfc003::SwapCommunication<AL, BL, AA, BA, AI,
                 beta_refund_identity: None,
                 secret_hash: request.secret_hash,
             },
+            Failed { request } => Self {
+                status: SwapCommunicationState::Failed,
+                alpha_expiry: request.alpha_expiry,
+                beta_expiry: request.beta_expiry,
+                alpha_redeem_identity: None,
+                beta_redeem_identity: Http(request.beta_ledger_redeem_identity),
+                alpha_refund_identity: Http(request.alpha_ledger_refund_identity),
+                beta_refund_identity: None,
+                secret_hash: request.secret_hash,
+            },
         }
     }
 }
@@ -177,7 +188,7 @@ impl SwapStatus {
         use self::SwapCommunicationState::*;
         use crate::swap_protocols::rfc003::HtlcState::*;
 
-        if swap_communication_state == Declined {
+        if swap_communication_state == Declined || swap_communication_state == Failed {
             return SwapStatus::NotSwapped;
         }
 
@@ -193,10 +204,11 @@ impl SwapStatus {
 #[cfg(test)]
 impl quickcheck::Arbitrary for SwapCommunicationState {
     fn arbitrary<G: quickcheck::Gen>(g: &mut G) -> Self {
-        match g.next_u32() % 3 {
+        match g.next_u32() % 4 {
             0 => SwapCommunicationState::Declined,
             1 => SwapCommunicationState::Accepted,
             2 => SwapCommunicationState::Sent,
+            3 => SwapCommunicationState::Failed,
             _ => unreachable!(),
         }
     }
@@ -242,6 +254,14 @@ mod tests {
         )
     }
 
+    #[test]
+    fn given_failed_should_not_be_swapped() {
+        assert_eq!(
+            SwapStatus::new(Failed, NotDeployed, NotDeployed),
+            SwapStatus::NotSwapped
+        )
+    }
+
     #[test]
     fn given_both_redeem_should_be_swapped() {
         assert_eq!(
@@ -1,7 +1,7 @@
 #![allow(clippy::type_repetition_in_bounds)]
 
 use crate::{
-    db::{Swap, SwapTypes},
+    db::{ArchivedSwap, FinalStatus, Swap, SwapTypes},
     http_api::{
         action::ToSirenAction,
         route_factory::swap_path,
@@ -10,7 +10,10 @@ use crate::{
     },
     seed::DeriveSwapSeed,
     swap_protocols::{
-        actions::Actions, rfc003, state::Get, Facade, HashFunction, SwapId, SwapProtocol,
+        actions::Actions,
+        rfc003::{self, watchdog::Warning, LedgerTimes},
+        state::Get,
+        Facade, SwapId, SwapProtocol,
     },
 };
 use anyhow::anyhow;
@@ -27,6 +30,9 @@ pub struct SwapResource<S> {
     pub protocol: Http<SwapProtocol>,
     pub status: SwapStatus,
     pub parameters: SwapParameters,
+    /// What the watchdog noticed about the counterparty, see
+    /// `rfc003::watchdog`.
+    pub warnings: Vec<Warning>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<S>,
 }
@@ -116,14 +122,22 @@ pub async fn build_rfc003_siren_entity(
         let alpha_ledger = LedgerState::from(alpha_ledger_state.clone());
         let beta_ledger = LedgerState::from(beta_ledger_state.clone());
         let parameters = SwapParameters::from(swap_communication.request().clone());
+        let hash_function = swap_communication.request().hash_function;
 
         let secret_source = dependencies.derive_swap_seed(id);
+        let ledger_times = LedgerTimes::fetch(
+            dependencies,
+            &swap_communication.request().alpha_ledger,
+            &swap_communication.request().beta_ledger,
+        )
+        .await;
 
         let actions = {
             let state = RoleState::new(
                 swap_communication,
                 alpha_ledger_state,
                 beta_ledger_state,
+                ledger_times,
                 secret_source,
             );
             state.actions()
@@ -138,10 +152,11 @@ pub async fn build_rfc003_siren_entity(
         let swap = SwapResource {
             id: Http(id),
             status,
-            protocol: Http(SwapProtocol::Rfc003(HashFunction::Sha256)),
+            protocol: Http(SwapProtocol::Rfc003(hash_function)),
             parameters,
             role: swap.role.to_string(),
             counterparty: Http(swap.counterparty),
+            warnings: dependencies.swap_warnings.get(&id).await,
             state: match include_state {
                 IncludeState::Yes => Some(SwapState::<AH, BH, AI, BI, AT, BT> {
                     communication,
@@ -181,3 +196,52 @@ pub async fn build_rfc003_siren_entity(
         Ok(entity)
     })
 }
+
+/// Builds the siren entity of a swap from the archive tables.
+///
+/// The ledgers of archived swaps are no longer watched, hence there is neither
+/// state nor actions and the status is derived from the final status.
+pub fn build_archived_siren_entity(archived: ArchivedSwap) -> anyhow::Result<siren::Entity> {
+    let id = archived.swap.swap_id;
+
+    with_swap_types!(archived.types, {
+        let request = archived.request::<AL, BL, AA, BA, AI, BI>()?;
+
+        let status = match archived.final_status {
+            FinalStatus::Swapped => SwapStatus::Swapped,
+            FinalStatus::NotSwapped | FinalStatus::Refunded | FinalStatus::Failed => {
+                SwapStatus::NotSwapped
+            }
+        };
+
+        let swap = SwapResource::<()> {
+            id: Http(id),
+            status,
+            protocol: Http(SwapProtocol::Rfc003(request.hash_function)),
+            parameters: SwapParameters::from(request),
+            role: archived.swap.role.to_string(),
+            counterparty: Http(archived.swap.counterparty.clone()),
+            warnings: Vec::new(),
+            state: None,
+        };
+
+        let entity = siren::Entity::default()
+            .with_class_member("swap")
+            .with_class_member("archived")
+            .with_properties(swap)
+            .map_err(|e| {
+                tracing::error!("failed to set properties of entity: {:?}", e);
+                HttpApiProblem::with_title_and_type_from_status(StatusCode::INTERNAL_SERVER_ERROR)
+            })?
+            .with_link(
+                siren::NavigationalLink::new(
+                    &["describedBy"],
+                    "https://github.com/comit-network/RFCs/blob/master/RFC-003-SWAP-Basic.adoc",
+                )
+                .with_type("text/html")
+                .with_class_member("protocol-spec"),
+            );
+
+        Ok(entity)
+    })
+}
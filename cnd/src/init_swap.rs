@@ -1,20 +1,23 @@
 use crate::{
-    db::AcceptedSwap,
+    db::{AcceptedSwap, LedgerEvent, LoadLedgerEvents, Save},
     swap_protocols::{
         rfc003::{
-            create_swap::{create_watcher, OngoingSwap},
+            create_swap::{create_watcher, restore_ledger_state, OngoingSwap},
             events::{HtlcDeployed, HtlcFunded, HtlcRedeemed, HtlcRefunded},
-            Accept, Request, SwapCommunication,
+            watchdog::{self, Deadlines},
+            Accept, LedgerState, Request, SwapCommunication,
         },
         state::Insert,
-        Facade,
+        Facade, LedgerSide, Role,
     },
+    timestamp::Timestamp,
 };
 use tracing_futures::Instrument;
 
 #[allow(clippy::cognitive_complexity)]
 pub async fn init_accepted_swap<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
     dependencies: &Facade,
+    role: Role,
     accepted: AcceptedSwap<AL, BL, AA, BA, AI, BI>,
 ) -> anyhow::Result<()>
 where
@@ -25,7 +28,11 @@ where
         + HtlcRedeemed<AL, AA, AH, AI, AT>
         + HtlcRedeemed<BL, BA, BH, BI, BT>
         + HtlcRefunded<AL, AA, AH, AI, AT>
-        + HtlcRefunded<BL, BA, BH, BI, BT>,
+        + HtlcRefunded<BL, BA, BH, BI, BT>
+        + Save<LedgerEvent<AA, AH, AT>>
+        + Save<LedgerEvent<BA, BH, BT>>
+        + LoadLedgerEvents<AA, AH, AT>
+        + LoadLedgerEvents<BA, BH, BT>,
     AL: Clone + Send + Sync + 'static,
     BL: Clone + Send + Sync + 'static,
     AA: Ord + Clone + Send + Sync + 'static,
@@ -58,10 +65,11 @@ where
             dependencies.clone(),
             dependencies.alpha_ledger_state.clone(),
             id,
+            LedgerSide::Alpha,
             swap.alpha_htlc_params(),
             accepted_at,
         )
-        .instrument(tracing::info_span!("alpha")),
+        .instrument(tracing::info_span!("alpha", swap_id = %id)),
     );
 
     tokio::task::spawn(
@@ -69,11 +77,112 @@ where
             dependencies.clone(),
             dependencies.beta_ledger_state.clone(),
             id,
+            LedgerSide::Beta,
             swap.beta_htlc_params(),
             accepted_at,
         )
-        .instrument(tracing::info_span!("beta")),
+        .instrument(tracing::info_span!("beta", swap_id = %id)),
+    );
+
+    tokio::task::spawn(
+        watchdog::watch::<_, AA, AH, AT, BA, BH, BT>(
+            dependencies.alpha_ledger_state.clone(),
+            dependencies.beta_ledger_state.clone(),
+            dependencies.swap_warnings.clone(),
+            id,
+            role,
+            Deadlines {
+                accepted_at: Timestamp::from(accepted_at),
+                alpha_expiry: swap.alpha_htlc_params().expiry,
+                beta_expiry: swap.beta_htlc_params().expiry,
+            },
+            dependencies.watchdog,
+        )
+        .instrument(tracing::info_span!("watchdog", swap_id = %id)),
     );
 
     Ok(())
 }
+
+/// Restores the state of a swap that has finished without watching its ledgers
+/// again.
+pub async fn init_finished_swap<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
+    dependencies: &Facade,
+    accepted: AcceptedSwap<AL, BL, AA, BA, AI, BI>,
+) -> anyhow::Result<()>
+where
+    Facade: LoadLedgerEvents<AA, AH, AT> + LoadLedgerEvents<BA, BH, BT>,
+    AL: Clone + Send + Sync + 'static,
+    BL: Clone + Send + Sync + 'static,
+    AA: Ord + Clone + Send + Sync + 'static,
+    BA: Ord + Clone + Send + Sync + 'static,
+    AH: Clone + Send + Sync + 'static,
+    BH: Clone + Send + Sync + 'static,
+    AI: Clone + Send + Sync + 'static,
+    BI: Clone + Send + Sync + 'static,
+    AT: Clone + Send + Sync + 'static,
+    BT: Clone + Send + Sync + 'static,
+    Request<AL, BL, AA, BA, AI, BI>: Clone,
+    Accept<AI, BI>: Copy,
+{
+    let (request, accept, _) = accepted;
+    let id = request.swap_id;
+
+    dependencies
+        .insert(id, SwapCommunication::Accepted {
+            request,
+            response: accept,
+        })
+        .await;
+
+    tracing::trace!("restoring finished swap: {}", id);
+
+    restore_ledger_state::<_, _, AA, AH, AT>(
+        dependencies,
+        &*dependencies.alpha_ledger_state,
+        id,
+        LedgerSide::Alpha,
+    )
+    .await;
+    restore_ledger_state::<_, _, BA, BH, BT>(
+        dependencies,
+        &*dependencies.beta_ledger_state,
+        id,
+        LedgerSide::Beta,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Restores the state of a swap whose request could not be sent or was not
+/// answered.
+pub async fn init_failed_swap<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
+    dependencies: &Facade,
+    request: Request<AL, BL, AA, BA, AI, BI>,
+) where
+    AL: Send + 'static,
+    BL: Send + 'static,
+    AA: Send + 'static,
+    BA: Send + 'static,
+    AH: Send + 'static,
+    BH: Send + 'static,
+    AI: Send + 'static,
+    BI: Send + 'static,
+    AT: Send + 'static,
+    BT: Send + 'static,
+{
+    let id = request.swap_id;
+
+    dependencies
+        .insert(id, SwapCommunication::Failed { request })
+        .await;
+    dependencies
+        .alpha_ledger_state
+        .insert(id, LedgerState::<AA, AH, AT>::NotDeployed)
+        .await;
+    dependencies
+        .beta_ledger_state
+        .insert(id, LedgerState::<BA, BH, BT>::NotDeployed)
+        .await;
+}
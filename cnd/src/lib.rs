@@ -44,6 +44,7 @@ pub mod load_swaps;
 pub mod network;
 #[cfg(test)]
 pub mod quickcheck;
+pub mod recover;
 #[macro_use]
 pub mod seed;
 pub mod file_lock;
@@ -16,19 +16,7 @@ macro_rules! header {
     ($e:expr) => {
         header_internal!($e, {
             tracing::info!("Header was not present, early returning with decline response!");
-            let decline_body = DeclineResponseBody {
-                reason: Some(SwapDeclineReason::MissingMandatoryHeader),
-            };
-
-            return Err(Response::empty().with_header(
-                "decision",
-                Decision::Declined
-                    .to_header()
-                    .expect("Decision should not fail to serialize"),
-            )
-            .with_body(serde_json::to_value(decline_body).expect(
-                "decline body should always serialize into serde_json::Value",
-            )));
+            return Err(decline(SwapDeclineReason::MissingMandatoryHeader));
         })
     };
 }
@@ -40,19 +28,7 @@ macro_rules! body {
             Ok(body) => body,
             Err(e) => {
                 tracing::error!("Failed to deserialize body because of unexpected field: {}", e);
-                let decline_body = DeclineResponseBody {
-                    reason: Some(SwapDeclineReason::BadJsonField),
-                };
-
-                return Err(Response::empty().with_header(
-                    "decision",
-                    Decision::Declined
-                        .to_header()
-                        .expect("Decision should not fail to serialize"),
-                )
-                .with_body(serde_json::to_value(decline_body).expect(
-                    "decline body should always serialize into serde_json::Value",
-                )));
+                return Err(decline(SwapDeclineReason::BadJsonField));
             }
         }
     };
@@ -66,20 +42,7 @@ macro_rules! header_internal {
             Some(Err(e)) => {
                 tracing::error!("Failed to deserialize header because of unexpected field: {}", e);
 
-                let decline_body = DeclineResponseBody {
-                    reason: Some(SwapDeclineReason::BadJsonField),
-                };
-
-                return Err(Response::empty().with_header(
-                    "decision",
-                    Decision::Declined
-                        .to_header()
-                        .expect("Decision should not fail to serialize"),
-                )
-                .with_body(serde_json::to_value(decline_body).expect(
-                    "decline body should always serialize into serde_json::Value",
-                )));
-
+                return Err(decline(SwapDeclineReason::BadJsonField));
             },
             None => $none,
         }
@@ -1,7 +1,7 @@
 #![allow(clippy::type_repetition_in_bounds)]
 use crate::{
-    db::{DetermineTypes, LoadAcceptedSwap, Retrieve},
-    init_swap::init_accepted_swap,
+    db::{DetermineTypes, FinalStatus, LoadAcceptedSwap, Retrieve},
+    init_swap::{init_accepted_swap, init_failed_swap, init_finished_swap},
     swap_protocols::Facade,
 };
 
@@ -14,19 +14,49 @@ pub async fn load_swaps_from_database(facade: Facade) -> anyhow::Result<()> {
         tracing::debug!("got swap from database: {}", swap_id);
 
         let types = DetermineTypes::determine_types(&facade, &swap_id).await?;
+        let final_status = facade.db.final_status(&swap_id).await?;
 
         with_swap_types!(types, {
-            let accepted =
-                LoadAcceptedSwap::<AL, BL, AA, BA, AI, BI>::load_accepted_swap(&facade, &swap_id)
+            match final_status {
+                // the request was never answered, hence there is nothing to watch
+                Some(FinalStatus::Failed) => {
+                    match facade
+                        .db
+                        .load_request::<AL, BL, AA, BA, AI, BI>(&swap_id)
+                        .await
+                    {
+                        Ok(request) => {
+                            init_failed_swap::<_, _, AA, BA, AH, BH, _, _, AT, BT>(&facade, request)
+                                .await
+                        }
+                        Err(e) => tracing::error!("failed to load swap: {}, continuing ...", e),
+                    }
+                }
+                final_status => {
+                    // the ledgers of finished swaps are not watched again
+                    let finished = final_status.is_some();
+                    let accepted = LoadAcceptedSwap::<AL, BL, AA, BA, AI, BI>::load_accepted_swap(
+                        &facade, &swap_id,
+                    )
                     .await;
 
-            match accepted {
-                Ok(accepted) => {
-                    init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(&facade, accepted)
-                        .await?;
+                    match accepted {
+                        Ok(accepted) if finished => {
+                            init_finished_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(
+                                &facade, accepted,
+                            )
+                            .await?;
+                        }
+                        Ok(accepted) => {
+                            init_accepted_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(
+                                &facade, swap.role, accepted,
+                            )
+                            .await?;
+                        }
+                        Err(e) => tracing::error!("failed to load swap: {}, continuing ...", e),
+                    };
                 }
-                Err(e) => tracing::error!("failed to load swap: {}, continuing ...", e),
-            };
+            }
         });
     }
     Ok(())
@@ -12,30 +12,32 @@
     clippy::dbg_macro
 )]
 #![forbid(unsafe_code)]
-use crate::cli::Options;
+use crate::cli::{Command, Options, Seed};
 use anyhow::Context;
+use chrono::Utc;
 use cnd::{
     btsieve::{
         bitcoin::{self, BitcoindConnector},
         ethereum::{self, Web3Connector},
     },
     config::{self, validation::validate_blockchain_config, Settings},
-    db::Sqlite,
+    db::{Database, SwapDocument},
     file_lock::TryLockExclusive,
     http_api::route_factory,
     jsonrpc,
     lnd::LndConnectorParams,
     load_swaps,
     network::Swarm,
+    recover::{self, BuildTransactions, Recovery},
     seed::RootSeed,
     swap_protocols::{
-        halight::InvoiceStates, Facade, Facade2, LedgerStates, SwapCommunicationStates,
-        SwapErrorStates,
+        halight::InvoiceStates, rfc003::watchdog::SwapWarnings, Facade, Facade2, LedgerStates,
+        SwapCommunicationStates, SwapErrorStates,
     },
 };
 
 use rand::rngs::OsRng;
-use std::{process, sync::Arc};
+use std::{collections::HashMap, env, fs, path::Path, process, sync::Arc, time::Duration};
 use structopt::StructOpt;
 use tokio::runtime;
 mod cli;
@@ -56,11 +58,33 @@ fn main() -> anyhow::Result<()> {
         process::exit(0);
     }
 
-    crate::trace::init_tracing(settings.logging.level)?;
+    let passphrase_fd = options.passphrase_fd;
+    let trust_seed = options.trust_seed;
+
+    let recover = match options.cmd {
+        Some(Command::Seed(command)) => {
+            seed(command, &settings, passphrase_fd)?;
+            process::exit(0);
+        }
+        Some(Command::Export) => {
+            export(&settings)?;
+            process::exit(0);
+        }
+        Some(Command::Import { file }) => {
+            import(&settings, &file)?;
+            process::exit(0);
+        }
+        Some(Command::Recover(recover)) => Some(recover),
+        None => None,
+    };
+
+    let _tracing = crate::trace::init_tracing(&settings.logging)?;
 
-    let database = Sqlite::new_in_dir(&settings.data.dir)?;
+    let database = Database::open(&settings.data)?;
 
-    let seed = RootSeed::from_dir_or_generate(&settings.data.dir, OsRng)?;
+    let seed = RootSeed::from_dir_or_generate(&settings.data.dir, OsRng, || {
+        read_passphrase(passphrase_fd)
+    })?;
 
     let _locked_datadir = &settings.data.dir.try_lock_exclusive()?;
 
@@ -70,50 +94,41 @@ fn main() -> anyhow::Result<()> {
         .thread_stack_size(1024 * 1024 * 8) // the default is 2MB but that causes a segfault for some reason
         .build()?;
 
-    let bitcoin_connector = {
-        let config::Bitcoin { bitcoind, network } = &settings.bitcoin;
-        let connector = BitcoindConnector::new(bitcoind.node_url.clone(), *network)?;
-
-        runtime.block_on(async {
-            validate_blockchain_config(&connector, *network)
-                .await
-                .or_else::<anyhow::Error, _>(|e| {
-                    let conn_error = e.downcast::<reqwest::Error>()?;
-                    tracing::warn!("Could not validate Bitcoin node config: {}", conn_error);
-
-                    Ok(())
-                })
-        })?;
-
-        const BITCOIN_BLOCK_CACHE_CAPACITY: usize = 144;
-
-        Arc::new(bitcoin::Cache::new(connector, BITCOIN_BLOCK_CACHE_CAPACITY))
-    };
+    runtime
+        .block_on(database.verify_seed(&seed, trust_seed))
+        .context(
+            "refusing to start, the seed cannot be verified against the swaps in the database",
+        )?;
+
+    let bitcoin_connector = connect_to_bitcoind(&mut runtime, &settings.bitcoin, "Bitcoin")?;
+    let litecoin_connector = settings
+        .litecoin
+        .as_ref()
+        .map(|litecoin| connect_to_bitcoind(&mut runtime, litecoin, "Litecoin"))
+        .transpose()?;
+
+    let ethereum_connector = connect_to_ethereum(&mut runtime, &settings.ethereum)?;
+
+    let mut ethereum_connectors = HashMap::new();
+    ethereum_connectors.insert(settings.ethereum.chain_id, Arc::clone(&ethereum_connector));
+    for chain in settings.additional_ethereum_chains.iter() {
+        let connector = connect_to_ethereum(&mut runtime, chain)?;
+        ethereum_connectors.insert(chain.chain_id, connector);
+    }
 
-    let ethereum_connector = {
-        let config::Ethereum { parity, chain_id } = &settings.ethereum;
-        let connector = Web3Connector::new(parity.node_url.clone());
-
-        runtime.block_on(async {
-            validate_blockchain_config(&connector, *chain_id)
-                .await
-                .or_else::<anyhow::Error, _>(|e| {
-                    let conn_error = e.downcast::<jsonrpc::Error>()?;
-                    tracing::warn!("Could not validate Ethereum node config: {}", conn_error);
-
-                    Ok(())
-                })
-        })?;
-
-        const ETHEREUM_BLOCK_CACHE_CAPACITY: usize = 720;
-        const ETHEREUM_RECEIPT_CACHE_CAPACITY: usize = 720;
-
-        Arc::new(ethereum::Cache::new(
-            connector,
-            ETHEREUM_BLOCK_CACHE_CAPACITY,
-            ETHEREUM_RECEIPT_CACHE_CAPACITY,
-        ))
-    };
+    if let Some(options) = recover {
+        let recovery = Recovery {
+            bitcoin_connector,
+            litecoin_connector,
+            ethereum_connector,
+            db: database,
+            seed,
+            funding: settings.funding,
+            scan_timeout: Duration::from_secs(options.scan_timeout_secs),
+        };
+        runtime.block_on(recover_swaps(recovery, options))?;
+        process::exit(0);
+    }
 
     let lnd_connector_params = LndConnectorParams {
         lnd_url: settings.lightning.lnd.rest_api_url.clone(),
@@ -131,6 +146,7 @@ fn main() -> anyhow::Result<()> {
     let invoice_states = Arc::new(InvoiceStates::default());
 
     let swap_error_states = Arc::new(SwapErrorStates::default());
+    let swap_warnings = Arc::new(SwapWarnings::default());
 
     let swarm = Swarm::new(
         &settings,
@@ -154,17 +170,26 @@ fn main() -> anyhow::Result<()> {
 
     let deps = Facade {
         bitcoin_connector,
-        ethereum_connector,
+        litecoin_connector,
+        ethereum_connectors: Arc::new(ethereum_connectors),
         alpha_ledger_state,
         beta_ledger_state,
         swap_communication_states,
         swap_error_states,
+        swap_warnings,
+        funding: settings.funding,
+        expiries: settings.expiries.clone(),
+        watchdog: settings.watchdog,
         seed,
         db: database,
         swarm,
     };
 
+    runtime.block_on(deps.db.mark_finished_swaps())?;
     runtime.block_on(load_swaps::load_swaps_from_database(deps.clone()))?;
+    if let Some(archive) = settings.data.archive {
+        runtime.spawn(archive_finished_swaps(deps.db.clone(), archive));
+    }
     runtime.spawn(spawn_warp_instance(settings, deps, facade2));
 
     // Block the current thread.
@@ -172,6 +197,116 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Litecoin nodes serve the same REST interface as bitcoind, hence both are
+/// watched through a `BitcoindConnector`.
+fn connect_to_bitcoind(
+    runtime: &mut runtime::Runtime,
+    config: &config::Bitcoin,
+    chain: &str,
+) -> anyhow::Result<Arc<bitcoin::Cache<BitcoindConnector>>> {
+    let config::Bitcoin { bitcoind, network } = config;
+    let connector = BitcoindConnector::new(bitcoind.node_url.clone(), *network)?;
+
+    runtime.block_on(async {
+        validate_blockchain_config(&connector, *network)
+            .await
+            .or_else::<anyhow::Error, _>(|e| {
+                let conn_error = e.downcast::<reqwest::Error>()?;
+                tracing::warn!("Could not validate {} node config: {}", chain, conn_error);
+
+                Ok(())
+            })
+    })?;
+
+    const BITCOIN_BLOCK_CACHE_CAPACITY: usize = 144;
+
+    Ok(Arc::new(bitcoin::Cache::new(
+        connector,
+        BITCOIN_BLOCK_CACHE_CAPACITY,
+    )))
+}
+
+fn connect_to_ethereum(
+    runtime: &mut runtime::Runtime,
+    ethereum: &config::Ethereum,
+) -> anyhow::Result<Arc<ethereum::Cache<Web3Connector>>> {
+    let config::Ethereum { parity, chain_id } = ethereum;
+    let connector = Web3Connector::new(parity.node_url.clone());
+
+    runtime.block_on(async {
+        validate_blockchain_config(&connector, *chain_id)
+            .await
+            .or_else::<anyhow::Error, _>(|e| {
+                let conn_error = e.downcast::<jsonrpc::Error>()?;
+                tracing::warn!("Could not validate Ethereum node config: {}", conn_error);
+
+                Ok(())
+            })
+    })?;
+
+    const ETHEREUM_BLOCK_CACHE_CAPACITY: usize = 720;
+    const ETHEREUM_RECEIPT_CACHE_CAPACITY: usize = 720;
+
+    Ok(Arc::new(ethereum::Cache::new(
+        connector,
+        ETHEREUM_BLOCK_CACHE_CAPACITY,
+        ETHEREUM_RECEIPT_CACHE_CAPACITY,
+    )))
+}
+
+#[allow(clippy::print_stdout)] // The export is the output of the command
+fn export(settings: &Settings) -> anyhow::Result<()> {
+    let _locked_datadir = &settings.data.dir.try_lock_exclusive()?;
+    let database = Database::open(&settings.data)?;
+
+    let swaps = runtime::Runtime::new()?.block_on(database.export_swaps())?;
+    println!("{}", serde_json::to_string_pretty(&swaps)?);
+
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)] // We do not initialize tracing for this command
+fn import(settings: &Settings, file: &Path) -> anyhow::Result<()> {
+    let _locked_datadir = &settings.data.dir.try_lock_exclusive()?;
+    let database = Database::open(&settings.data)?;
+
+    let contents =
+        fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))?;
+    let swaps: Vec<SwapDocument> = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a swap export", file.display()))?;
+    let total = swaps.len();
+
+    let imported = runtime::Runtime::new()?.block_on(database.import_swaps(swaps))?;
+    println!(
+        "Imported {} swaps, skipped {} that already existed",
+        imported,
+        total - imported
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)] // The report is the output of the command
+async fn recover_swaps(recovery: Recovery, options: cli::Recover) -> anyhow::Result<()> {
+    let build_transactions = if options.build_transactions {
+        Some(BuildTransactions {
+            bitcoin_address: options.bitcoin_address,
+            litecoin_address: options.litecoin_address,
+            bitcoin_fee_per_wu: options.fee_per_wu,
+        })
+    } else {
+        None
+    };
+
+    let reports = recover::recover(&recovery, build_transactions.as_ref()).await?;
+
+    for report in reports {
+        println!("{}", serde_json::to_string(&report)?);
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::print_stdout)] // We cannot use `log` before we have the config file
 fn version() {
     let name: &'static str = "COMIT network daemon";
@@ -197,6 +332,26 @@ async fn spawn_warp_instance(settings: Settings, dependencies: Facade, facade2:
     warp::serve(routes).bind(listen_addr).await
 }
 
+/// How often we look for finished swaps that are old enough to be archived.
+const ARCHIVE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+async fn archive_finished_swaps(database: Database, archive: config::Archive) {
+    let mut interval = tokio::time::interval(ARCHIVE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let finished_before =
+            Utc::now().naive_utc() - chrono::Duration::days(archive.after_days.into());
+
+        match database.archive_finished_swaps(finished_before).await {
+            Ok(0) => {}
+            Ok(archived) => tracing::info!("archived {} finished swaps", archived),
+            Err(e) => tracing::warn!("failed to archive finished swaps: {:?}", e),
+        }
+    }
+}
+
 #[allow(clippy::print_stdout)] // We cannot use `log` before we have the config file
 fn read_config(options: &Options) -> anyhow::Result<config::File> {
     // if the user specifies a config path, use it
@@ -230,3 +385,85 @@ fn dump_config(settings: Settings) -> anyhow::Result<()> {
     println!("{}", serialized);
     Ok(())
 }
+
+#[allow(clippy::print_stdout)] // Print the mnemonic only, so it can be piped somewhere safe
+fn seed(command: Seed, settings: &Settings, passphrase_fd: Option<u32>) -> anyhow::Result<()> {
+    match command {
+        Seed::ExportMnemonic => {
+            let seed = RootSeed::from_dir(&settings.data.dir, || read_passphrase(passphrase_fd))?;
+            println!("{}", seed.to_mnemonic());
+        }
+        Seed::Encrypt => {
+            let passphrase = read_new_passphrase(passphrase_fd)?;
+            RootSeed::encrypt_file_in_dir(&settings.data.dir, &passphrase, OsRng)?;
+            eprintln!(
+                "Encrypted seed in data directory {}",
+                settings.data.dir.display()
+            );
+        }
+        Seed::Restore { mnemonic } => {
+            let seed = RootSeed::from_mnemonic(&mnemonic)?;
+            let passphrase = configured_passphrase(passphrase_fd)?;
+            if passphrase.as_deref() == Some("") {
+                anyhow::bail!("the seed passphrase must not be empty");
+            }
+            seed.write_to_dir(&settings.data.dir, passphrase.as_deref(), OsRng)?;
+            eprintln!(
+                "Restored seed to data directory {}",
+                settings.data.dir.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+const PASSPHRASE_ENV_VAR: &str = "CND_SEED_PASSPHRASE";
+
+/// Read the passphrase of the seed file from the given file descriptor, the
+/// environment or by prompting the user, in that order.
+fn read_passphrase(passphrase_fd: Option<u32>) -> anyhow::Result<String> {
+    if let Some(passphrase) = configured_passphrase(passphrase_fd)? {
+        return Ok(passphrase);
+    }
+
+    rpassword::read_password_from_tty(Some("Seed passphrase: "))
+        .context("failed to prompt for the seed passphrase")
+}
+
+/// Read the passphrase of the seed file from the given file descriptor or
+/// the environment, without prompting the user.
+fn configured_passphrase(passphrase_fd: Option<u32>) -> anyhow::Result<Option<String>> {
+    if let Some(fd) = passphrase_fd {
+        let path = format!("/dev/fd/{}", fd);
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read passphrase from {}", path))?;
+
+        return Ok(Some(
+            contents.trim_end_matches(&['\r', '\n'][..]).to_owned(),
+        ));
+    }
+
+    Ok(env::var(PASSPHRASE_ENV_VAR).ok())
+}
+
+/// Like `read_passphrase` but asks twice if the user is prompted and refuses
+/// empty passphrases.
+fn read_new_passphrase(passphrase_fd: Option<u32>) -> anyhow::Result<String> {
+    let prompted = passphrase_fd.is_none() && env::var(PASSPHRASE_ENV_VAR).is_err();
+
+    let passphrase = read_passphrase(passphrase_fd)?;
+    if passphrase.is_empty() {
+        anyhow::bail!("the seed passphrase must not be empty");
+    }
+
+    if prompted {
+        let confirmation = rpassword::read_password_from_tty(Some("Repeat seed passphrase: "))
+            .context("failed to prompt for the seed passphrase")?;
+        if confirmation != passphrase {
+            anyhow::bail!("the passphrases do not match");
+        }
+    }
+
+    Ok(passphrase)
+}
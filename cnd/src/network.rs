@@ -1,8 +1,11 @@
 pub mod comit_ln;
 pub mod oneshot_behaviour;
 pub mod oneshot_protocol;
+pub mod orderbook;
 pub mod protocols;
+mod serde_peer_id;
 pub mod transport;
+mod upnp;
 
 pub use transport::ComitTransport;
 
@@ -13,12 +16,16 @@ use crate::{
         ethereum::{self, Web3Connector},
     },
     comit_api::LedgerKind,
-    config::Settings,
-    db::{Save, Sqlite, Swap},
-    htlc_location,
+    config::{self, Settings},
+    db::{Database, Save, Swap},
+    htlc_location, identity,
     libp2p_comit_ext::{FromHeader, ToHeader},
     lnd::LndConnectorParams,
-    network::comit_ln::ComitLN,
+    network::{
+        comit_ln::ComitLN,
+        orderbook::{Order, Orderbook, SignedOrder},
+        protocols::capabilities::{self, Capabilities},
+    },
     seed::RootSeed,
     swap_protocols::{
         halight::InvoiceStates,
@@ -29,9 +36,10 @@ use crate::{
             LedgerState, SwapCommunication,
         },
         state::Insert,
-        CreateSwapParams, HashFunction, LedgerStates, NodeLocalSwapId, Role,
-        SwapCommunicationStates, SwapId, SwapProtocol,
+        HashFunction, LedgerStates, NodeLocalSwapId, Role, SwapCommunicationStates, SwapId,
+        SwapParams, SwapProtocol,
     },
+    timestamp::Timestamp,
     transaction,
 };
 use async_trait::async_trait;
@@ -48,7 +56,7 @@ use libp2p::{
 };
 use libp2p_comit::{
     frame::{OutboundRequest, Response, ValidatedInboundRequest},
-    BehaviourOutEvent, Comit, PendingInboundRequest,
+    BehaviourOutEvent, Comit, PendingInboundRequest, RetryPolicy,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
@@ -59,11 +67,13 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use tokio::{
     runtime::{Handle, Runtime},
     sync::Mutex,
 };
+use tracing_futures::Instrument;
 
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
@@ -87,7 +97,7 @@ impl Swarm {
         alpha_ledger_state: Arc<LedgerStates>,
         beta_ledger_state: Arc<LedgerStates>,
         invoice_states: Arc<InvoiceStates>,
-        database: &Sqlite,
+        database: &Database,
     ) -> anyhow::Result<Self> {
         let local_key_pair = derive_key_pair(&seed);
         let local_peer_id = PeerId::from(local_key_pair.clone().public());
@@ -95,6 +105,7 @@ impl Swarm {
 
         let transport = transport::build_comit_transport(local_key_pair)?;
         let behaviour = ComitNode::new(
+            settings,
             bitcoin_connector,
             ethereum_connector,
             lnd_connector_params,
@@ -118,8 +129,16 @@ impl Swarm {
                 .expect("Could not listen on specified address");
         }
 
+        for addr in settings.network.external_addresses.clone() {
+            libp2p::Swarm::add_external_address(&mut swarm, addr);
+        }
+
         let swarm = Arc::new(Mutex::new(swarm));
 
+        if settings.network.upnp {
+            runtime.spawn(upnp::run(settings.network.listen.clone(), swarm.clone()));
+        }
+
         runtime.spawn(SwarmWorker {
             swarm: swarm.clone(),
         });
@@ -150,7 +169,7 @@ impl Swarm {
         unimplemented!()
     }
 
-    pub async fn initiate_communication(&self, id: NodeLocalSwapId, swap_params: CreateSwapParams) {
+    pub async fn initiate_communication(&self, id: NodeLocalSwapId, swap_params: SwapParams) {
         let mut guard = self.swarm.lock().await;
 
         guard.initiate_communication(id, swap_params)
@@ -162,6 +181,15 @@ impl Swarm {
         guard.get_finalized_swap(id)
     }
 
+    pub async fn get_finalized_hbit_swap(
+        &self,
+        id: NodeLocalSwapId,
+    ) -> Option<comit_ln::FinalizedHbitSwap> {
+        let mut guard = self.swarm.lock().await;
+
+        guard.get_finalized_hbit_swap(id)
+    }
+
     // On Bob's side, when an announce message is received execute the required
     // communication protocols and write the finalized swap to the database.  Then
     // spawn the same as is done for Alice.
@@ -201,12 +229,15 @@ fn derive_key_pair(seed: &RootSeed) -> Keypair {
     Keypair::Ed25519(key.into())
 }
 
-/// A `NetworkBehaviour` that delegates to the `Comit` and `Mdns` behaviours.
+/// A `NetworkBehaviour` that delegates to the `Comit`, `Orderbook`,
+/// `Capabilities` and `Mdns` behaviours.
 #[derive(NetworkBehaviour)]
 #[allow(missing_debug_implementations)]
 pub struct ComitNode {
     comit: Comit,
     comit_ln: ComitLN,
+    orderbook: Orderbook,
+    capabilities: Capabilities,
     mdns: Mdns,
 
     #[behaviour(ignore)]
@@ -222,7 +253,17 @@ pub struct ComitNode {
     #[behaviour(ignore)]
     pub seed: RootSeed,
     #[behaviour(ignore)]
-    pub db: Sqlite,
+    pub db: Database,
+    #[behaviour(ignore)]
+    expiries: config::Expiries,
+    /// The Ethereum chains we have a node for, requests for other chains are
+    /// declined.
+    #[behaviour(ignore)]
+    ethereum_chain_ids: Vec<ledger::ethereum::ChainId>,
+    /// The network of the Litecoin node we watch, if any, requests for other
+    /// Litecoin networks are declined.
+    #[behaviour(ignore)]
+    litecoin_network: Option<::bitcoin::Network>,
     #[behaviour(ignore)]
     response_channels: Arc<Mutex<HashMap<SwapId, oneshot::Sender<Response>>>>,
     #[behaviour(ignore)]
@@ -254,6 +295,8 @@ pub enum RequestError {
     Connecting(io::ErrorKind),
     #[error("unable to send the data on the existing connection")]
     Connection,
+    #[error("peer node did not respond in time")]
+    Timeout,
 }
 
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
@@ -264,6 +307,7 @@ pub struct Reason {
 impl ComitNode {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
+        settings: &Settings,
         bitcoin_connector: Arc<bitcoin::Cache<BitcoindConnector>>,
         ethereum_connector: Arc<ethereum::Cache<Web3Connector>>,
         lnd_connector_params: LndConnectorParams,
@@ -272,7 +316,7 @@ impl ComitNode {
         beta_ledger_state: Arc<LedgerStates>,
         invoice_states: Arc<InvoiceStates>,
         seed: RootSeed,
-        db: Sqlite,
+        db: Database,
         task_executor: Handle,
     ) -> Result<Self, io::Error> {
         let mut swap_headers = HashSet::new();
@@ -287,10 +331,17 @@ impl ComitNode {
         known_headers.insert("SWAP".into(), swap_headers);
 
         Ok(Self {
-            comit: Comit::new(known_headers),
+            comit: Comit::new(known_headers)
+                .with_request_timeout(Duration::from_secs(settings.network.request_timeout_secs))
+                .with_retry_policy(RetryPolicy {
+                    max_retries: settings.network.request_retries,
+                }),
+            orderbook: Orderbook::new(derive_key_pair(&seed)),
+            capabilities: Capabilities::new(capabilities::Message::ours()),
             mdns: Mdns::new()?,
             comit_ln: ComitLN::new(
                 lnd_connector_params,
+                bitcoin_connector.clone(),
                 ethereum_connector.clone(),
                 alpha_ledger_state.clone(),
                 invoice_states,
@@ -303,6 +354,12 @@ impl ComitNode {
             swap_communication_states,
             seed,
             db,
+            expiries: settings.expiries.clone(),
+            ethereum_chain_ids: std::iter::once(&settings.ethereum)
+                .chain(settings.additional_ethereum_chains.iter())
+                .map(|ethereum| ethereum.chain_id)
+                .collect(),
+            litecoin_network: settings.litecoin.as_ref().map(|litecoin| litecoin.network),
             response_channels: Arc::new(Mutex::new(HashMap::new())),
             task_executor,
         })
@@ -312,28 +369,41 @@ impl ComitNode {
         &mut self,
         peer_id: DialInformation,
         request: OutboundRequest,
-    ) -> impl futures::Future<Output = Result<Response, ()>> + Send + 'static + Unpin {
+    ) -> impl futures::Future<Output = Result<Response, libp2p_comit::RequestError>>
+           + Send
+           + 'static
+           + Unpin {
         self.comit
             .send_request((peer_id.peer_id, peer_id.address_hint), request)
     }
 
-    pub fn initiate_communication(&mut self, id: NodeLocalSwapId, swap_params: CreateSwapParams) {
+    pub fn initiate_communication(&mut self, id: NodeLocalSwapId, swap_params: SwapParams) {
         self.comit_ln.initiate_communication(id, swap_params)
     }
 
     pub fn get_finalized_swap(&mut self, id: NodeLocalSwapId) -> Option<comit_ln::FinalizedSwap> {
         self.comit_ln.get_finalized_swap(id)
     }
+
+    pub fn get_finalized_hbit_swap(
+        &mut self,
+        id: NodeLocalSwapId,
+    ) -> Option<comit_ln::FinalizedHbitSwap> {
+        self.comit_ln.get_finalized_hbit_swap(id)
+    }
 }
 
 // This is due to the introduction of a struct per Bitcoin network and can be
 // iteratively improved
-#[allow(clippy::cognitive_complexity)]
+#[allow(clippy::cognitive_complexity, clippy::too_many_arguments)]
 async fn handle_request(
-    db: Sqlite,
+    db: Database,
     swap_communication_states: Arc<SwapCommunicationStates>,
     alpha_ledger_state: Arc<LedgerStates>,
     beta_ledger_state: Arc<LedgerStates>,
+    expiries: config::Expiries,
+    ethereum_chain_ids: Vec<ledger::ethereum::ChainId>,
+    litecoin_network: Option<::bitcoin::Network>,
     counterparty: PeerId,
     mut request: ValidatedInboundRequest,
 ) -> Result<SwapId, Response> {
@@ -343,8 +413,19 @@ async fn handle_request(
                 .take_header("protocol")
                 .map(SwapProtocol::from_header));
             match protocol {
+                SwapProtocol::Rfc003(hash_function)
+                    if !rfc003::HASH_FUNCTIONS.contains(&hash_function) =>
+                {
+                    tracing::warn!(
+                        "rfc003 swaps using {} are currently not supported",
+                        hash_function
+                    );
+
+                    Err(decline(SwapDeclineReason::UnsupportedProtocol))
+                }
                 SwapProtocol::Rfc003(hash_function) => {
                     let swap_id = header!(request.take_header("id").map(SwapId::from_header));
+                    tracing::Span::current().record("swap_id", &tracing::field::display(swap_id));
                     let alpha_ledger = header!(request
                         .take_header("alpha_ledger")
                         .map(LedgerKind::from_header));
@@ -357,34 +438,64 @@ async fn handle_request(
                     let beta_asset = header!(request
                         .take_header("beta_asset")
                         .map(AssetKind::from_header));
+                    let expiry_rules = expiries.rules_for(alpha_ledger.into(), beta_ledger.into());
+
+                    for ledger_kind in [alpha_ledger, beta_ledger].iter() {
+                        if let LedgerKind::Ethereum(ethereum) = ledger_kind {
+                            if !ethereum_chain_ids.contains(&ethereum.chain_id) {
+                                tracing::warn!(
+                                    "no ethereum node is configured for chain id {:?}",
+                                    ethereum.chain_id
+                                );
 
-                    match (alpha_ledger, beta_ledger, alpha_asset, beta_asset) {
+                                return Err(decline(SwapDeclineReason::UnsupportedSwap));
+                            }
+                        }
+                        if let Some(network) = ledger_kind.litecoin_network() {
+                            if litecoin_network != Some(network) {
+                                tracing::warn!(
+                                    "no litecoin node is configured for network {:?}",
+                                    network
+                                );
+
+                                return Err(decline(SwapDeclineReason::UnsupportedSwap));
+                            }
+                        }
+                    }
+
+                    // The supported combinations only differ in the ledgers and
+                    // assets and in whether each ledger is Bitcoin or Ethereum.
+                    // Litecoin uses the HTLC and the types of Bitcoin.
+                    macro_rules! insert_request {
                         (
-                            LedgerKind::BitcoinRegtest,
-                            LedgerKind::Ethereum(beta_ledger),
-                            AssetKind::Bitcoin(alpha_asset),
-                            AssetKind::Ether(beta_asset),
-                        ) => {
+                            $alpha_ledger:expr,
+                            $beta_ledger:expr,
+                            $alpha_asset:expr,
+                            $beta_asset:expr,
+                            $alpha:ident,
+                            $beta:ident
+                        ) => {{
                             let request = rfc003_swap_request(
                                 swap_id,
-                                ledger::bitcoin::Regtest,
-                                beta_ledger,
-                                alpha_asset,
-                                beta_asset,
+                                $alpha_ledger,
+                                $beta_ledger,
+                                $alpha_asset,
+                                $beta_asset,
                                 hash_function,
                                 body!(request.take_body_as()),
-                            );
+                                &expiry_rules,
+                            )?;
                             insert_state_for_bob::<
                                 _,
                                 _,
                                 _,
                                 _,
-                                htlc_location::Bitcoin,
-                                htlc_location::Ethereum,
-                                _,
-                                _,
-                                transaction::Bitcoin,
-                                transaction::Ethereum,
+                                htlc_location::$alpha,
+                                htlc_location::$beta,
+                                identity::$alpha,
+                                identity::$beta,
+                                transaction::$alpha,
+                                transaction::$beta,
                                 _,
                             >(
                                 db.clone(),
@@ -397,458 +508,436 @@ async fn handle_request(
                             .await
                             .expect("Could not save state to db");
                             Ok(swap_id)
+                        }};
+                    }
+
+                    match (alpha_ledger, beta_ledger, alpha_asset, beta_asset) {
+                        (
+                            LedgerKind::BitcoinRegtest,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::bitcoin::Regtest,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
                         }
                         (
                             LedgerKind::BitcoinTestnet,
-                            LedgerKind::Ethereum(beta_ledger),
+                            LedgerKind::Ethereum(beta),
                             AssetKind::Bitcoin(alpha_asset),
                             AssetKind::Ether(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
+                            insert_request!(
                                 ledger::bitcoin::Testnet,
-                                beta_ledger,
+                                beta,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Bitcoin,
-                                htlc_location::Ethereum,
-                                _,
-                                _,
-                                transaction::Bitcoin,
-                                transaction::Ethereum,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Bitcoin,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
                             LedgerKind::BitcoinMainnet,
-                            LedgerKind::Ethereum(beta_ledger),
+                            LedgerKind::Ethereum(beta),
                             AssetKind::Bitcoin(alpha_asset),
                             AssetKind::Ether(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
+                            insert_request!(
                                 ledger::bitcoin::Mainnet,
-                                beta_ledger,
+                                beta,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Bitcoin,
-                                htlc_location::Ethereum,
-                                _,
-                                _,
-                                transaction::Bitcoin,
-                                transaction::Ethereum,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Bitcoin,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinRegtest,
                             AssetKind::Ether(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Regtest,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinTestnet,
                             AssetKind::Ether(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Testnet,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinMainnet,
                             AssetKind::Ether(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Mainnet,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
                             LedgerKind::BitcoinRegtest,
-                            LedgerKind::Ethereum(beta_ledger),
+                            LedgerKind::Ethereum(beta),
                             AssetKind::Bitcoin(alpha_asset),
                             AssetKind::Erc20(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
+                            insert_request!(
                                 ledger::bitcoin::Regtest,
-                                beta_ledger,
+                                beta,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Bitcoin,
-                                htlc_location::Ethereum,
-                                _,
-                                _,
-                                transaction::Bitcoin,
-                                transaction::Ethereum,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Bitcoin,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-
-                            Ok(swap_id)
                         }
                         (
                             LedgerKind::BitcoinTestnet,
-                            LedgerKind::Ethereum(beta_ledger),
+                            LedgerKind::Ethereum(beta),
                             AssetKind::Bitcoin(alpha_asset),
                             AssetKind::Erc20(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
+                            insert_request!(
                                 ledger::bitcoin::Testnet,
-                                beta_ledger,
+                                beta,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Bitcoin,
-                                htlc_location::Ethereum,
-                                _,
-                                _,
-                                transaction::Bitcoin,
-                                transaction::Ethereum,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Bitcoin,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-
-                            Ok(swap_id)
                         }
                         (
                             LedgerKind::BitcoinMainnet,
-                            LedgerKind::Ethereum(beta_ledger),
+                            LedgerKind::Ethereum(beta),
                             AssetKind::Bitcoin(alpha_asset),
                             AssetKind::Erc20(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
+                            insert_request!(
                                 ledger::bitcoin::Mainnet,
-                                beta_ledger,
+                                beta,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Bitcoin,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinRegtest,
                             AssetKind::Erc20(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Regtest,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinTestnet,
                             AssetKind::Erc20(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Testnet,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (
-                            LedgerKind::Ethereum(alpha_ledger),
+                            LedgerKind::Ethereum(alpha),
                             LedgerKind::BitcoinMainnet,
                             AssetKind::Erc20(alpha_asset),
                             AssetKind::Bitcoin(beta_asset),
                         ) => {
-                            let request = rfc003_swap_request(
-                                swap_id,
-                                alpha_ledger,
+                            insert_request!(
+                                alpha,
                                 ledger::bitcoin::Mainnet,
                                 alpha_asset,
                                 beta_asset,
-                                hash_function,
-                                body!(request.take_body_as()),
-                            );
-                            insert_state_for_bob::<
-                                _,
-                                _,
-                                _,
-                                _,
-                                htlc_location::Ethereum,
-                                htlc_location::Bitcoin,
-                                _,
-                                _,
-                                transaction::Ethereum,
-                                transaction::Bitcoin,
-                                _,
-                            >(
-                                db.clone(),
-                                swap_communication_states.clone(),
-                                alpha_ledger_state.clone(),
-                                beta_ledger_state.clone(),
-                                counterparty,
-                                request,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinRegtest,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Regtest,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinTestnet,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Testnet,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinMainnet,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Mainnet,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinRegtest,
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Regtest,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinTestnet,
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Testnet,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinMainnet,
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Mainnet,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinRegtest,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Regtest,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinTestnet,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Testnet,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::LitecoinMainnet,
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Bitcoin(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            insert_request!(
+                                ledger::litecoin::Mainnet,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Bitcoin,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinRegtest,
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Regtest,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinTestnet,
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Testnet,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::LitecoinMainnet,
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Bitcoin(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                ledger::litecoin::Mainnet,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Bitcoin
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Erc20(alpha_asset),
+                            AssetKind::Erc20(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Ethereum
+                            )
+                        }
+                        (
+                            LedgerKind::Ethereum(alpha),
+                            LedgerKind::Ethereum(beta),
+                            AssetKind::Ether(alpha_asset),
+                            AssetKind::Ether(beta_asset),
+                        ) => {
+                            insert_request!(
+                                alpha,
+                                beta,
+                                alpha_asset,
+                                beta_asset,
+                                Ethereum,
+                                Ethereum
                             )
-                            .await
-                            .expect("Could not save state to db");
-                            Ok(swap_id)
                         }
                         (alpha_ledger, beta_ledger, alpha_asset, beta_asset) => {
                             tracing::warn!(
                                     "swapping {:?} to {:?} from {:?} to {:?} is currently not supported", alpha_asset, beta_asset, alpha_ledger, beta_ledger
                                 );
 
-                            let decline_body = DeclineResponseBody {
-                                reason: Some(SwapDeclineReason::UnsupportedSwap),
-                            };
-
-                            Err(Response::empty()
-                                .with_header(
-                                    "decision",
-                                    Decision::Declined
-                                        .to_header()
-                                        .expect("Decision should not fail to serialize"),
-                                )
-                                .with_body(serde_json::to_value(decline_body).expect(
-                                    "decline body should always serialize into serde_json::Value",
-                                )))
+                            Err(decline(SwapDeclineReason::UnsupportedSwap))
                         }
                     }
                 }
@@ -872,6 +961,25 @@ async fn handle_request(
     }
 }
 
+/// A response that declines a swap request for the given reason.
+fn decline(reason: SwapDeclineReason) -> Response {
+    let decline_body = DeclineResponseBody {
+        reason: Some(reason),
+    };
+
+    Response::empty()
+        .with_header(
+            "decision",
+            Decision::Declined
+                .to_header()
+                .expect("Decision should not fail to serialize"),
+        )
+        .with_body(
+            serde_json::to_value(decline_body)
+                .expect("decline body should always serialize into serde_json::Value"),
+        )
+}
+
 #[allow(clippy::type_complexity)]
 async fn insert_state_for_bob<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT, DB>(
     db: DB,
@@ -983,6 +1091,42 @@ impl PendingRequestFor for Swarm {
     }
 }
 
+/// Publish our own orders and get the orders gossiped by other nodes.
+#[async_trait]
+#[ambassador::delegatable_trait]
+pub trait Orders {
+    async fn publish_order(&self, order: Order) -> anyhow::Result<SignedOrder>;
+    async fn orders(&self) -> Vec<SignedOrder>;
+}
+
+#[async_trait]
+impl Orders for Swarm {
+    async fn publish_order(&self, order: Order) -> anyhow::Result<SignedOrder> {
+        let mut swarm = self.swarm.lock().await;
+        swarm.orderbook.publish(order)
+    }
+
+    async fn orders(&self) -> Vec<SignedOrder> {
+        let mut swarm = self.swarm.lock().await;
+        swarm.orderbook.orders()
+    }
+}
+
+/// Get the capabilities a connected peer advertised to us.
+#[async_trait]
+#[ambassador::delegatable_trait]
+pub trait PeerCapabilities {
+    async fn peer_capabilities(&self, peer: &PeerId) -> Option<capabilities::Message>;
+}
+
+#[async_trait]
+impl PeerCapabilities for Swarm {
+    async fn peer_capabilities(&self, peer: &PeerId) -> Option<capabilities::Message> {
+        let swarm = self.swarm.lock().await;
+        swarm.capabilities.of(peer).cloned()
+    }
+}
+
 /// Send swap request to connected peer.
 #[async_trait]
 pub trait SendRequest {
@@ -1074,6 +1218,14 @@ impl SendRequest for Swarm {
                     None => Err(RequestError::InvalidResponse),
                 }
             }
+            Err(libp2p_comit::RequestError::Timeout(timeout)) => {
+                tracing::error!(
+                    "Request to {:?} timed out after {:?}",
+                    dial_information,
+                    timeout
+                );
+                Err(RequestError::Timeout)
+            }
             Err(e) => {
                 tracing::error!(
                     "Unable to request over connection {:?}:{:?}",
@@ -1097,6 +1249,16 @@ impl libp2p::swarm::NetworkBehaviourEventProcess<BehaviourOutEvent> for ComitNod
                 let swap_communication_states = self.swap_communication_states.clone();
                 let alpha_ledger_state = self.alpha_ledger_state.clone();
                 let beta_ledger_state = self.beta_ledger_state.clone();
+                let expiries = self.expiries.clone();
+                let ethereum_chain_ids = self.ethereum_chain_ids.clone();
+                let litecoin_network = self.litecoin_network;
+
+                // the swap id is recorded once the request has been decoded
+                let span = tracing::info_span!(
+                    "inbound_request",
+                    peer = %peer_id,
+                    swap_id = tracing::field::Empty
+                );
 
                 self.task_executor.spawn(async move {
                     match handle_request(
@@ -1104,9 +1266,13 @@ impl libp2p::swarm::NetworkBehaviourEventProcess<BehaviourOutEvent> for ComitNod
                         swap_communication_states,
                         alpha_ledger_state,
                         beta_ledger_state,
+                        expiries,
+                        ethereum_chain_ids,
+                        litecoin_network,
                         peer_id,
                         request,
                     )
+                    .instrument(span)
                     .await
                     {
                         Ok(id) => {
@@ -1124,7 +1290,21 @@ impl libp2p::swarm::NetworkBehaviourEventProcess<BehaviourOutEvent> for ComitNod
 }
 
 impl libp2p::swarm::NetworkBehaviourEventProcess<libp2p::mdns::MdnsEvent> for ComitNode {
-    fn inject_event(&mut self, _event: libp2p::mdns::MdnsEvent) {}
+    fn inject_event(&mut self, event: libp2p::mdns::MdnsEvent) {
+        match event {
+            libp2p::mdns::MdnsEvent::Discovered(addresses) => {
+                let peers = addresses
+                    .map(|(peer_id, _)| peer_id)
+                    .collect::<HashSet<_>>();
+
+                for peer_id in peers {
+                    tracing::debug!("discovered peer {} through mDNS", peer_id);
+                    self.orderbook.discovered(peer_id);
+                }
+            }
+            libp2p::mdns::MdnsEvent::Expired(_) => {}
+        }
+    }
 }
 
 impl libp2p::swarm::NetworkBehaviourEventProcess<()> for ComitNode {
@@ -1172,6 +1352,7 @@ impl<AL, BL, AA, BA, AI, BI> From<Request<AL, BL, AA, BA, AI, BI>> for RequestBo
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn rfc003_swap_request<AL, BL, AA, BA, AI, BI>(
     id: SwapId,
     alpha_ledger: AL,
@@ -1180,8 +1361,30 @@ fn rfc003_swap_request<AL, BL, AA, BA, AI, BI>(
     beta_asset: BA,
     hash_function: HashFunction,
     body: rfc003::messages::RequestBody<AI, BI>,
-) -> rfc003::Request<AL, BL, AA, BA, AI, BI> {
-    rfc003::Request {
+    expiry_rules: &config::ExpiryRules,
+) -> Result<rfc003::Request<AL, BL, AA, BA, AI, BI>, Response> {
+    if let Err(e) = body.secret_hash.check_length(hash_function) {
+        tracing::warn!(
+            "declining swap with a bad {} secret hash: {}",
+            hash_function,
+            e
+        );
+
+        return Err(decline(SwapDeclineReason::BadJsonField));
+    }
+
+    if let Err(e) = rfc003::expiries::validate(
+        expiry_rules,
+        body.alpha_expiry,
+        body.beta_expiry,
+        Timestamp::now(),
+    ) {
+        tracing::warn!("declining swap with unsafe expiries: {}", e);
+
+        return Err(decline(SwapDeclineReason::UnsafeExpiries));
+    }
+
+    Ok(rfc003::Request {
         swap_id: id,
         alpha_asset,
         beta_asset,
@@ -1193,5 +1396,5 @@ fn rfc003_swap_request<AL, BL, AA, BA, AI, BI>(
         alpha_expiry: body.alpha_expiry,
         beta_expiry: body.beta_expiry,
         secret_hash: body.secret_hash,
-    }
+    })
 }
@@ -1,6 +1,10 @@
 use crate::{
     asset,
-    btsieve::ethereum::{Cache, Web3Connector},
+    btsieve::{
+        self,
+        bitcoin::BitcoindConnector,
+        ethereum::{Cache, Web3Connector},
+    },
     htlc_location, identity,
     lnd::{LndConnectorAsReceiver, LndConnectorAsSender, LndConnectorParams},
     network::{
@@ -11,28 +15,27 @@ use crate::{
                 behaviour::{Announce, BehaviourOutEvent},
                 SwapDigest,
             },
-            ethereum_identity, finalize, lightning_identity, secret_hash,
+            bitcoin_identity, ethereum_identity, finalize, lightning_identity, secret_hash,
         },
     },
-    seed::{DeriveSwapSeedFromNodeLocal, RootSeed},
+    seed::{DeriveSwapSeedFromNodeLocal, RootSeed, SwapSeed},
     swap_protocols::{
         halight::{self, InvoiceStates},
         han, ledger,
         ledger::{ethereum::ChainId, lightning, Ethereum},
-        rfc003::{create_swap::HtlcParams, DeriveSecret, Secret, SecretHash},
+        rfc003::{create_swap::HtlcParams, DeriveIdentities, DeriveSecret, Secret, SecretHash},
         state::Update,
-        CreateSwapParams, LedgerStates, NodeLocalSwapId, Role, SwapId,
+        HashFunction, LedgerStates, NodeLocalSwapId, Role, SwapId, SwapParams,
     },
     timestamp::Timestamp,
     transaction,
 };
 use blockchain_contracts::ethereum::rfc003::ether_htlc::EtherHtlc;
 use chrono::Utc;
-use digest::Digest;
 use futures::{AsyncWriteExt, TryStreamExt};
 use libp2p::{
     swarm::{NetworkBehaviour, NetworkBehaviourEventProcess},
-    NetworkBehaviour,
+    NetworkBehaviour, PeerId,
 };
 use std::{collections::HashMap, sync::Arc};
 use tracing_futures::Instrument;
@@ -42,18 +45,21 @@ pub struct ComitLN {
     announce: Announce,
     secret_hash: oneshot_behaviour::Behaviour<secret_hash::Message>,
     ethereum_identity: oneshot_behaviour::Behaviour<ethereum_identity::Message>,
+    bitcoin_identity: oneshot_behaviour::Behaviour<bitcoin_identity::Message>,
     lightning_identity: oneshot_behaviour::Behaviour<lightning_identity::Message>,
     finalize: oneshot_behaviour::Behaviour<finalize::Message>,
 
     #[behaviour(ignore)]
     swaps_waiting_for_announcement: HashMap<SwapDigest, NodeLocalSwapId>,
     #[behaviour(ignore)]
-    swaps: HashMap<NodeLocalSwapId, CreateSwapParams>,
+    swaps: HashMap<NodeLocalSwapId, SwapParams>,
     #[behaviour(ignore)]
     swap_ids: HashMap<NodeLocalSwapId, SwapId>,
     #[behaviour(ignore)]
     ethereum_identities: HashMap<SwapId, identity::Ethereum>,
     #[behaviour(ignore)]
+    bitcoin_identities: HashMap<SwapId, identity::Bitcoin>,
+    #[behaviour(ignore)]
     lightning_identities: HashMap<SwapId, identity::Lightning>,
     #[behaviour(ignore)]
     communication_state: HashMap<SwapId, CommunicationState>,
@@ -64,10 +70,12 @@ pub struct ComitLN {
     #[behaviour(ignore)]
     lnd_connector_as_receiver: Arc<LndConnectorAsReceiver>,
 
+    #[behaviour(ignore)]
+    bitcoin_connector: Arc<btsieve::bitcoin::Cache<BitcoindConnector>>,
     #[behaviour(ignore)]
     ethereum_connector: Arc<Cache<Web3Connector>>,
     #[behaviour(ignore)]
-    ethereum_ledger_state: Arc<LedgerStates>,
+    alpha_ledger_state: Arc<LedgerStates>,
     #[behaviour(ignore)]
     invoices_states: Arc<InvoiceStates>,
 
@@ -77,7 +85,7 @@ pub struct ComitLN {
 
 #[derive(Debug, Default)]
 struct CommunicationState {
-    ethereum_identity_sent: bool,
+    alpha_identity_sent: bool,
     lightning_identity_sent: bool,
     received_finalized: bool,
     sent_finalized: bool,
@@ -87,8 +95,9 @@ struct CommunicationState {
 impl ComitLN {
     pub fn new(
         lnd_connector_params: LndConnectorParams,
+        bitcoin_connector: Arc<btsieve::bitcoin::Cache<BitcoindConnector>>,
         ethereum_connector: Arc<Cache<Web3Connector>>,
-        ethereum_ledger_state: Arc<LedgerStates>,
+        alpha_ledger_state: Arc<LedgerStates>,
         invoices_state: Arc<InvoiceStates>,
         seed: RootSeed,
     ) -> Self {
@@ -96,34 +105,33 @@ impl ComitLN {
             announce: Default::default(),
             secret_hash: Default::default(),
             ethereum_identity: Default::default(),
+            bitcoin_identity: Default::default(),
             lightning_identity: Default::default(),
             finalize: Default::default(),
             swaps_waiting_for_announcement: Default::default(),
             swaps: Default::default(),
             swap_ids: Default::default(),
             ethereum_identities: Default::default(),
+            bitcoin_identities: Default::default(),
             lightning_identities: Default::default(),
             communication_state: Default::default(),
             secret_hashes: Default::default(),
             lnd_connector_as_sender: Arc::new(lnd_connector_params.clone().into()),
             lnd_connector_as_receiver: Arc::new(lnd_connector_params.into()),
+            bitcoin_connector,
             ethereum_connector,
-            ethereum_ledger_state,
+            alpha_ledger_state,
             invoices_states: invoices_state,
             seed,
         }
     }
 
-    pub fn initiate_communication(
-        &mut self,
-        id: NodeLocalSwapId,
-        create_swap_params: CreateSwapParams,
-    ) {
-        let digest = create_swap_params.clone().digest();
+    pub fn initiate_communication(&mut self, id: NodeLocalSwapId, swap_params: SwapParams) {
+        let digest = swap_params.digest();
 
-        self.swaps.insert(id, create_swap_params.clone());
+        self.swaps.insert(id, swap_params.clone());
 
-        match create_swap_params.role {
+        match swap_params.role() {
             Role::Alice => {
                 if self.swaps_waiting_for_announcement.contains_key(&digest) {
                     // To fix this panic, we should either pass the local swap id to the
@@ -133,7 +141,7 @@ impl ComitLN {
                 }
 
                 self.announce
-                    .start_announce_protocol(digest.clone(), create_swap_params.peer);
+                    .start_announce_protocol(digest.clone(), swap_params.peer());
 
                 self.swaps_waiting_for_announcement.insert(digest, id);
             }
@@ -146,8 +154,8 @@ impl ComitLN {
 
     pub fn get_finalized_swap(&self, local_id: NodeLocalSwapId) -> Option<FinalizedSwap> {
         let create_swap_params = match self.swaps.get(&local_id) {
-            Some(body) => body,
-            None => return None,
+            Some(SwapParams::HanHalight(body)) => body,
+            _ => return None,
         };
 
         let secret = match create_swap_params.role {
@@ -213,6 +221,153 @@ impl ComitLN {
             role: create_swap_params.role,
         })
     }
+
+    pub fn get_finalized_hbit_swap(&self, local_id: NodeLocalSwapId) -> Option<FinalizedHbitSwap> {
+        let create_swap_params = match self.swaps.get(&local_id) {
+            Some(SwapParams::HbitHalight(body)) => body,
+            _ => return None,
+        };
+
+        let swap_seed = self.seed.derive_swap_seed_from_node_local(local_id);
+
+        let secret = match create_swap_params.role {
+            Role::Alice => Some(swap_seed.derive_secret()),
+            Role::Bob => None,
+        };
+
+        let id = match self.swap_ids.get(&local_id).copied() {
+            Some(id) => id,
+            None => return None,
+        };
+
+        let bitcoin_identity = match self.bitcoin_identities.get(&id).copied() {
+            Some(identity) => identity,
+            None => return None,
+        };
+        let (alpha_ledger_redeem_identity, alpha_ledger_refund_identity) =
+            match create_swap_params.role {
+                Role::Alice => (
+                    bitcoin_identity,
+                    self.own_bitcoin_identity(local_id, Role::Alice),
+                ),
+                Role::Bob => (
+                    self.own_bitcoin_identity(local_id, Role::Bob),
+                    bitcoin_identity,
+                ),
+            };
+        let beta_ledger_redeem_identity = match create_swap_params.role {
+            Role::Alice => create_swap_params.lightning_identity,
+            Role::Bob => match self.lightning_identities.get(&id).copied() {
+                Some(identity) => identity,
+                None => return None,
+            },
+        };
+        let beta_ledger_refund_identity = match create_swap_params.role {
+            Role::Alice => match self.lightning_identities.get(&id).copied() {
+                Some(identity) => identity,
+                None => return None,
+            },
+            Role::Bob => create_swap_params.lightning_identity,
+        };
+
+        Some(FinalizedHbitSwap {
+            alpha_ledger: ledger::bitcoin::Regtest,
+            beta_ledger: lightning::Regtest,
+            alpha_asset: create_swap_params.bitcoin_amount,
+            beta_asset: create_swap_params.lightning_amount,
+            alpha_ledger_redeem_identity,
+            alpha_ledger_refund_identity,
+            beta_ledger_redeem_identity,
+            beta_ledger_refund_identity,
+            alpha_expiry: create_swap_params.bitcoin_absolute_expiry,
+            beta_expiry: create_swap_params.lightning_cltv_expiry,
+            local_id,
+            swap_seed,
+            secret,
+            secret_hash: match self.secret_hashes.get(&id).copied() {
+                Some(secret_hash) => secret_hash,
+                None => return None,
+            },
+            role: create_swap_params.role,
+        })
+    }
+
+    /// Alice refunds and Bob redeems the Bitcoin HTLC, both derive the
+    /// identity they use for this from the seed of the swap.
+    fn own_bitcoin_identity(&self, local_id: NodeLocalSwapId, role: Role) -> identity::Bitcoin {
+        let swap_seed = self.seed.derive_swap_seed_from_node_local(local_id);
+        let secret_key = match role {
+            Role::Alice => swap_seed.derive_refund_identity(),
+            Role::Bob => swap_seed.derive_redeem_identity(),
+        };
+
+        identity::Bitcoin::from_secret_key(&*crate::SECP, &secret_key)
+    }
+
+    fn send_identities(&mut self, peer: PeerId, swap_id: SwapId, local_id: NodeLocalSwapId) {
+        let addresses = self.announce.addresses_of_peer(&peer);
+        self.secret_hash
+            .register_addresses(peer.clone(), addresses.clone());
+        self.ethereum_identity
+            .register_addresses(peer.clone(), addresses.clone());
+        self.bitcoin_identity
+            .register_addresses(peer.clone(), addresses.clone());
+        self.lightning_identity
+            .register_addresses(peer.clone(), addresses.clone());
+        self.finalize.register_addresses(peer.clone(), addresses);
+
+        let swap_params = self
+            .swaps
+            .get(&local_id)
+            .cloned()
+            .expect("swap params exist");
+
+        match &swap_params {
+            SwapParams::HanHalight(params) => self.ethereum_identity.send(
+                peer.clone(),
+                ethereum_identity::Message::new(swap_id, params.ethereum_identity.into()),
+            ),
+            SwapParams::HbitHalight(params) => {
+                let identity = self.own_bitcoin_identity(local_id, params.role);
+
+                self.bitcoin_identity.send(
+                    peer.clone(),
+                    bitcoin_identity::Message::new(swap_id, identity),
+                )
+            }
+        }
+        self.lightning_identity.send(
+            peer,
+            lightning_identity::Message::new(swap_id, swap_params.lightning_identity()),
+        );
+    }
+
+    fn swap_params(&self, swap_id: &SwapId) -> Option<&SwapParams> {
+        let (local_id, _) = self.swap_ids.iter().find(|(_, id)| *id == swap_id)?;
+
+        self.swaps.get(local_id)
+    }
+
+    fn ready_to_finalize(&self, swap_id: &SwapId) -> bool {
+        let state = match self.communication_state.get(swap_id) {
+            Some(state) => state,
+            None => return false,
+        };
+
+        // Finalizing looks up the identity for the alpha ledger of the swap, one
+        // for another ledger doesn't do.
+        let received_alpha_identity = match self.swap_params(swap_id) {
+            Some(SwapParams::HanHalight(_)) => self.ethereum_identities.contains_key(swap_id),
+            Some(SwapParams::HbitHalight(_)) => self.bitcoin_identities.contains_key(swap_id),
+            None => false,
+        };
+
+        received_alpha_identity
+            && self.lightning_identities.contains_key(swap_id)
+            && state.lightning_identity_sent
+            && state.alpha_identity_sent
+            && state.secret_hash_sent_or_received
+    }
 }
 
 #[derive(Debug)]
@@ -242,11 +397,48 @@ impl FinalizedSwap {
             refund_identity: self.alpha_ledger_refund_identity,
             expiry: self.alpha_expiry,
             secret_hash: self.secret_hash,
+            hash_function: HashFunction::Sha256,
         }
         .into()
     }
 }
 
+#[derive(Debug)]
+pub struct FinalizedHbitSwap {
+    pub alpha_ledger: ledger::bitcoin::Regtest,
+    pub beta_ledger: lightning::Regtest,
+    pub alpha_asset: asset::Bitcoin,
+    pub beta_asset: asset::Lightning,
+    pub alpha_ledger_refund_identity: identity::Bitcoin,
+    pub alpha_ledger_redeem_identity: identity::Bitcoin,
+    pub beta_ledger_refund_identity: identity::Lightning,
+    pub beta_ledger_redeem_identity: identity::Lightning,
+    pub alpha_expiry: Timestamp,
+    pub beta_expiry: Timestamp,
+    pub local_id: NodeLocalSwapId,
+    /// The source of the key that signs the redeem or refund transaction.
+    pub swap_seed: SwapSeed,
+    pub secret_hash: SecretHash,
+    pub secret: Option<Secret>,
+    pub role: Role,
+}
+
+impl FinalizedHbitSwap {
+    pub fn hbit_params(
+        &self,
+    ) -> HtlcParams<ledger::bitcoin::Regtest, asset::Bitcoin, identity::Bitcoin> {
+        HtlcParams {
+            asset: self.alpha_asset,
+            ledger: ledger::bitcoin::Regtest,
+            redeem_identity: self.alpha_ledger_redeem_identity,
+            refund_identity: self.alpha_ledger_refund_identity,
+            expiry: self.alpha_expiry,
+            secret_hash: self.secret_hash,
+            hash_function: HashFunction::Sha256,
+        }
+    }
+}
+
 impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<secret_hash::Message>> for ComitLN {
     fn inject_event(&mut self, event: oneshot_behaviour::OutEvent<secret_hash::Message>) {
         let (peer, swap_id) = match event {
@@ -292,15 +484,8 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<secret_hash::Messa
             }
         };
 
-        let state = self.communication_state.get(&swap_id).unwrap();
-
         // check if we are done
-        if self.ethereum_identities.contains_key(&swap_id)
-            && self.lightning_identities.contains_key(&swap_id)
-            && state.lightning_identity_sent
-            && state.ethereum_identity_sent
-            && state.secret_hash_sent_or_received
-        {
+        if self.ready_to_finalize(&swap_id) {
             self.finalize.send(peer, finalize::Message::new(swap_id));
         }
     }
@@ -318,28 +503,7 @@ impl NetworkBehaviourEventProcess<announce::behaviour::BehaviourOutEvent> for Co
 
                     tokio::task::spawn(io.send(id));
 
-                    let create_swap_params = self.swaps.get(&local_id).unwrap();
-
-                    let addresses = self.announce.addresses_of_peer(&peer);
-                    self.secret_hash
-                        .register_addresses(peer.clone(), addresses.clone());
-                    self.ethereum_identity
-                        .register_addresses(peer.clone(), addresses.clone());
-                    self.lightning_identity
-                        .register_addresses(peer.clone(), addresses.clone());
-                    self.finalize.register_addresses(peer.clone(), addresses);
-
-                    self.ethereum_identity.send(
-                        peer.clone(),
-                        ethereum_identity::Message::new(
-                            id,
-                            create_swap_params.ethereum_identity.into(),
-                        ),
-                    );
-                    self.lightning_identity.send(
-                        peer,
-                        lightning_identity::Message::new(id, create_swap_params.lightning_identity),
-                    );
+                    self.send_identities(peer, id, local_id);
 
                     self.communication_state
                         .insert(id, CommunicationState::default());
@@ -367,34 +531,10 @@ impl NetworkBehaviourEventProcess<announce::behaviour::BehaviourOutEvent> for Co
 
                 self.swap_ids.insert(local_swap_id, swap_id);
 
-                let addresses = self.announce.addresses_of_peer(&peer);
-                self.secret_hash
-                    .register_addresses(peer.clone(), addresses.clone());
-                self.ethereum_identity
-                    .register_addresses(peer.clone(), addresses.clone());
-                self.lightning_identity
-                    .register_addresses(peer.clone(), addresses.clone());
-                self.finalize.register_addresses(peer.clone(), addresses);
-
-                let create_swap_params = self.swaps.get(&local_swap_id).unwrap();
-
-                self.ethereum_identity.send(
-                    peer.clone(),
-                    ethereum_identity::Message::new(
-                        swap_id,
-                        create_swap_params.ethereum_identity.into(),
-                    ),
-                );
-                self.lightning_identity.send(
-                    peer.clone(),
-                    lightning_identity::Message::new(
-                        swap_id,
-                        create_swap_params.lightning_identity,
-                    ),
-                );
+                self.send_identities(peer.clone(), swap_id, local_swap_id);
 
                 let seed = self.seed.derive_swap_seed_from_node_local(local_swap_id);
-                let secret_hash = seed.derive_secret().hash();
+                let secret_hash = seed.derive_secret().hash(HashFunction::Sha256);
 
                 self.secret_hashes.insert(swap_id, secret_hash);
                 self.secret_hash
@@ -437,21 +577,60 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<ethereum_identity:
                     .get_mut(&swap_id)
                     .expect("this should exist");
 
-                state.ethereum_identity_sent = true;
+                state.alpha_identity_sent = true;
 
                 (peer, swap_id)
             }
         };
 
-        let state = self.communication_state.get(&swap_id).unwrap();
+        // check if we are done
+        if self.ready_to_finalize(&swap_id) {
+            self.finalize.send(peer, finalize::Message::new(swap_id));
+        }
+    }
+}
+
+impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<bitcoin_identity::Message>>
+    for ComitLN
+{
+    fn inject_event(&mut self, event: oneshot_behaviour::OutEvent<bitcoin_identity::Message>) {
+        let (peer, swap_id) = match event {
+            oneshot_behaviour::OutEvent::Received {
+                peer,
+                message: bitcoin_identity::Message { swap_id, pubkey },
+            } => match bitcoin::PublicKey::from_slice(&pubkey) {
+                Ok(pubkey) => {
+                    self.bitcoin_identities.insert(swap_id, pubkey.into());
+
+                    (peer, swap_id)
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Peer {} sent an invalid bitcoin identity for swap {}: {}",
+                        peer,
+                        swap_id,
+                        e
+                    );
+                    return;
+                }
+            },
+            oneshot_behaviour::OutEvent::Sent {
+                peer,
+                message: bitcoin_identity::Message { swap_id, .. },
+            } => {
+                let state = self
+                    .communication_state
+                    .get_mut(&swap_id)
+                    .expect("this should exist");
+
+                state.alpha_identity_sent = true;
+
+                (peer, swap_id)
+            }
+        };
 
         // check if we are done
-        if self.ethereum_identities.contains_key(&swap_id)
-            && self.lightning_identities.contains_key(&swap_id)
-            && state.lightning_identity_sent
-            && state.ethereum_identity_sent
-            && state.secret_hash_sent_or_received
-        {
+        if self.ready_to_finalize(&swap_id) {
             self.finalize.send(peer, finalize::Message::new(swap_id));
         }
     }
@@ -488,15 +667,8 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<lightning_identity
             }
         };
 
-        let state = self.communication_state.get(&swap_id).unwrap();
-
         // check if we are done
-        if self.ethereum_identities.contains_key(&swap_id)
-            && self.lightning_identities.contains_key(&swap_id)
-            && state.lightning_identity_sent
-            && state.ethereum_identity_sent
-            && state.secret_hash_sent_or_received
-        {
+        if self.ready_to_finalize(&swap_id) {
             self.finalize.send(peer, finalize::Message::new(swap_id));
         }
     }
@@ -554,7 +726,7 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<finalize::Message>
                 .copied()
                 .unwrap();
 
-            let create_swap_params = self
+            let swap_params = self
                 .swaps
                 .get(&local_swap_id)
                 .cloned()
@@ -568,7 +740,7 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<finalize::Message>
 
             let invoice_states = self.invoices_states.clone();
 
-            let role = create_swap_params.role;
+            let role = swap_params.role();
 
             if role == Role::Alice {
                 tokio::task::spawn({
@@ -611,65 +783,97 @@ impl NetworkBehaviourEventProcess<oneshot_behaviour::OutEvent<finalize::Message>
                 });
             }
 
-            if role == Role::Alice {
-                tokio::task::spawn({
-                    let connector = self.ethereum_connector.clone();
-                    let alice_ethereum_identity = create_swap_params.ethereum_identity;
-                    let bob_ethereum_identity =
-                        self.ethereum_identities.get(&swap_id).copied().unwrap();
-
-                    let asset = create_swap_params.ethereum_amount.clone();
-                    let ledger = ledger::Ethereum::default();
-                    let expiry = create_swap_params.ethereum_absolute_expiry;
-                    let secret_hash = self
-                        .secret_hashes
-                        .get(&swap_id)
-                        .copied()
-                        .expect("must exist");
-
-                    new_han_ethereum_ether_swap(
-                        local_swap_id,
-                        connector,
-                        self.ethereum_ledger_state.clone(),
-                        HtlcParams {
-                            asset,
-                            ledger,
-                            redeem_identity: bob_ethereum_identity,
-                            refund_identity: alice_ethereum_identity.into(),
-                            expiry,
-                            secret_hash,
-                        },
-                        role,
-                    )
-                });
-            } else {
-                tokio::task::spawn({
-                    // This is Bob
-                    let connector = self.ethereum_connector.clone();
-                    let alice_ethereum_identity =
-                        self.ethereum_identities.get(&swap_id).copied().unwrap();
-                    let bob_ethereum_identity = create_swap_params.ethereum_identity;
-
-                    let asset = create_swap_params.ethereum_amount.clone();
-                    let ledger = ledger::Ethereum::default();
-                    let expiry = create_swap_params.ethereum_absolute_expiry;
-                    let secret_hash = self.secret_hashes.get(&swap_id).copied().unwrap();
-
-                    new_han_ethereum_ether_swap(
+            match swap_params {
+                SwapParams::HanHalight(create_swap_params) => {
+                    if role == Role::Alice {
+                        tokio::task::spawn({
+                            let connector = self.ethereum_connector.clone();
+                            let alice_ethereum_identity = create_swap_params.ethereum_identity;
+                            let bob_ethereum_identity =
+                                self.ethereum_identities.get(&swap_id).copied().unwrap();
+
+                            let asset = create_swap_params.ethereum_amount.clone();
+                            let ledger = ledger::Ethereum::default();
+                            let expiry = create_swap_params.ethereum_absolute_expiry;
+                            let secret_hash = self
+                                .secret_hashes
+                                .get(&swap_id)
+                                .copied()
+                                .expect("must exist");
+
+                            new_han_ethereum_ether_swap(
+                                local_swap_id,
+                                connector,
+                                self.alpha_ledger_state.clone(),
+                                HtlcParams {
+                                    asset,
+                                    ledger,
+                                    redeem_identity: bob_ethereum_identity,
+                                    refund_identity: alice_ethereum_identity.into(),
+                                    expiry,
+                                    secret_hash,
+                                    hash_function: HashFunction::Sha256,
+                                },
+                                role,
+                            )
+                        });
+                    } else {
+                        tokio::task::spawn({
+                            // This is Bob
+                            let connector = self.ethereum_connector.clone();
+                            let alice_ethereum_identity =
+                                self.ethereum_identities.get(&swap_id).copied().unwrap();
+                            let bob_ethereum_identity = create_swap_params.ethereum_identity;
+
+                            let asset = create_swap_params.ethereum_amount.clone();
+                            let ledger = ledger::Ethereum::default();
+                            let expiry = create_swap_params.ethereum_absolute_expiry;
+                            let secret_hash = self.secret_hashes.get(&swap_id).copied().unwrap();
+
+                            new_han_ethereum_ether_swap(
+                                local_swap_id,
+                                connector,
+                                self.alpha_ledger_state.clone(),
+                                HtlcParams {
+                                    asset,
+                                    ledger,
+                                    redeem_identity: bob_ethereum_identity.into(),
+                                    refund_identity: alice_ethereum_identity,
+                                    expiry,
+                                    secret_hash,
+                                    hash_function: HashFunction::Sha256,
+                                },
+                                role,
+                            )
+                        });
+                    }
+                }
+                SwapParams::HbitHalight(create_swap_params) => {
+                    let own_bitcoin_identity = self.own_bitcoin_identity(local_swap_id, role);
+                    let other_bitcoin_identity =
+                        self.bitcoin_identities.get(&swap_id).copied().unwrap();
+
+                    let (redeem_identity, refund_identity) = match role {
+                        Role::Alice => (other_bitcoin_identity, own_bitcoin_identity),
+                        Role::Bob => (own_bitcoin_identity, other_bitcoin_identity),
+                    };
+
+                    tokio::task::spawn(new_hbit_bitcoin_swap(
                         local_swap_id,
-                        connector,
-                        self.ethereum_ledger_state.clone(),
+                        self.bitcoin_connector.clone(),
+                        self.alpha_ledger_state.clone(),
                         HtlcParams {
-                            asset,
-                            ledger,
-                            redeem_identity: bob_ethereum_identity.into(),
-                            refund_identity: alice_ethereum_identity,
-                            expiry,
+                            asset: create_swap_params.bitcoin_amount,
+                            ledger: ledger::bitcoin::Regtest,
+                            redeem_identity,
+                            refund_identity,
+                            expiry: create_swap_params.bitcoin_absolute_expiry,
                             secret_hash,
+                            hash_function: HashFunction::Sha256,
                         },
                         role,
-                    )
-                });
+                    ));
+                }
             }
         }
     }
@@ -721,3 +925,21 @@ async fn new_han_ethereum_ether_swap(
     .instrument(tracing::error_span!("alpha_ledger", swap_id = %local_swap_id, role = %role))
     .await
 }
+
+async fn new_hbit_bitcoin_swap(
+    local_swap_id: NodeLocalSwapId,
+    connector: Arc<btsieve::bitcoin::Cache<BitcoindConnector>>,
+    bitcoin_ledger_state: Arc<LedgerStates>,
+    htlc_params: HtlcParams<ledger::bitcoin::Regtest, asset::Bitcoin, identity::Bitcoin>,
+    role: Role,
+) {
+    han::create_watcher::<_, _, _, _, htlc_location::Bitcoin, _, transaction::Bitcoin>(
+        connector.as_ref(),
+        bitcoin_ledger_state,
+        local_swap_id,
+        htlc_params,
+        Utc::now().naive_local(),
+    )
+    .instrument(tracing::error_span!("alpha_ledger", swap_id = %local_swap_id, role = %role))
+    .await
+}
@@ -0,0 +1,653 @@
+use crate::{
+    config::serde_bitcoin_network, ethereum::Address, network::serde_peer_id,
+    swap_protocols::ledger::ethereum::ChainId, timestamp::Timestamp,
+};
+use bigdecimal::BigDecimal;
+use libp2p::{
+    gossipsub::{Gossipsub, GossipsubConfig, GossipsubEvent, Topic},
+    identity::{Keypair, PublicKey},
+    swarm::{
+        DialPeerCondition, NetworkBehaviourAction, NetworkBehaviourEventProcess, PollParameters,
+    },
+    Multiaddr, NetworkBehaviour, PeerId,
+};
+use num::BigUint;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    str::FromStr,
+    task::{Context, Poll},
+};
+use uuid::Uuid;
+
+/// The gossipsub topic all orders are published on.
+pub const TOPIC: &str = "/comit/orderbook/1.0.0";
+
+/// How many orders of other nodes we keep at most.
+const MAX_ORDERS: usize = 1000;
+
+/// How many orders of a single maker we keep at most, so that one maker
+/// cannot fill the whole order book.
+const MAX_ORDERS_PER_MAKER: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct OrderId(pub Uuid);
+
+impl Default for OrderId {
+    fn default() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl FromStr for OrderId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::from_str(s).map(OrderId)
+    }
+}
+
+impl fmt::Display for OrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An offer to swap `alpha_asset` for `beta_asset`.
+///
+/// The maker of the order plays the role of Bob, the node taking the order
+/// starts a swap of the given `protocol` against `maker` as Alice.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub id: OrderId,
+    pub maker: Maker,
+    pub protocol: OrderProtocol,
+    pub alpha_ledger: OrderLedger,
+    pub beta_ledger: OrderLedger,
+    pub alpha_asset: OrderAsset,
+    pub beta_asset: OrderAsset,
+    /// How many units of the beta asset the maker gives for one unit of the
+    /// alpha asset.
+    pub rate: Rate,
+    /// Smallest amount of the alpha asset the maker is willing to swap, in
+    /// the smallest unit of the asset (satoshi, wei, ...).
+    pub min_quantity: Quantity,
+    /// Biggest amount of the alpha asset the maker is willing to swap, in
+    /// the smallest unit of the asset (satoshi, wei, ...).
+    pub max_quantity: Quantity,
+    /// Point in time after which the order must no longer be taken.
+    pub expiry: Timestamp,
+}
+
+/// The dial information of the node that published an order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Maker {
+    #[serde(with = "serde_peer_id")]
+    pub peer_id: PeerId,
+    pub addresses: Vec<Multiaddr>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OrderProtocol {
+    #[serde(rename = "rfc003")]
+    Rfc003,
+    #[serde(rename = "han-halight")]
+    HanHalight,
+    #[serde(rename = "hbit-halight")]
+    HbitHalight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "lowercase")]
+pub enum OrderLedger {
+    Bitcoin {
+        #[serde(with = "serde_bitcoin_network")]
+        network: bitcoin::Network,
+    },
+    Ethereum {
+        chain_id: ChainId,
+    },
+    Lightning {
+        #[serde(with = "serde_bitcoin_network")]
+        network: bitcoin::Network,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "lowercase")]
+pub enum OrderAsset {
+    Bitcoin,
+    Ether,
+    Erc20 { token_contract: Address },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rate(pub BigDecimal);
+
+impl Serialize for Rate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let rate = BigDecimal::from_str(&value).map_err(D::Error::custom)?;
+
+        Ok(Rate(rate))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantity(pub BigUint);
+
+impl Serialize for Quantity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_str_radix(10))
+    }
+}
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let quantity = BigUint::parse_bytes(value.as_bytes(), 10)
+            .ok_or_else(|| D::Error::custom(format!("invalid quantity {}", value)))?;
+
+        Ok(Quantity(quantity))
+    }
+}
+
+/// An order together with the signature of its maker.
+///
+/// The signature is made with the key pair that belongs to the `PeerId` of
+/// the maker, hence nobody can publish orders in the name of another node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedOrder {
+    pub order: Order,
+    #[serde(with = "hex")]
+    pub public_key: Vec<u8>,
+    #[serde(with = "hex")]
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum InvalidOrder {
+    #[error("public key of the order is malformed")]
+    MalformedPublicKey,
+    #[error("order was not signed by its maker")]
+    MakerMismatch,
+    #[error("signature of the order is invalid")]
+    InvalidSignature,
+    #[error("order is expired")]
+    Expired,
+    #[error("min_quantity of the order is bigger than its max_quantity")]
+    QuantityRange,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum RejectedOrder {
+    #[error("another maker already published an order with this id")]
+    IdTaken,
+    #[error("the order book is full")]
+    Full,
+}
+
+impl SignedOrder {
+    pub fn sign(order: Order, key_pair: &Keypair) -> anyhow::Result<Self> {
+        let signature = key_pair.sign(&serde_json::to_vec(&order)?)?;
+
+        Ok(Self {
+            order,
+            public_key: key_pair.public().into_protobuf_encoding(),
+            signature,
+        })
+    }
+
+    pub fn verify(&self) -> Result<(), InvalidOrder> {
+        let public_key = PublicKey::from_protobuf_encoding(&self.public_key)
+            .map_err(|_| InvalidOrder::MalformedPublicKey)?;
+
+        if public_key.clone().into_peer_id() != self.order.maker.peer_id {
+            return Err(InvalidOrder::MakerMismatch);
+        }
+
+        let message =
+            serde_json::to_vec(&self.order).map_err(|_| InvalidOrder::InvalidSignature)?;
+        if !public_key.verify(&message, &self.signature) {
+            return Err(InvalidOrder::InvalidSignature);
+        }
+
+        if self.is_expired() {
+            return Err(InvalidOrder::Expired);
+        }
+
+        if self.order.min_quantity.0 > self.order.max_quantity.0 {
+            return Err(InvalidOrder::QuantityRange);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.order.expiry <= Timestamp::now()
+    }
+}
+
+/// Network behaviour that gossips orders between all nodes subscribed to
+/// [`TOPIC`].
+///
+/// Our own orders are re-published whenever a peer subscribes to the topic
+/// so that nodes joining the network learn about orders that were published
+/// before they connected.
+#[derive(NetworkBehaviour)]
+#[behaviour(poll_method = "poll")]
+#[allow(missing_debug_implementations)]
+pub struct Orderbook {
+    gossipsub: Gossipsub,
+
+    #[behaviour(ignore)]
+    topic: Topic,
+    #[behaviour(ignore)]
+    key_pair: Keypair,
+    #[behaviour(ignore)]
+    own_orders: HashMap<OrderId, SignedOrder>,
+    #[behaviour(ignore)]
+    orders: HashMap<(PeerId, OrderId), SignedOrder>,
+    #[behaviour(ignore)]
+    peers_to_dial: VecDeque<PeerId>,
+}
+
+impl Orderbook {
+    pub fn new(key_pair: Keypair) -> Self {
+        let peer_id = key_pair.public().into_peer_id();
+        let topic = Topic::new(TOPIC.to_string());
+
+        let mut gossipsub = Gossipsub::new(peer_id, GossipsubConfig::default());
+        gossipsub.subscribe(topic.clone());
+
+        Self {
+            gossipsub,
+            topic,
+            key_pair,
+            own_orders: HashMap::new(),
+            orders: HashMap::new(),
+            peers_to_dial: VecDeque::new(),
+        }
+    }
+
+    /// Sign the given order with our key pair and publish it to all
+    /// subscribed peers.
+    pub fn publish(&mut self, order: Order) -> anyhow::Result<SignedOrder> {
+        let signed_order = SignedOrder::sign(order, &self.key_pair)?;
+        let bytes = serde_json::to_vec(&signed_order)?;
+
+        self.gossipsub.publish(&self.topic, bytes);
+        self.own_orders
+            .insert(signed_order.order.id, signed_order.clone());
+
+        Ok(signed_order)
+    }
+
+    /// All orders that have not yet expired, including our own.
+    pub fn orders(&mut self) -> Vec<SignedOrder> {
+        self.remove_expired_orders();
+
+        self.own_orders
+            .values()
+            .chain(self.orders.values())
+            .cloned()
+            .collect()
+    }
+
+    /// Connect to a peer we discovered so that gossip can flow between us.
+    pub fn discovered(&mut self, peer_id: PeerId) {
+        self.peers_to_dial.push_back(peer_id);
+    }
+
+    fn republish(&mut self) {
+        self.remove_expired_orders();
+
+        for signed_order in self.own_orders.values() {
+            match serde_json::to_vec(signed_order) {
+                Ok(bytes) => self.gossipsub.publish(&self.topic, bytes),
+                Err(e) => tracing::warn!("failed to serialize order: {:?}", e),
+            }
+        }
+    }
+
+    /// Store an order of another node, replacing the previous version of it.
+    ///
+    /// Only the maker of an order can replace it, the ids of the orders of
+    /// different makers never collide. A maker that already has
+    /// [`MAX_ORDERS_PER_MAKER`] orders loses the one that expires first.
+    fn insert(&mut self, signed_order: SignedOrder) -> Result<(), RejectedOrder> {
+        let id = signed_order.order.id;
+        let key = (signed_order.order.maker.peer_id.clone(), id);
+
+        let id_taken = self.own_orders.contains_key(&id)
+            || self
+                .orders
+                .keys()
+                .any(|(maker, other)| *other == id && *maker != key.0);
+        if id_taken {
+            return Err(RejectedOrder::IdTaken);
+        }
+
+        if !self.orders.contains_key(&key) {
+            let orders_of_maker = self
+                .orders
+                .keys()
+                .filter(|(maker, _)| *maker == key.0)
+                .count();
+
+            if orders_of_maker >= MAX_ORDERS_PER_MAKER {
+                self.remove_first_expiring_order_of(&key.0);
+            } else if self.orders.len() >= MAX_ORDERS {
+                self.remove_expired_orders();
+
+                if self.orders.len() >= MAX_ORDERS {
+                    return Err(RejectedOrder::Full);
+                }
+            }
+        }
+
+        self.orders.insert(key, signed_order);
+
+        Ok(())
+    }
+
+    fn remove_first_expiring_order_of(&mut self, maker: &PeerId) {
+        let first_expiring = self
+            .orders
+            .iter()
+            .filter(|((other, _), _)| other == maker)
+            .min_by_key(|(_, signed_order)| signed_order.order.expiry)
+            .map(|(key, _)| key.clone());
+
+        if let Some(key) = first_expiring {
+            self.orders.remove(&key);
+        }
+    }
+
+    fn remove_expired_orders(&mut self) {
+        self.own_orders.retain(|_, order| !order.is_expired());
+        self.orders.retain(|_, order| !order.is_expired());
+    }
+
+    fn poll<TEvent>(
+        &mut self,
+        _: &mut Context<'_>,
+        _: &mut impl PollParameters,
+    ) -> Poll<NetworkBehaviourAction<TEvent, ()>> {
+        if let Some(peer_id) = self.peers_to_dial.pop_front() {
+            return Poll::Ready(NetworkBehaviourAction::DialPeer {
+                peer_id,
+                condition: DialPeerCondition::Disconnected,
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl NetworkBehaviourEventProcess<GossipsubEvent> for Orderbook {
+    fn inject_event(&mut self, event: GossipsubEvent) {
+        match event {
+            GossipsubEvent::Message(peer_id, _, message) => {
+                let signed_order = match serde_json::from_slice::<SignedOrder>(&message.data) {
+                    Ok(signed_order) => signed_order,
+                    Err(e) => {
+                        tracing::warn!("received malformed order from {}: {:?}", peer_id, e);
+                        return;
+                    }
+                };
+
+                if let Err(e) = signed_order.verify() {
+                    tracing::warn!(
+                        "discarding order {} from {}: {}",
+                        signed_order.order.id,
+                        peer_id,
+                        e
+                    );
+                    return;
+                }
+
+                let id = signed_order.order.id;
+                let maker = signed_order.order.maker.peer_id.clone();
+
+                match self.insert(signed_order) {
+                    Ok(()) => tracing::debug!("received order {} of maker {}", id, maker),
+                    Err(e) => tracing::warn!("discarding order {} from {}: {}", id, peer_id, e),
+                }
+            }
+            GossipsubEvent::Subscribed { topic, .. } if topic == self.topic.no_hash() => {
+                self.republish()
+            }
+            GossipsubEvent::Subscribed { .. } | GossipsubEvent::Unsubscribed { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::ed25519;
+    use spectral::prelude::*;
+
+    fn key_pair() -> Keypair {
+        Keypair::Ed25519(ed25519::Keypair::generate())
+    }
+
+    fn order(maker: PeerId, expiry: Timestamp) -> Order {
+        Order {
+            id: OrderId(Uuid::from_str("ad2652ca-ecf2-4cc6-b35c-b4351ac28a34").unwrap()),
+            maker: Maker {
+                peer_id: maker,
+                addresses: vec!["/ip4/127.0.0.1/tcp/9939".parse().unwrap()],
+            },
+            protocol: OrderProtocol::Rfc003,
+            alpha_ledger: OrderLedger::Bitcoin {
+                network: bitcoin::Network::Regtest,
+            },
+            beta_ledger: OrderLedger::Ethereum {
+                chain_id: ChainId::regtest(),
+            },
+            alpha_asset: OrderAsset::Bitcoin,
+            beta_asset: OrderAsset::Ether,
+            rate: Rate(BigDecimal::from_str("42.5").unwrap()),
+            min_quantity: Quantity(BigUint::from(100_000u32)),
+            max_quantity: Quantity(BigUint::from(100_000_000u32)),
+            expiry,
+        }
+    }
+
+    #[test]
+    fn signed_order_verifies() {
+        let key_pair = key_pair();
+        let order = order(key_pair.public().into_peer_id(), Timestamp::now().plus(60));
+
+        let signed_order = SignedOrder::sign(order, &key_pair).unwrap();
+
+        assert_that(&signed_order.verify()).is_ok();
+    }
+
+    #[test]
+    fn tampered_order_does_not_verify() {
+        let key_pair = key_pair();
+        let order = order(key_pair.public().into_peer_id(), Timestamp::now().plus(60));
+
+        let mut signed_order = SignedOrder::sign(order, &key_pair).unwrap();
+        signed_order.order.rate = Rate(BigDecimal::from_str("1000").unwrap());
+
+        assert!(matches!(
+            signed_order.verify(),
+            Err(InvalidOrder::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn order_signed_by_someone_else_does_not_verify() {
+        let maker = key_pair();
+        let order = order(maker.public().into_peer_id(), Timestamp::now().plus(60));
+
+        let signed_order = SignedOrder::sign(order, &key_pair()).unwrap();
+
+        assert!(matches!(
+            signed_order.verify(),
+            Err(InvalidOrder::MakerMismatch)
+        ));
+    }
+
+    #[test]
+    fn expired_order_does_not_verify() {
+        let key_pair = key_pair();
+        let order = order(key_pair.public().into_peer_id(), Timestamp::from(0));
+
+        let signed_order = SignedOrder::sign(order, &key_pair).unwrap();
+
+        assert!(matches!(signed_order.verify(), Err(InvalidOrder::Expired)));
+    }
+
+    #[test]
+    fn order_with_min_quantity_above_max_quantity_does_not_verify() {
+        let key_pair = key_pair();
+        let mut order = order(key_pair.public().into_peer_id(), Timestamp::now().plus(60));
+        order.min_quantity = Quantity(BigUint::from(200_000_000u32));
+
+        let signed_order = SignedOrder::sign(order, &key_pair).unwrap();
+
+        assert!(matches!(
+            signed_order.verify(),
+            Err(InvalidOrder::QuantityRange)
+        ));
+    }
+
+    #[test]
+    fn only_the_maker_can_replace_an_order() {
+        let mut orderbook = Orderbook::new(key_pair());
+        let maker = key_pair();
+        let other = key_pair();
+        let order = order(maker.public().into_peer_id(), Timestamp::now().plus(60));
+
+        let mut replacement = order.clone();
+        replacement.rate = Rate(BigDecimal::from_str("43").unwrap());
+        let mut forgery = order.clone();
+        forgery.maker.peer_id = other.public().into_peer_id();
+
+        let signed_order = SignedOrder::sign(order, &maker).unwrap();
+        let signed_replacement = SignedOrder::sign(replacement, &maker).unwrap();
+        let signed_forgery = SignedOrder::sign(forgery, &other).unwrap();
+
+        assert_that(&orderbook.insert(signed_order)).is_ok();
+        assert_that(&orderbook.insert(signed_replacement.clone())).is_ok();
+        assert!(matches!(
+            orderbook.insert(signed_forgery),
+            Err(RejectedOrder::IdTaken)
+        ));
+        assert_that(&orderbook.orders()).is_equal_to(vec![signed_replacement]);
+    }
+
+    fn new_order(maker: &Keypair, expiry: Timestamp) -> SignedOrder {
+        let mut order = order(maker.public().into_peer_id(), expiry);
+        order.id = OrderId::default();
+
+        SignedOrder::sign(order, maker).unwrap()
+    }
+
+    #[test]
+    fn orders_beyond_the_limit_are_rejected() {
+        let mut orderbook = Orderbook::new(key_pair());
+
+        for _ in 0..MAX_ORDERS / MAX_ORDERS_PER_MAKER {
+            let maker = key_pair();
+
+            for _ in 0..MAX_ORDERS_PER_MAKER {
+                let signed_order = new_order(&maker, Timestamp::now().plus(60));
+
+                assert_that(&orderbook.insert(signed_order)).is_ok();
+            }
+        }
+
+        let signed_order = new_order(&key_pair(), Timestamp::now().plus(60));
+
+        assert!(matches!(
+            orderbook.insert(signed_order),
+            Err(RejectedOrder::Full)
+        ));
+    }
+
+    #[test]
+    fn maker_beyond_their_limit_loses_the_order_expiring_first() {
+        let mut orderbook = Orderbook::new(key_pair());
+        let maker = key_pair();
+        let other = key_pair();
+
+        let first_expiring = new_order(&maker, Timestamp::now().plus(30));
+        assert_that(&orderbook.insert(first_expiring.clone())).is_ok();
+        for _ in 1..MAX_ORDERS_PER_MAKER {
+            let signed_order = new_order(&maker, Timestamp::now().plus(60));
+
+            assert_that(&orderbook.insert(signed_order)).is_ok();
+        }
+        let order_of_other = new_order(&other, Timestamp::now().plus(10));
+        assert_that(&orderbook.insert(order_of_other.clone())).is_ok();
+
+        let latest = new_order(&maker, Timestamp::now().plus(60));
+        assert_that(&orderbook.insert(latest.clone())).is_ok();
+
+        let orders = orderbook.orders();
+        let orders_of_maker = orders
+            .iter()
+            .filter(|signed_order| {
+                signed_order.order.maker.peer_id == maker.public().into_peer_id()
+            })
+            .count();
+
+        assert_that(&orders_of_maker).is_equal_to(MAX_ORDERS_PER_MAKER);
+        assert_that(&orders).does_not_contain(&first_expiring);
+        assert_that(&orders).contains(&latest);
+        assert_that(&orders).contains(&order_of_other);
+    }
+
+    #[test]
+    fn signed_order_survives_serialization_roundtrip() {
+        let key_pair = key_pair();
+        let order = order(key_pair.public().into_peer_id(), Timestamp::now().plus(60));
+        let signed_order = SignedOrder::sign(order, &key_pair).unwrap();
+
+        let bytes = serde_json::to_vec(&signed_order).unwrap();
+        let deserialized = serde_json::from_slice::<SignedOrder>(&bytes).unwrap();
+
+        assert_that(&deserialized).is_equal_to(&signed_order);
+        assert_that(&deserialized.verify()).is_ok();
+    }
+
+    #[test]
+    fn order_serializes_correctly() {
+        let maker = "QmfUfpC2frwFvcDzpspnfZitHt5wct6n4kpG5jzgRdsxkY"
+            .parse()
+            .unwrap();
+        let order = order(maker, Timestamp::from(1_000_000));
+
+        let json = serde_json::to_string(&order).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"id":"ad2652ca-ecf2-4cc6-b35c-b4351ac28a34","maker":{"peer_id":"QmfUfpC2frwFvcDzpspnfZitHt5wct6n4kpG5jzgRdsxkY","addresses":["/ip4/127.0.0.1/tcp/9939"]},"protocol":"rfc003","alpha_ledger":{"name":"bitcoin","network":"regtest"},"beta_ledger":{"name":"ethereum","chain_id":17},"alpha_asset":{"name":"bitcoin"},"beta_asset":{"name":"ether"},"rate":"42.5","min_quantity":"100000","max_quantity":"100000000","expiry":1000000}"#
+        );
+    }
+}
@@ -0,0 +1,525 @@
+use crate::{
+    network::{oneshot_behaviour, oneshot_protocol},
+    swap_protocols::{rfc003, HashFunction},
+};
+use libp2p::{
+    core::{
+        connection::ConnectionId,
+        upgrade::{InboundUpgrade, OutboundUpgrade},
+        ConnectedPoint, Multiaddr, PeerId,
+    },
+    swarm::{
+        KeepAlive, NegotiatedSubstream, NetworkBehaviour, NetworkBehaviourAction, PollParameters,
+        ProtocolsHandler, ProtocolsHandlerEvent, ProtocolsHandlerUpgrErr, SubstreamProtocol,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
+
+/// The message for the capabilities protocol.
+///
+/// Every node sends this message to its peers as soon as a connection is
+/// established so that the peers know which swaps they can propose.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Message {
+    /// The version of cnd the node runs.
+    pub version: String,
+    pub swaps: Vec<SwapCapability>,
+}
+
+/// The ledgers rfc003 swaps are negotiated on together with their assets.
+const RFC003_LEDGERS: [(Ledger, &[Asset]); 3] = [
+    (Ledger::Bitcoin, &[Asset::Bitcoin]),
+    (Ledger::Ethereum, &[Asset::Ether, Asset::Erc20]),
+    (Ledger::Litecoin, &[Asset::Bitcoin]),
+];
+
+impl Message {
+    /// The capabilities of this build of cnd.
+    pub fn ours() -> Self {
+        let mut swaps = rfc003_swaps();
+        swaps.push(SwapCapability::new(
+            Protocol::HanHalight,
+            (Ledger::Ethereum, Ledger::Lightning),
+            (Asset::Ether, Asset::Bitcoin),
+        ));
+        swaps.push(SwapCapability::new(
+            Protocol::HbitHalight,
+            (Ledger::Bitcoin, Ledger::Lightning),
+            (Asset::Bitcoin, Asset::Bitcoin),
+        ));
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            swaps,
+        }
+    }
+
+    pub fn supports(&self, swap: &SwapCapability) -> bool {
+        self.swaps.contains(swap)
+    }
+}
+
+impl oneshot_protocol::Message for Message {
+    const INFO: &'static str = "/comit/capabilities/1.0.0";
+}
+
+/// Every combination of the ledgers and assets in `RFC003_LEDGERS` with every
+/// hash function the HTLC templates implement. Swaps between two ledgers
+/// derived from Bitcoin, e.g. Bitcoin and Litecoin, are not implemented.
+fn rfc003_swaps() -> Vec<SwapCapability> {
+    let mut swaps = Vec::new();
+
+    for hash_function in rfc003::HASH_FUNCTIONS.iter() {
+        let protocol = Protocol::Rfc003 {
+            hash_function: *hash_function,
+        };
+
+        for (alpha_ledger, alpha_assets) in RFC003_LEDGERS.iter() {
+            for (beta_ledger, beta_assets) in RFC003_LEDGERS.iter() {
+                if alpha_ledger.is_utxo_chain() && beta_ledger.is_utxo_chain() {
+                    continue;
+                }
+
+                for alpha_asset in alpha_assets.iter() {
+                    for beta_asset in beta_assets.iter() {
+                        swaps.push(SwapCapability::new(
+                            protocol,
+                            (*alpha_ledger, *beta_ledger),
+                            (*alpha_asset, *beta_asset),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    swaps
+}
+
+/// A swap protocol together with the ledgers and assets it is used with.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SwapCapability {
+    pub protocol: Protocol,
+    pub alpha_ledger: Ledger,
+    pub beta_ledger: Ledger,
+    pub alpha_asset: Asset,
+    pub beta_asset: Asset,
+}
+
+impl SwapCapability {
+    pub fn new(protocol: Protocol, ledgers: (Ledger, Ledger), assets: (Asset, Asset)) -> Self {
+        Self {
+            protocol,
+            alpha_ledger: ledgers.0,
+            beta_ledger: ledgers.1,
+            alpha_asset: assets.0,
+            beta_asset: assets.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "name", rename_all = "kebab-case")]
+pub enum Protocol {
+    Rfc003 { hash_function: HashFunction },
+    HanHalight,
+    HbitHalight,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ledger {
+    Bitcoin,
+    Ethereum,
+    Lightning,
+    Litecoin,
+}
+
+impl Ledger {
+    fn is_utxo_chain(self) -> bool {
+        match self {
+            Ledger::Bitcoin | Ledger::Litecoin => true,
+            Ledger::Ethereum | Ledger::Lightning => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Asset {
+    Bitcoin,
+    Ether,
+    Erc20,
+}
+
+type OneShotHandler = <oneshot_behaviour::Behaviour<Message> as NetworkBehaviour>::ProtocolsHandler;
+
+/// Protocol handler for the capabilities protocol.
+///
+/// The oneshot handler closes the connection if the message cannot be sent.
+/// Peers that don't speak the capabilities protocol, e.g. because they run an
+/// older cnd, can still swap with us, hence this handler reports the failure
+/// instead and keeps the connection.
+#[derive(Debug)]
+pub struct Handler {
+    inner: OneShotHandler,
+    upgrade_failed: bool,
+}
+
+/// Event produced by the `Handler`.
+#[derive(Debug)]
+pub enum HandlerEvent {
+    Oneshot(oneshot_protocol::OutEvent<Message>),
+    /// Sending our capabilities to the peer failed.
+    UpgradeFailed,
+}
+
+impl ProtocolsHandler for Handler {
+    type InEvent = <OneShotHandler as ProtocolsHandler>::InEvent;
+    type OutEvent = HandlerEvent;
+    type Error = <OneShotHandler as ProtocolsHandler>::Error;
+    type InboundProtocol = <OneShotHandler as ProtocolsHandler>::InboundProtocol;
+    type OutboundProtocol = <OneShotHandler as ProtocolsHandler>::OutboundProtocol;
+    type OutboundOpenInfo = <OneShotHandler as ProtocolsHandler>::OutboundOpenInfo;
+
+    fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol> {
+        self.inner.listen_protocol()
+    }
+
+    fn inject_fully_negotiated_inbound(
+        &mut self,
+        protocol: <Self::InboundProtocol as InboundUpgrade<NegotiatedSubstream>>::Output,
+    ) {
+        self.inner.inject_fully_negotiated_inbound(protocol)
+    }
+
+    fn inject_fully_negotiated_outbound(
+        &mut self,
+        protocol: <Self::OutboundProtocol as OutboundUpgrade<NegotiatedSubstream>>::Output,
+        info: Self::OutboundOpenInfo,
+    ) {
+        self.inner.inject_fully_negotiated_outbound(protocol, info)
+    }
+
+    fn inject_event(&mut self, event: Self::InEvent) {
+        self.inner.inject_event(event)
+    }
+
+    fn inject_dial_upgrade_error(
+        &mut self,
+        _info: Self::OutboundOpenInfo,
+        err: ProtocolsHandlerUpgrErr<
+            <Self::OutboundProtocol as OutboundUpgrade<NegotiatedSubstream>>::Error,
+        >,
+    ) {
+        tracing::debug!("failed to send our capabilities: {:?}", err);
+        self.upgrade_failed = true;
+    }
+
+    fn connection_keep_alive(&self) -> KeepAlive {
+        self.inner.connection_keep_alive()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<
+        ProtocolsHandlerEvent<
+            Self::OutboundProtocol,
+            Self::OutboundOpenInfo,
+            HandlerEvent,
+            Self::Error,
+        >,
+    > {
+        if self.upgrade_failed {
+            self.upgrade_failed = false;
+            return Poll::Ready(ProtocolsHandlerEvent::Custom(HandlerEvent::UpgradeFailed));
+        }
+
+        self.inner
+            .poll(cx)
+            .map(|event| event.map_custom(HandlerEvent::Oneshot))
+    }
+}
+
+/// Network behaviour that sends our capabilities to every peer we connect to
+/// and remembers the capabilities of our peers.
+#[derive(Debug)]
+pub struct Capabilities {
+    inner: oneshot_behaviour::Behaviour<Message>,
+    ours: Message,
+    peers: HashMap<PeerId, Message>,
+}
+
+impl Capabilities {
+    pub fn new(ours: Message) -> Self {
+        Self {
+            inner: oneshot_behaviour::Behaviour::default(),
+            ours,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// The capabilities of the given peer, `None` if the peer did not tell us
+    /// (yet).
+    pub fn of(&self, peer_id: &PeerId) -> Option<&Message> {
+        self.peers.get(peer_id)
+    }
+}
+
+impl NetworkBehaviour for Capabilities {
+    type ProtocolsHandler = Handler;
+    type OutEvent = ();
+
+    fn new_handler(&mut self) -> Self::ProtocolsHandler {
+        Handler {
+            inner: self.inner.new_handler(),
+            upgrade_failed: false,
+        }
+    }
+
+    fn addresses_of_peer(&mut self, peer_id: &PeerId) -> Vec<Multiaddr> {
+        self.inner.addresses_of_peer(peer_id)
+    }
+
+    fn inject_connected(&mut self, peer_id: &PeerId) {
+        self.inner.inject_connected(peer_id);
+        self.inner.send(peer_id.clone(), self.ours.clone());
+    }
+
+    fn inject_disconnected(&mut self, peer_id: &PeerId) {
+        self.inner.inject_disconnected(peer_id);
+        self.peers.remove(peer_id);
+    }
+
+    fn inject_connection_established(
+        &mut self,
+        peer_id: &PeerId,
+        connection: &ConnectionId,
+        endpoint: &ConnectedPoint,
+    ) {
+        self.inner
+            .inject_connection_established(peer_id, connection, endpoint)
+    }
+
+    fn inject_connection_closed(
+        &mut self,
+        peer_id: &PeerId,
+        connection: &ConnectionId,
+        endpoint: &ConnectedPoint,
+    ) {
+        self.inner
+            .inject_connection_closed(peer_id, connection, endpoint)
+    }
+
+    fn inject_event(
+        &mut self,
+        peer_id: PeerId,
+        connection: ConnectionId,
+        event: <Self::ProtocolsHandler as ProtocolsHandler>::OutEvent,
+    ) {
+        match event {
+            HandlerEvent::Oneshot(event) => self.inner.inject_event(peer_id, connection, event),
+            HandlerEvent::UpgradeFailed => tracing::debug!(
+                "peer {} did not receive our capabilities, we don't know its capabilities either",
+                peer_id
+            ),
+        }
+    }
+
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+        params: &mut impl PollParameters,
+    ) -> Poll<NetworkBehaviourAction<<Self::ProtocolsHandler as ProtocolsHandler>::InEvent, ()>>
+    {
+        loop {
+            let action = match self.inner.poll(cx, params) {
+                Poll::Ready(action) => action,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let action = match action {
+                NetworkBehaviourAction::GenerateEvent(oneshot_behaviour::OutEvent::Received {
+                    peer,
+                    message,
+                }) => {
+                    tracing::debug!(
+                        "peer {} runs cnd {} and supports {} swaps",
+                        peer,
+                        message.version,
+                        message.swaps.len()
+                    );
+                    self.peers.insert(peer, message);
+                    continue;
+                }
+                NetworkBehaviourAction::GenerateEvent(oneshot_behaviour::OutEvent::Sent {
+                    ..
+                }) => continue,
+                NetworkBehaviourAction::DialAddress { address } => {
+                    NetworkBehaviourAction::DialAddress { address }
+                }
+                NetworkBehaviourAction::DialPeer { peer_id, condition } => {
+                    NetworkBehaviourAction::DialPeer { peer_id, condition }
+                }
+                NetworkBehaviourAction::NotifyHandler {
+                    peer_id,
+                    handler,
+                    event,
+                } => NetworkBehaviourAction::NotifyHandler {
+                    peer_id,
+                    handler,
+                    event,
+                },
+                NetworkBehaviourAction::ReportObservedAddr { address } => {
+                    NetworkBehaviourAction::ReportObservedAddr { address }
+                }
+            };
+
+            return Poll::Ready(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn serialization_format_stability_test() {
+        let given = Message {
+            version: "0.7.2".to_owned(),
+            swaps: vec![
+                SwapCapability::new(
+                    Protocol::Rfc003 {
+                        hash_function: HashFunction::Sha256,
+                    },
+                    (Ledger::Bitcoin, Ledger::Ethereum),
+                    (Asset::Bitcoin, Asset::Erc20),
+                ),
+                SwapCapability::new(
+                    Protocol::HanHalight,
+                    (Ledger::Ethereum, Ledger::Lightning),
+                    (Asset::Ether, Asset::Bitcoin),
+                ),
+            ],
+        };
+
+        let actual = serde_json::to_string(&given);
+
+        assert_that(&actual).is_ok_containing(r#"{"version":"0.7.2","swaps":[{"protocol":{"name":"rfc003","hash_function":"SHA-256"},"alpha_ledger":"bitcoin","beta_ledger":"ethereum","alpha_asset":"bitcoin","beta_asset":"erc20"},{"protocol":{"name":"han-halight"},"alpha_ledger":"ethereum","beta_ledger":"lightning","alpha_asset":"ether","beta_asset":"bitcoin"}]}"#.to_owned())
+    }
+
+    #[test]
+    fn we_support_every_rfc003_swap_except_between_utxo_chains() {
+        let swaps_with = |hash_function| {
+            let swap = |ledgers, assets| {
+                SwapCapability::new(Protocol::Rfc003 { hash_function }, ledgers, assets)
+            };
+            let bitcoin_ethereum = (Ledger::Bitcoin, Ledger::Ethereum);
+            let ethereum_bitcoin = (Ledger::Ethereum, Ledger::Bitcoin);
+            let ethereum_ethereum = (Ledger::Ethereum, Ledger::Ethereum);
+            let ethereum_litecoin = (Ledger::Ethereum, Ledger::Litecoin);
+            let litecoin_ethereum = (Ledger::Litecoin, Ledger::Ethereum);
+
+            vec![
+                swap(bitcoin_ethereum, (Asset::Bitcoin, Asset::Ether)),
+                swap(bitcoin_ethereum, (Asset::Bitcoin, Asset::Erc20)),
+                swap(ethereum_bitcoin, (Asset::Ether, Asset::Bitcoin)),
+                swap(ethereum_bitcoin, (Asset::Erc20, Asset::Bitcoin)),
+                swap(ethereum_ethereum, (Asset::Ether, Asset::Ether)),
+                swap(ethereum_ethereum, (Asset::Ether, Asset::Erc20)),
+                swap(ethereum_ethereum, (Asset::Erc20, Asset::Ether)),
+                swap(ethereum_ethereum, (Asset::Erc20, Asset::Erc20)),
+                swap(ethereum_litecoin, (Asset::Ether, Asset::Bitcoin)),
+                swap(ethereum_litecoin, (Asset::Erc20, Asset::Bitcoin)),
+                swap(litecoin_ethereum, (Asset::Bitcoin, Asset::Ether)),
+                swap(litecoin_ethereum, (Asset::Bitcoin, Asset::Erc20)),
+            ]
+        };
+        let mut expected = swaps_with(HashFunction::Sha256);
+        expected.extend(swaps_with(HashFunction::Ripemd160Sha256));
+
+        assert_that(&rfc003_swaps()).is_equal_to(expected);
+    }
+
+    #[test]
+    fn we_do_not_support_sha3_256_with_rfc003() {
+        let swap = SwapCapability::new(
+            Protocol::Rfc003 {
+                hash_function: HashFunction::Sha3_256,
+            },
+            (Ledger::Bitcoin, Ledger::Ethereum),
+            (Asset::Bitcoin, Asset::Ether),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_false();
+    }
+
+    #[test]
+    fn we_support_bitcoin_for_ether_with_rfc003() {
+        let swap = SwapCapability::new(
+            Protocol::Rfc003 {
+                hash_function: HashFunction::Sha256,
+            },
+            (Ledger::Bitcoin, Ledger::Ethereum),
+            (Asset::Bitcoin, Asset::Ether),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_true();
+    }
+
+    #[test]
+    fn we_do_not_support_bitcoin_for_litecoin_with_rfc003() {
+        let swap = SwapCapability::new(
+            Protocol::Rfc003 {
+                hash_function: HashFunction::Sha256,
+            },
+            (Ledger::Bitcoin, Ledger::Litecoin),
+            (Asset::Bitcoin, Asset::Bitcoin),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_false();
+    }
+
+    #[test]
+    fn we_support_ether_for_erc20() {
+        let swap = SwapCapability::new(
+            Protocol::Rfc003 {
+                hash_function: HashFunction::Sha256,
+            },
+            (Ledger::Ethereum, Ledger::Ethereum),
+            (Asset::Ether, Asset::Erc20),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_true();
+    }
+
+    #[test]
+    fn we_do_not_support_ether_for_erc20_with_han_halight() {
+        let swap = SwapCapability::new(
+            Protocol::HanHalight,
+            (Ledger::Ethereum, Ledger::Ethereum),
+            (Asset::Ether, Asset::Erc20),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_false();
+    }
+
+    #[test]
+    fn we_support_bitcoin_for_lightning_with_hbit_halight() {
+        let swap = SwapCapability::new(
+            Protocol::HbitHalight,
+            (Ledger::Bitcoin, Ledger::Lightning),
+            (Asset::Bitcoin, Asset::Bitcoin),
+        );
+
+        assert_that(&Message::ours().supports(&swap)).is_true();
+    }
+}
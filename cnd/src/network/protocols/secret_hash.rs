@@ -4,6 +4,7 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use serde_hex::{SerHex, Strict};
+use std::convert::TryFrom;
 
 /// The message for the secret hash sharing protocol.
 #[derive(Clone, Copy, Deserialize, Debug, Serialize)]
@@ -18,7 +19,8 @@ impl Message {
     pub fn new(swap_id: SwapId, secret_hash: SecretHash) -> Self {
         Self {
             swap_id,
-            secret_hash: secret_hash.into_raw(),
+            secret_hash: <[u8; 32]>::try_from(secret_hash)
+                .expect("the secret hash of han and halight swaps is a SHA-256 hash"),
         }
     }
 }
@@ -0,0 +1,19 @@
+use libp2p::PeerId;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<PeerId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    let peer_id = value.parse().map_err(D::Error::custom)?;
+
+    Ok(peer_id)
+}
+
+pub fn serialize<S>(value: &PeerId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_base58())
+}
@@ -0,0 +1,135 @@
+use crate::network::ComitNode;
+use igd::{aio::search_gateway, PortMappingProtocol, SearchOptions};
+use libp2p::{multiaddr::Protocol, Multiaddr};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Mutex;
+
+const MAPPING_DESCRIPTION: &str = "cnd";
+const LEASE_DURATION_SECS: u32 = 60 * 60;
+const RENEWAL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Keeps the TCP ports of the given `listen` addresses mapped on the local
+/// internet gateway and advertises the resulting public addresses as external
+/// addresses of the swarm.
+///
+/// Port mappings are leased and therefore renewed periodically for as long as
+/// this future is running.
+pub async fn run(listen: Vec<Multiaddr>, swarm: Arc<Mutex<libp2p::Swarm<ComitNode>>>) {
+    let mut advertised = Vec::new();
+
+    loop {
+        match map_ports(&listen).await {
+            Ok(external_addresses) => {
+                {
+                    let mut guard = swarm.lock().await;
+
+                    for address in external_addresses {
+                        if !advertised.contains(&address) {
+                            tracing::info!("Advertising UPnP mapped address {}", address);
+                            libp2p::Swarm::add_external_address(&mut guard, address.clone());
+                            advertised.push(address);
+                        }
+                    }
+                }
+
+                tokio::time::delay_for(RENEWAL_INTERVAL).await;
+            }
+            Err(e) => {
+                tracing::warn!("Could not map ports through UPnP: {:#}", e);
+
+                tokio::time::delay_for(RETRY_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// Maps the TCP port of every IPv4 address in `listen` to the same port on
+/// the gateway and returns the addresses that are reachable from the outside.
+async fn map_ports(listen: &[Multiaddr]) -> anyhow::Result<Vec<Multiaddr>> {
+    let gateway = search_gateway(SearchOptions::default()).await?;
+    let external_ip = gateway.get_external_ip().await?;
+    let local_ip = local_ip_towards(gateway.addr)?;
+
+    let mut external_addresses = Vec::new();
+
+    for port in listen.iter().filter_map(ipv4_tcp_port) {
+        gateway
+            .add_port(
+                PortMappingProtocol::TCP,
+                port,
+                SocketAddrV4::new(local_ip, port),
+                LEASE_DURATION_SECS,
+                MAPPING_DESCRIPTION,
+            )
+            .await?;
+
+        external_addresses.push(tcp_multiaddr(external_ip, port));
+    }
+
+    Ok(external_addresses)
+}
+
+/// Returns the IP address of the local interface that is used to talk to
+/// the given gateway.
+///
+/// Connecting a UDP socket does not send any packets, it only makes the OS
+/// pick the outgoing interface.
+fn local_ip_towards(gateway: SocketAddrV4) -> anyhow::Result<Ipv4Addr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.connect(gateway)?;
+
+    match socket.local_addr()? {
+        SocketAddr::V4(addr) => Ok(*addr.ip()),
+        SocketAddr::V6(addr) => Err(anyhow::anyhow!(
+            "gateway {} is reachable only through IPv6 address {}",
+            gateway,
+            addr
+        )),
+    }
+}
+
+fn ipv4_tcp_port(address: &Multiaddr) -> Option<u16> {
+    let mut protocols = address.iter();
+
+    match (protocols.next(), protocols.next()) {
+        (Some(Protocol::Ip4(_)), Some(Protocol::Tcp(port))) => Some(port),
+        _ => None,
+    }
+}
+
+fn tcp_multiaddr(ip: Ipv4Addr, port: u16) -> Multiaddr {
+    Multiaddr::empty()
+        .with(Protocol::Ip4(ip))
+        .with(Protocol::Tcp(port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn only_ipv4_tcp_addresses_are_mapped() {
+        let ipv4: Multiaddr = "/ip4/0.0.0.0/tcp/9939".parse().unwrap();
+        let ipv6: Multiaddr = "/ip6/::1/tcp/9939".parse().unwrap();
+        let dns: Multiaddr = "/dns4/example.com/tcp/9939".parse().unwrap();
+
+        assert_that(&ipv4_tcp_port(&ipv4))
+            .is_some()
+            .is_equal_to(9939);
+        assert_that(&ipv4_tcp_port(&ipv6)).is_none();
+        assert_that(&ipv4_tcp_port(&dns)).is_none();
+    }
+
+    #[test]
+    fn builds_external_tcp_address() {
+        let address = tcp_multiaddr(Ipv4Addr::new(203, 0, 113, 7), 9939);
+
+        assert_eq!(address, "/ip4/203.0.113.7/tcp/9939".parse().unwrap());
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     identity,
     swap_protocols::{
         ledger,
-        ledger::{bitcoin, ethereum::ChainId},
+        ledger::{bitcoin, ethereum::ChainId, litecoin},
         rfc003::{Accept, Request, SecretHash},
         HashFunction, Role, SwapId,
     },
@@ -20,7 +20,9 @@ use ::bitcoin::{
 use impl_template::impl_template;
 use libp2p::PeerId;
 use quickcheck::{Arbitrary, Gen};
+use rand::seq::SliceRandom;
 use std::ops::Deref;
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 /// Generic newtype that allows us to implement quickcheck::Arbitrary on foreign
@@ -146,8 +148,13 @@ impl Arbitrary for Quickcheck<crate::asset::Erc20> {
 }
 
 impl Arbitrary for Quickcheck<HashFunction> {
-    fn arbitrary<G: Gen>(_g: &mut G) -> Self {
-        Quickcheck(HashFunction::Sha256)
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let hash_functions = HashFunction::iter().collect::<Vec<_>>();
+        let hash_function = hash_functions
+            .choose(g)
+            .expect("there is at least one hash function");
+
+        Quickcheck(*hash_function)
     }
 }
 
@@ -213,6 +220,13 @@ impl Arbitrary for Quickcheck<((bitcoin::Mainnet, bitcoin::Testnet, bitcoin::Reg
     }
 }
 
+#[impl_template]
+impl Arbitrary for Quickcheck<((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest))> {
+    fn arbitrary<G: Gen>(_g: &mut G) -> Self {
+        Quickcheck(__TYPE0__)
+    }
+}
+
 impl Arbitrary for Quickcheck<ledger::Ethereum> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         let chain_id = *Quickcheck::<ChainId>::arbitrary(g);
@@ -0,0 +1,936 @@
+//! Reconstructs the state of the swaps in the database from the blockchains
+//! alone.
+//!
+//! This is meant for operators whose node was offline for a long time: for
+//! every swap it recomputes the HTLCs, looks them up on chain and reports which
+//! of them can still be refunded or redeemed.
+
+#![allow(clippy::type_repetition_in_bounds)]
+use crate::{
+    asset,
+    btsieve::{
+        bitcoin::{self, BitcoindConnector},
+        ethereum::{self, Web3Connector},
+        ConsensusTime,
+    },
+    config,
+    db::{Database, DetermineTypes, LoadAcceptedSwap, Retrieve},
+    htlc_location,
+    http_api::action::{ActionExecutionParameters, ActionResponseBody, IntoResponsePayload},
+    identity,
+    seed::{DeriveSwapSeed, RootSeed, SwapSeed},
+    swap_protocols::{
+        actions::{bitcoin::SpendOutput, ethereum::CallContract, litecoin},
+        ledger::{self, Ethereum},
+        rfc003::{
+            actions::{MakeRedeemAction, MakeRefundAction},
+            create_swap::{HtlcParams, OngoingSwap},
+            events::{
+                Deployed, Funded, HtlcDeployed, HtlcFunded, HtlcRedeemed, HtlcRefunded, Redeemed,
+                Refunded,
+            },
+            DeriveSecret, LedgerTime, Secret,
+        },
+        LedgerSide, NoLitecoinConnector, Role, SwapId,
+    },
+    timestamp::Timestamp,
+    transaction,
+};
+use chrono::NaiveDateTime;
+use futures::future::{self, Either};
+use impl_template::impl_template;
+use serde::Serialize;
+use std::{future::Future, sync::Arc, time::Duration};
+
+/// Everything that is needed to recover swaps, notably without a network
+/// connection to other nodes.
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct Recovery {
+    #[derivative(Debug = "ignore")]
+    pub bitcoin_connector: Arc<bitcoin::Cache<BitcoindConnector>>,
+    #[derivative(Debug = "ignore")]
+    pub litecoin_connector: Option<Arc<bitcoin::Cache<BitcoindConnector>>>,
+    #[derivative(Debug = "ignore")]
+    pub ethereum_connector: Arc<ethereum::Cache<Web3Connector>>,
+    pub db: Database,
+    pub seed: RootSeed,
+    pub funding: config::Funding,
+    /// How long to wait for an event on chain before concluding that it did
+    /// not happen (yet).
+    pub scan_timeout: Duration,
+}
+
+/// How to build the refund and redeem transactions.
+#[derive(Clone, Debug, Default)]
+pub struct BuildTransactions {
+    /// The address Bitcoin is refunded or redeemed to.
+    pub bitcoin_address: Option<::bitcoin::Address>,
+    /// The address Litecoin is refunded or redeemed to.
+    pub litecoin_address: Option<String>,
+    /// The fee per weight unit of Bitcoin and Litecoin transactions.
+    pub bitcoin_fee_per_wu: Option<usize>,
+}
+
+impl Recovery {
+    fn litecoin_connector(&self) -> anyhow::Result<&bitcoin::Cache<BitcoindConnector>> {
+        self.litecoin_connector
+            .as_ref()
+            .map(|connector| connector.as_ref())
+            .ok_or_else(|| anyhow::Error::from(NoLitecoinConnector))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SwapReport {
+    pub swap_id: SwapId,
+    pub role: String,
+    pub alpha_ledger: HtlcState,
+    pub beta_ledger: HtlcState,
+    pub recommendations: Vec<Recommendation>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtlcState {
+    /// The HTLC was not found on chain.
+    NotDeployed,
+    /// The HTLC was deployed but not funded (yet).
+    Deployed,
+    /// The HTLC is funded and was neither redeemed nor refunded.
+    Funded,
+    IncorrectlyFunded,
+    Redeemed,
+    Refunded,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recommendation {
+    /// Refund the HTLC we funded, it expired.
+    Refund {
+        ledger: LedgerSide,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        action: Option<ActionResponseBody>,
+    },
+    /// Redeem the HTLC funded by the counterparty before it expires.
+    Redeem {
+        ledger: LedgerSide,
+        expiry: Timestamp,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        action: Option<ActionResponseBody>,
+    },
+    /// Our HTLC can only be refunded after it expired.
+    WaitForExpiry {
+        ledger: LedgerSide,
+        expiry: Timestamp,
+    },
+}
+
+/// The outcome of looking for the events of one HTLC on chain.
+#[derive(Debug)]
+enum Scan<A, H, T> {
+    NotDeployed,
+    Deployed,
+    Funded {
+        deployed: Deployed<H, T>,
+        funded: Funded<A, T>,
+    },
+    Redeemed(Redeemed<T>),
+    Refunded(Refunded<T>),
+}
+
+impl<A, H, T> Scan<A, H, T> {
+    fn state(&self) -> HtlcState {
+        match self {
+            Scan::NotDeployed => HtlcState::NotDeployed,
+            Scan::Deployed => HtlcState::Deployed,
+            Scan::Funded {
+                funded: Funded::Correctly { .. },
+                ..
+            } => HtlcState::Funded,
+            Scan::Funded {
+                funded: Funded::Incorrectly { .. },
+                ..
+            } => HtlcState::IncorrectlyFunded,
+            Scan::Redeemed(_) => HtlcState::Redeemed,
+            Scan::Refunded(_) => HtlcState::Refunded,
+        }
+    }
+}
+
+/// Report what can still be done for every swap in the database.
+#[allow(clippy::cognitive_complexity)]
+pub async fn recover(
+    recovery: &Recovery,
+    build_transactions: Option<&BuildTransactions>,
+) -> anyhow::Result<Vec<SwapReport>> {
+    let mut reports = Vec::new();
+
+    for swap in Retrieve::all(&recovery.db).await?.iter() {
+        let swap_id = swap.swap_id;
+        let role = swap.role;
+        let types = DetermineTypes::determine_types(&recovery.db, &swap_id).await?;
+
+        with_swap_types!(types, {
+            let accepted = LoadAcceptedSwap::<AL, BL, AA, BA, AI, BI>::load_accepted_swap(
+                &recovery.db,
+                &swap_id,
+            )
+            .await;
+
+            match accepted {
+                Ok((request, accept, accepted_at)) => {
+                    let report = recover_swap::<_, _, _, _, AH, BH, _, _, AT, BT>(
+                        recovery,
+                        swap_id,
+                        role,
+                        OngoingSwap::new(request, accept),
+                        accepted_at,
+                        build_transactions,
+                    )
+                    .await?;
+
+                    reports.push(report);
+                }
+                Err(e) => tracing::warn!("swap {} was never accepted, skipping: {}", swap_id, e),
+            };
+        });
+    }
+
+    Ok(reports)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn recover_swap<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT>(
+    recovery: &Recovery,
+    swap_id: SwapId,
+    role: Role,
+    swap: OngoingSwap<AL, BL, AA, BA, AI, BI>,
+    accepted_at: NaiveDateTime,
+    build_transactions: Option<&BuildTransactions>,
+) -> anyhow::Result<SwapReport>
+where
+    Recovery: HtlcFunded<AL, AA, AH, AI, AT>
+        + HtlcFunded<BL, BA, BH, BI, BT>
+        + HtlcDeployed<AL, AA, AH, AI, AT>
+        + HtlcDeployed<BL, BA, BH, BI, BT>
+        + HtlcRedeemed<AL, AA, AH, AI, AT>
+        + HtlcRedeemed<BL, BA, BH, BI, BT>
+        + HtlcRefunded<AL, AA, AH, AI, AT>
+        + HtlcRefunded<BL, BA, BH, BI, BT>
+        + LedgerTime<AL>
+        + LedgerTime<BL>,
+    AL: Clone,
+    BL: Clone,
+    AA: Clone,
+    BA: Clone,
+    AH: Clone,
+    BH: Clone,
+    AI: Clone,
+    BI: Clone,
+    AT: Clone,
+    BT: Clone,
+    (AL, AA): MakeRedeemAction<
+            HtlcParams = HtlcParams<AL, AA, AI>,
+            HtlcLocation = AH,
+            FundTransaction = AT,
+        > + MakeRefundAction<
+            HtlcParams = HtlcParams<AL, AA, AI>,
+            HtlcLocation = AH,
+            FundTransaction = AT,
+        >,
+    (BL, BA): MakeRedeemAction<
+            HtlcParams = HtlcParams<BL, BA, BI>,
+            HtlcLocation = BH,
+            FundTransaction = BT,
+        > + MakeRefundAction<
+            HtlcParams = HtlcParams<BL, BA, BI>,
+            HtlcLocation = BH,
+            FundTransaction = BT,
+        >,
+    <(AL, AA) as MakeRedeemAction>::Output: BuildPayload,
+    <(AL, AA) as MakeRefundAction>::Output: BuildPayload,
+    <(BL, BA) as MakeRedeemAction>::Output: BuildPayload,
+    <(BL, BA) as MakeRefundAction>::Output: BuildPayload,
+{
+    let alpha_params = swap.alpha_htlc_params();
+    let beta_params = swap.beta_htlc_params();
+
+    let alpha = scan(recovery, &alpha_params, accepted_at).await?;
+    let beta = scan(recovery, &beta_params, accepted_at).await?;
+
+    // The expiries are checked against the consensus time of each ledger,
+    // not our wall clock.
+    let alpha_time = LedgerTime::<AL>::ledger_time(recovery, &alpha_params.ledger).await?;
+    let beta_time = LedgerTime::<BL>::ledger_time(recovery, &beta_params.ledger).await?;
+
+    let swap_seed = recovery.seed.derive_swap_seed(swap_id);
+    let mut recommendations = Vec::new();
+
+    match role {
+        Role::Alice => {
+            // Alice funds alpha and redeems beta with the secret she generated.
+            if let Some(recommendation) = refund::<AL, AA, AH, AI, AT>(
+                LedgerSide::Alpha,
+                &alpha,
+                alpha_params,
+                alpha_time,
+                &swap_seed,
+                build_transactions,
+            )? {
+                recommendations.push(recommendation);
+            }
+            if let Some(recommendation) = redeem::<BL, BA, BH, BI, BT>(
+                LedgerSide::Beta,
+                &beta,
+                beta_params,
+                beta_time,
+                &swap_seed,
+                swap_seed.derive_secret(),
+                build_transactions,
+            )? {
+                recommendations.push(recommendation);
+            }
+        }
+        Role::Bob => {
+            // Bob funds beta and redeems alpha with the secret Alice revealed
+            // when redeeming beta.
+            if let Some(recommendation) = refund::<BL, BA, BH, BI, BT>(
+                LedgerSide::Beta,
+                &beta,
+                beta_params,
+                beta_time,
+                &swap_seed,
+                build_transactions,
+            )? {
+                recommendations.push(recommendation);
+            }
+            if let Scan::Redeemed(Redeemed { secret, .. }) = &beta {
+                if let Some(recommendation) = redeem::<AL, AA, AH, AI, AT>(
+                    LedgerSide::Alpha,
+                    &alpha,
+                    alpha_params,
+                    alpha_time,
+                    &swap_seed,
+                    *secret,
+                    build_transactions,
+                )? {
+                    recommendations.push(recommendation);
+                }
+            }
+        }
+    }
+
+    Ok(SwapReport {
+        swap_id,
+        role: role.to_string(),
+        alpha_ledger: alpha.state(),
+        beta_ledger: beta.state(),
+        recommendations,
+    })
+}
+
+/// Look for the deployment, funding, redemption and refund of the HTLC.
+///
+/// The btsieve watchers first go through all blocks since the start of the
+/// swap and then wait for new blocks. An event that is not found within the
+/// scan timeout is therefore assumed to not have happened yet.
+async fn scan<D, L, A, H, I, T>(
+    dependencies: &D,
+    htlc_params: &HtlcParams<L, A, I>,
+    start_of_swap: NaiveDateTime,
+) -> anyhow::Result<Scan<A, H, T>>
+where
+    D: HtlcFunded<L, A, H, I, T>
+        + HtlcDeployed<L, A, H, I, T>
+        + HtlcRedeemed<L, A, H, I, T>
+        + HtlcRefunded<L, A, H, I, T>
+        + ScanTimeout,
+{
+    let timeout = dependencies.scan_timeout();
+
+    let deployed = match within(
+        timeout,
+        dependencies.htlc_deployed(htlc_params, start_of_swap),
+    )
+    .await?
+    {
+        Some(deployed) => deployed,
+        None => return Ok(Scan::NotDeployed),
+    };
+
+    let funded = match within(
+        timeout,
+        dependencies.htlc_funded(htlc_params, &deployed, start_of_swap),
+    )
+    .await?
+    {
+        Some(funded) => funded,
+        None => return Ok(Scan::Deployed),
+    };
+
+    let redeemed = dependencies.htlc_redeemed(htlc_params, &deployed, start_of_swap);
+    let refunded = dependencies.htlc_refunded(htlc_params, &deployed, start_of_swap);
+
+    let scan = match within(timeout, future::try_select(redeemed, refunded)).await {
+        Ok(Some(Either::Left((redeemed, _)))) => Scan::Redeemed(redeemed),
+        Ok(Some(Either::Right((refunded, _)))) => Scan::Refunded(refunded),
+        Ok(None) => Scan::Funded { deployed, funded },
+        Err(either) => return Err(either.factor_first().0),
+    };
+
+    Ok(scan)
+}
+
+/// Resolves to `None` if the future did not complete within the timeout.
+async fn within<F, T, E>(timeout: Duration, future: F) -> Result<Option<T>, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => result.map(Some),
+        Err(_elapsed) => Ok(None),
+    }
+}
+
+fn refund<L, A, H, I, T>(
+    ledger: LedgerSide,
+    scan: &Scan<A, H, T>,
+    htlc_params: HtlcParams<L, A, I>,
+    ledger_time: Timestamp,
+    secret_source: &SwapSeed,
+    build_transactions: Option<&BuildTransactions>,
+) -> anyhow::Result<Option<Recommendation>>
+where
+    H: Clone,
+    (L, A):
+        MakeRefundAction<HtlcParams = HtlcParams<L, A, I>, HtlcLocation = H, FundTransaction = T>,
+    <(L, A) as MakeRefundAction>::Output: BuildPayload,
+{
+    let (deployed, funded) = match scan {
+        Scan::Funded { deployed, funded } => (deployed, funded),
+        _ => return Ok(None),
+    };
+
+    if !<(L, A)>::is_refundable(htlc_params.expiry, ledger_time) {
+        return Ok(Some(Recommendation::WaitForExpiry {
+            ledger,
+            expiry: htlc_params.expiry,
+        }));
+    }
+
+    let fund_transaction = match funded {
+        Funded::Correctly { transaction, .. } | Funded::Incorrectly { transaction, .. } => {
+            transaction
+        }
+    };
+
+    let action = build_transactions
+        .map(|build_transactions| {
+            <(L, A)>::make_refund_action(
+                htlc_params,
+                deployed.location.clone(),
+                secret_source,
+                fund_transaction,
+            )?
+            .build_payload(build_transactions)
+        })
+        .transpose()?;
+
+    Ok(Some(Recommendation::Refund { ledger, action }))
+}
+
+fn redeem<L, A, H, I, T>(
+    ledger: LedgerSide,
+    scan: &Scan<A, H, T>,
+    htlc_params: HtlcParams<L, A, I>,
+    ledger_time: Timestamp,
+    secret_source: &SwapSeed,
+    secret: Secret,
+    build_transactions: Option<&BuildTransactions>,
+) -> anyhow::Result<Option<Recommendation>>
+where
+    H: Clone,
+    (L, A):
+        MakeRedeemAction<HtlcParams = HtlcParams<L, A, I>, HtlcLocation = H, FundTransaction = T>,
+    <(L, A) as MakeRedeemAction>::Output: BuildPayload,
+{
+    let (deployed, fund_transaction) = match scan {
+        Scan::Funded {
+            deployed,
+            funded: Funded::Correctly { transaction, .. },
+        } => (deployed, transaction),
+        _ => return Ok(None),
+    };
+
+    // Redeeming after the expiry is a race against the refund of the
+    // counterparty, we don't recommend it.
+    if ledger_time >= htlc_params.expiry {
+        return Ok(None);
+    }
+
+    let expiry = htlc_params.expiry;
+    let action = build_transactions
+        .map(|build_transactions| {
+            <(L, A)>::make_redeem_action(
+                htlc_params,
+                deployed.location.clone(),
+                secret_source,
+                fund_transaction,
+                secret,
+            )?
+            .build_payload(build_transactions)
+        })
+        .transpose()?;
+
+    Ok(Some(Recommendation::Redeem {
+        ledger,
+        expiry,
+        action,
+    }))
+}
+
+pub trait ScanTimeout {
+    fn scan_timeout(&self) -> Duration;
+}
+
+impl ScanTimeout for Recovery {
+    fn scan_timeout(&self) -> Duration {
+        self.scan_timeout
+    }
+}
+
+/// Turns a refund or redeem action into the same payload the HTTP API
+/// returns for it.
+pub trait BuildPayload {
+    fn build_payload(
+        self,
+        build_transactions: &BuildTransactions,
+    ) -> anyhow::Result<ActionResponseBody>;
+}
+
+impl BuildPayload for SpendOutput {
+    fn build_payload(
+        self,
+        build_transactions: &BuildTransactions,
+    ) -> anyhow::Result<ActionResponseBody> {
+        match build_transactions {
+            BuildTransactions {
+                bitcoin_address: Some(address),
+                bitcoin_fee_per_wu: Some(fee_per_wu),
+                ..
+            } => self.into_response_payload(ActionExecutionParameters::BitcoinAddressAndFee {
+                address: address.clone(),
+                fee_per_wu: fee_per_wu.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "a Bitcoin address and fee per WU are needed to build Bitcoin transactions"
+            )),
+        }
+    }
+}
+
+impl BuildPayload for litecoin::SpendOutput {
+    fn build_payload(
+        self,
+        build_transactions: &BuildTransactions,
+    ) -> anyhow::Result<ActionResponseBody> {
+        match build_transactions {
+            BuildTransactions {
+                litecoin_address: Some(address),
+                bitcoin_fee_per_wu: Some(fee_per_wu),
+                ..
+            } => self.into_response_payload(ActionExecutionParameters::UtxoChainAddressAndFee {
+                address: address.clone(),
+                fee_per_wu: fee_per_wu.to_string(),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "a Litecoin address and fee per WU are needed to build Litecoin transactions"
+            )),
+        }
+    }
+}
+
+impl BuildPayload for CallContract {
+    fn build_payload(self, _: &BuildTransactions) -> anyhow::Result<ActionResponseBody> {
+        self.into_response_payload(ActionExecutionParameters::None {})
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcFunded<
+        ((
+            ledger::bitcoin::Mainnet,
+            ledger::bitcoin::Testnet,
+            ledger::bitcoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_funded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Funded<asset::Bitcoin, transaction::Bitcoin>> {
+        let funded = self
+            .bitcoin_connector
+            .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcDeployed<
+        ((
+            ledger::bitcoin::Mainnet,
+            ledger::bitcoin::Testnet,
+            ledger::bitcoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_deployed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Deployed<htlc_location::Bitcoin, transaction::Bitcoin>> {
+        self.bitcoin_connector
+            .htlc_deployed(htlc_params, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRedeemed<
+        ((
+            ledger::bitcoin::Mainnet,
+            ledger::bitcoin::Testnet,
+            ledger::bitcoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_redeemed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Redeemed<transaction::Bitcoin>> {
+        self.bitcoin_connector
+            .htlc_redeemed(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRefunded<
+        ((
+            ledger::bitcoin::Mainnet,
+            ledger::bitcoin::Testnet,
+            ledger::bitcoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_refunded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Refunded<transaction::Bitcoin>> {
+        self.bitcoin_connector
+            .htlc_refunded(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcFunded<
+        ((
+            ledger::litecoin::Mainnet,
+            ledger::litecoin::Testnet,
+            ledger::litecoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_funded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Funded<asset::Bitcoin, transaction::Bitcoin>> {
+        let funded = self
+            .litecoin_connector()?
+            .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcDeployed<
+        ((
+            ledger::litecoin::Mainnet,
+            ledger::litecoin::Testnet,
+            ledger::litecoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_deployed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Deployed<htlc_location::Bitcoin, transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_deployed(htlc_params, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRedeemed<
+        ((
+            ledger::litecoin::Mainnet,
+            ledger::litecoin::Testnet,
+            ledger::litecoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_redeemed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Redeemed<transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_redeemed(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRefunded<
+        ((
+            ledger::litecoin::Mainnet,
+            ledger::litecoin::Testnet,
+            ledger::litecoin::Regtest,
+        )),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Recovery
+{
+    async fn htlc_refunded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Refunded<transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_refunded(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcFunded<
+        Ethereum,
+        ((asset::Ether, asset::Erc20)),
+        htlc_location::Ethereum,
+        identity::Ethereum,
+        transaction::Ethereum,
+    > for Recovery
+{
+    async fn htlc_funded(
+        &self,
+        htlc_params: &HtlcParams<Ethereum, __TYPE0__, identity::Ethereum>,
+        htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Funded<__TYPE0__, transaction::Ethereum>> {
+        let funded = self
+            .ethereum_connector
+            .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcDeployed<
+        Ethereum,
+        ((asset::Ether, asset::Erc20)),
+        htlc_location::Ethereum,
+        identity::Ethereum,
+        transaction::Ethereum,
+    > for Recovery
+{
+    async fn htlc_deployed(
+        &self,
+        htlc_params: &HtlcParams<Ethereum, __TYPE0__, identity::Ethereum>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Deployed<htlc_location::Ethereum, transaction::Ethereum>> {
+        self.ethereum_connector
+            .htlc_deployed(htlc_params, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRedeemed<
+        Ethereum,
+        ((asset::Ether, asset::Erc20)),
+        htlc_location::Ethereum,
+        identity::Ethereum,
+        transaction::Ethereum,
+    > for Recovery
+{
+    async fn htlc_redeemed(
+        &self,
+        htlc_params: &HtlcParams<Ethereum, __TYPE0__, identity::Ethereum>,
+        htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Redeemed<transaction::Ethereum>> {
+        self.ethereum_connector
+            .htlc_redeemed(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRefunded<
+        Ethereum,
+        ((asset::Ether, asset::Erc20)),
+        htlc_location::Ethereum,
+        identity::Ethereum,
+        transaction::Ethereum,
+    > for Recovery
+{
+    async fn htlc_refunded(
+        &self,
+        htlc_params: &HtlcParams<Ethereum, __TYPE0__, identity::Ethereum>,
+        htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Refunded<transaction::Ethereum>> {
+        self.ethereum_connector
+            .htlc_refunded(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    LedgerTime<
+        ((
+            ledger::bitcoin::Mainnet,
+            ledger::bitcoin::Testnet,
+            ledger::bitcoin::Regtest,
+        )),
+    > for Recovery
+{
+    async fn ledger_time(&self, _: &__TYPE0__) -> anyhow::Result<Timestamp> {
+        self.bitcoin_connector.consensus_time().await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    LedgerTime<
+        ((
+            ledger::litecoin::Mainnet,
+            ledger::litecoin::Testnet,
+            ledger::litecoin::Regtest,
+        )),
+    > for Recovery
+{
+    async fn ledger_time(&self, _: &__TYPE0__) -> anyhow::Result<Timestamp> {
+        self.litecoin_connector()?.consensus_time().await
+    }
+}
+
+#[async_trait::async_trait]
+impl LedgerTime<Ethereum> for Recovery {
+    async fn ledger_time(&self, _: &Ethereum) -> anyhow::Result<Timestamp> {
+        self.ethereum_connector.consensus_time().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn serialization_format_stability_test() {
+        let given = SwapReport {
+            swap_id: "ad2652ca-ecf2-4cc6-b35c-b4351ac28a34".parse().unwrap(),
+            role: Role::Alice.to_string(),
+            alpha_ledger: HtlcState::Funded,
+            beta_ledger: HtlcState::NotDeployed,
+            recommendations: vec![Recommendation::WaitForExpiry {
+                ledger: LedgerSide::Alpha,
+                expiry: Timestamp::from(1_591_000_000),
+            }],
+        };
+
+        let actual = serde_json::to_string(&given);
+
+        assert_that(&actual).is_ok_containing(r#"{"swap_id":"ad2652ca-ecf2-4cc6-b35c-b4351ac28a34","role":"Alice","alpha_ledger":"funded","beta_ledger":"not_deployed","recommendations":[{"kind":"wait_for_expiry","ledger":"alpha","expiry":1591000000}]}"#.to_owned())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::swap_protocols::{NodeLocalSwapId, SwapId};
+use bip39::{Language, Mnemonic};
 use pem::{encode, Pem};
 use rand::Rng;
 use sha2::{Digest, Sha256};
@@ -11,6 +12,11 @@ use std::{
 };
 use thiserror;
 
+mod encryption;
+
+const PLAINTEXT_TAG: &str = "SEED";
+const ENCRYPTED_TAG: &str = "ENCRYPTED SEED";
+
 /// We create a `RootSeed` either randomly or by reading in the PEM file from
 /// disk.  This `RootSeed` is used to generate a per swap `SwapSeed` which is
 /// then use as the secret source for deriving redeem/refund identities.
@@ -125,26 +131,35 @@ impl RootSeed {
 
     /// Read the seed from the default location if it exists, otherwise
     /// generate a random seed and write it to the default location.
-    pub fn from_default_dir_or_generate<R>(rand: R) -> Result<RootSeed, Error>
+    pub fn from_default_dir_or_generate<R, P>(rand: R, passphrase: P) -> Result<RootSeed, Error>
     where
         R: Rng,
+        P: FnOnce() -> anyhow::Result<String>,
     {
         let path = default_seed_path()?;
-        RootSeed::from_dir_or_generate(&path, rand)
+        RootSeed::from_dir_or_generate(&path, rand, passphrase)
     }
 
     /// Read the seed from the directory if it exists, otherwise
     /// generate a random seed and write it to that location.
-    pub fn from_dir_or_generate<D, R>(data_dir: D, rand: R) -> Result<RootSeed, Error>
+    ///
+    /// Both plaintext and encrypted seed files are supported, `passphrase` is
+    /// only called if the seed file is encrypted.
+    pub fn from_dir_or_generate<D, R, P>(
+        data_dir: D,
+        rand: R,
+        passphrase: P,
+    ) -> Result<RootSeed, Error>
     where
         D: AsRef<OsStr>,
         R: Rng,
+        P: FnOnce() -> anyhow::Result<String>,
     {
         let dir = Path::new(&data_dir);
         let path = seed_path_from_dir(dir);
 
         if path.exists() {
-            return Self::from_file(&path);
+            return Self::from_file(&path, passphrase);
         }
 
         let random_seed = RootSeed::new_random(rand)?;
@@ -155,9 +170,147 @@ impl RootSeed {
         Ok(random_seed)
     }
 
-    fn from_file<D>(seed_file: D) -> Result<RootSeed, Error>
+    /// Read the seed from the directory, fails if there is no seed file.
+    pub fn from_dir<D, P>(data_dir: D, passphrase: P) -> Result<RootSeed, Error>
+    where
+        D: AsRef<OsStr>,
+        P: FnOnce() -> anyhow::Result<String>,
+    {
+        let dir = Path::new(&data_dir);
+        let path = seed_path_from_dir(dir);
+
+        if !path.exists() {
+            return Err(Error::NotFound(path));
+        }
+
+        Self::from_file(&path, passphrase)
+    }
+
+    /// Write the seed to the directory, refuses to replace a different seed
+    /// that is already stored there.
+    ///
+    /// The seed file is encrypted if a passphrase is given, the passphrase is
+    /// also used to read an encrypted seed file that is already stored there.
+    pub fn write_to_dir<D, R>(
+        &self,
+        data_dir: D,
+        passphrase: Option<&str>,
+        rand: R,
+    ) -> Result<(), Error>
+    where
+        D: AsRef<OsStr>,
+        R: Rng,
+    {
+        let dir = Path::new(&data_dir);
+        let path = seed_path_from_dir(dir);
+
+        if path.exists() {
+            let existing = Self::from_file(&path, || {
+                passphrase
+                    .map(str::to_owned)
+                    .ok_or_else(|| anyhow::anyhow!("the seed file is encrypted"))
+            })?;
+
+            if existing != *self {
+                return Err(Error::AlreadyExists(path));
+            }
+
+            return Ok(());
+        }
+
+        match passphrase {
+            Some(passphrase) => {
+                ensure_directory_exists(path.clone())?;
+                write_pem(&path, &self.encrypted_pem(passphrase, rand)?)?;
+            }
+            None => self.write_to(path.clone())?,
+        }
+
+        tracing::info!("Wrote seed to: {}", path.display());
+
+        Ok(())
+    }
+
+    /// Restore the seed from a BIP39 mnemonic, the checksum encoded in the
+    /// last word is verified.
+    pub fn from_mnemonic(phrase: &str) -> Result<RootSeed, Error> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English).map_err(Error::Mnemonic)?;
+        let entropy = mnemonic.entropy();
+
+        if entropy.len() != SEED_LENGTH {
+            return Err(Error::IncorrectLength(entropy.len()));
+        }
+
+        let mut array = [0; SEED_LENGTH];
+        array.copy_from_slice(entropy);
+
+        Ok(RootSeed::from(array))
+    }
+
+    /// Encode the seed as a 24 word BIP39 mnemonic.
+    pub fn to_mnemonic(&self) -> String {
+        Mnemonic::from_entropy(&(self.0).0, Language::English)
+            .expect("32 bytes are valid entropy for a 24 word mnemonic")
+            .into_phrase()
+    }
+
+    /// A value that identifies the seed without revealing it.
+    pub fn fingerprint(&self) -> String {
+        hex::encode(self.sha256_with_seed(&[b"FINGERPRINT"]))
+    }
+
+    /// Encrypt the plaintext seed file in the directory with the given
+    /// passphrase.
+    ///
+    /// The encrypted file is written next to the plaintext one and then moved
+    /// over it, so the seed is never lost if this is interrupted.
+    pub fn encrypt_file_in_dir<D, R>(data_dir: D, passphrase: &str, rand: R) -> Result<(), Error>
     where
         D: AsRef<OsStr>,
+        R: Rng,
+    {
+        let dir = Path::new(&data_dir);
+        let path = seed_path_from_dir(dir);
+
+        if !path.exists() {
+            return Err(Error::NotFound(path));
+        }
+
+        let pem = pem::parse(fs::read_to_string(&path)?)?;
+        if pem.tag == ENCRYPTED_TAG {
+            return Err(Error::AlreadyEncrypted(path));
+        }
+        let seed = RootSeed::from_pem(pem)?;
+        let encrypted = seed.encrypted_pem(passphrase, rand)?;
+
+        let tmp_path = path.with_extension("pem.tmp");
+        write_pem(&tmp_path, &encrypted)?;
+        fs::rename(&tmp_path, &path)?;
+
+        tracing::info!("Encrypted seed file: {}", path.display());
+
+        Ok(())
+    }
+
+    fn encrypted_pem<R>(&self, passphrase: &str, rand: R) -> Result<Pem, Error>
+    where
+        R: Rng,
+    {
+        Ok(Pem {
+            tag: String::from(ENCRYPTED_TAG),
+            contents: encryption::encrypt(
+                &(self.0).0,
+                passphrase,
+                encryption::DEFAULT_PARAMS,
+                rand,
+            )?,
+        })
+    }
+
+    fn from_file<D, P>(seed_file: D, passphrase: P) -> Result<RootSeed, Error>
+    where
+        D: AsRef<OsStr>,
+        P: FnOnce() -> anyhow::Result<String>,
     {
         let file = Path::new(&seed_file);
         let contents = fs::read_to_string(file)?;
@@ -165,7 +318,21 @@ impl RootSeed {
 
         tracing::info!("Read in seed from file: {}", file.display());
 
-        RootSeed::from_pem(pem)
+        match pem.tag.as_str() {
+            PLAINTEXT_TAG => {
+                tracing::warn!(
+                    "Seed file is not encrypted, run `cnd seed encrypt` to protect it with a passphrase"
+                );
+                RootSeed::from_pem(pem)
+            }
+            ENCRYPTED_TAG => {
+                let passphrase = passphrase().map_err(Error::Passphrase)?;
+                let seed = encryption::decrypt(&pem.contents, &passphrase)?;
+
+                Ok(RootSeed::from(seed))
+            }
+            tag => Err(Error::UnknownFormat(tag.to_owned())),
+        }
     }
 
     fn from_pem(pem: pem::Pem) -> Result<RootSeed, Error> {
@@ -190,17 +357,20 @@ impl RootSeed {
     fn _write_to(&self, path: PathBuf) -> Result<(), Error> {
         let data = (self.0).0;
         let pem = Pem {
-            tag: String::from("SEED"),
+            tag: String::from(PLAINTEXT_TAG),
             contents: data.to_vec(),
         };
 
-        let pem_string = encode(&pem);
+        write_pem(&path, &pem)
+    }
+}
 
-        let mut file = File::create(path)?;
-        file.write_all(pem_string.as_bytes())?;
+fn write_pem(path: &Path, pem: &Pem) -> Result<(), Error> {
+    let mut file = File::create(path)?;
+    file.write_all(encode(pem).as_bytes())?;
+    file.sync_all()?;
 
-        Ok(())
-    }
+    Ok(())
 }
 
 impl SwapSeed {
@@ -244,6 +414,20 @@ pub enum Error {
     Rand(#[from] rand::Error),
     #[error("no default path")]
     NoDefaultPath,
+    #[error("no seed file found at {0}")]
+    NotFound(PathBuf),
+    #[error("a different seed is already stored at {0}, move it away first")]
+    AlreadyExists(PathBuf),
+    #[error("invalid mnemonic: {0}")]
+    Mnemonic(anyhow::Error),
+    #[error("unknown seed file format {0}")]
+    UnknownFormat(String),
+    #[error("seed file {0} is already encrypted")]
+    AlreadyEncrypted(PathBuf),
+    #[error("could not read passphrase: {0}")]
+    Passphrase(anyhow::Error),
+    #[error("seed encryption: ")]
+    Encryption(#[from] encryption::Error),
 }
 
 impl From<[u8; SEED_LENGTH]> for RootSeed {
@@ -258,6 +442,10 @@ mod tests {
     use pem;
     use rand::rngs::OsRng;
 
+    fn no_passphrase() -> anyhow::Result<String> {
+        panic!("seed file should not be encrypted")
+    }
+
     #[test]
     fn seed_byte_string_must_be_32_bytes_long() {
         let _seed = RootSeed::from(*b"this string is exactly 32 bytes!");
@@ -360,7 +548,119 @@ mbKANv2qKGmNVg1qtquj6Hx1pFPelpqOfE2JaJJAMEg1FlFhNRNlFlE=
         seed._write_to(path.clone())
             .expect("Write seed to temp file");
 
-        let rinsed = RootSeed::from_file(path).expect("Read from temp file");
+        let rinsed = RootSeed::from_file(path, no_passphrase).expect("Read from temp file");
         assert_eq!(seed.0, rinsed.0);
     }
+
+    #[test]
+    fn round_trip_through_mnemonic() {
+        let seed = RootSeed::new_random(OsRng).unwrap();
+
+        let mnemonic = seed.to_mnemonic();
+        let restored = RootSeed::from_mnemonic(&mnemonic).unwrap();
+
+        assert_eq!(mnemonic.split_whitespace().count(), 24);
+        assert_eq!(seed, restored);
+    }
+
+    #[test]
+    fn mnemonic_matches_bip39_test_vector() {
+        let seed = RootSeed::from([0x7f; SEED_LENGTH]);
+
+        assert_eq!(
+            seed.to_mnemonic(),
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title"
+        );
+    }
+
+    #[test]
+    fn mnemonic_with_wrong_checksum_is_rejected() {
+        let mnemonic = "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful";
+
+        match RootSeed::from_mnemonic(mnemonic) {
+            Err(Error::Mnemonic(_)) => {} // pass
+            _ => panic!("should fail with Mnemonic error"),
+        }
+    }
+
+    #[test]
+    fn mnemonic_with_less_than_32_bytes_of_entropy_is_rejected() {
+        let mnemonic =
+            "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+        match RootSeed::from_mnemonic(mnemonic) {
+            Err(Error::IncorrectLength(16)) => {} // pass
+            _ => panic!("should fail with IncorrectLength error"),
+        }
+    }
+
+    #[test]
+    fn write_to_dir_refuses_to_replace_a_different_seed() {
+        let dir = tempfile::tempdir().unwrap();
+        let seed = RootSeed::new_random(OsRng).unwrap();
+        let other = RootSeed::new_random(OsRng).unwrap();
+
+        seed.write_to_dir(dir.path(), None, OsRng).unwrap();
+
+        assert!(seed.write_to_dir(dir.path(), None, OsRng).is_ok());
+        match other.write_to_dir(dir.path(), None, OsRng) {
+            Err(Error::AlreadyExists(_)) => {} // pass
+            _ => panic!("should fail with AlreadyExists error"),
+        }
+        assert_eq!(RootSeed::from_dir(dir.path(), no_passphrase).unwrap(), seed);
+    }
+
+    #[test]
+    fn write_to_dir_encrypts_if_a_passphrase_is_given() {
+        let dir = tempfile::tempdir().unwrap();
+        let seed = RootSeed::new_random(OsRng).unwrap();
+
+        seed.write_to_dir(dir.path(), Some("correct horse"), OsRng)
+            .unwrap();
+
+        let contents = fs::read_to_string(seed_path_from_dir(dir.path())).unwrap();
+        assert_eq!(pem::parse(contents).unwrap().tag, ENCRYPTED_TAG);
+        assert!(seed
+            .write_to_dir(dir.path(), Some("correct horse"), OsRng)
+            .is_ok());
+        assert_eq!(
+            RootSeed::from_dir(dir.path(), || Ok("correct horse".to_owned())).unwrap(),
+            seed
+        );
+    }
+
+    #[test]
+    fn encrypted_seed_file_is_detected_and_decrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let seed = RootSeed::from_dir_or_generate(dir.path(), OsRng, no_passphrase).unwrap();
+
+        RootSeed::encrypt_file_in_dir(dir.path(), "correct horse", OsRng).unwrap();
+
+        let contents = fs::read_to_string(seed_path_from_dir(dir.path())).unwrap();
+        assert_eq!(pem::parse(contents).unwrap().tag, ENCRYPTED_TAG);
+
+        let decrypted =
+            RootSeed::from_dir_or_generate(dir.path(), OsRng, || Ok("correct horse".to_owned()))
+                .unwrap();
+        assert_eq!(decrypted, seed);
+
+        let wrong_passphrase = RootSeed::from_dir(dir.path(), || Ok("battery staple".to_owned()));
+        match wrong_passphrase {
+            Err(Error::Encryption(_)) => {} // pass
+            _ => panic!("should fail with Encryption error"),
+        }
+    }
+
+    #[test]
+    fn encrypting_twice_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        RootSeed::from_dir_or_generate(dir.path(), OsRng, no_passphrase).unwrap();
+
+        RootSeed::encrypt_file_in_dir(dir.path(), "correct horse", OsRng).unwrap();
+
+        match RootSeed::encrypt_file_in_dir(dir.path(), "correct horse", OsRng) {
+            Err(Error::AlreadyEncrypted(_)) => {} // pass
+            _ => panic!("should fail with AlreadyEncrypted error"),
+        }
+    }
 }
@@ -0,0 +1,190 @@
+//! Passphrase based encryption of the seed file.
+//!
+//! The key is derived from the passphrase with scrypt and the seed is
+//! encrypted with ChaCha20-Poly1305. The scrypt parameters, the salt and the
+//! nonce are stored in front of the ciphertext so that the parameters can be
+//! changed without breaking existing files:
+//!
+//! `version (1) | log_n (1) | r (4) | p (4) | salt (16) | nonce (12) |
+//! ciphertext (32 + 16)`
+
+use crate::seed::SEED_LENGTH;
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::Rng;
+use scrypt::{scrypt, ScryptParams};
+use std::convert::TryInto;
+
+const VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+const HEADER_LENGTH: usize = 1 + 1 + 4 + 4 + SALT_LENGTH + NONCE_LENGTH;
+const ENCRYPTED_LENGTH: usize = HEADER_LENGTH + SEED_LENGTH + TAG_LENGTH;
+
+/// Parameters recommended for interactive logins, derivation takes around
+/// 100ms and 32MiB of memory.
+#[cfg(not(test))]
+pub const DEFAULT_PARAMS: Params = Params {
+    log_n: 15,
+    r: 8,
+    p: 1,
+};
+
+// Keep the tests fast, the parameters are stored in the file anyway.
+#[cfg(test)]
+pub const DEFAULT_PARAMS: Params = Params {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+/// Upper bound for the parameters read from a seed file. Deriving the key
+/// with these takes around 1GiB of memory, larger values would let a crafted
+/// seed file exhaust the memory of the node.
+const MAX_PARAMS: Params = Params {
+    log_n: 20,
+    r: 8,
+    p: 16,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Params {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum Error {
+    #[error("encrypted seed has unsupported version {0}")]
+    UnsupportedVersion(u8),
+    #[error("encrypted seed has unexpected length of {0} bytes")]
+    IncorrectLength(usize),
+    #[error("invalid scrypt parameters")]
+    InvalidParams,
+    #[error("wrong passphrase or corrupted seed file")]
+    Decryption,
+}
+
+pub fn encrypt<R>(
+    seed: &[u8; SEED_LENGTH],
+    passphrase: &str,
+    params: Params,
+    mut rand: R,
+) -> Result<Vec<u8>, Error>
+where
+    R: Rng,
+{
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand.fill(&mut salt);
+    rand.fill(&mut nonce);
+
+    let cipher = cipher(passphrase, &salt, params)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), &seed[..])
+        .map_err(|_| Error::Decryption)?;
+
+    let mut contents = Vec::with_capacity(ENCRYPTED_LENGTH);
+    contents.push(VERSION);
+    contents.push(params.log_n);
+    contents.extend_from_slice(&params.r.to_be_bytes());
+    contents.extend_from_slice(&params.p.to_be_bytes());
+    contents.extend_from_slice(&salt);
+    contents.extend_from_slice(&nonce);
+    contents.extend_from_slice(&ciphertext);
+
+    Ok(contents)
+}
+
+pub fn decrypt(contents: &[u8], passphrase: &str) -> Result<[u8; SEED_LENGTH], Error> {
+    if contents.len() != ENCRYPTED_LENGTH {
+        return Err(Error::IncorrectLength(contents.len()));
+    }
+    if contents[0] != VERSION {
+        return Err(Error::UnsupportedVersion(contents[0]));
+    }
+
+    let params = Params {
+        log_n: contents[1],
+        r: u32::from_be_bytes(contents[2..6].try_into().expect("4 bytes")),
+        p: u32::from_be_bytes(contents[6..10].try_into().expect("4 bytes")),
+    };
+    if params.log_n > MAX_PARAMS.log_n || params.r > MAX_PARAMS.r || params.p > MAX_PARAMS.p {
+        return Err(Error::InvalidParams);
+    }
+    let salt = &contents[10..10 + SALT_LENGTH];
+    let nonce = &contents[10 + SALT_LENGTH..HEADER_LENGTH];
+    let ciphertext = &contents[HEADER_LENGTH..];
+
+    let cipher = cipher(passphrase, salt, params)?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::Decryption)?;
+
+    let mut seed = [0u8; SEED_LENGTH];
+    seed.copy_from_slice(&plaintext);
+
+    Ok(seed)
+}
+
+fn cipher(passphrase: &str, salt: &[u8], params: Params) -> Result<ChaCha20Poly1305, Error> {
+    let params =
+        ScryptParams::new(params.log_n, params.r, params.p).map_err(|_| Error::InvalidParams)?;
+
+    let mut key = [0u8; 32];
+    scrypt(passphrase.as_bytes(), salt, &params, &mut key).map_err(|_| Error::InvalidParams)?;
+
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use spectral::prelude::*;
+
+    #[test]
+    fn round_trip_through_encryption() {
+        let seed = [7u8; SEED_LENGTH];
+
+        let encrypted = encrypt(&seed, "correct horse", DEFAULT_PARAMS, OsRng).unwrap();
+        let decrypted = decrypt(&encrypted, "correct horse");
+
+        assert_that(&encrypted.len()).is_equal_to(ENCRYPTED_LENGTH);
+        assert_that(&decrypted).is_ok().is_equal_to(seed);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_to_decrypt() {
+        let seed = [7u8; SEED_LENGTH];
+
+        let encrypted = encrypt(&seed, "correct horse", DEFAULT_PARAMS, OsRng).unwrap();
+        let decrypted = decrypt(&encrypted, "battery staple");
+
+        assert_that(&decrypted).is_err();
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails_to_decrypt() {
+        let seed = [7u8; SEED_LENGTH];
+
+        let mut encrypted = encrypt(&seed, "correct horse", DEFAULT_PARAMS, OsRng).unwrap();
+        encrypted[HEADER_LENGTH] ^= 1;
+
+        assert_that(&decrypt(&encrypted, "correct horse")).is_err();
+    }
+
+    #[test]
+    fn excessive_scrypt_parameters_are_rejected() {
+        let seed = [7u8; SEED_LENGTH];
+
+        let mut encrypted = encrypt(&seed, "correct horse", DEFAULT_PARAMS, OsRng).unwrap();
+        encrypted[1] = 64;
+
+        assert_that(&decrypt(&encrypted, "correct horse")).is_err();
+    }
+}
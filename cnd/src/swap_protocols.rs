@@ -35,6 +35,23 @@ pub enum HashFunction {
     #[strum(serialize = "SHA-256")]
     #[serde(rename = "SHA-256")]
     Sha256,
+    #[strum(serialize = "SHA3-256")]
+    #[serde(rename = "SHA3-256")]
+    Sha3_256,
+    /// RIPEMD-160 of SHA-256, `OP_HASH160` in Bitcoin script.
+    #[strum(serialize = "RIPEMD160-SHA256")]
+    #[serde(rename = "RIPEMD160-SHA256")]
+    Ripemd160Sha256,
+}
+
+impl HashFunction {
+    /// The length in bytes of the hashes this function produces.
+    pub fn digest_length(self) -> usize {
+        match self {
+            HashFunction::Sha256 | HashFunction::Sha3_256 => 32,
+            HashFunction::Ripemd160Sha256 => 20,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -48,6 +65,15 @@ pub enum Role {
     Bob,
 }
 
+/// The two ledgers of a swap: Alice funds the HTLC on alpha, Bob the one on
+/// beta.
+#[derive(Clone, Copy, Debug, Display, EnumString, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerSide {
+    Alpha,
+    Beta,
+}
+
 /// These are the traits that represent the steps involved in a COMIT atomic
 /// swap.  Different protocols have different requirements/functionality for
 /// each trait method but the abstractions are the same for all protocols.
@@ -25,20 +25,54 @@ pub mod bitcoin {
     #[derive(Debug, Clone, PartialEq)]
     pub struct SpendOutput {
         // Remember: One man's input is another man's output!
-        pub output: PrimedInput,
+        pub outputs: Vec<PrimedInput>,
         pub network: bitcoin::Network,
     }
 
     impl SpendOutput {
         pub fn spend_to(self, to_address: Address) -> PrimedTransaction {
             PrimedTransaction {
-                inputs: vec![self.output],
+                inputs: self.outputs,
                 output_address: to_address,
             }
         }
     }
 }
 
+pub mod litecoin {
+    use crate::{asset, swap_protocols::ledger::utxo_chain::ChainParams};
+    use bitcoin::Address;
+    use blockchain_contracts::bitcoin::witness::{PrimedInput, PrimedTransaction};
+
+    /// `bitcoin::Address` only encodes Bitcoin addresses, hence the address
+    /// is given the way Litecoin wallets show it.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SendToAddress {
+        pub to: String,
+        pub amount: asset::Bitcoin,
+        pub network: bitcoin::Network,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SpendOutput {
+        pub outputs: Vec<PrimedInput>,
+        pub params: ChainParams,
+        pub network: bitcoin::Network,
+    }
+
+    impl SpendOutput {
+        /// `None` if `to_address` is not an address of the chain.
+        pub fn spend_to(self, to_address: &str) -> Option<PrimedTransaction> {
+            let script_pubkey = self.params.script_pubkey(to_address)?;
+
+            Some(PrimedTransaction {
+                inputs: self.outputs,
+                output_address: Address::from_script(&script_pubkey, self.network)?,
+            })
+        }
+    }
+}
+
 pub mod ethereum {
     use crate::{
         asset, ethereum::Bytes, identity, swap_protocols::ledger::ethereum::ChainId,
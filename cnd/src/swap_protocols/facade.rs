@@ -4,27 +4,35 @@ use crate::{
         self,
         bitcoin::BitcoindConnector,
         ethereum::{self, Web3Connector},
+        ConsensusTime,
+    },
+    config,
+    db::{
+        AcceptedSwap, Database, DetermineTypes, LoadAcceptedSwap, LoadLedgerEvents, Retrieve, Save,
+        Swap, SwapTypes,
     },
-    db::{AcceptedSwap, DetermineTypes, LoadAcceptedSwap, Retrieve, Save, Sqlite, Swap, SwapTypes},
     htlc_location, identity,
     network::{
-        ComitPeers, DialInformation, ListenAddresses, LocalPeerId, PendingRequestFor, RequestError,
-        SendRequest, Swarm,
+        ComitPeers, DialInformation, ListenAddresses, LocalPeerId, Orders, PeerCapabilities,
+        PendingRequestFor, RequestError, SendRequest, Swarm,
     },
     seed::{DeriveSwapSeed, RootSeed, SwapSeed},
     swap_protocols::{
-        ledger::{bitcoin, Ethereum},
+        ledger::{bitcoin, ethereum::ChainId, litecoin, Ethereum},
         rfc003::{
             self,
-            create_swap::HtlcParams,
+            create_swap::{HtlcParams, SwapEvent},
             events::{
                 Deployed, Funded, HtlcDeployed, HtlcFunded, HtlcRedeemed, HtlcRefunded, Redeemed,
                 Refunded,
             },
-            SwapCommunication,
+            watchdog::SwapWarnings,
+            LedgerTime, SwapCommunication,
         },
-        state, InsertFailedSwap, LedgerStates, SwapCommunicationStates, SwapErrorStates, SwapId,
+        state, InsertFailedSwap, LedgerSide, LedgerStates, SwapCommunicationStates,
+        SwapErrorStates, SwapId,
     },
+    timestamp::Timestamp,
     transaction,
 };
 use async_trait::async_trait;
@@ -34,7 +42,7 @@ use impl_template::impl_template;
 use libp2p::{Multiaddr, PeerId};
 use libp2p_comit::frame::{OutboundRequest, Response};
 use serde::de::DeserializeOwned;
-use std::{convert::TryInto, fmt::Debug, sync::Arc};
+use std::{collections::HashMap, convert::TryInto, fmt::Debug, sync::Arc};
 
 /// This is a facade that implements all the required traits and forwards them
 /// to another implementation. This allows us to keep the number of arguments to
@@ -45,20 +53,62 @@ use std::{convert::TryInto, fmt::Debug, sync::Arc};
 #[delegate(ComitPeers, target = "swarm")]
 #[delegate(ListenAddresses, target = "swarm")]
 #[delegate(PendingRequestFor, target = "swarm")]
+#[delegate(Orders, target = "swarm")]
+#[delegate(PeerCapabilities, target = "swarm")]
 #[delegate(Retrieve, target = "db")]
 #[delegate(DetermineTypes, target = "db")]
 pub struct Facade {
     pub bitcoin_connector: Arc<btsieve::bitcoin::Cache<BitcoindConnector>>,
-    pub ethereum_connector: Arc<ethereum::Cache<Web3Connector>>,
+    pub litecoin_connector: Option<Arc<btsieve::bitcoin::Cache<BitcoindConnector>>>,
+    pub ethereum_connectors: Arc<HashMap<ChainId, Arc<ethereum::Cache<Web3Connector>>>>,
     pub alpha_ledger_state: Arc<LedgerStates>,
     pub beta_ledger_state: Arc<LedgerStates>,
     pub swap_communication_states: Arc<SwapCommunicationStates>,
     pub swap_error_states: Arc<SwapErrorStates>,
+    pub swap_warnings: Arc<SwapWarnings>,
+    pub funding: config::Funding,
+    pub expiries: config::Expiries,
+    pub watchdog: config::Watchdog,
     pub seed: RootSeed,
     pub swarm: Swarm,
-    pub db: Sqlite,
+    pub db: Database,
+}
+
+impl Facade {
+    /// The connector to the node of the given Ethereum chain, each leg of a
+    /// swap is watched on its own chain.
+    fn ethereum_connector(
+        &self,
+        chain_id: ChainId,
+    ) -> anyhow::Result<&ethereum::Cache<Web3Connector>> {
+        self.ethereum_connectors
+            .get(&chain_id)
+            .map(|connector| connector.as_ref())
+            .ok_or_else(|| anyhow::Error::from(NoEthereumConnector { chain_id }))
+    }
+
+    fn litecoin_connector(&self) -> anyhow::Result<&btsieve::bitcoin::Cache<BitcoindConnector>> {
+        self.litecoin_connector
+            .as_ref()
+            .map(|connector| connector.as_ref())
+            .ok_or_else(|| anyhow::Error::from(NoLitecoinConnector))
+    }
+}
+
+/// An error type for describing that a swap uses an Ethereum chain we are not
+/// connected to.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("no ethereum node is configured for chain id {chain_id:?}")]
+pub struct NoEthereumConnector {
+    chain_id: ChainId,
 }
 
+/// An error type for describing that a swap uses Litecoin but the config file
+/// has no `[litecoin]` section.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("no litecoin node is configured")]
+pub struct NoLitecoinConnector;
+
 #[async_trait]
 impl<AL, BL, AA, BA, AI, BI> state::Insert<SwapCommunication<AL, BL, AA, BA, AI, BI>> for Facade
 where
@@ -109,7 +159,7 @@ impl SendRequest for Facade {
 #[async_trait]
 impl<AL, BL, AA, BA, AI, BI> LoadAcceptedSwap<AL, BL, AA, BA, AI, BI> for Facade
 where
-    Sqlite: LoadAcceptedSwap<AL, BL, AA, BA, AI, BI>,
+    Database: LoadAcceptedSwap<AL, BL, AA, BA, AI, BI>,
     AcceptedSwap<AL, BL, AA, BA, AI, BI>: Send + 'static,
 {
     async fn load_accepted_swap(
@@ -120,11 +170,25 @@ where
     }
 }
 
+#[async_trait]
+impl<A, H, T> LoadLedgerEvents<A, H, T> for Facade
+where
+    Database: LoadLedgerEvents<A, H, T>,
+{
+    async fn load_ledger_events(
+        &self,
+        swap_id: &SwapId,
+        side: LedgerSide,
+    ) -> anyhow::Result<Vec<SwapEvent<A, H, T>>> {
+        self.db.load_ledger_events(swap_id, side).await
+    }
+}
+
 #[async_trait]
 impl<T> Save<T> for Facade
 where
     T: Send + 'static,
-    Sqlite: Save<T>,
+    Database: Save<T>,
 {
     async fn save(&self, data: T) -> anyhow::Result<()> {
         self.db.save(data).await
@@ -148,9 +212,12 @@ impl
         htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Funded<asset::Bitcoin, transaction::Bitcoin>> {
-        self.bitcoin_connector
+        let funded = self
+            .bitcoin_connector
             .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
-            .await
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
     }
 }
 
@@ -222,6 +289,100 @@ impl
     }
 }
 
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcFunded<
+        ((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest)),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Facade
+{
+    async fn htlc_funded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Funded<asset::Bitcoin, transaction::Bitcoin>> {
+        let funded = self
+            .litecoin_connector()?
+            .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcDeployed<
+        ((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest)),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Facade
+{
+    async fn htlc_deployed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Deployed<htlc_location::Bitcoin, transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_deployed(htlc_params, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRedeemed<
+        ((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest)),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Facade
+{
+    async fn htlc_redeemed(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Redeemed<transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_redeemed(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl
+    HtlcRefunded<
+        ((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest)),
+        asset::Bitcoin,
+        htlc_location::Bitcoin,
+        identity::Bitcoin,
+        transaction::Bitcoin,
+    > for Facade
+{
+    async fn htlc_refunded(
+        &self,
+        htlc_params: &HtlcParams<__TYPE0__, asset::Bitcoin, identity::Bitcoin>,
+        htlc_deployment: &Deployed<htlc_location::Bitcoin, transaction::Bitcoin>,
+        start_of_swap: NaiveDateTime,
+    ) -> anyhow::Result<Refunded<transaction::Bitcoin>> {
+        self.litecoin_connector()?
+            .htlc_refunded(htlc_params, htlc_deployment, start_of_swap)
+            .await
+    }
+}
+
 #[impl_template]
 #[async_trait::async_trait]
 impl
@@ -239,9 +400,12 @@ impl
         htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Funded<__TYPE0__, transaction::Ethereum>> {
-        self.ethereum_connector
+        let funded = self
+            .ethereum_connector(htlc_params.ledger.chain_id)?
             .htlc_funded(htlc_params, htlc_deployment, start_of_swap)
-            .await
+            .await?;
+
+        Ok(funded.tolerate_overfunding(&htlc_params.asset, self.funding.accept_overfunding))
     }
 }
 
@@ -261,7 +425,7 @@ impl
         htlc_params: &HtlcParams<Ethereum, __TYPE0__, identity::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Deployed<htlc_location::Ethereum, transaction::Ethereum>> {
-        self.ethereum_connector
+        self.ethereum_connector(htlc_params.ledger.chain_id)?
             .htlc_deployed(htlc_params, start_of_swap)
             .await
     }
@@ -284,7 +448,7 @@ impl
         htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Redeemed<transaction::Ethereum>> {
-        self.ethereum_connector
+        self.ethereum_connector(htlc_params.ledger.chain_id)?
             .htlc_redeemed(htlc_params, htlc_deployment, start_of_swap)
             .await
     }
@@ -307,8 +471,33 @@ impl
         htlc_deployment: &Deployed<htlc_location::Ethereum, transaction::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Refunded<transaction::Ethereum>> {
-        self.ethereum_connector
+        self.ethereum_connector(htlc_params.ledger.chain_id)?
             .htlc_refunded(htlc_params, htlc_deployment, start_of_swap)
             .await
     }
 }
+
+#[impl_template]
+#[async_trait::async_trait]
+impl LedgerTime<((bitcoin::Mainnet, bitcoin::Testnet, bitcoin::Regtest))> for Facade {
+    async fn ledger_time(&self, _: &__TYPE0__) -> anyhow::Result<Timestamp> {
+        self.bitcoin_connector.consensus_time().await
+    }
+}
+
+#[impl_template]
+#[async_trait::async_trait]
+impl LedgerTime<((litecoin::Mainnet, litecoin::Testnet, litecoin::Regtest))> for Facade {
+    async fn ledger_time(&self, _: &__TYPE0__) -> anyhow::Result<Timestamp> {
+        self.litecoin_connector()?.consensus_time().await
+    }
+}
+
+#[async_trait::async_trait]
+impl LedgerTime<Ethereum> for Facade {
+    async fn ledger_time(&self, ledger: &Ethereum) -> anyhow::Result<Timestamp> {
+        self.ethereum_connector(ledger.chain_id)?
+            .consensus_time()
+            .await
+    }
+}
@@ -30,6 +30,81 @@ pub struct CreateSwapParams {
     pub lightning_amount: asset::Lightning,
 }
 
+/// This represent the information available on a hbit-halight swap before
+/// communication with the other node has started. The Bitcoin identity is
+/// derived from the swap seed, hence it is not part of the parameters.
+#[derive(Clone, Digest, Debug)]
+#[digest(hash = "SwapDigest")]
+pub struct CreateHbitSwapParams {
+    #[digest(prefix = "")]
+    pub role: Role,
+    #[digest(prefix = "")]
+    pub peer: DialInformation,
+    #[digest(prefix = "1001")]
+    pub bitcoin_absolute_expiry: Timestamp,
+    #[digest(prefix = "1002")]
+    pub bitcoin_amount: asset::Bitcoin,
+    #[digest(prefix = "")]
+    pub lightning_identity: identity::Lightning,
+    #[digest(prefix = "3001")]
+    pub lightning_cltv_expiry: Timestamp,
+    #[digest(prefix = "3002")]
+    pub lightning_amount: asset::Lightning,
+}
+
+/// The parameters of all swaps that can be negotiated with `ComitLN`, they all
+/// use halight on the beta ledger.
+#[derive(Clone, Debug)]
+pub enum SwapParams {
+    HanHalight(CreateSwapParams),
+    HbitHalight(CreateHbitSwapParams),
+}
+
+impl SwapParams {
+    pub fn role(&self) -> Role {
+        match self {
+            SwapParams::HanHalight(params) => params.role,
+            SwapParams::HbitHalight(params) => params.role,
+        }
+    }
+
+    pub fn peer(&self) -> DialInformation {
+        match self {
+            SwapParams::HanHalight(params) => params.peer.clone(),
+            SwapParams::HbitHalight(params) => params.peer.clone(),
+        }
+    }
+
+    pub fn lightning_identity(&self) -> identity::Lightning {
+        match self {
+            SwapParams::HanHalight(params) => params.lightning_identity,
+            SwapParams::HbitHalight(params) => params.lightning_identity,
+        }
+    }
+
+    /// The digest of a swap only depends on the parameters of the swap
+    /// protocol, the prefixes of the alpha ledger fields make sure that
+    /// different protocols never have the same digest.
+    pub fn digest(&self) -> SwapDigest {
+        match self.clone() {
+            SwapParams::HanHalight(params) => params.digest(),
+            SwapParams::HbitHalight(params) => params.digest(),
+        }
+    }
+}
+
+impl From<CreateSwapParams> for SwapParams {
+    fn from(params: CreateSwapParams) -> Self {
+        SwapParams::HanHalight(params)
+    }
+}
+
+impl From<CreateHbitSwapParams> for SwapParams {
+    fn from(params: CreateHbitSwapParams) -> Self {
+        SwapParams::HbitHalight(params)
+    }
+}
+
 impl IntoDigestInput for asset::Lightning {
     fn into_digest_input(self) -> Vec<u8> {
         self.to_le_bytes().to_vec()
@@ -42,6 +117,12 @@ impl IntoDigestInput for identity::Lightning {
     }
 }
 
+impl IntoDigestInput for asset::Bitcoin {
+    fn into_digest_input(self) -> Vec<u8> {
+        self.as_sat().to_le_bytes().to_vec()
+    }
+}
+
 impl IntoDigestInput for asset::Ether {
     fn into_digest_input(self) -> Vec<u8> {
         self.to_bytes()
@@ -93,20 +174,27 @@ impl IntoDigestInput for Timestamp {
 #[derive(Clone, Debug)]
 pub struct Facade2 {
     pub swarm: Swarm,
-    pub alpha_ledger_state: Arc<LedgerStates>, /* We currently only support Han-HALight, this is
-                                                * Ethereum. */
-    pub beta_ledger_state: Arc<InvoiceStates>, /* We currently only support Han-HALight, this is
-                                                * Lightning. */
+    pub alpha_ledger_state: Arc<LedgerStates>, /* We currently only support Han-HALight and
+                                                * Hbit-HALight, this is Ethereum or Bitcoin. */
+    pub beta_ledger_state: Arc<InvoiceStates>, /* We currently only support Han-HALight and
+                                                * Hbit-HALight, this is Lightning. */
 }
 
 impl Facade2 {
     pub async fn save(&self, _id: NodeLocalSwapId, _swap_params: ()) {}
 
-    pub async fn initiate_communication(&self, id: NodeLocalSwapId, swap_params: CreateSwapParams) {
+    pub async fn initiate_communication(&self, id: NodeLocalSwapId, swap_params: SwapParams) {
         self.swarm.initiate_communication(id, swap_params).await;
     }
 
     pub async fn get_finalized_swap(&self, id: NodeLocalSwapId) -> Option<comit_ln::FinalizedSwap> {
         self.swarm.get_finalized_swap(id).await
     }
+
+    pub async fn get_finalized_hbit_swap(
+        &self,
+        id: NodeLocalSwapId,
+    ) -> Option<comit_ln::FinalizedHbitSwap> {
+        self.swarm.get_finalized_hbit_swap(id).await
+    }
 }
@@ -1,5 +1,7 @@
 pub mod bitcoin;
 pub mod ethereum;
 pub mod lightning;
+pub mod litecoin;
+pub mod utxo_chain;
 
-pub use self::{bitcoin::Bitcoin, ethereum::Ethereum};
+pub use self::{bitcoin::Bitcoin, ethereum::Ethereum, utxo_chain::UtxoChain};
@@ -1,4 +1,7 @@
-use crate::comit_api::LedgerKind;
+use crate::{
+    comit_api::LedgerKind,
+    swap_protocols::ledger::utxo_chain::{ChainParams, UtxoChain},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Mainnet;
@@ -9,10 +12,7 @@ pub struct Testnet;
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Regtest;
 
-pub trait Bitcoin:
-    Sized + std::fmt::Debug + std::hash::Hash + Eq + Sync + Copy + Send + 'static
-{
-}
+pub trait Bitcoin: UtxoChain {}
 
 pub trait Network {
     fn network() -> ::bitcoin::Network;
@@ -30,6 +30,17 @@ impl Network for Mainnet {
         ::bitcoin::Network::Bitcoin
     }
 }
+impl UtxoChain for Mainnet {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "bitcoin",
+            p2pkh_prefix: 0x00,
+            p2sh_prefix: 0x05,
+            bech32_hrp: "bc",
+            default_node_port: 8332,
+        }
+    }
+}
 impl Bitcoin for Testnet {}
 
 impl From<Testnet> for LedgerKind {
@@ -42,6 +53,17 @@ impl Network for Testnet {
         ::bitcoin::Network::Testnet
     }
 }
+impl UtxoChain for Testnet {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "bitcoin",
+            p2pkh_prefix: 0x6f,
+            p2sh_prefix: 0xc4,
+            bech32_hrp: "tb",
+            default_node_port: 18332,
+        }
+    }
+}
 
 impl Bitcoin for Regtest {}
 impl From<Regtest> for LedgerKind {
@@ -54,3 +76,22 @@ impl Network for Regtest {
         ::bitcoin::Network::Regtest
     }
 }
+impl UtxoChain for Regtest {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "bitcoin",
+            p2pkh_prefix: 0x6f,
+            p2sh_prefix: 0xc4,
+            bech32_hrp: "bcrt",
+            default_node_port: 18443,
+        }
+    }
+}
+
+pub fn chain_params(network: ::bitcoin::Network) -> ChainParams {
+    match network {
+        ::bitcoin::Network::Bitcoin => Mainnet::params(),
+        ::bitcoin::Network::Testnet => Testnet::params(),
+        ::bitcoin::Network::Regtest => Regtest::params(),
+    }
+}
@@ -0,0 +1,93 @@
+//! Litecoin follows the script rules of Bitcoin, hence `Network` maps each of
+//! its networks to the Bitcoin network of the same kind.
+
+use crate::{
+    comit_api::LedgerKind,
+    swap_protocols::ledger::{
+        bitcoin::Network,
+        utxo_chain::{ChainParams, UtxoChain},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Mainnet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Testnet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Regtest;
+
+impl From<Mainnet> for LedgerKind {
+    fn from(_: Mainnet) -> Self {
+        LedgerKind::LitecoinMainnet
+    }
+}
+impl Network for Mainnet {
+    fn network() -> ::bitcoin::Network {
+        ::bitcoin::Network::Bitcoin
+    }
+}
+impl UtxoChain for Mainnet {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "litecoin",
+            p2pkh_prefix: 0x30,
+            p2sh_prefix: 0x32,
+            bech32_hrp: "ltc",
+            default_node_port: 9332,
+        }
+    }
+}
+
+impl From<Testnet> for LedgerKind {
+    fn from(_: Testnet) -> Self {
+        LedgerKind::LitecoinTestnet
+    }
+}
+impl Network for Testnet {
+    fn network() -> ::bitcoin::Network {
+        ::bitcoin::Network::Testnet
+    }
+}
+impl UtxoChain for Testnet {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "litecoin",
+            p2pkh_prefix: 0x6f,
+            p2sh_prefix: 0x3a,
+            bech32_hrp: "tltc",
+            default_node_port: 19332,
+        }
+    }
+}
+
+impl From<Regtest> for LedgerKind {
+    fn from(_: Regtest) -> Self {
+        LedgerKind::LitecoinRegtest
+    }
+}
+impl Network for Regtest {
+    fn network() -> ::bitcoin::Network {
+        ::bitcoin::Network::Regtest
+    }
+}
+impl UtxoChain for Regtest {
+    fn params() -> ChainParams {
+        ChainParams {
+            name: "litecoin",
+            p2pkh_prefix: 0x6f,
+            p2sh_prefix: 0x3a,
+            bech32_hrp: "rltc",
+            default_node_port: 19443,
+        }
+    }
+}
+
+pub fn chain_params(network: ::bitcoin::Network) -> ChainParams {
+    match network {
+        ::bitcoin::Network::Bitcoin => Mainnet::params(),
+        ::bitcoin::Network::Testnet => Testnet::params(),
+        ::bitcoin::Network::Regtest => Regtest::params(),
+    }
+}
@@ -0,0 +1,212 @@
+use crate::swap_protocols::ledger::bitcoin::Network;
+use ::bitcoin::{
+    bech32::{self, u5, FromBase32, ToBase32},
+    blockdata::{opcodes, script::Builder},
+    util::base58,
+    Script,
+};
+use reqwest::Url;
+use std::{fmt::Debug, hash::Hash};
+
+/// The parameters in which a chain derived from Bitcoin differs from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainParams {
+    pub name: &'static str,
+    pub p2pkh_prefix: u8,
+    pub p2sh_prefix: u8,
+    pub bech32_hrp: &'static str,
+    /// The port the REST interface of the node listens on by default.
+    pub default_node_port: u16,
+}
+
+impl ChainParams {
+    pub fn default_node_url(&self) -> Url {
+        format!("http://localhost:{}", self.default_node_port)
+            .parse()
+            .expect("to be a valid url")
+    }
+
+    /// Encodes the address of an output on this chain, `None` if the script
+    /// has no address representation.
+    pub fn address(&self, script_pubkey: &Script) -> Option<String> {
+        let bytes = script_pubkey.as_bytes();
+
+        if script_pubkey.is_p2pkh() {
+            Some(base58_check(self.p2pkh_prefix, &bytes[3..23]))
+        } else if script_pubkey.is_p2sh() {
+            Some(base58_check(self.p2sh_prefix, &bytes[2..22]))
+        } else if script_pubkey.is_v0_p2wpkh() || script_pubkey.is_v0_p2wsh() {
+            let mut data = vec![u5::try_from_u8(0).expect("0 fits into 5 bits")];
+            data.extend(bytes[2..].to_base32());
+
+            bech32::encode(self.bech32_hrp, data).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Decodes an address of this chain into the script of the output paying
+    /// to it, `None` if it is not an address of this chain.
+    pub fn script_pubkey(&self, address: &str) -> Option<Script> {
+        if let Ok((hrp, data)) = bech32::decode(address) {
+            if hrp != self.bech32_hrp || data.first()?.to_u8() != 0 {
+                return None;
+            }
+            let program = Vec::<u8>::from_base32(&data[1..]).ok()?;
+            if program.len() != 20 && program.len() != 32 {
+                return None;
+            }
+
+            return Some(
+                Builder::new()
+                    .push_int(0)
+                    .push_slice(&program)
+                    .into_script(),
+            );
+        }
+
+        let data = base58::from_check(address).ok()?;
+        if data.len() != 21 {
+            return None;
+        }
+        let (prefix, hash) = (data[0], &data[1..]);
+
+        if prefix == self.p2pkh_prefix {
+            Some(
+                Builder::new()
+                    .push_opcode(opcodes::all::OP_DUP)
+                    .push_opcode(opcodes::all::OP_HASH160)
+                    .push_slice(hash)
+                    .push_opcode(opcodes::all::OP_EQUALVERIFY)
+                    .push_opcode(opcodes::all::OP_CHECKSIG)
+                    .into_script(),
+            )
+        } else if prefix == self.p2sh_prefix {
+            Some(
+                Builder::new()
+                    .push_opcode(opcodes::all::OP_HASH160)
+                    .push_slice(hash)
+                    .push_opcode(opcodes::all::OP_EQUAL)
+                    .into_script(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+fn base58_check(prefix: u8, hash: &[u8]) -> String {
+    let mut data = vec![prefix];
+    data.extend_from_slice(hash);
+
+    base58::check_encode_slice(&data)
+}
+
+/// A ledger that shares the transaction format, the HTLC script and the REST
+/// interface of its node with Bitcoin, e.g. Litecoin.
+///
+/// `Network::network` is the Bitcoin network whose script rules the chain
+/// follows, addresses must be encoded with `params` instead.
+pub trait UtxoChain: Network + Sized + Debug + Hash + Eq + Sync + Copy + Send + 'static {
+    fn params() -> ChainParams;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swap_protocols::ledger::{bitcoin, litecoin};
+    use spectral::prelude::*;
+
+    fn hash() -> Vec<u8> {
+        (1..=20).collect()
+    }
+
+    fn p2pkh() -> Script {
+        Builder::new()
+            .push_opcode(opcodes::all::OP_DUP)
+            .push_opcode(opcodes::all::OP_HASH160)
+            .push_slice(&hash())
+            .push_opcode(opcodes::all::OP_EQUALVERIFY)
+            .push_opcode(opcodes::all::OP_CHECKSIG)
+            .into_script()
+    }
+
+    fn p2sh() -> Script {
+        Builder::new()
+            .push_opcode(opcodes::all::OP_HASH160)
+            .push_slice(&hash())
+            .push_opcode(opcodes::all::OP_EQUAL)
+            .into_script()
+    }
+
+    fn p2wpkh() -> Script {
+        Builder::new().push_int(0).push_slice(&hash()).into_script()
+    }
+
+    #[test]
+    fn encodes_litecoin_addresses() {
+        let params = litecoin::Mainnet::params();
+
+        assert_that(&params.address(&p2pkh()))
+            .is_some()
+            .is_equal_to("LKKHMBjCU89fyFNgSRprDoD8Jb25N8uWvd".to_owned());
+        assert_that(&params.address(&p2sh()))
+            .is_some()
+            .is_equal_to("M7zVKQKmtV5Rc7erVGVVC3khZbXxsS5HEX".to_owned());
+        assert_that(&params.address(&p2wpkh()))
+            .is_some()
+            .is_equal_to("ltc1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5dyg36p".to_owned());
+    }
+
+    #[test]
+    fn encodes_bitcoin_addresses_like_rust_bitcoin() {
+        let public_key = ::bitcoin::PublicKey::from_slice(
+            &hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+        )
+        .unwrap();
+        let p2pkh = ::bitcoin::Address::p2pkh(&public_key, ::bitcoin::Network::Bitcoin);
+        let p2wpkh = ::bitcoin::Address::p2wpkh(&public_key, ::bitcoin::Network::Bitcoin);
+        let p2wsh = ::bitcoin::Address::p2wsh(&p2pkh.script_pubkey(), ::bitcoin::Network::Bitcoin);
+
+        for address in vec![p2pkh, p2wpkh, p2wsh] {
+            assert_that(&bitcoin::Mainnet::params().address(&address.script_pubkey()))
+                .is_some()
+                .is_equal_to(address.to_string());
+        }
+    }
+
+    #[test]
+    fn scripts_without_address_are_not_encoded() {
+        let script = Builder::new()
+            .push_opcode(opcodes::all::OP_RETURN)
+            .into_script();
+
+        assert_that(&litecoin::Mainnet::params().address(&script)).is_none();
+    }
+
+    #[test]
+    fn decodes_litecoin_addresses() {
+        let params = litecoin::Mainnet::params();
+
+        for script in vec![p2pkh(), p2sh(), p2wpkh()] {
+            let address = params.address(&script).unwrap();
+
+            assert_that(&params.script_pubkey(&address))
+                .is_some()
+                .is_equal_to(script);
+        }
+    }
+
+    #[test]
+    fn does_not_decode_addresses_of_other_chains() {
+        let address = bitcoin::Mainnet::params().address(&p2wpkh()).unwrap();
+        assert_that(&litecoin::Mainnet::params().script_pubkey(&address)).is_none();
+
+        let address = bitcoin::Mainnet::params().address(&p2pkh()).unwrap();
+        assert_that(&litecoin::Mainnet::params().script_pubkey(&address)).is_none();
+
+        let address = litecoin::Testnet::params().address(&p2wpkh()).unwrap();
+        assert_that(&litecoin::Regtest::params().script_pubkey(&address)).is_none();
+    }
+}
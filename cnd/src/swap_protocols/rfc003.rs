@@ -4,8 +4,11 @@ pub mod bob;
 pub mod create_swap;
 pub mod ethereum;
 pub mod events;
+pub mod expiries;
 pub mod ledger_state;
+pub mod ledger_time;
 pub mod messages;
+pub mod watchdog;
 
 pub mod actions;
 mod secret;
@@ -13,14 +16,18 @@ mod secret;
 pub use self::{
     create_swap::create_watcher,
     ledger_state::{HtlcState, LedgerState},
+    ledger_time::{LedgerTime, LedgerTimes},
     secret::{FromErr, Secret, SecretHash},
 };
 
 pub use self::messages::{Accept, Decline, Request};
 
-use crate::seed::SwapSeed;
+use crate::{seed::SwapSeed, swap_protocols::HashFunction};
 use ::bitcoin::secp256k1::SecretKey;
 
+/// The hash functions the HTLC templates implement.
+pub const HASH_FUNCTIONS: [HashFunction; 2] = [HashFunction::Sha256, HashFunction::Ripemd160Sha256];
+
 /// Swap request response as received from peer node acting as Bob.
 pub type Response<AI, BI> = Result<Accept<AI, BI>, Decline>;
 
@@ -37,6 +44,11 @@ pub enum SwapCommunication<AL, BL, AA, BA, AI, BI> {
         request: Request<AL, BL, AA, BA, AI, BI>,
         response: Decline,
     },
+    /// The request could not be delivered or the peer did not respond in
+    /// time.
+    Failed {
+        request: Request<AL, BL, AA, BA, AI, BI>,
+    },
 }
 
 impl<AL, BL, AA, BA, AI, BI> SwapCommunication<AL, BL, AA, BA, AI, BI> {
@@ -45,6 +57,7 @@ impl<AL, BL, AA, BA, AI, BI> SwapCommunication<AL, BL, AA, BA, AI, BI> {
             SwapCommunication::Accepted { request, .. } => request,
             SwapCommunication::Proposed { request } => request,
             SwapCommunication::Declined { request, .. } => request,
+            SwapCommunication::Failed { request } => request,
         }
     }
 }
@@ -1,8 +1,12 @@
 pub mod bitcoin;
 pub mod erc20;
 pub mod ether;
+pub mod litecoin;
 
-use crate::swap_protocols::rfc003::{DeriveIdentities, Secret};
+use crate::{
+    swap_protocols::rfc003::{create_swap::UnsupportedSecretHash, DeriveIdentities, Secret},
+    timestamp::Timestamp,
+};
 use std::marker::PhantomData;
 
 /// Defines the set of actions available in the RFC003 protocol
@@ -25,7 +29,9 @@ pub trait MakeFundAction {
     type HtlcParams;
     type Output;
 
-    fn make_fund_action(htlc_params: Self::HtlcParams) -> Self::Output;
+    fn make_fund_action(
+        htlc_params: Self::HtlcParams,
+    ) -> Result<Self::Output, UnsupportedSecretHash>;
 }
 
 pub trait MakeRefundAction {
@@ -39,20 +45,42 @@ pub trait MakeRefundAction {
         htlc_location: Self::HtlcLocation,
         secret_source: &dyn DeriveIdentities,
         fund_transaction: &Self::FundTransaction,
-    ) -> Self::Output;
+    ) -> Result<Self::Output, UnsupportedSecretHash>;
+
+    /// Whether the ledger accepts a refund of an HTLC with the given expiry,
+    /// `consensus_time` being the time the ledger checks timelocks against.
+    fn is_refundable(expiry: Timestamp, consensus_time: Timestamp) -> bool;
 }
 
 pub trait MakeRedeemAction {
     type HtlcParams;
     type HtlcLocation;
+    type FundTransaction;
     type Output;
 
     fn make_redeem_action(
         htlc_params: Self::HtlcParams,
         htlc_location: Self::HtlcLocation,
         secret_source: &dyn DeriveIdentities,
+        fund_transaction: &Self::FundTransaction,
         secret: Secret,
-    ) -> Self::Output;
+    ) -> Result<Self::Output, UnsupportedSecretHash>;
+}
+
+/// The actions of a swap that could be built.
+///
+/// The HTLC of a swap with a secret hash the templates don't support cannot be
+/// built, hence neither can the actions on it. They are left out instead of
+/// failing the whole swap.
+pub fn buildable<A>(actions: Vec<Result<A, UnsupportedSecretHash>>) -> Vec<A> {
+    actions
+        .into_iter()
+        .filter_map(|action| {
+            action
+                .map_err(|e| tracing::warn!("failed to build action: {}", e))
+                .ok()
+        })
+        .collect()
 }
 
 #[derive(Clone, Debug, Default)]
@@ -85,6 +113,10 @@ impl<AL, BL> Decline<AL, BL> {
 mod tests {
 
     use super::*;
+    use crate::{
+        asset,
+        swap_protocols::ledger::{bitcoin, Ethereum},
+    };
 
     #[test]
     fn action_kind_serializes_into_lowercase_str() {
@@ -95,4 +127,33 @@ mod tests {
         assert_eq!(ActionKind::Redeem.to_string(), "redeem".to_string());
         assert_eq!(ActionKind::Deploy.to_string(), "deploy".to_string());
     }
+
+    #[test]
+    fn bitcoin_refund_needs_median_time_past_after_expiry() {
+        let expiry = Timestamp::from(1_591_000_000);
+
+        assert!(!<(bitcoin::Regtest, asset::Bitcoin)>::is_refundable(
+            expiry,
+            Timestamp::from(1_590_999_999)
+        ));
+        assert!(!<(bitcoin::Regtest, asset::Bitcoin)>::is_refundable(
+            expiry, expiry
+        ));
+        assert!(<(bitcoin::Regtest, asset::Bitcoin)>::is_refundable(
+            expiry,
+            expiry.plus(1)
+        ));
+    }
+
+    #[test]
+    fn ethereum_refund_is_possible_once_latest_block_reaches_expiry() {
+        let expiry = Timestamp::from(1_591_000_000);
+
+        assert!(!<(Ethereum, asset::Ether)>::is_refundable(
+            expiry,
+            Timestamp::from(1_590_999_999)
+        ));
+        assert!(<(Ethereum, asset::Ether)>::is_refundable(expiry, expiry));
+        assert!(<(Ethereum, asset::Erc20)>::is_refundable(expiry, expiry));
+    }
 }
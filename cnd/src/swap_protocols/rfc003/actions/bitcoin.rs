@@ -5,13 +5,15 @@ use crate::{
         ledger,
         rfc003::{
             actions::{MakeFundAction, MakeRedeemAction, MakeRefundAction},
-            create_swap::HtlcParams,
+            bitcoin::{htlc_outputs, HtlcScript},
+            create_swap::{HtlcParams, UnsupportedSecretHash},
             DeriveIdentities, Secret,
         },
     },
+    timestamp::Timestamp,
 };
-use ::bitcoin::{Amount, OutPoint, Transaction};
-use blockchain_contracts::bitcoin::{rfc003::bitcoin_htlc::BitcoinHtlc, witness::PrimedInput};
+use ::bitcoin::{OutPoint, Transaction};
+use blockchain_contracts::bitcoin::witness::PrimedInput;
 
 impl<B> MakeFundAction for (B, asset::Bitcoin)
 where
@@ -20,14 +22,16 @@ where
     type HtlcParams = HtlcParams<B, asset::Bitcoin, identity::Bitcoin>;
     type Output = SendToAddress;
 
-    fn make_fund_action(htlc_params: Self::HtlcParams) -> Self::Output {
-        let to = htlc_params.compute_address();
+    fn make_fund_action(
+        htlc_params: Self::HtlcParams,
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        let to = htlc_params.compute_address()?;
 
-        SendToAddress {
+        Ok(SendToAddress {
             to,
             amount: htlc_params.asset,
             network: B::network(),
-        }
+        })
     }
 }
 
@@ -42,20 +46,28 @@ where
 
     fn make_refund_action(
         htlc_params: Self::HtlcParams,
-        htlc_location: Self::HtlcLocation,
+        _htlc_location: Self::HtlcLocation,
         secret_source: &dyn DeriveIdentities,
         fund_transaction: &Self::FundTransaction,
-    ) -> Self::Output {
-        let htlc = BitcoinHtlc::from(htlc_params);
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        let script_pubkey = htlc_params.script_pubkey()?;
+        let unlock = htlc_params
+            .htlc()?
+            .unlock_after_timeout(&*crate::SECP, secret_source.derive_refund_identity());
 
-        SpendOutput {
-            output: PrimedInput::new(
-                htlc_location,
-                Amount::from_sat(fund_transaction.output[htlc_location.vout as usize].value),
-                htlc.unlock_after_timeout(&*crate::SECP, secret_source.derive_refund_identity()),
-            ),
+        Ok(SpendOutput {
+            outputs: htlc_outputs(fund_transaction, &script_pubkey)
+                .into_iter()
+                .map(|(outpoint, amount)| PrimedInput::new(outpoint, amount, unlock.clone()))
+                .collect(),
             network: B::network(),
-        }
+        })
+    }
+
+    /// The refund transaction has the expiry as lock time, which is only
+    /// satisfied once the median time past is greater than it.
+    fn is_refundable(expiry: Timestamp, consensus_time: Timestamp) -> bool {
+        consensus_time > expiry
     }
 }
 
@@ -65,27 +77,29 @@ where
 {
     type HtlcParams = HtlcParams<B, asset::Bitcoin, identity::Bitcoin>;
     type HtlcLocation = OutPoint;
+    type FundTransaction = Transaction;
     type Output = SpendOutput;
 
     fn make_redeem_action(
         htlc_params: Self::HtlcParams,
-        htlc_location: Self::HtlcLocation,
+        _htlc_location: Self::HtlcLocation,
         secret_source: &dyn DeriveIdentities,
+        fund_transaction: &Self::FundTransaction,
         secret: Secret,
-    ) -> Self::Output {
-        let htlc = BitcoinHtlc::from(htlc_params);
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        let script_pubkey = htlc_params.script_pubkey()?;
+        let unlock = htlc_params.htlc()?.unlock_with_secret(
+            &*crate::SECP,
+            secret_source.derive_redeem_identity(),
+            secret,
+        );
 
-        SpendOutput {
-            output: PrimedInput::new(
-                htlc_location,
-                htlc_params.asset.clone().into(),
-                htlc.unlock_with_secret(
-                    &*crate::SECP,
-                    secret_source.derive_redeem_identity(),
-                    secret.into_raw_secret(),
-                ),
-            ),
+        Ok(SpendOutput {
+            outputs: htlc_outputs(fund_transaction, &script_pubkey)
+                .into_iter()
+                .map(|(outpoint, amount)| PrimedInput::new(outpoint, amount, unlock.clone()))
+                .collect(),
             network: B::network(),
-        }
+        })
     }
 }
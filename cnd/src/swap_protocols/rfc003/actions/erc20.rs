@@ -1,11 +1,15 @@
 use crate::{
     asset,
-    ethereum::Bytes,
+    ethereum::{Bytes, Transaction},
     htlc_location, identity,
     swap_protocols::{
         actions::ethereum::{CallContract, DeployContract},
-        ledger::{ethereum::ChainId, Ethereum},
-        rfc003::{create_swap::HtlcParams, Secret},
+        ledger::Ethereum,
+        rfc003::{
+            actions::{MakeRedeemAction, MakeRefundAction},
+            create_swap::{HtlcParams, UnsupportedSecretHash},
+            DeriveIdentities, Secret,
+        },
     },
     timestamp::Timestamp,
 };
@@ -13,17 +17,13 @@ use blockchain_contracts::ethereum::rfc003::erc20_htlc::Erc20Htlc;
 
 pub fn deploy_action(
     htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
-) -> DeployContract {
-    let chain_id = htlc_params.ledger.chain_id;
-    let htlc = Erc20Htlc::from(htlc_params);
-    let gas_limit = Erc20Htlc::deploy_tx_gas_limit();
-
-    DeployContract {
-        data: htlc.into(),
+) -> Result<DeployContract, UnsupportedSecretHash> {
+    Ok(DeployContract {
+        data: htlc_params.bytecode()?,
         amount: asset::Ether::zero(),
-        gas_limit,
-        chain_id,
-    }
+        gas_limit: htlc_params.deploy_tx_gas_limit(),
+        chain_id: htlc_params.ledger.chain_id,
+    })
 }
 
 pub fn fund_action(
@@ -48,35 +48,72 @@ pub fn fund_action(
 }
 
 pub fn refund_action(
-    chain_id: ChainId,
-    expiry: Timestamp,
+    htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
     beta_htlc_location: htlc_location::Ethereum,
 ) -> CallContract {
     let data = Bytes::default();
-    let gas_limit = Erc20Htlc::refund_tx_gas_limit();
+    let gas_limit = htlc_params.refund_tx_gas_limit();
 
     CallContract {
         to: beta_htlc_location,
         data: Some(data),
         gas_limit,
-        chain_id,
-        min_block_timestamp: Some(expiry),
+        chain_id: htlc_params.ledger.chain_id,
+        min_block_timestamp: Some(htlc_params.expiry),
     }
 }
 
 pub fn redeem_action(
+    htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
     alpha_htlc_location: htlc_location::Ethereum,
     secret: Secret,
-    chain_id: ChainId,
 ) -> CallContract {
     let data = Bytes::from(secret.as_raw_secret().to_vec());
-    let gas_limit = Erc20Htlc::redeem_tx_gas_limit();
+    let gas_limit = htlc_params.redeem_tx_gas_limit();
 
     CallContract {
         to: alpha_htlc_location,
         data: Some(data),
         gas_limit,
-        chain_id,
+        chain_id: htlc_params.ledger.chain_id,
         min_block_timestamp: None,
     }
 }
+
+impl MakeRefundAction for (Ethereum, asset::Erc20) {
+    type HtlcParams = HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>;
+    type HtlcLocation = htlc_location::Ethereum;
+    type FundTransaction = Transaction;
+    type Output = CallContract;
+
+    fn make_refund_action(
+        htlc_params: Self::HtlcParams,
+        htlc_location: Self::HtlcLocation,
+        _secret_source: &dyn DeriveIdentities,
+        _fund_transaction: &Self::FundTransaction,
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        Ok(refund_action(htlc_params, htlc_location))
+    }
+
+    /// The ERC20 HTLC checks the expiry the same way as the Ether HTLC.
+    fn is_refundable(expiry: Timestamp, consensus_time: Timestamp) -> bool {
+        <(Ethereum, asset::Ether)>::is_refundable(expiry, consensus_time)
+    }
+}
+
+impl MakeRedeemAction for (Ethereum, asset::Erc20) {
+    type HtlcParams = HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>;
+    type HtlcLocation = htlc_location::Ethereum;
+    type FundTransaction = Transaction;
+    type Output = CallContract;
+
+    fn make_redeem_action(
+        htlc_params: Self::HtlcParams,
+        htlc_location: Self::HtlcLocation,
+        _secret_source: &dyn DeriveIdentities,
+        _fund_transaction: &Self::FundTransaction,
+        secret: Secret,
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        Ok(redeem_action(htlc_params, htlc_location, secret))
+    }
+}
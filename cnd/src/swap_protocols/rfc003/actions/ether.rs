@@ -7,27 +7,29 @@ use crate::{
         ledger::Ethereum,
         rfc003::{
             actions::{MakeFundAction, MakeRedeemAction, MakeRefundAction},
-            create_swap::HtlcParams,
+            create_swap::{HtlcParams, UnsupportedSecretHash},
             DeriveIdentities, Secret,
         },
     },
+    timestamp::Timestamp,
 };
-use blockchain_contracts::ethereum::rfc003::ether_htlc::EtherHtlc;
 
 impl MakeFundAction for (Ethereum, asset::Ether) {
     type HtlcParams = HtlcParams<Ethereum, asset::Ether, identity::Ethereum>;
     type Output = DeployContract;
 
-    fn make_fund_action(htlc_params: Self::HtlcParams) -> Self::Output {
-        let htlc = EtherHtlc::from(htlc_params.clone());
-        let gas_limit = EtherHtlc::deploy_tx_gas_limit();
+    fn make_fund_action(
+        htlc_params: Self::HtlcParams,
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        let data = htlc_params.bytecode()?;
+        let gas_limit = htlc_params.deploy_tx_gas_limit();
 
-        DeployContract {
-            data: htlc.into(),
+        Ok(DeployContract {
+            data,
             amount: htlc_params.asset.clone(),
             gas_limit,
             chain_id: htlc_params.ledger.chain_id,
-        }
+        })
     }
 }
 
@@ -42,39 +44,47 @@ impl MakeRefundAction for (Ethereum, asset::Ether) {
         htlc_location: Self::HtlcLocation,
         _secret_source: &dyn DeriveIdentities,
         _fund_transaction: &Self::FundTransaction,
-    ) -> Self::Output {
-        let gas_limit = EtherHtlc::refund_tx_gas_limit();
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
+        let gas_limit = htlc_params.refund_tx_gas_limit();
 
-        CallContract {
+        Ok(CallContract {
             to: htlc_location,
             data: None,
             gas_limit,
             chain_id: htlc_params.ledger.chain_id,
             min_block_timestamp: Some(htlc_params.expiry),
-        }
+        })
+    }
+
+    /// The HTLC compares the expiry with the timestamp of the block the refund
+    /// is mined in, which is later than the one of the latest block.
+    fn is_refundable(expiry: Timestamp, consensus_time: Timestamp) -> bool {
+        consensus_time >= expiry
     }
 }
 
 impl MakeRedeemAction for (Ethereum, asset::Ether) {
     type HtlcParams = HtlcParams<Ethereum, asset::Ether, identity::Ethereum>;
     type HtlcLocation = htlc_location::Ethereum;
+    type FundTransaction = Transaction;
     type Output = CallContract;
 
     fn make_redeem_action(
         htlc_params: Self::HtlcParams,
         htlc_location: Self::HtlcLocation,
         _secret_source: &dyn DeriveIdentities,
+        _fund_transaction: &Self::FundTransaction,
         secret: Secret,
-    ) -> Self::Output {
+    ) -> Result<Self::Output, UnsupportedSecretHash> {
         let data = Bytes::from(secret.as_raw_secret().to_vec());
-        let gas_limit = EtherHtlc::redeem_tx_gas_limit();
+        let gas_limit = htlc_params.redeem_tx_gas_limit();
 
-        CallContract {
+        Ok(CallContract {
             to: htlc_location,
             data: Some(data),
             gas_limit,
             chain_id: htlc_params.ledger.chain_id,
             min_block_timestamp: None,
-        }
+        })
     }
 }
@@ -0,0 +1,114 @@
+use crate::{
+    asset, identity,
+    swap_protocols::{
+        actions::litecoin::{SendToAddress, SpendOutput},
+        ledger::{litecoin, UtxoChain},
+        rfc003::{
+            actions::{MakeFundAction, MakeRedeemAction, MakeRefundAction},
+            bitcoin::{htlc_outputs, HtlcScript},
+            create_swap::{HtlcParams, UnsupportedSecretHash},
+            DeriveIdentities, Secret,
+        },
+    },
+    timestamp::Timestamp,
+};
+use ::bitcoin::{OutPoint, Transaction};
+use blockchain_contracts::bitcoin::witness::PrimedInput;
+
+/// The HTLC and the transactions spending it are the same as on Bitcoin, only
+/// the addresses are encoded with the parameters of the Litecoin network.
+macro_rules! impl_actions_for_litecoin {
+    ($network:ident) => {
+        impl MakeFundAction for (litecoin::$network, asset::Bitcoin) {
+            type HtlcParams = HtlcParams<litecoin::$network, asset::Bitcoin, identity::Bitcoin>;
+            type Output = SendToAddress;
+
+            fn make_fund_action(
+                htlc_params: Self::HtlcParams,
+            ) -> Result<Self::Output, UnsupportedSecretHash> {
+                let to = litecoin::$network::params()
+                    .address(&htlc_params.script_pubkey()?)
+                    .expect("the HTLC is a P2WSH output, which has an address");
+
+                Ok(SendToAddress {
+                    to,
+                    amount: htlc_params.asset,
+                    network: litecoin::$network::network(),
+                })
+            }
+        }
+
+        impl MakeRefundAction for (litecoin::$network, asset::Bitcoin) {
+            type HtlcParams = HtlcParams<litecoin::$network, asset::Bitcoin, identity::Bitcoin>;
+            type HtlcLocation = OutPoint;
+            type FundTransaction = Transaction;
+            type Output = SpendOutput;
+
+            fn make_refund_action(
+                htlc_params: Self::HtlcParams,
+                _htlc_location: Self::HtlcLocation,
+                secret_source: &dyn DeriveIdentities,
+                fund_transaction: &Self::FundTransaction,
+            ) -> Result<Self::Output, UnsupportedSecretHash> {
+                let script_pubkey = htlc_params.script_pubkey()?;
+                let unlock = htlc_params
+                    .htlc()?
+                    .unlock_after_timeout(&*crate::SECP, secret_source.derive_refund_identity());
+
+                Ok(SpendOutput {
+                    outputs: htlc_outputs(fund_transaction, &script_pubkey)
+                        .into_iter()
+                        .map(|(outpoint, amount)| {
+                            PrimedInput::new(outpoint, amount, unlock.clone())
+                        })
+                        .collect(),
+                    params: litecoin::$network::params(),
+                    network: litecoin::$network::network(),
+                })
+            }
+
+            /// Litecoin checks the lock time of the refund transaction against
+            /// the median time past, like Bitcoin.
+            fn is_refundable(expiry: Timestamp, consensus_time: Timestamp) -> bool {
+                consensus_time > expiry
+            }
+        }
+
+        impl MakeRedeemAction for (litecoin::$network, asset::Bitcoin) {
+            type HtlcParams = HtlcParams<litecoin::$network, asset::Bitcoin, identity::Bitcoin>;
+            type HtlcLocation = OutPoint;
+            type FundTransaction = Transaction;
+            type Output = SpendOutput;
+
+            fn make_redeem_action(
+                htlc_params: Self::HtlcParams,
+                _htlc_location: Self::HtlcLocation,
+                secret_source: &dyn DeriveIdentities,
+                fund_transaction: &Self::FundTransaction,
+                secret: Secret,
+            ) -> Result<Self::Output, UnsupportedSecretHash> {
+                let script_pubkey = htlc_params.script_pubkey()?;
+                let unlock = htlc_params.htlc()?.unlock_with_secret(
+                    &*crate::SECP,
+                    secret_source.derive_redeem_identity(),
+                    secret,
+                );
+
+                Ok(SpendOutput {
+                    outputs: htlc_outputs(fund_transaction, &script_pubkey)
+                        .into_iter()
+                        .map(|(outpoint, amount)| {
+                            PrimedInput::new(outpoint, amount, unlock.clone())
+                        })
+                        .collect(),
+                    params: litecoin::$network::params(),
+                    network: litecoin::$network::network(),
+                })
+            }
+        }
+    };
+}
+
+impl_actions_for_litecoin!(Mainnet);
+impl_actions_for_litecoin!(Testnet);
+impl_actions_for_litecoin!(Regtest);
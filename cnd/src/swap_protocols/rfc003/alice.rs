@@ -4,7 +4,7 @@ pub use self::actions::*;
 
 use crate::{
     seed::SwapSeed,
-    swap_protocols::rfc003::{ledger_state::LedgerState, messages, SwapCommunication},
+    swap_protocols::rfc003::{ledger_state::LedgerState, messages, LedgerTimes, SwapCommunication},
 };
 use derivative::Derivative;
 
@@ -14,6 +14,7 @@ pub struct State<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT> {
     pub swap_communication: SwapCommunication<AL, BL, AA, BA, AI, BI>,
     pub alpha_ledger_state: LedgerState<AA, AH, AT>,
     pub beta_ledger_state: LedgerState<BA, BH, BT>,
+    pub ledger_times: LedgerTimes,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     pub secret_source: SwapSeed, // Used to derive identities and also to generate the secret.
 }
@@ -23,12 +24,14 @@ impl<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT> State<AL, BL, AA, BA, AH, BH, AI, B
         swap_communication: SwapCommunication<AL, BL, AA, BA, AI, BI>,
         alpha_ledger_state: LedgerState<AA, AH, AT>,
         beta_ledger_state: LedgerState<BA, BH, BT>,
+        ledger_times: LedgerTimes,
         secret_source: SwapSeed,
     ) -> Self {
         Self {
             swap_communication,
             alpha_ledger_state,
             beta_ledger_state,
+            ledger_times,
             secret_source,
         }
     }
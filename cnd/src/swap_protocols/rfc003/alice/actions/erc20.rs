@@ -6,7 +6,7 @@ use crate::{
         ledger::Ethereum,
         rfc003::{
             actions::{
-                erc20, Accept, Action, Decline, MakeFundAction, MakeRedeemAction, MakeRefundAction,
+                buildable, erc20, Accept, Action, Decline, MakeRedeemAction, MakeRefundAction,
             },
             alice,
             create_swap::HtlcParams,
@@ -15,7 +15,6 @@ use crate::{
     },
     transaction,
 };
-use std::convert::Infallible;
 
 impl<BL, BA, BH, BI, BT> Actions
     for alice::State<
@@ -36,7 +35,11 @@ where
     BH: Clone,
     BI: Clone,
     BT: Clone,
-    (BL, BA): MakeRedeemAction<HtlcParams = HtlcParams<BL, BA, BI>, HtlcLocation = BH>,
+    (BL, BA): MakeRedeemAction<
+        HtlcParams = HtlcParams<BL, BA, BI>,
+        HtlcLocation = BH,
+        FundTransaction = BT,
+    >,
 {
     #[allow(clippy::type_complexity)]
     type ActionKind = Action<
@@ -58,111 +61,48 @@ where
         };
         let alpha_state = &self.alpha_ledger_state;
         let beta_state = &self.beta_ledger_state;
+        let alpha_refundable = self.ledger_times.alpha.map_or(false, |time| {
+            <(Ethereum, asset::Erc20)>::is_refundable(request.alpha_expiry, time)
+        });
 
         use self::LedgerState::*;
 
         let mut actions = match alpha_state {
-            NotDeployed => vec![Action::Deploy(erc20::deploy_action(
-                HtlcParams::new_alpha_params(request, response),
-            ))],
-            Deployed { htlc_location, .. } => vec![Action::Fund(erc20::fund_action(
+            NotDeployed => {
+                let htlc_params = HtlcParams::new_alpha_params(request, response);
+                vec![erc20::deploy_action(htlc_params).map(Action::Deploy)]
+            }
+            Deployed { htlc_location, .. } => vec![Ok(Action::Fund(erc20::fund_action(
                 HtlcParams::new_alpha_params(request, response),
                 request.alpha_asset.token_contract,
                 *htlc_location,
-            ))],
-            Funded { htlc_location, .. } => vec![Action::Refund(erc20::refund_action(
-                request.alpha_ledger.chain_id,
-                request.alpha_expiry,
-                *htlc_location,
-            ))],
+            )))],
+            Funded { htlc_location, .. } if alpha_refundable => {
+                vec![Ok(Action::Refund(erc20::refund_action(
+                    HtlcParams::new_alpha_params(request, response),
+                    *htlc_location,
+                )))]
+            }
             _ => vec![],
         };
 
-        if let Funded { htlc_location, .. } = beta_state {
-            actions.push(Action::Redeem(<(BL, BA)>::make_redeem_action(
-                HtlcParams::new_beta_params(request, response),
-                htlc_location.clone(),
-                &self.secret_source, // Derive identities with this.
-                self.secret_source.derive_secret(), // The secret used by Alice.
-            )));
-        }
-        actions
-    }
-}
-
-impl<AL, AA, AH, AI, AT> Actions
-    for alice::State<
-        AL,
-        Ethereum,
-        AA,
-        asset::Erc20,
-        AH,
-        htlc_location::Ethereum,
-        AI,
-        identity::Ethereum,
-        AT,
-        transaction::Ethereum,
-    >
-where
-    AL: Clone,
-    AA: Clone,
-    AH: Copy,
-    AI: Clone,
-    AT: Clone,
-    (AL, AA): MakeFundAction<HtlcParams = HtlcParams<AL, AA, AI>>
-        + MakeRefundAction<
-            HtlcParams = HtlcParams<AL, AA, AI>,
-            HtlcLocation = AH,
-            FundTransaction = AT,
-        >,
-{
-    #[allow(clippy::type_complexity)]
-    type ActionKind = Action<
-        Accept<AL, Ethereum>,
-        Decline<AL, Ethereum>,
-        Infallible,
-        <(AL, AA) as MakeFundAction>::Output,
-        ethereum::CallContract,
-        <(AL, AA) as MakeRefundAction>::Output,
-    >;
-
-    fn actions(&self) -> Vec<Self::ActionKind> {
-        let (request, response) = match self.swap_communication {
-            SwapCommunication::Accepted {
-                ref request,
-                ref response,
-            } => (request, response),
-            _ => return vec![],
-        };
-        let alpha_state = &self.alpha_ledger_state;
-        let beta_state = &self.beta_ledger_state;
-
-        use self::LedgerState::*;
-
-        let mut actions = match alpha_state {
-            NotDeployed => vec![Action::Fund(<(AL, AA)>::make_fund_action(
-                HtlcParams::new_alpha_params(request, response),
-            ))],
-            Funded {
-                htlc_location,
-                fund_transaction,
-                ..
-            } => vec![Action::Refund(<(AL, AA)>::make_refund_action(
-                HtlcParams::new_alpha_params(request, response),
-                *htlc_location,
-                &self.secret_source,
-                fund_transaction,
-            ))],
-            _ => vec![],
-        };
-
-        if let Funded { htlc_location, .. } = beta_state {
-            actions.push(Action::Redeem(erc20::redeem_action(
-                *htlc_location,
-                self.secret_source.derive_secret(),
-                request.beta_ledger.chain_id,
-            )));
+        if let Funded {
+            htlc_location,
+            fund_transaction,
+            ..
+        } = beta_state
+        {
+            actions.push(
+                <(BL, BA)>::make_redeem_action(
+                    HtlcParams::new_beta_params(request, response),
+                    htlc_location.clone(),
+                    &self.secret_source, // Derive identities with this.
+                    fund_transaction,
+                    self.secret_source.derive_secret(), // The secret used by Alice.
+                )
+                .map(Action::Redeem),
+            );
         }
-        actions
+        buildable(actions)
     }
 }
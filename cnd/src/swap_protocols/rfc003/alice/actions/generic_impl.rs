@@ -1,7 +1,9 @@
 use crate::swap_protocols::{
     actions::Actions,
     rfc003::{
-        actions::{Accept, Action, Decline, MakeFundAction, MakeRedeemAction, MakeRefundAction},
+        actions::{
+            buildable, Accept, Action, Decline, MakeFundAction, MakeRedeemAction, MakeRefundAction,
+        },
         alice,
         create_swap::HtlcParams,
         DeriveSecret, LedgerState, SwapCommunication,
@@ -28,7 +30,11 @@ where
             HtlcLocation = AH,
             FundTransaction = AT,
         >,
-    (BL, BA): MakeRedeemAction<HtlcParams = HtlcParams<BL, BA, BI>, HtlcLocation = BH>,
+    (BL, BA): MakeRedeemAction<
+        HtlcParams = HtlcParams<BL, BA, BI>,
+        HtlcLocation = BH,
+        FundTransaction = BT,
+    >,
 {
     #[allow(clippy::type_complexity)]
     type ActionKind = Action<
@@ -50,12 +56,16 @@ where
         };
         let alpha_state = &self.alpha_ledger_state;
         let beta_state = &self.beta_ledger_state;
+        let alpha_refundable = self.ledger_times.alpha.map_or(false, |time| {
+            <(AL, AA)>::is_refundable(request.alpha_expiry, time)
+        });
 
         use self::LedgerState::*;
         let mut actions = match alpha_state {
-            NotDeployed => vec![Action::Fund(<(AL, AA)>::make_fund_action(
-                HtlcParams::new_alpha_params(request, response),
-            ))],
+            NotDeployed => {
+                let htlc_params = HtlcParams::new_alpha_params(request, response);
+                vec![<(AL, AA)>::make_fund_action(htlc_params).map(Action::Fund)]
+            }
             Funded {
                 htlc_location,
                 fund_transaction,
@@ -65,23 +75,33 @@ where
                 htlc_location,
                 fund_transaction,
                 ..
-            } => vec![Action::Refund(<(AL, AA)>::make_refund_action(
+            } if alpha_refundable => vec![<(AL, AA)>::make_refund_action(
                 HtlcParams::new_alpha_params(request, response),
                 htlc_location.clone(),
                 &self.secret_source,
                 fund_transaction,
-            ))],
+            )
+            .map(Action::Refund)],
             _ => vec![],
         };
 
-        if let Funded { htlc_location, .. } = beta_state {
-            actions.push(Action::Redeem(<(BL, BA)>::make_redeem_action(
-                HtlcParams::new_beta_params(request, response),
-                htlc_location.clone(),
-                &self.secret_source, // Derive identities with this.
-                self.secret_source.derive_secret(), // The secret used by Alice.
-            )));
+        if let Funded {
+            htlc_location,
+            fund_transaction,
+            ..
+        } = beta_state
+        {
+            actions.push(
+                <(BL, BA)>::make_redeem_action(
+                    HtlcParams::new_beta_params(request, response),
+                    htlc_location.clone(),
+                    &self.secret_source, // Derive identities with this.
+                    fund_transaction,
+                    self.secret_source.derive_secret(), // The secret used by Alice.
+                )
+                .map(Action::Redeem),
+            );
         }
-        actions
+        buildable(actions)
     }
 }
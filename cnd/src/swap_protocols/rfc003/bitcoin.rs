@@ -1,43 +1,326 @@
 mod extract_secret;
+mod hash160_htlc;
 mod htlc_events;
 
 use crate::{
     asset, identity,
-    swap_protocols::{ledger, rfc003::create_swap::HtlcParams},
+    swap_protocols::{
+        ledger,
+        rfc003::{
+            create_swap::{HtlcParams, UnsupportedSecretHash},
+            Secret,
+        },
+        HashFunction,
+    },
 };
 use ::bitcoin::{
     hashes::{hash160, Hash},
-    Address,
+    secp256k1::{All, Secp256k1, SecretKey},
+    Address, Amount, Network, OutPoint, Script, Transaction,
 };
-use blockchain_contracts::bitcoin::rfc003::bitcoin_htlc::BitcoinHtlc;
+use blockchain_contracts::bitcoin::{rfc003::bitcoin_htlc::BitcoinHtlc, witness::UnlockParameters};
+use std::convert::TryFrom;
 
 pub use self::htlc_events::*;
 
-impl<B> From<HtlcParams<B, asset::Bitcoin, identity::Bitcoin>> for BitcoinHtlc
+use self::hash160_htlc::Hash160BitcoinHtlc;
+
+fn identity_hashes(
+    refund_identity: identity::Bitcoin,
+    redeem_identity: identity::Bitcoin,
+) -> (hash160::Hash, hash160::Hash) {
+    let refund_public_key = ::bitcoin::PublicKey::from(refund_identity);
+    let redeem_public_key = ::bitcoin::PublicKey::from(redeem_identity);
+
+    (
+        hash160::Hash::hash(&refund_public_key.key.serialize()),
+        hash160::Hash::hash(&redeem_public_key.key.serialize()),
+    )
+}
+
+impl<B> TryFrom<HtlcParams<B, asset::Bitcoin, identity::Bitcoin>> for BitcoinHtlc
 where
-    B: ledger::Bitcoin,
+    B: ledger::UtxoChain,
 {
-    fn from(htlc_params: HtlcParams<B, asset::Bitcoin, identity::Bitcoin>) -> Self {
-        let refund_public_key = ::bitcoin::PublicKey::from(htlc_params.refund_identity);
-        let redeem_public_key = ::bitcoin::PublicKey::from(htlc_params.redeem_identity);
+    type Error = UnsupportedSecretHash;
 
-        let refund_identity = hash160::Hash::hash(&refund_public_key.key.serialize());
-        let redeem_identity = hash160::Hash::hash(&redeem_public_key.key.serialize());
+    fn try_from(
+        htlc_params: HtlcParams<B, asset::Bitcoin, identity::Bitcoin>,
+    ) -> Result<Self, Self::Error> {
+        let (refund_identity, redeem_identity) =
+            identity_hashes(htlc_params.refund_identity, htlc_params.redeem_identity);
 
-        BitcoinHtlc::new(
+        Ok(BitcoinHtlc::new(
             htlc_params.expiry.into(),
             refund_identity,
             redeem_identity,
-            htlc_params.secret_hash.into_raw(),
-        )
+            htlc_params.sha256_secret_hash()?,
+        ))
+    }
+}
+
+impl<B> TryFrom<HtlcParams<B, asset::Bitcoin, identity::Bitcoin>> for Hash160BitcoinHtlc
+where
+    B: ledger::UtxoChain,
+{
+    type Error = UnsupportedSecretHash;
+
+    fn try_from(
+        htlc_params: HtlcParams<B, asset::Bitcoin, identity::Bitcoin>,
+    ) -> Result<Self, Self::Error> {
+        let (refund_identity, redeem_identity) =
+            identity_hashes(htlc_params.refund_identity, htlc_params.redeem_identity);
+
+        Ok(Hash160BitcoinHtlc::new(
+            htlc_params.expiry.into(),
+            refund_identity,
+            redeem_identity,
+            htlc_params.ripemd160_sha256_secret_hash()?,
+        ))
+    }
+}
+
+/// A script template for the Bitcoin HTLC of an rfc003 swap.
+///
+/// Funding is detected through `script_pubkey` and the secret is extracted
+/// from the witness of the redeem transaction, hence neither depends on the
+/// kind of output a template creates. The template is chosen by the hash
+/// function of the swap, it is not negotiated otherwise.
+///
+/// Every template is a P2WSH output because `UnlockParameters` can only
+/// express SegWit v0 spends. A Taproot template would also need Schnorr
+/// signatures and bech32m addresses, which bitcoin 0.23 does not provide.
+pub trait HtlcScript {
+    /// The script of the output the funder pays to.
+    fn script_pubkey(&self, network: Network) -> Script;
+
+    /// The address to send the funds to.
+    fn address(&self, network: Network) -> Address;
+
+    fn unlock_with_secret(
+        self,
+        secp: &Secp256k1<All>,
+        key: SecretKey,
+        secret: Secret,
+    ) -> UnlockParameters;
+
+    fn unlock_after_timeout(self, secp: &Secp256k1<All>, key: SecretKey) -> UnlockParameters;
+}
+
+/// The native SegWit v0 (P2WSH) HTLC.
+impl HtlcScript for BitcoinHtlc {
+    fn script_pubkey(&self, network: Network) -> Script {
+        self.compute_address(network).script_pubkey()
+    }
+
+    fn address(&self, network: Network) -> Address {
+        self.compute_address(network)
+    }
+
+    fn unlock_with_secret(
+        self,
+        secp: &Secp256k1<All>,
+        key: SecretKey,
+        secret: Secret,
+    ) -> UnlockParameters {
+        BitcoinHtlc::unlock_with_secret(self, secp, key, secret.into_raw_secret())
+    }
+
+    fn unlock_after_timeout(self, secp: &Secp256k1<All>, key: SecretKey) -> UnlockParameters {
+        BitcoinHtlc::unlock_after_timeout(self, secp, key)
+    }
+}
+
+/// The P2WSH HTLC of a swap, one per hash function Bitcoin script can check.
+pub enum Htlc {
+    Sha256(BitcoinHtlc),
+    Ripemd160Sha256(Hash160BitcoinHtlc),
+}
+
+impl HtlcScript for Htlc {
+    fn script_pubkey(&self, network: Network) -> Script {
+        match self {
+            Htlc::Sha256(htlc) => htlc.script_pubkey(network),
+            Htlc::Ripemd160Sha256(htlc) => htlc.script_pubkey(network),
+        }
+    }
+
+    fn address(&self, network: Network) -> Address {
+        match self {
+            Htlc::Sha256(htlc) => htlc.address(network),
+            Htlc::Ripemd160Sha256(htlc) => htlc.address(network),
+        }
+    }
+
+    fn unlock_with_secret(
+        self,
+        secp: &Secp256k1<All>,
+        key: SecretKey,
+        secret: Secret,
+    ) -> UnlockParameters {
+        match self {
+            Htlc::Sha256(htlc) => HtlcScript::unlock_with_secret(htlc, secp, key, secret),
+            Htlc::Ripemd160Sha256(htlc) => htlc.unlock_with_secret(secp, key, secret),
+        }
+    }
+
+    fn unlock_after_timeout(self, secp: &Secp256k1<All>, key: SecretKey) -> UnlockParameters {
+        match self {
+            Htlc::Sha256(htlc) => HtlcScript::unlock_after_timeout(htlc, secp, key),
+            Htlc::Ripemd160Sha256(htlc) => htlc.unlock_after_timeout(secp, key),
+        }
     }
 }
 
+/// The HTLC of a chain derived from Bitcoin is the same as on Bitcoin, only
+/// its address is encoded differently.
 impl<B> HtlcParams<B, asset::Bitcoin, identity::Bitcoin>
 where
-    B: ledger::Bitcoin + ledger::bitcoin::Network,
+    B: ledger::UtxoChain,
 {
-    pub fn compute_address(&self) -> Address {
-        BitcoinHtlc::from(*self).compute_address(B::network())
+    /// The script template locking the funds of this swap.
+    pub fn htlc(&self) -> Result<Htlc, UnsupportedSecretHash> {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => {
+                Hash160BitcoinHtlc::try_from(*self).map(Htlc::Ripemd160Sha256)
+            }
+            _ => BitcoinHtlc::try_from(*self).map(Htlc::Sha256),
+        }
+    }
+
+    pub fn script_pubkey(&self) -> Result<Script, UnsupportedSecretHash> {
+        Ok(self.htlc()?.script_pubkey(B::network()))
+    }
+
+    /// The address of the HTLC as shown by the wallets of the chain.
+    pub fn encoded_address(&self) -> Option<String> {
+        B::params().address(&self.script_pubkey().ok()?)
+    }
+}
+
+impl<B> HtlcParams<B, asset::Bitcoin, identity::Bitcoin>
+where
+    B: ledger::Bitcoin,
+{
+    pub fn compute_address(&self) -> Result<Address, UnsupportedSecretHash> {
+        Ok(self.htlc()?.address(B::network()))
+    }
+}
+
+/// The outputs of `transaction` that pay to the HTLC locked by
+/// `script_pubkey`.
+///
+/// An HTLC that was funded through several outputs locks their sum and is
+/// redeemed or refunded by spending all of them in one transaction. Only the
+/// funding transaction is considered, outputs that pay to the HTLC in later
+/// transactions are neither credited nor spent.
+pub fn htlc_outputs(transaction: &Transaction, script_pubkey: &Script) -> Vec<(OutPoint, Amount)> {
+    let txid = transaction.txid();
+
+    transaction
+        .output
+        .iter()
+        .enumerate()
+        .filter(|(_, txout)| txout.script_pubkey == script_pubkey)
+        .map(|(index, txout)| {
+            // Bitcoin limits the number of outputs to u32, see
+            // `watch_for_created_outpoint`.
+            #[allow(clippy::cast_possible_truncation)]
+            let vout = index as u32;
+
+            (OutPoint { txid, vout }, Amount::from_sat(txout.value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{swap_protocols::rfc003::SecretHash, timestamp::Timestamp};
+    use ::bitcoin::{Network, PrivateKey, TxOut};
+    use spectral::prelude::*;
+    use std::str::FromStr;
+
+    fn address(wif: &str) -> Address {
+        let private_key = PrivateKey::from_str(wif).unwrap();
+        Address::p2wpkh(&private_key.public_key(&*crate::SECP), Network::Regtest)
+    }
+
+    fn output(address: &Address, value: u64) -> TxOut {
+        TxOut {
+            value,
+            script_pubkey: address.script_pubkey(),
+        }
+    }
+
+    fn htlc_params(
+        hash_function: HashFunction,
+        secret_hash: SecretHash,
+    ) -> HtlcParams<ledger::bitcoin::Regtest, asset::Bitcoin, identity::Bitcoin> {
+        let identity = |byte| {
+            let key = SecretKey::from_slice(&[byte; 32]).unwrap();
+            identity::Bitcoin::from_secret_key(&*crate::SECP, &key)
+        };
+
+        HtlcParams {
+            asset: asset::Bitcoin::from_sat(100_000),
+            ledger: ledger::bitcoin::Regtest,
+            redeem_identity: identity(1),
+            refund_identity: identity(2),
+            expiry: Timestamp::from(1_600_000_000),
+            hash_function,
+            secret_hash,
+        }
+    }
+
+    #[test]
+    fn htlc_is_chosen_by_the_hash_function() {
+        let secret = Secret::from([1u8; 32]);
+
+        let sha256 = htlc_params(HashFunction::Sha256, secret.hash(HashFunction::Sha256));
+        let hash160 = htlc_params(
+            HashFunction::Ripemd160Sha256,
+            secret.hash(HashFunction::Ripemd160Sha256),
+        );
+
+        assert!(matches!(sha256.htlc(), Ok(Htlc::Sha256(_))));
+        assert!(matches!(hash160.htlc(), Ok(Htlc::Ripemd160Sha256(_))));
+        assert_that(&sha256.script_pubkey().unwrap())
+            .is_not_equal_to(hash160.script_pubkey().unwrap());
+    }
+
+    #[test]
+    fn there_is_no_sha3_256_htlc() {
+        let secret = Secret::from([1u8; 32]);
+        let params = htlc_params(HashFunction::Sha3_256, secret.hash(HashFunction::Sha3_256));
+
+        assert_that(&params.script_pubkey()).is_equal_to(Err(UnsupportedSecretHash::HashFunction(
+            HashFunction::Sha3_256,
+        )));
+    }
+
+    #[test]
+    fn finds_all_outputs_paying_the_htlc() {
+        let htlc = address("cSrWvMrWE3biZinxPZc1hSwMMEdYgYsFpB6iEoh8KraLqYZUUCtt");
+        let change = address("cNH3CSp6h5CHhHNoL8jJ6PmjGWqcaZ6VWg5MaGn2gDzL6E4thDZo");
+
+        let transaction = Transaction {
+            version: 2,
+            lock_time: 0,
+            input: vec![],
+            output: vec![
+                output(&htlc, 60_000),
+                output(&change, 10_000),
+                output(&htlc, 40_000),
+            ],
+        };
+        let txid = transaction.txid();
+
+        let outputs = htlc_outputs(&transaction, &htlc.script_pubkey());
+
+        assert_that(&outputs).is_equal_to(vec![
+            (OutPoint { txid, vout: 0 }, Amount::from_sat(60_000)),
+            (OutPoint { txid, vout: 2 }, Amount::from_sat(40_000)),
+        ]);
     }
 }
@@ -0,0 +1,170 @@
+use crate::swap_protocols::rfc003::{bitcoin::HtlcScript, Secret};
+use ::bitcoin::{
+    blockdata::{opcodes::all::*, script::Builder},
+    hashes::{hash160, Hash},
+    secp256k1::{All, PublicKey, Secp256k1, SecretKey},
+    Address, Network, Script,
+};
+use blockchain_contracts::bitcoin::witness::{UnlockParameters, Witness};
+
+/// Enables the lock time of the spending transaction without signalling
+/// replace-by-fee.
+const SEQUENCE_ALLOW_NTIMELOCK_NO_RBF: u32 = 0xFFFF_FFFE;
+
+/// The P2WSH HTLC of swaps locked with a RIPEMD160(SHA-256) secret hash.
+///
+/// The script only differs from the SHA-256 one of `blockchain_contracts` in
+/// hashing the secret with `OP_HASH160`, hence it is unlocked with the same
+/// witness.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hash160BitcoinHtlc {
+    script: Script,
+    expiry: u32,
+}
+
+impl Hash160BitcoinHtlc {
+    pub fn new(
+        expiry: u32,
+        refund_identity: hash160::Hash,
+        redeem_identity: hash160::Hash,
+        secret_hash: [u8; 20],
+    ) -> Self {
+        let script = Builder::new()
+            .push_opcode(OP_IF)
+            .push_opcode(OP_SIZE)
+            .push_int(i64::from(Secret::LENGTH_U8))
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_HASH160)
+            .push_slice(&secret_hash)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_DUP)
+            .push_opcode(OP_HASH160)
+            .push_slice(&redeem_identity.into_inner())
+            .push_opcode(OP_ELSE)
+            .push_int(i64::from(expiry))
+            .push_opcode(OP_CLTV)
+            .push_opcode(OP_DROP)
+            .push_opcode(OP_DUP)
+            .push_opcode(OP_HASH160)
+            .push_slice(&refund_identity.into_inner())
+            .push_opcode(OP_ENDIF)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_CHECKSIG)
+            .into_script();
+
+        Hash160BitcoinHtlc { script, expiry }
+    }
+}
+
+impl HtlcScript for Hash160BitcoinHtlc {
+    fn script_pubkey(&self, network: Network) -> Script {
+        self.address(network).script_pubkey()
+    }
+
+    fn address(&self, network: Network) -> Address {
+        Address::p2wsh(&self.script, network)
+    }
+
+    fn unlock_with_secret(
+        self,
+        secp: &Secp256k1<All>,
+        key: SecretKey,
+        secret: Secret,
+    ) -> UnlockParameters {
+        UnlockParameters {
+            witness: vec![
+                Witness::Signature(key),
+                Witness::PublicKey(PublicKey::from_secret_key(secp, &key)),
+                Witness::Data(secret.as_raw_secret().to_vec()),
+                Witness::Bool(true),
+                Witness::PrevScript,
+            ],
+            sequence: SEQUENCE_ALLOW_NTIMELOCK_NO_RBF,
+            locktime: 0,
+            prev_script: self.script,
+        }
+    }
+
+    fn unlock_after_timeout(self, secp: &Secp256k1<All>, key: SecretKey) -> UnlockParameters {
+        UnlockParameters {
+            witness: vec![
+                Witness::Signature(key),
+                Witness::PublicKey(PublicKey::from_secret_key(secp, &key)),
+                Witness::Bool(false),
+                Witness::PrevScript,
+            ],
+            sequence: SEQUENCE_ALLOW_NTIMELOCK_NO_RBF,
+            locktime: self.expiry,
+            prev_script: self.script,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use spectral::prelude::*;
+
+    fn htlc() -> Hash160BitcoinHtlc {
+        Hash160BitcoinHtlc::new(
+            1_600_000_000,
+            hash160::Hash::from_inner([0x11; 20]),
+            hash160::Hash::from_inner([0x22; 20]),
+            [0x33; 20],
+        )
+    }
+
+    #[test]
+    fn script_hashes_the_secret_with_hash160() {
+        let htlc = htlc();
+
+        let expected = concat!(
+            "63",                                         // OP_IF
+            "82",                                         // OP_SIZE
+            "0120",                                       // 32
+            "88",                                         // OP_EQUALVERIFY
+            "a9",                                         // OP_HASH160
+            "143333333333333333333333333333333333333333", // secret hash
+            "88",                                         // OP_EQUALVERIFY
+            "76",                                         // OP_DUP
+            "a9",                                         // OP_HASH160
+            "142222222222222222222222222222222222222222", // redeem identity
+            "67",                                         // OP_ELSE
+            "0400105e5f",                                 // 1_600_000_000
+            "b1",                                         // OP_CHECKLOCKTIMEVERIFY
+            "75",                                         // OP_DROP
+            "76",                                         // OP_DUP
+            "a9",                                         // OP_HASH160
+            "141111111111111111111111111111111111111111", // refund identity
+            "68",                                         // OP_ENDIF
+            "88",                                         // OP_EQUALVERIFY
+            "ac",                                         // OP_CHECKSIG
+        );
+
+        assert_that(&hex::encode(htlc.script.as_bytes())).is_equal_to(expected.to_owned());
+    }
+
+    #[test]
+    fn script_pubkey_is_p2wsh() {
+        let htlc = htlc();
+
+        // version 0, push 32 bytes, SHA-256 of the script
+        let mut expected = vec![0x00, 0x20];
+        expected.extend(Sha256::digest(htlc.script.as_bytes()).iter());
+
+        assert_that(&htlc.script_pubkey(Network::Regtest).as_bytes().to_vec())
+            .is_equal_to(expected);
+    }
+
+    #[test]
+    fn refund_is_locked_until_the_expiry() {
+        let htlc = htlc();
+        let key = SecretKey::from_slice(&[0x44; 32]).unwrap();
+
+        let unlock = htlc.unlock_after_timeout(&*crate::SECP, key);
+
+        assert_that(&unlock.locktime).is_equal_to(1_600_000_000);
+        assert_that(&unlock.sequence).is_equal_to(SEQUENCE_ALLOW_NTIMELOCK_NO_RBF);
+    }
+}
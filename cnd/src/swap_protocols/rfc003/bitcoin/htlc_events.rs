@@ -1,13 +1,13 @@
 use crate::{
     asset,
     btsieve::bitcoin::{
-        watch_for_created_outpoint, watch_for_spent_outpoint, BitcoindConnector, Cache,
+        watch_for_created_output, watch_for_spent_outpoint, BitcoindConnector, Cache,
     },
     htlc_location, identity,
     swap_protocols::{
-        ledger::bitcoin,
+        ledger::UtxoChain,
         rfc003::{
-            bitcoin::extract_secret::extract_secret,
+            bitcoin::{extract_secret::extract_secret, htlc_outputs},
             create_swap::HtlcParams,
             events::{
                 Deployed, Funded, HtlcDeployed, HtlcFunded, HtlcRedeemed, HtlcRefunded, Redeemed,
@@ -26,7 +26,7 @@ impl<B>
     HtlcFunded<B, asset::Bitcoin, htlc_location::Bitcoin, identity::Bitcoin, transaction::Bitcoin>
     for Cache<BitcoindConnector>
 where
-    B: bitcoin::Bitcoin + bitcoin::Network,
+    B: UtxoChain,
 {
     async fn htlc_funded(
         &self,
@@ -37,8 +37,12 @@ where
         let expected_asset = htlc_params.asset;
 
         let tx = &htlc_deployment.transaction;
-        let asset =
-            asset::Bitcoin::from_sat(tx.output[htlc_deployment.location.vout as usize].value);
+        let asset = asset::Bitcoin::from_sat(
+            htlc_outputs(tx, &htlc_params.script_pubkey()?)
+                .into_iter()
+                .map(|(_, amount)| amount.as_sat())
+                .sum(),
+        );
 
         let event = match expected_asset.cmp(&asset) {
             Ordering::Equal => Funded::Correctly {
@@ -60,7 +64,7 @@ impl<B>
     HtlcDeployed<B, asset::Bitcoin, htlc_location::Bitcoin, identity::Bitcoin, transaction::Bitcoin>
     for Cache<BitcoindConnector>
 where
-    B: bitcoin::Bitcoin + bitcoin::Network,
+    B: UtxoChain,
 {
     async fn htlc_deployed(
         &self,
@@ -68,7 +72,7 @@ where
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Deployed<htlc_location::Bitcoin, transaction::Bitcoin>> {
         let (transaction, location) =
-            watch_for_created_outpoint(self, start_of_swap, htlc_params.compute_address())
+            watch_for_created_output(self, start_of_swap, htlc_params.script_pubkey()?)
                 .instrument(tracing::info_span!("htlc_deployed"))
                 .await?;
 
@@ -84,7 +88,7 @@ impl<B>
     HtlcRedeemed<B, asset::Bitcoin, htlc_location::Bitcoin, identity::Bitcoin, transaction::Bitcoin>
     for Cache<BitcoindConnector>
 where
-    B: bitcoin::Bitcoin + bitcoin::Network,
+    B: UtxoChain,
 {
     async fn htlc_redeemed(
         &self,
@@ -101,8 +105,12 @@ where
         .instrument(tracing::info_span!("htlc_redeemed"))
         .await?;
 
-        let secret = extract_secret(&transaction, &htlc_params.secret_hash)
-            .expect("Redeem transaction must contain secret");
+        let secret = extract_secret(
+            &transaction,
+            &htlc_params.secret_hash,
+            htlc_params.hash_function,
+        )
+        .expect("Redeem transaction must contain secret");
 
         Ok(Redeemed {
             transaction,
@@ -116,7 +124,7 @@ impl<B>
     HtlcRefunded<B, asset::Bitcoin, htlc_location::Bitcoin, identity::Bitcoin, transaction::Bitcoin>
     for Cache<BitcoindConnector>
 where
-    B: bitcoin::Bitcoin + bitcoin::Network,
+    B: UtxoChain,
 {
     async fn htlc_refunded(
         &self,
@@ -3,7 +3,8 @@ pub mod actions;
 use crate::{
     seed::SwapSeed,
     swap_protocols::rfc003::{
-        ledger_state::LedgerState, messages::Request, DeriveIdentities, SwapCommunication,
+        ledger_state::LedgerState, messages::Request, DeriveIdentities, LedgerTimes,
+        SwapCommunication,
     },
 };
 use derivative::Derivative;
@@ -15,6 +16,7 @@ pub struct State<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT> {
     pub swap_communication: SwapCommunication<AL, BL, AA, BA, AI, BI>,
     pub alpha_ledger_state: LedgerState<AA, AH, AT>,
     pub beta_ledger_state: LedgerState<BA, BH, BT>,
+    pub ledger_times: LedgerTimes,
     #[derivative(Debug = "ignore")]
     pub secret_source: Arc<dyn DeriveIdentities>,
 }
@@ -24,12 +26,14 @@ impl<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT> State<AL, BL, AA, BA, AH, BH, AI, B
         swap_communication: SwapCommunication<AL, BL, AA, BA, AI, BI>,
         alpha_ledger_state: LedgerState<AA, AH, AT>,
         beta_ledger_state: LedgerState<BA, BH, BT>,
+        ledger_times: LedgerTimes,
         secret_source: SwapSeed,
     ) -> Self {
         Self {
             swap_communication,
             alpha_ledger_state,
             beta_ledger_state,
+            ledger_times,
             secret_source: Arc::new(secret_source),
         }
     }
@@ -38,7 +42,8 @@ impl<AL, BL, AA, BA, AH, BH, AI, BI, AT, BT> State<AL, BL, AA, BA, AH, BH, AI, B
         match &self.swap_communication {
             SwapCommunication::Accepted { request, .. }
             | SwapCommunication::Proposed { request, .. }
-            | SwapCommunication::Declined { request, .. } => request,
+            | SwapCommunication::Declined { request, .. }
+            | SwapCommunication::Failed { request } => request,
         }
     }
 }
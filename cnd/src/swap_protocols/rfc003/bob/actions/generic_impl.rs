@@ -1,7 +1,9 @@
 use crate::swap_protocols::{
     actions::Actions,
     rfc003::{
-        actions::{Accept, Action, Decline, MakeFundAction, MakeRedeemAction, MakeRefundAction},
+        actions::{
+            buildable, Accept, Action, Decline, MakeFundAction, MakeRedeemAction, MakeRefundAction,
+        },
         bob,
         create_swap::HtlcParams,
         LedgerState, SwapCommunication,
@@ -28,7 +30,11 @@ where
             HtlcLocation = BH,
             FundTransaction = BT,
         >,
-    (AL, AA): MakeRedeemAction<HtlcParams = HtlcParams<AL, AA, AI>, HtlcLocation = AH>,
+    (AL, AA): MakeRedeemAction<
+        HtlcParams = HtlcParams<AL, AA, AI>,
+        HtlcLocation = AH,
+        FundTransaction = AT,
+    >,
 {
     #[allow(clippy::type_complexity)]
     type ActionKind = Action<
@@ -57,21 +63,31 @@ where
 
         let alpha_state = &self.alpha_ledger_state;
         let beta_state = &self.beta_ledger_state;
+        let beta_refundable = self.ledger_times.beta.map_or(false, |time| {
+            <(BL, BA)>::is_refundable(request.beta_expiry, time)
+        });
 
         use self::LedgerState::*;
         let mut actions = match (alpha_state, beta_state) {
-            (Funded { htlc_location, .. }, Redeemed { secret, .. }) => {
-                vec![Action::Redeem(<(AL, AA)>::make_redeem_action(
-                    HtlcParams::new_alpha_params(request, response),
-                    htlc_location.clone(),
-                    &*self.secret_source, // Derive identities with this.
-                    *secret,              /* Bob uses the secret learned from Alice redeem
-                                           * action. */
-                ))]
+            (
+                Funded {
+                    htlc_location,
+                    fund_transaction,
+                    ..
+                },
+                Redeemed { secret, .. },
+            ) => vec![<(AL, AA)>::make_redeem_action(
+                HtlcParams::new_alpha_params(request, response),
+                htlc_location.clone(),
+                &*self.secret_source, // Derive identities with this.
+                fund_transaction,
+                *secret, // Bob uses the secret learned from Alice redeem action.
+            )
+            .map(Action::Redeem)],
+            (Funded { .. }, NotDeployed) => {
+                let htlc_params = HtlcParams::new_beta_params(request, response);
+                vec![<(BL, BA)>::make_fund_action(htlc_params).map(Action::Fund)]
             }
-            (Funded { .. }, NotDeployed) => vec![Action::Fund(<(BL, BA)>::make_fund_action(
-                HtlcParams::new_beta_params(request, response),
-            ))],
             _ => vec![],
         };
 
@@ -86,14 +102,19 @@ where
             ..
         } = beta_state
         {
-            actions.push(Action::Refund(<(BL, BA)>::make_refund_action(
-                HtlcParams::new_beta_params(request, response),
-                htlc_location.clone(),
-                &*self.secret_source,
-                fund_transaction,
-            )))
+            if beta_refundable {
+                actions.push(
+                    <(BL, BA)>::make_refund_action(
+                        HtlcParams::new_beta_params(request, response),
+                        htlc_location.clone(),
+                        &*self.secret_source,
+                        fund_transaction,
+                    )
+                    .map(Action::Refund),
+                )
+            }
         }
 
-        actions
+        buildable(actions)
     }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    db::{LedgerEvent, LoadLedgerEvents, Save},
     swap_protocols::{
         rfc003::{
             self,
@@ -8,7 +9,7 @@ use crate::{
             },
             Accept, LedgerState, Request, SecretHash,
         },
-        state, HashFunction, InsertFailedSwap, SwapId,
+        state, HashFunction, InsertFailedSwap, LedgerSide, SwapId,
     },
     timestamp::Timestamp,
 };
@@ -18,6 +19,7 @@ use genawaiter::{
     sync::{Co, Gen},
     GeneratorState,
 };
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Returns a future that tracks the swap negotiated from the given request and
@@ -30,15 +32,23 @@ use std::sync::Arc;
 /// - refunded
 ///
 /// It is highly unlikely for Bob to fund the HTLC now, yet the current
-/// implementation is still waiting for that.
+/// implementation is still waiting for that. The watchdog in
+/// `rfc003::watchdog` warns the user about such swaps instead.
+///
+/// Every event is persisted so that the ledger state can be restored after a
+/// restart. Events that were already observed in a previous run are replayed
+/// into the ledger state before watching the ledger again.
 pub async fn create_watcher<D, S, L, A, H, I, T>(
     dependencies: D,
     ledger_state: Arc<S>,
     id: SwapId,
+    side: LedgerSide,
     htlc_params: HtlcParams<L, A, I>,
     accepted_at: NaiveDateTime,
 ) where
     D: InsertFailedSwap
+        + Save<LedgerEvent<A, H, T>>
+        + LoadLedgerEvents<A, H, T>
         + HtlcFunded<L, A, H, I, T>
         + HtlcDeployed<L, A, H, I, T>
         + HtlcRedeemed<L, A, H, I, T>
@@ -50,9 +60,7 @@ pub async fn create_watcher<D, S, L, A, H, I, T>(
     I: Clone,
     T: Clone,
 {
-    ledger_state
-        .insert(id, LedgerState::<A, H, T>::NotDeployed)
-        .await;
+    let known_events = restore_ledger_state(&dependencies, &*ledger_state, id, side).await;
 
     // construct a generator that watches alpha and beta ledger concurrently
     let mut generator = Gen::new({
@@ -66,8 +74,29 @@ pub async fn create_watcher<D, S, L, A, H, I, T>(
         match generator.async_resume().await {
             // every event that is yielded is passed on
             GeneratorState::Yielded(event) => {
+                // the ledger is watched from the start of the swap, hence events we already
+                // know about are observed again after a restart
+                if known_events
+                    .iter()
+                    .any(|known| std::mem::discriminant(known) == std::mem::discriminant(&event))
+                {
+                    tracing::debug!("swap {} observed known event {}", id, event);
+                    continue;
+                }
+
                 tracing::info!("swap {} yielded event {}", id, event);
-                ledger_state.update(&id, event).await;
+                ledger_state.update(&id, event.clone()).await;
+
+                if let Err(e) = dependencies
+                    .save(LedgerEvent {
+                        swap_id: id,
+                        side,
+                        event,
+                    })
+                    .await
+                {
+                    tracing::warn!("failed to persist event of swap {}: {:?}", id, e);
+                }
             }
             // the generator stopped executing, this means there are no more events that can be
             // watched.
@@ -84,6 +113,39 @@ pub async fn create_watcher<D, S, L, A, H, I, T>(
     }
 }
 
+/// Restores the state of a ledger of a swap from the events that were
+/// observed in a previous run, returns these events.
+pub async fn restore_ledger_state<D, S, A, H, T>(
+    dependencies: &D,
+    ledger_state: &S,
+    id: SwapId,
+    side: LedgerSide,
+) -> Vec<SwapEvent<A, H, T>>
+where
+    D: LoadLedgerEvents<A, H, T>,
+    S: state::Update<SwapEvent<A, H, T>> + state::Insert<LedgerState<A, H, T>>,
+    A: Clone,
+    H: Clone,
+    T: Clone,
+{
+    ledger_state
+        .insert(id, LedgerState::<A, H, T>::NotDeployed)
+        .await;
+
+    let known_events = match dependencies.load_ledger_events(&id, side).await {
+        Ok(events) => events,
+        Err(e) => {
+            tracing::warn!("failed to load known events of swap {}: {:?}", id, e);
+            Vec::new()
+        }
+    };
+    for event in known_events.iter().cloned() {
+        ledger_state.update(&id, event).await;
+    }
+
+    known_events
+}
+
 /// Returns a future that waits for events to happen on a ledger.
 ///
 /// Each event is yielded through the controller handle (co) of the coroutine.
@@ -140,9 +202,62 @@ pub struct HtlcParams<L, A, I> {
     pub redeem_identity: I,
     pub refund_identity: I,
     pub expiry: Timestamp,
+    pub hash_function: HashFunction,
     pub secret_hash: SecretHash,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum UnsupportedSecretHash {
+    #[error("rfc003 HTLCs don't support {0}")]
+    HashFunction(HashFunction),
+    #[error("secret hash has {got} bytes, {hash_function} hashes have {expected}")]
+    Length {
+        hash_function: HashFunction,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl<L, A, I> HtlcParams<L, A, I> {
+    /// The secret hash in the form the SHA-256 HTLC templates expect it.
+    ///
+    /// Requests that negotiate a hash function without templates or whose
+    /// secret hash doesn't match the negotiated one are declined, this only
+    /// fails for swaps that were stored before that.
+    pub fn sha256_secret_hash(&self) -> Result<[u8; 32], UnsupportedSecretHash> {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(self.secret_hash_of(HashFunction::Sha256)?);
+
+        Ok(hash)
+    }
+
+    /// The secret hash in the form the RIPEMD160(SHA-256) HTLC templates
+    /// expect it.
+    pub fn ripemd160_sha256_secret_hash(&self) -> Result<[u8; 20], UnsupportedSecretHash> {
+        let mut hash = [0u8; 20];
+        hash.copy_from_slice(self.secret_hash_of(HashFunction::Ripemd160Sha256)?);
+
+        Ok(hash)
+    }
+
+    fn secret_hash_of(&self, hash_function: HashFunction) -> Result<&[u8], UnsupportedSecretHash> {
+        if self.hash_function != hash_function {
+            return Err(UnsupportedSecretHash::HashFunction(self.hash_function));
+        }
+
+        let secret_hash = self.secret_hash.as_raw();
+        if secret_hash.len() != hash_function.digest_length() {
+            return Err(UnsupportedSecretHash::Length {
+                hash_function,
+                expected: hash_function.digest_length(),
+                got: secret_hash.len(),
+            });
+        }
+
+        Ok(secret_hash)
+    }
+}
+
 impl<L, A, I> HtlcParams<L, A, I>
 where
     L: Clone,
@@ -159,6 +274,7 @@ where
             redeem_identity: accept_response.alpha_ledger_redeem_identity.clone(),
             refund_identity: request.alpha_ledger_refund_identity.clone(),
             expiry: request.alpha_expiry,
+            hash_function: request.hash_function,
             secret_hash: request.secret_hash,
         }
     }
@@ -173,6 +289,7 @@ where
             redeem_identity: request.beta_ledger_redeem_identity.clone(),
             refund_identity: accept_response.beta_ledger_refund_identity.clone(),
             expiry: request.beta_expiry,
+            hash_function: request.hash_function,
             secret_hash: request.secret_hash,
         }
     }
@@ -226,6 +343,7 @@ where
             redeem_identity: self.alpha_ledger_redeem_identity.clone(),
             refund_identity: self.alpha_ledger_refund_identity.clone(),
             expiry: self.alpha_expiry,
+            hash_function: self.hash_function,
             secret_hash: self.secret_hash,
         }
     }
@@ -244,12 +362,13 @@ where
             redeem_identity: self.beta_ledger_redeem_identity.clone(),
             refund_identity: self.beta_ledger_refund_identity.clone(),
             expiry: self.beta_expiry,
+            hash_function: self.hash_function,
             secret_hash: self.secret_hash,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, strum_macros::Display)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, strum_macros::Display)]
 pub enum SwapEvent<A, H, T> {
     Deployed(Deployed<H, T>),
     Funded(Funded<A, T>),
@@ -276,4 +395,63 @@ mod tests {
 
         assert_eq!(formatted, "Deployed")
     }
+
+    fn htlc_params(hash_function: HashFunction, secret_hash: SecretHash) -> HtlcParams<(), (), ()> {
+        HtlcParams {
+            asset: (),
+            ledger: (),
+            redeem_identity: (),
+            refund_identity: (),
+            expiry: Timestamp::from(0),
+            hash_function,
+            secret_hash,
+        }
+    }
+
+    #[test]
+    fn only_32_byte_sha256_secret_hashes_are_supported() {
+        let hash = [1u8; 32];
+
+        assert_eq!(
+            htlc_params(HashFunction::Sha256, SecretHash::from(hash)).sha256_secret_hash(),
+            Ok(hash)
+        );
+        assert_eq!(
+            htlc_params(HashFunction::Sha256, SecretHash::from([1u8; 20])).sha256_secret_hash(),
+            Err(UnsupportedSecretHash::Length {
+                hash_function: HashFunction::Sha256,
+                expected: 32,
+                got: 20
+            })
+        );
+        assert_eq!(
+            htlc_params(HashFunction::Sha3_256, SecretHash::from(hash)).sha256_secret_hash(),
+            Err(UnsupportedSecretHash::HashFunction(HashFunction::Sha3_256))
+        );
+    }
+
+    #[test]
+    fn only_20_byte_ripemd160_sha256_secret_hashes_are_supported() {
+        let hash = [1u8; 20];
+
+        assert_eq!(
+            htlc_params(HashFunction::Ripemd160Sha256, SecretHash::from(hash))
+                .ripemd160_sha256_secret_hash(),
+            Ok(hash)
+        );
+        assert_eq!(
+            htlc_params(HashFunction::Ripemd160Sha256, SecretHash::from([1u8; 32]))
+                .ripemd160_sha256_secret_hash(),
+            Err(UnsupportedSecretHash::Length {
+                hash_function: HashFunction::Ripemd160Sha256,
+                expected: 20,
+                got: 32
+            })
+        );
+        assert_eq!(
+            htlc_params(HashFunction::Sha256, SecretHash::from([1u8; 32]))
+                .ripemd160_sha256_secret_hash(),
+            Err(UnsupportedSecretHash::HashFunction(HashFunction::Sha256))
+        );
+    }
 }
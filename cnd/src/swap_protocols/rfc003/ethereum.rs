@@ -1,37 +1,94 @@
+mod hash160_htlc;
 pub mod htlc_events;
 
+use self::hash160_htlc::{Hash160Erc20Htlc, Hash160EtherHtlc};
+
 use crate::{
     asset,
     ethereum::Bytes,
     identity,
-    swap_protocols::{ledger::Ethereum, rfc003::create_swap::HtlcParams},
+    swap_protocols::{
+        ledger::Ethereum,
+        rfc003::create_swap::{HtlcParams, UnsupportedSecretHash},
+        HashFunction,
+    },
 };
 use blockchain_contracts::ethereum::rfc003::{erc20_htlc::Erc20Htlc, ether_htlc::EtherHtlc};
+use std::convert::TryFrom;
+
+impl TryFrom<HtlcParams<Ethereum, asset::Ether, identity::Ethereum>> for EtherHtlc {
+    type Error = UnsupportedSecretHash;
 
-impl From<HtlcParams<Ethereum, asset::Ether, identity::Ethereum>> for EtherHtlc {
-    fn from(htlc_params: HtlcParams<Ethereum, asset::Ether, identity::Ethereum>) -> Self {
+    fn try_from(
+        htlc_params: HtlcParams<Ethereum, asset::Ether, identity::Ethereum>,
+    ) -> Result<Self, Self::Error> {
         let refund_address =
             blockchain_contracts::ethereum::Address(htlc_params.refund_identity.into());
         let redeem_address =
             blockchain_contracts::ethereum::Address(htlc_params.redeem_identity.into());
 
-        EtherHtlc::new(
+        Ok(EtherHtlc::new(
             htlc_params.expiry.into(),
             refund_address,
             redeem_address,
-            htlc_params.secret_hash.into(),
-        )
+            htlc_params.sha256_secret_hash()?,
+        ))
     }
 }
 
+impl TryFrom<HtlcParams<Ethereum, asset::Ether, identity::Ethereum>> for Hash160EtherHtlc {
+    type Error = UnsupportedSecretHash;
+
+    fn try_from(
+        htlc_params: HtlcParams<Ethereum, asset::Ether, identity::Ethereum>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Hash160EtherHtlc::new(
+            htlc_params.expiry.into(),
+            htlc_params.refund_identity,
+            htlc_params.redeem_identity,
+            htlc_params.ripemd160_sha256_secret_hash()?,
+        ))
+    }
+}
+
+/// The template of the HTLC depends on the hash function of the swap, SHA3-256
+/// has none.
 impl HtlcParams<Ethereum, asset::Ether, identity::Ethereum> {
-    pub fn bytecode(&self) -> Bytes {
-        EtherHtlc::from(self.clone()).into()
+    pub fn bytecode(&self) -> Result<Bytes, UnsupportedSecretHash> {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Ok(Hash160EtherHtlc::try_from(self.clone())?.into()),
+            _ => Ok(EtherHtlc::try_from(self.clone())?.into()),
+        }
+    }
+
+    pub fn deploy_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160EtherHtlc::deploy_tx_gas_limit(),
+            _ => EtherHtlc::deploy_tx_gas_limit(),
+        }
+    }
+
+    pub fn redeem_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160EtherHtlc::redeem_tx_gas_limit(),
+            _ => EtherHtlc::redeem_tx_gas_limit(),
+        }
+    }
+
+    pub fn refund_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160EtherHtlc::refund_tx_gas_limit(),
+            _ => EtherHtlc::refund_tx_gas_limit(),
+        }
     }
 }
 
-impl From<HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>> for Erc20Htlc {
-    fn from(htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>) -> Self {
+impl TryFrom<HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>> for Erc20Htlc {
+    type Error = UnsupportedSecretHash;
+
+    fn try_from(
+        htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
+    ) -> Result<Self, Self::Error> {
         let refund_address =
             blockchain_contracts::ethereum::Address(htlc_params.refund_identity.into());
         let redeem_address =
@@ -39,19 +96,106 @@ impl From<HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>> for Erc20Htlc
         let token_contract_address =
             blockchain_contracts::ethereum::Address(htlc_params.asset.token_contract.into());
 
-        Erc20Htlc::new(
+        Ok(Erc20Htlc::new(
             htlc_params.expiry.into(),
             refund_address,
             redeem_address,
-            htlc_params.secret_hash.into(),
+            htlc_params.sha256_secret_hash()?,
             token_contract_address,
             htlc_params.asset.quantity.into(),
-        )
+        ))
+    }
+}
+
+impl TryFrom<HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>> for Hash160Erc20Htlc {
+    type Error = UnsupportedSecretHash;
+
+    fn try_from(
+        htlc_params: HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
+    ) -> Result<Self, Self::Error> {
+        let secret_hash = htlc_params.ripemd160_sha256_secret_hash()?;
+
+        Ok(Hash160Erc20Htlc::new(
+            htlc_params.expiry.into(),
+            htlc_params.refund_identity,
+            htlc_params.redeem_identity,
+            secret_hash,
+            htlc_params.asset.token_contract,
+            htlc_params.asset.quantity,
+        ))
     }
 }
 
 impl HtlcParams<Ethereum, asset::Erc20, identity::Ethereum> {
-    pub fn bytecode(&self) -> Bytes {
-        Erc20Htlc::from(self.clone()).into()
+    pub fn bytecode(&self) -> Result<Bytes, UnsupportedSecretHash> {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Ok(Hash160Erc20Htlc::try_from(self.clone())?.into()),
+            _ => Ok(Erc20Htlc::try_from(self.clone())?.into()),
+        }
+    }
+
+    pub fn deploy_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160Erc20Htlc::deploy_tx_gas_limit(),
+            _ => Erc20Htlc::deploy_tx_gas_limit(),
+        }
+    }
+
+    pub fn redeem_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160Erc20Htlc::redeem_tx_gas_limit(),
+            _ => Erc20Htlc::redeem_tx_gas_limit(),
+        }
+    }
+
+    pub fn refund_tx_gas_limit(&self) -> u64 {
+        match self.hash_function {
+            HashFunction::Ripemd160Sha256 => Hash160Erc20Htlc::refund_tx_gas_limit(),
+            _ => Erc20Htlc::refund_tx_gas_limit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        swap_protocols::{ledger::ethereum::ChainId, rfc003::Secret},
+        timestamp::Timestamp,
+    };
+    use spectral::prelude::*;
+
+    fn htlc_params(
+        hash_function: HashFunction,
+    ) -> HtlcParams<Ethereum, asset::Ether, identity::Ethereum> {
+        HtlcParams {
+            asset: asset::Ether::zero(),
+            ledger: Ethereum::new(ChainId::regtest()),
+            redeem_identity: identity::Ethereum::from([1u8; 20]),
+            refund_identity: identity::Ethereum::from([2u8; 20]),
+            expiry: Timestamp::from(1_600_000_000),
+            hash_function,
+            secret_hash: Secret::from([1u8; 32]).hash(hash_function),
+        }
+    }
+
+    #[test]
+    fn bytecode_is_chosen_by_the_hash_function() {
+        let sha256 = htlc_params(HashFunction::Sha256);
+        let hash160 = htlc_params(HashFunction::Ripemd160Sha256);
+
+        let expected: Vec<u8> = Hash160EtherHtlc::try_from(hash160.clone()).unwrap().into();
+
+        assert_that(&hash160.bytecode().unwrap().0).is_equal_to(expected);
+        assert_that(&sha256.bytecode().unwrap().0).is_not_equal_to(hash160.bytecode().unwrap().0);
+        assert_that(&hash160.deploy_tx_gas_limit())
+            .is_equal_to(Hash160EtherHtlc::deploy_tx_gas_limit());
+    }
+
+    #[test]
+    fn there_is_no_sha3_256_bytecode() {
+        assert_that(&htlc_params(HashFunction::Sha3_256).bytecode()).is_equal_to(Err(
+            UnsupportedSecretHash::HashFunction(HashFunction::Sha3_256),
+        ));
     }
 }
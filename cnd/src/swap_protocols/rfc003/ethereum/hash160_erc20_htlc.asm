@@ -0,0 +1,150 @@
+;; ERC20 HTLC locked with a RIPEMD160(SHA-256) secret hash.
+;;
+;; Calling the contract with the 32 byte secret as data transfers <amount>
+;; tokens of <token_contract> to <redeem_address> and logs the secret under
+;; <redeemed_topic>. Calling it without data once the block timestamp reached
+;; <expiry> transfers them to <refund_address> and logs <refunded_topic>.
+;; Everything else, including a failed or refused transfer, reverts.
+;;
+;; The <placeholders> are filled in by `Hash160Erc20Htlc::new`.
+
+;; Deploy: return the runtime code that follows these 12 bytes.
+    PUSH2 0x01fe                ; length of the runtime code
+    DUP1
+    PUSH1 0x0c
+    PUSH1 0x00
+    CODECOPY
+    PUSH1 0x00
+    RETURN
+
+;; Runtime, jump destinations are offsets into the runtime code.
+    CALLDATASIZE
+    PUSH1 0x20
+    EQ
+    PUSH2 @redeem
+    JUMPI
+
+    PUSH4 <expiry>
+    TIMESTAMP
+    LT
+    PUSH2 @revert
+    JUMPI
+
+    ;; transfer(<refund_address>, <amount>), tokens that don't return a value
+    ;; leave the 1 at memory[0xa4]
+    PUSH1 0x01
+    PUSH1 0xa4
+    MSTORE
+    PUSH32 0xa9059cbb00000000000000000000000000000000000000000000000000000000
+    PUSH1 0x60
+    MSTORE
+    PUSH20 <refund_address>
+    PUSH1 0x64
+    MSTORE
+    PUSH32 <amount>
+    PUSH1 0x84
+    MSTORE
+    PUSH1 0x20
+    PUSH1 0xa4
+    PUSH1 0x44
+    PUSH1 0x60
+    PUSH1 0x00
+    PUSH20 <token_contract>
+    GAS
+    CALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+    PUSH1 0xa4
+    MLOAD
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    PUSH32 <refunded_topic>
+    PUSH1 0x00
+    PUSH1 0x00
+    LOG1
+    PUSH20 <refund_address>
+    SELFDESTRUCT
+
+redeem:
+    ;; memory[0x00] = secret
+    PUSH1 0x20
+    PUSH1 0x00
+    PUSH1 0x00
+    CALLDATACOPY
+
+    ;; memory[0x20] = SHA-256(secret)
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x00
+    PUSH1 0x02
+    GAS
+    STATICCALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    ;; memory[0x40] = RIPEMD-160(SHA-256(secret)), right aligned
+    PUSH1 0x20
+    PUSH1 0x40
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x03
+    GAS
+    STATICCALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    PUSH1 0x40
+    MLOAD
+    PUSH20 <secret_hash>
+    EQ
+    PUSH2 @redeem_with_secret
+    JUMPI
+
+revert:
+    PUSH1 0x00
+    DUP1
+    REVERT
+
+redeem_with_secret:
+    ;; transfer(<redeem_address>, <amount>)
+    PUSH1 0x01
+    PUSH1 0xa4
+    MSTORE
+    PUSH32 0xa9059cbb00000000000000000000000000000000000000000000000000000000
+    PUSH1 0x60
+    MSTORE
+    PUSH20 <redeem_address>
+    PUSH1 0x64
+    MSTORE
+    PUSH32 <amount>
+    PUSH1 0x84
+    MSTORE
+    PUSH1 0x20
+    PUSH1 0xa4
+    PUSH1 0x44
+    PUSH1 0x60
+    PUSH1 0x00
+    PUSH20 <token_contract>
+    GAS
+    CALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+    PUSH1 0xa4
+    MLOAD
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    PUSH32 <redeemed_topic>
+    PUSH1 0x20
+    PUSH1 0x00
+    LOG1
+    PUSH20 <redeem_address>
+    SELFDESTRUCT
@@ -0,0 +1,88 @@
+;; Ether HTLC locked with a RIPEMD160(SHA-256) secret hash.
+;;
+;; Calling the contract with the 32 byte secret as data sends its balance to
+;; <redeem_address> and logs the secret under <redeemed_topic>. Calling it
+;; without data once the block timestamp reached <expiry> sends its balance
+;; to <refund_address> and logs <refunded_topic>. Everything else reverts.
+;;
+;; The <placeholders> are filled in by `Hash160EtherHtlc::new`.
+
+;; Deploy: return the runtime code that follows these 12 bytes.
+    PUSH2 0x00d8                ; length of the runtime code
+    DUP1
+    PUSH1 0x0c
+    PUSH1 0x00
+    CODECOPY
+    PUSH1 0x00
+    RETURN
+
+;; Runtime, jump destinations are offsets into the runtime code.
+    CALLDATASIZE
+    PUSH1 0x20
+    EQ
+    PUSH2 @redeem
+    JUMPI
+
+    PUSH4 <expiry>
+    TIMESTAMP
+    LT
+    PUSH2 @revert
+    JUMPI
+
+    PUSH32 <refunded_topic>
+    PUSH1 0x00
+    PUSH1 0x00
+    LOG1
+    PUSH20 <refund_address>
+    SELFDESTRUCT
+
+redeem:
+    ;; memory[0x00] = secret
+    PUSH1 0x20
+    PUSH1 0x00
+    PUSH1 0x00
+    CALLDATACOPY
+
+    ;; memory[0x20] = SHA-256(secret)
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x00
+    PUSH1 0x02
+    GAS
+    STATICCALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    ;; memory[0x40] = RIPEMD-160(SHA-256(secret)), right aligned
+    PUSH1 0x20
+    PUSH1 0x40
+    PUSH1 0x20
+    PUSH1 0x20
+    PUSH1 0x03
+    GAS
+    STATICCALL
+    ISZERO
+    PUSH2 @revert
+    JUMPI
+
+    PUSH1 0x40
+    MLOAD
+    PUSH20 <secret_hash>
+    EQ
+    PUSH2 @redeem_with_secret
+    JUMPI
+
+revert:
+    PUSH1 0x00
+    DUP1
+    REVERT
+
+redeem_with_secret:
+    PUSH32 <redeemed_topic>
+    PUSH1 0x20
+    PUSH1 0x00
+    LOG1
+    PUSH20 <redeem_address>
+    SELFDESTRUCT
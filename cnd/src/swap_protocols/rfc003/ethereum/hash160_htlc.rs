@@ -0,0 +1,294 @@
+//! Ether and ERC20 HTLCs locked with a RIPEMD160(SHA-256) secret hash.
+//!
+//! The contracts are listed in `hash160_ether_htlc.asm` and
+//! `hash160_erc20_htlc.asm`, the templates are their bytecode with zeroed
+//! placeholders. They are redeemed and refunded with the same calls and log
+//! the same topics as the SHA-256 HTLCs of `blockchain_contracts`.
+
+use crate::{asset::Erc20Quantity, ethereum::Hash, identity};
+use blockchain_contracts::ethereum::{
+    rfc003::{REDEEMED_LOG_MSG, REFUNDED_LOG_MSG},
+    TokenQuantity,
+};
+
+const ETHER_HTLC_TEMPLATE: &str = concat!(
+    "6100d880600c6000396000f33660201461004f5763000000004210610096577f",
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "60006000a1730000000000000000000000000000000000000000ff5b60206000",
+    "600037602060206020600060025afa1561009657602060406020602060035afa",
+    "1561009657604051730000000000000000000000000000000000000000146100",
+    "9b575b600080fd5b7f0000000000000000000000000000000000000000000000",
+    "00000000000000000060206000a1730000000000000000000000000000000000",
+    "000000ff",
+);
+
+const ERC20_HTLC_TEMPLATE: &str = concat!(
+    "6101fe80600c6000396000f3366020146100e257630000000042106101295760",
+    "0160a4527fa9059cbb0000000000000000000000000000000000000000000000",
+    "0000000000606052730000000000000000000000000000000000000000606452",
+    "7f00000000000000000000000000000000000000000000000000000000000000",
+    "00608452602060a4604460606000730000000000000000000000000000000000",
+    "0000005af1156101295760a45115610129577f00000000000000000000000000",
+    "0000000000000000000000000000000000000060006000a17300000000000000",
+    "00000000000000000000000000ff5b6020600060003760206020602060006002",
+    "5afa1561012957602060406020602060035afa15610129576040517300000000",
+    "000000000000000000000000000000001461012e575b600080fd5b600160a452",
+    "7fa9059cbb000000000000000000000000000000000000000000000000000000",
+    "006060527300000000000000000000000000000000000000006064527f000000",
+    "0000000000000000000000000000000000000000000000000000000000608452",
+    "602060a46044606060007300000000000000000000000000000000000000005a",
+    "f1156101295760a45115610129577f0000000000000000000000000000000000",
+    "00000000000000000000000000000060206000a1730000000000000000000000",
+    "000000000000000000ff",
+);
+
+fn from_template(template: &str) -> Vec<u8> {
+    hex::decode(template).expect("template to be valid hex")
+}
+
+/// Writes `value` over the placeholder at each of the `offsets`.
+fn fill(code: &mut [u8], offsets: &[usize], value: &[u8]) {
+    for offset in offsets {
+        code[*offset..*offset + value.len()].copy_from_slice(value);
+    }
+}
+
+fn log_topic(log_msg: &str) -> [u8; 32] {
+    log_msg.parse::<Hash>().expect("to be valid hex").into()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hash160EtherHtlc(Vec<u8>);
+
+impl Hash160EtherHtlc {
+    const EXPIRY: &'static [usize] = &[21];
+    const REFUNDED_TOPIC: &'static [usize] = &[32];
+    const REFUND_ADDRESS: &'static [usize] = &[70];
+    const SECRET_HASH: &'static [usize] = &[137];
+    const REDEEMED_TOPIC: &'static [usize] = &[169];
+    const REDEEM_ADDRESS: &'static [usize] = &[207];
+
+    pub fn new(
+        expiry: u32,
+        refund_address: identity::Ethereum,
+        redeem_address: identity::Ethereum,
+        secret_hash: [u8; 20],
+    ) -> Self {
+        let mut code = from_template(ETHER_HTLC_TEMPLATE);
+
+        fill(&mut code, Self::EXPIRY, &expiry.to_be_bytes());
+        fill(
+            &mut code,
+            Self::REFUNDED_TOPIC,
+            &log_topic(REFUNDED_LOG_MSG),
+        );
+        fill(
+            &mut code,
+            Self::REFUND_ADDRESS,
+            &<[u8; 20]>::from(refund_address),
+        );
+        fill(&mut code, Self::SECRET_HASH, &secret_hash);
+        fill(
+            &mut code,
+            Self::REDEEMED_TOPIC,
+            &log_topic(REDEEMED_LOG_MSG),
+        );
+        fill(
+            &mut code,
+            Self::REDEEM_ADDRESS,
+            &<[u8; 20]>::from(redeem_address),
+        );
+
+        Hash160EtherHtlc(code)
+    }
+
+    pub fn deploy_tx_gas_limit() -> u64 {
+        130_000
+    }
+
+    pub fn redeem_tx_gas_limit() -> u64 {
+        100_000
+    }
+
+    pub fn refund_tx_gas_limit() -> u64 {
+        100_000
+    }
+}
+
+impl From<Hash160EtherHtlc> for Vec<u8> {
+    fn from(htlc: Hash160EtherHtlc) -> Self {
+        htlc.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hash160Erc20Htlc(Vec<u8>);
+
+impl Hash160Erc20Htlc {
+    const EXPIRY: &'static [usize] = &[21];
+    const REFUND_ADDRESS: &'static [usize] = &[73, 217];
+    const AMOUNT: &'static [usize] = &[97, 381];
+    const TOKEN_CONTRACT: &'static [usize] = &[143, 427];
+    const REFUNDED_TOPIC: &'static [usize] = &[179];
+    const SECRET_HASH: &'static [usize] = &[284];
+    const REDEEM_ADDRESS: &'static [usize] = &[357, 501];
+    const REDEEMED_TOPIC: &'static [usize] = &[463];
+
+    pub fn new(
+        expiry: u32,
+        refund_address: identity::Ethereum,
+        redeem_address: identity::Ethereum,
+        secret_hash: [u8; 20],
+        token_contract: identity::Ethereum,
+        amount: Erc20Quantity,
+    ) -> Self {
+        let mut code = from_template(ERC20_HTLC_TEMPLATE);
+
+        fill(&mut code, Self::EXPIRY, &expiry.to_be_bytes());
+        fill(
+            &mut code,
+            Self::REFUND_ADDRESS,
+            &<[u8; 20]>::from(refund_address),
+        );
+        fill(&mut code, Self::AMOUNT, &TokenQuantity::from(amount).0);
+        fill(
+            &mut code,
+            Self::TOKEN_CONTRACT,
+            &<[u8; 20]>::from(token_contract),
+        );
+        fill(
+            &mut code,
+            Self::REFUNDED_TOPIC,
+            &log_topic(REFUNDED_LOG_MSG),
+        );
+        fill(&mut code, Self::SECRET_HASH, &secret_hash);
+        fill(
+            &mut code,
+            Self::REDEEM_ADDRESS,
+            &<[u8; 20]>::from(redeem_address),
+        );
+        fill(
+            &mut code,
+            Self::REDEEMED_TOPIC,
+            &log_topic(REDEEMED_LOG_MSG),
+        );
+
+        Hash160Erc20Htlc(code)
+    }
+
+    pub fn deploy_tx_gas_limit() -> u64 {
+        200_000
+    }
+
+    pub fn redeem_tx_gas_limit() -> u64 {
+        100_000
+    }
+
+    pub fn refund_tx_gas_limit() -> u64 {
+        100_000
+    }
+}
+
+impl From<Hash160Erc20Htlc> for Vec<u8> {
+    fn from(htlc: Hash160Erc20Htlc) -> Self {
+        htlc.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    const REFUND_ADDRESS: [u8; 20] = [0x11; 20];
+    const REDEEM_ADDRESS: [u8; 20] = [0x22; 20];
+    const SECRET_HASH: [u8; 20] = [0x33; 20];
+    const TOKEN_CONTRACT: [u8; 20] = [0x44; 20];
+
+    fn assert_filled_in(code: &[u8], offsets: &[usize], value: &[u8]) {
+        for offset in offsets {
+            // PUSH1 is 0x60, the placeholders are the data of PUSHn
+            assert_that(&usize::from(code[offset - 1])).is_equal_to(0x5f + value.len());
+            assert_that(&&code[*offset..*offset + value.len()]).is_equal_to(value);
+        }
+    }
+
+    /// The first 12 bytes return the runtime code following them, the length
+    /// they copy is pushed by the first instruction.
+    fn assert_runtime_length(code: &[u8]) {
+        let length = u16::from_be_bytes([code[1], code[2]]);
+
+        assert_that(&usize::from(length)).is_equal_to(code.len() - 12);
+    }
+
+    #[test]
+    fn ether_htlc_fills_in_all_placeholders() {
+        let code: Vec<u8> = Hash160EtherHtlc::new(
+            1_600_000_000,
+            REFUND_ADDRESS.into(),
+            REDEEM_ADDRESS.into(),
+            SECRET_HASH,
+        )
+        .into();
+
+        assert_runtime_length(&code);
+        assert_filled_in(
+            &code,
+            Hash160EtherHtlc::EXPIRY,
+            &1_600_000_000u32.to_be_bytes(),
+        );
+        assert_filled_in(
+            &code,
+            Hash160EtherHtlc::REFUNDED_TOPIC,
+            &log_topic(REFUNDED_LOG_MSG),
+        );
+        assert_filled_in(&code, Hash160EtherHtlc::REFUND_ADDRESS, &REFUND_ADDRESS);
+        assert_filled_in(&code, Hash160EtherHtlc::SECRET_HASH, &SECRET_HASH);
+        assert_filled_in(
+            &code,
+            Hash160EtherHtlc::REDEEMED_TOPIC,
+            &log_topic(REDEEMED_LOG_MSG),
+        );
+        assert_filled_in(&code, Hash160EtherHtlc::REDEEM_ADDRESS, &REDEEM_ADDRESS);
+    }
+
+    #[test]
+    fn erc20_htlc_fills_in_all_placeholders() {
+        let amount = Erc20Quantity::from_wei_dec_str("1000").unwrap();
+        let code: Vec<u8> = Hash160Erc20Htlc::new(
+            1_600_000_000,
+            REFUND_ADDRESS.into(),
+            REDEEM_ADDRESS.into(),
+            SECRET_HASH,
+            TOKEN_CONTRACT.into(),
+            amount.clone(),
+        )
+        .into();
+
+        assert_runtime_length(&code);
+        assert_filled_in(
+            &code,
+            Hash160Erc20Htlc::EXPIRY,
+            &1_600_000_000u32.to_be_bytes(),
+        );
+        assert_filled_in(&code, Hash160Erc20Htlc::REFUND_ADDRESS, &REFUND_ADDRESS);
+        assert_filled_in(
+            &code,
+            Hash160Erc20Htlc::AMOUNT,
+            &TokenQuantity::from(amount).0,
+        );
+        assert_filled_in(&code, Hash160Erc20Htlc::TOKEN_CONTRACT, &TOKEN_CONTRACT);
+        assert_filled_in(
+            &code,
+            Hash160Erc20Htlc::REFUNDED_TOPIC,
+            &log_topic(REFUNDED_LOG_MSG),
+        );
+        assert_filled_in(&code, Hash160Erc20Htlc::SECRET_HASH, &SECRET_HASH);
+        assert_filled_in(&code, Hash160Erc20Htlc::REDEEM_ADDRESS, &REDEEM_ADDRESS);
+        assert_filled_in(
+            &code,
+            Hash160Erc20Htlc::REDEEMED_TOPIC,
+            &log_topic(REDEEMED_LOG_MSG),
+        );
+    }
+}
@@ -80,7 +80,7 @@ impl
         htlc_params: &HtlcParams<Ethereum, asset::Ether, identity::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Deployed<htlc_location::Ethereum, transaction::Ethereum>> {
-        let expected_bytecode = htlc_params.bytecode();
+        let expected_bytecode = htlc_params.bytecode()?;
 
         let (transaction, location) =
             watch_for_contract_creation(self, start_of_swap, &expected_bytecode)
@@ -228,7 +228,7 @@ impl
         htlc_params: &HtlcParams<Ethereum, asset::Erc20, identity::Ethereum>,
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Deployed<htlc_location::Ethereum, transaction::Ethereum>> {
-        let expected_bytecode = htlc_params.clone().bytecode();
+        let expected_bytecode = htlc_params.clone().bytecode()?;
 
         let (transaction, location) =
             watch_for_contract_creation(self, start_of_swap, &expected_bytecode)
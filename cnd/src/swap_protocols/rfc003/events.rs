@@ -1,4 +1,7 @@
-use crate::swap_protocols::rfc003::{create_swap::HtlcParams, Secret};
+use crate::{
+    asset,
+    swap_protocols::rfc003::{create_swap::HtlcParams, Secret},
+};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +11,52 @@ pub enum Funded<A, T> {
     Incorrectly { asset: A, transaction: T },
 }
 
+impl<A, T> Funded<A, T> {
+    pub fn transaction(&self) -> &T {
+        match self {
+            Funded::Correctly { transaction, .. } | Funded::Incorrectly { transaction, .. } => {
+                transaction
+            }
+        }
+    }
+
+    /// An HTLC that locks more than `expected` can be redeemed just like a
+    /// correctly funded one if it pays out the excess, if `accept_overfunding`
+    /// is set it is treated as such.
+    pub fn tolerate_overfunding(self, expected: &A, accept_overfunding: bool) -> Self
+    where
+        A: Ord + PaysOutExcess,
+    {
+        match self {
+            Funded::Incorrectly { asset, transaction }
+                if accept_overfunding && A::PAYS_OUT_EXCESS && asset > *expected =>
+            {
+                Funded::Correctly { asset, transaction }
+            }
+            funded => funded,
+        }
+    }
+}
+
+/// Whether redeeming or refunding the HTLC of an asset pays out everything it
+/// locks. The ERC20 HTLC only transfers the agreed amount, the excess of an
+/// overfunded one stays locked forever.
+pub trait PaysOutExcess {
+    const PAYS_OUT_EXCESS: bool;
+}
+
+impl PaysOutExcess for asset::Bitcoin {
+    const PAYS_OUT_EXCESS: bool = true;
+}
+
+impl PaysOutExcess for asset::Ether {
+    const PAYS_OUT_EXCESS: bool = true;
+}
+
+impl PaysOutExcess for asset::Erc20 {
+    const PAYS_OUT_EXCESS: bool = false;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Redeemed<T> {
     pub transaction: T,
@@ -63,3 +112,70 @@ pub trait HtlcRefunded<L, A, H, I, T>: Send + Sync + Sized + 'static {
         start_of_swap: NaiveDateTime,
     ) -> anyhow::Result<Refunded<T>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asset::{ethereum::FromWei, Erc20Quantity},
+        identity,
+    };
+    use spectral::prelude::*;
+
+    fn incorrectly_funded(sat: u64) -> Funded<asset::Bitcoin, ()> {
+        Funded::Incorrectly {
+            asset: asset::Bitcoin::from_sat(sat),
+            transaction: (),
+        }
+    }
+
+    #[test]
+    fn overfunded_htlc_counts_as_correctly_funded_if_accepted() {
+        let expected = asset::Bitcoin::from_sat(100_000);
+
+        let funded = incorrectly_funded(100_001).tolerate_overfunding(&expected, true);
+
+        assert_that(&funded).is_equal_to(Funded::Correctly {
+            asset: asset::Bitcoin::from_sat(100_001),
+            transaction: (),
+        });
+    }
+
+    #[test]
+    fn overfunded_htlc_stays_incorrectly_funded_if_not_accepted() {
+        let expected = asset::Bitcoin::from_sat(100_000);
+
+        let funded = incorrectly_funded(100_001).tolerate_overfunding(&expected, false);
+
+        assert_that(&funded).is_equal_to(incorrectly_funded(100_001));
+    }
+
+    #[test]
+    fn underfunded_htlc_stays_incorrectly_funded() {
+        let expected = asset::Bitcoin::from_sat(100_000);
+
+        let funded = incorrectly_funded(99_999).tolerate_overfunding(&expected, true);
+
+        assert_that(&funded).is_equal_to(incorrectly_funded(99_999));
+    }
+
+    #[test]
+    fn overfunded_erc20_htlc_stays_incorrectly_funded() {
+        let erc20 = |quantity: u64| {
+            asset::Erc20::new(
+                identity::Ethereum::from([7u8; 20]),
+                Erc20Quantity::from_wei(quantity),
+            )
+        };
+        let overfunded = Funded::Incorrectly {
+            asset: erc20(100_001),
+            transaction: (),
+        };
+
+        let funded = overfunded
+            .clone()
+            .tolerate_overfunding(&erc20(100_000), true);
+
+        assert_that(&funded).is_equal_to(overfunded);
+    }
+}
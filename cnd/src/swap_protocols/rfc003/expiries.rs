@@ -0,0 +1,161 @@
+//! Checks that the expiries of a swap are safe for both parties.
+//!
+//! Alice redeems beta and thereby reveals the secret, Bob uses it to redeem
+//! alpha. If beta does not expire well before alpha, Alice can wait for beta
+//! to expire and redeem it at the last moment, leaving Bob without the time to
+//! redeem alpha before Alice can refund it.
+
+use crate::{
+    comit_api::LedgerKind,
+    config::{ExpiryLedger, ExpiryRules},
+    timestamp::Timestamp,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum UnsafeExpiries {
+    #[error("beta expiry {beta} is not before alpha expiry {alpha}")]
+    BetaNotBeforeAlpha { alpha: u32, beta: u32 },
+    #[error("alpha expires {delta} seconds after beta, at least {min} are required")]
+    DeltaTooShort { delta: u32, min: u32 },
+    #[error("beta expires in {remaining} seconds, at least {min} are required")]
+    TooSoon { remaining: i64, min: u32 },
+    #[error("alpha expires in {remaining} seconds, at most {max} are allowed")]
+    TooLate { remaining: i64, max: u32 },
+}
+
+pub fn validate(
+    rules: &ExpiryRules,
+    alpha_expiry: Timestamp,
+    beta_expiry: Timestamp,
+    now: Timestamp,
+) -> Result<(), UnsafeExpiries> {
+    let alpha = u32::from(alpha_expiry);
+    let beta = u32::from(beta_expiry);
+
+    if beta >= alpha {
+        return Err(UnsafeExpiries::BetaNotBeforeAlpha { alpha, beta });
+    }
+
+    let delta = alpha - beta;
+    if delta < rules.min_delta_secs {
+        return Err(UnsafeExpiries::DeltaTooShort {
+            delta,
+            min: rules.min_delta_secs,
+        });
+    }
+
+    let remaining = i64::from(beta_expiry) - i64::from(now);
+    if remaining < i64::from(rules.min_expiry_secs) {
+        return Err(UnsafeExpiries::TooSoon {
+            remaining,
+            min: rules.min_expiry_secs,
+        });
+    }
+
+    if let Some(max) = rules.max_expiry_secs {
+        let remaining = i64::from(alpha_expiry) - i64::from(now);
+        if remaining > i64::from(max) {
+            return Err(UnsafeExpiries::TooLate { remaining, max });
+        }
+    }
+
+    Ok(())
+}
+
+impl From<LedgerKind> for ExpiryLedger {
+    fn from(ledger: LedgerKind) -> Self {
+        match ledger {
+            LedgerKind::BitcoinMainnet
+            | LedgerKind::BitcoinTestnet
+            | LedgerKind::BitcoinRegtest => ExpiryLedger::Bitcoin,
+            LedgerKind::LitecoinMainnet
+            | LedgerKind::LitecoinTestnet
+            | LedgerKind::LitecoinRegtest => ExpiryLedger::Litecoin,
+            LedgerKind::Ethereum(_) => ExpiryLedger::Ethereum,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    const NOW: u32 = 1_000_000;
+
+    fn rules() -> ExpiryRules {
+        ExpiryRules {
+            min_delta_secs: 3600,
+            min_expiry_secs: 600,
+            max_expiry_secs: Some(86400),
+        }
+    }
+
+    fn validate_relative(alpha: u32, beta: u32) -> Result<(), UnsafeExpiries> {
+        validate(
+            &rules(),
+            Timestamp::from(NOW + alpha),
+            Timestamp::from(NOW + beta),
+            Timestamp::from(NOW),
+        )
+    }
+
+    #[test]
+    fn accepts_safe_expiries() {
+        assert_that(&validate_relative(12 * 3600, 6 * 3600)).is_ok();
+    }
+
+    #[test]
+    fn rejects_beta_expiring_after_alpha() {
+        assert_that(&validate_relative(6 * 3600, 12 * 3600))
+            .is_err()
+            .is_equal_to(UnsafeExpiries::BetaNotBeforeAlpha {
+                alpha: NOW + 6 * 3600,
+                beta: NOW + 12 * 3600,
+            });
+    }
+
+    #[test]
+    fn rejects_short_delta() {
+        assert_that(&validate_relative(7200, 5400))
+            .is_err()
+            .is_equal_to(UnsafeExpiries::DeltaTooShort {
+                delta: 1800,
+                min: 3600,
+            });
+    }
+
+    #[test]
+    fn rejects_beta_expiring_too_soon() {
+        assert_that(&validate_relative(3900, 300))
+            .is_err()
+            .is_equal_to(UnsafeExpiries::TooSoon {
+                remaining: 300,
+                min: 600,
+            });
+    }
+
+    #[test]
+    fn rejects_alpha_expiring_too_late() {
+        assert_that(&validate_relative(2 * 86400, 86400))
+            .is_err()
+            .is_equal_to(UnsafeExpiries::TooLate {
+                remaining: 2 * 86400,
+                max: 86400,
+            });
+    }
+
+    #[test]
+    fn default_rules_only_require_beta_before_alpha() {
+        let now = Timestamp::from(NOW);
+
+        assert_that(&validate(
+            &ExpiryRules::default(),
+            now.plus(8),
+            now.plus(3),
+            now,
+        ))
+        .is_ok();
+        assert_that(&validate(&ExpiryRules::default(), now, now, now)).is_err();
+    }
+}
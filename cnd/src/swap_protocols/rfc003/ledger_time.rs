@@ -0,0 +1,49 @@
+//! The time against which the ledgers check the expiries of the HTLCs.
+//!
+//! Neither ledger uses our wall clock: Bitcoin checks the lock time of a
+//! refund against the median time past of the last blocks, which lags about
+//! an hour behind, and Ethereum against the timestamp of the block the refund
+//! is mined in. Offering a refund based on our wall clock would hand out
+//! transactions the ledger rejects.
+
+use crate::timestamp::Timestamp;
+
+#[async_trait::async_trait]
+pub trait LedgerTime<L>: Send + Sync + 'static {
+    async fn ledger_time(&self, ledger: &L) -> anyhow::Result<Timestamp>;
+}
+
+/// The consensus time of both ledgers of a swap, `None` if we could not get
+/// it from the node of the ledger.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LedgerTimes {
+    pub alpha: Option<Timestamp>,
+    pub beta: Option<Timestamp>,
+}
+
+impl LedgerTimes {
+    /// Failing to reach a node only hides the refund actions of the swap,
+    /// hence errors are logged instead of returned.
+    pub async fn fetch<D, AL, BL>(dependencies: &D, alpha_ledger: &AL, beta_ledger: &BL) -> Self
+    where
+        D: LedgerTime<AL> + LedgerTime<BL>,
+        AL: Sync,
+        BL: Sync,
+    {
+        Self {
+            alpha: fetch(dependencies, alpha_ledger).await,
+            beta: fetch(dependencies, beta_ledger).await,
+        }
+    }
+}
+
+async fn fetch<D, L>(dependencies: &D, ledger: &L) -> Option<Timestamp>
+where
+    D: LedgerTime<L>,
+    L: Sync,
+{
+    LedgerTime::<L>::ledger_time(dependencies, ledger)
+        .await
+        .map_err(|e| tracing::warn!("failed to get the consensus time of a ledger: {:?}", e))
+        .ok()
+}
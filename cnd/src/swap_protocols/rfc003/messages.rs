@@ -85,6 +85,7 @@ pub enum SwapDeclineReason {
     UnsupportedSwap,
     MissingMandatoryHeader,
     BadJsonField,
+    UnsafeExpiries,
 }
 
 pub trait IntoAcceptMessage<AI, BI> {
@@ -156,4 +157,16 @@ mod tests {
 
         assert_eq!(response, expected_response);
     }
+
+    #[test]
+    fn serialize_decline_body_unsafe_expiries() {
+        let decline_response_body = DeclineResponseBody {
+            reason: Some(SwapDeclineReason::UnsafeExpiries),
+        };
+
+        let response = serde_json::to_string(&decline_response_body).unwrap();
+        let expected_response = r#"{"reason":"unsafe-expiries"}"#;
+
+        assert_eq!(response, expected_response);
+    }
 }
@@ -1,9 +1,13 @@
+use crate::swap_protocols::HashFunction;
+use bitcoin::hashes::{hash160, Hash};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha256};
 use std::{
+    convert::TryFrom,
     fmt::{self, Debug},
     str::FromStr,
 };
+use tiny_keccak::{Hasher, Sha3};
 
 #[derive(PartialEq, Clone, Copy, Debug, thiserror::Error)]
 pub enum FromErr {
@@ -13,18 +17,32 @@ pub enum FromErr {
     FromHex(#[from] hex::FromHexError),
 }
 
+/// The hash of a `Secret`, 32 bytes for SHA-256 and SHA3-256 and 20 bytes for
+/// RIPEMD160(SHA-256).
 #[derive(Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
-pub struct SecretHash([u8; Self::LENGTH]);
+pub struct SecretHash {
+    bytes: [u8; Self::MAX_LENGTH],
+    length: usize,
+}
 
 impl SecretHash {
-    pub const LENGTH: usize = 32;
+    pub const MAX_LENGTH: usize = 32;
+    const LENGTHS: [usize; 2] = [20, 32];
 
-    pub fn as_raw(&self) -> &[u8; Self::LENGTH] {
-        &self.0
+    pub fn as_raw(&self) -> &[u8] {
+        &self.bytes[..self.length]
     }
 
-    pub fn into_raw(self) -> [u8; Self::LENGTH] {
-        self.0
+    /// Checks that this hash is as long as the hashes of `hash_function`.
+    pub fn check_length(&self, hash_function: HashFunction) -> Result<(), FromErr> {
+        if self.length != hash_function.digest_length() {
+            return Err(FromErr::InvalidLength {
+                expected: hash_function.digest_length(),
+                got: self.length,
+            });
+        }
+
+        Ok(())
     }
 }
 
@@ -48,7 +66,7 @@ impl fmt::Display for SecretHash {
 
 impl fmt::LowerHex for SecretHash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(hex::encode(&self.0).as_str())
+        f.write_str(hex::encode(self.as_raw()).as_str())
     }
 }
 
@@ -72,7 +90,7 @@ impl<'de> Deserialize<'de> for SecretHash {
             type Value = SecretHash;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-                formatter.write_str("a hex encoded 32 byte value")
+                formatter.write_str("a hex encoded 20 or 32 byte value")
             }
 
             fn visit_str<E>(self, v: &str) -> Result<SecretHash, E>
@@ -94,22 +112,36 @@ impl FromStr for SecretHash {
 
     fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
         let vec = hex::decode(s)?;
-        if vec.len() != Self::LENGTH {
+        if !Self::LENGTHS.contains(&vec.len()) {
             return Err(FromErr::InvalidLength {
-                expected: Self::LENGTH,
+                expected: Self::MAX_LENGTH,
                 got: vec.len(),
             });
         }
-        let mut data = [0; Self::LENGTH];
-        let vec = &vec[..Self::LENGTH];
-        data.copy_from_slice(vec);
-        Ok(SecretHash(data))
+        let mut bytes = [0; Self::MAX_LENGTH];
+        bytes[..vec.len()].copy_from_slice(&vec);
+        Ok(SecretHash {
+            bytes,
+            length: vec.len(),
+        })
     }
 }
 
-impl From<[u8; Self::LENGTH]> for SecretHash {
-    fn from(hash: [u8; Self::LENGTH]) -> Self {
-        SecretHash(hash)
+impl From<[u8; 32]> for SecretHash {
+    fn from(hash: [u8; 32]) -> Self {
+        SecretHash {
+            bytes: hash,
+            length: 32,
+        }
+    }
+}
+
+impl From<[u8; 20]> for SecretHash {
+    fn from(hash: [u8; 20]) -> Self {
+        let mut bytes = [0; Self::MAX_LENGTH];
+        bytes[..20].copy_from_slice(&hash);
+
+        SecretHash { bytes, length: 20 }
     }
 }
 
@@ -122,12 +154,6 @@ impl From<[u8; Self::LENGTH]> for Secret {
     }
 }
 
-impl From<Secret> for SecretHash {
-    fn from(secret: Secret) -> Self {
-        secret.hash()
-    }
-}
-
 impl Secret {
     // Both values need to stay the same!
     pub const LENGTH: usize = 32;
@@ -146,12 +172,27 @@ impl Secret {
         Ok(Secret(data))
     }
 
-    pub fn hash(&self) -> SecretHash {
-        let mut sha = Sha256::new();
-        sha.input(&self.0);
-        let hash: [u8; SecretHash::LENGTH] = sha.result().into();
+    pub fn hash(&self, hash_function: HashFunction) -> SecretHash {
+        match hash_function {
+            HashFunction::Sha256 => {
+                let mut sha = Sha256::new();
+                sha.input(&self.0);
+                let hash: [u8; 32] = sha.result().into();
 
-        SecretHash::from(hash)
+                SecretHash::from(hash)
+            }
+            HashFunction::Sha3_256 => {
+                let mut sha3 = Sha3::v256();
+                sha3.update(&self.0);
+                let mut hash = [0u8; 32];
+                sha3.finalize(&mut hash);
+
+                SecretHash::from(hash)
+            }
+            HashFunction::Ripemd160Sha256 => {
+                SecretHash::from(hash160::Hash::hash(&self.0).into_inner())
+            }
+        }
     }
 
     pub fn as_raw_secret(&self) -> &[u8; Self::LENGTH] {
@@ -178,9 +219,18 @@ impl FromStr for Secret {
     }
 }
 
-impl From<SecretHash> for [u8; 32] {
-    fn from(secret_hash: SecretHash) -> [u8; 32] {
-        secret_hash.0
+impl TryFrom<SecretHash> for [u8; 32] {
+    type Error = FromErr;
+
+    fn try_from(secret_hash: SecretHash) -> Result<[u8; 32], FromErr> {
+        if secret_hash.length != 32 {
+            return Err(FromErr::InvalidLength {
+                expected: 32,
+                got: secret_hash.length,
+            });
+        }
+
+        Ok(secret_hash.bytes)
     }
 }
 
@@ -230,17 +280,45 @@ mod tests {
         let bytes = b"hello world, you are beautiful!!";
         let secret = Secret::from(*bytes);
         assert_eq!(
-            secret.hash().to_string(),
+            secret.hash(HashFunction::Sha256).to_string(),
             "68d627971643a6f97f27c58957826fcba853ec2077fd10ec6b93d8e61deb4cec"
         );
     }
 
+    #[test]
+    fn new_sha3_256_secret_hash_as_hex() {
+        let bytes = b"hello world, you are beautiful!!";
+        let secret = Secret::from(*bytes);
+        assert_eq!(
+            secret.hash(HashFunction::Sha3_256).to_string(),
+            "da0450cbd39002b2bc11b18df4e9fafecd4a7e777cfa8c71f57413dbc41fd294"
+        );
+    }
+
+    #[test]
+    fn new_ripemd160_sha256_secret_hash_as_hex() {
+        let bytes = b"hello world, you are beautiful!!";
+        let secret = Secret::from(*bytes);
+        assert_eq!(
+            secret.hash(HashFunction::Ripemd160Sha256).to_string(),
+            "ce4be827894106ecf7bedfe6c0343a81e1e7814a"
+        );
+    }
+
+    #[test]
+    fn short_secret_hash_round_trips_through_from_str() {
+        let hash = SecretHash::from_str("ce4be827894106ecf7bedfe6c0343a81e1e7814a").unwrap();
+
+        assert_eq!(hash.as_raw().len(), 20);
+        assert_eq!(hash.to_string(), "ce4be827894106ecf7bedfe6c0343a81e1e7814a");
+    }
+
     #[test]
     fn secret_hash_should_be_displayed_as_hex() {
         let bytes = b"hello world, you are beautiful!!";
         let secret = Secret::from(*bytes);
 
-        let hash = secret.hash();
+        let hash = secret.hash(HashFunction::Sha256);
 
         let formatted_hash = hash.to_string();
 
@@ -274,6 +352,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn secret_hash_length_is_checked_against_the_hash_function() {
+        let hash = SecretHash::from([1u8; 20]);
+
+        assert_eq!(hash.check_length(HashFunction::Ripemd160Sha256), Ok(()));
+        assert_eq!(
+            hash.check_length(HashFunction::Sha256),
+            Err(FromErr::InvalidLength {
+                expected: 32,
+                got: 20
+            })
+        );
+        assert_eq!(
+            SecretHash::from([1u8; 32]).check_length(HashFunction::Ripemd160Sha256),
+            Err(FromErr::InvalidLength {
+                expected: 20,
+                got: 32
+            })
+        );
+    }
+
     #[test]
     fn secret_length_is_consistent() {
         assert_eq!(Secret::LENGTH, usize::from(Secret::LENGTH_U8));
@@ -0,0 +1,292 @@
+//! Warns about swaps whose counterparty misbehaves.
+//!
+//! The ledger watchers only report what happens on chain: if Bob never funds
+//! beta they keep waiting and the swap simply sits there. The watchdog derives
+//! warnings from the two ledger states and the expiries so that the user can
+//! stop waiting and refund, or stop trying to redeem.
+//!
+//! Warnings are advisory, hence they are computed against the wall clock and
+//! not the consensus time of the ledgers.
+
+use crate::{
+    config,
+    swap_protocols::{rfc003::LedgerState, state::Get, Role, SwapId},
+    timestamp::Timestamp,
+};
+use serde::Serialize;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::Mutex;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Warning {
+    /// The counterparty has not funded its HTLC although more than the
+    /// configured share of the time until beta expires has passed.
+    CounterpartyNotFunded,
+    /// Beta locks a different amount than agreed on.
+    BetaIncorrectlyFunded,
+    /// Beta expires too soon for Alice to redeem it before Bob can refund.
+    BetaExpiryTooClose,
+}
+
+/// The points in time the warnings of a swap are derived from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deadlines {
+    pub accepted_at: Timestamp,
+    pub alpha_expiry: Timestamp,
+    pub beta_expiry: Timestamp,
+}
+
+pub fn warnings<AA, AH, AT, BA, BH, BT>(
+    role: Role,
+    alpha_ledger_state: &LedgerState<AA, AH, AT>,
+    beta_ledger_state: &LedgerState<BA, BH, BT>,
+    deadlines: Deadlines,
+    config: &config::Watchdog,
+    now: Timestamp,
+) -> Vec<Warning> {
+    use self::LedgerState::*;
+
+    let mut warnings = Vec::new();
+
+    // Bob funds beta only after Alice funded alpha, hence Alice only waits for
+    // him from then on.
+    let counterparty_not_funded = match role {
+        Role::Alice => {
+            matches!(alpha_ledger_state, Funded { .. } | IncorrectlyFunded { .. })
+                && matches!(beta_ledger_state, NotDeployed | Deployed { .. })
+        }
+        Role::Bob => matches!(alpha_ledger_state, NotDeployed | Deployed { .. }),
+    };
+    if counterparty_not_funded && funding_timeout_passed(deadlines, config, now) {
+        warnings.push(Warning::CounterpartyNotFunded);
+    }
+
+    if let IncorrectlyFunded { .. } = beta_ledger_state {
+        warnings.push(Warning::BetaIncorrectlyFunded);
+    }
+
+    if let (Role::Alice, Funded { .. }) = (role, beta_ledger_state) {
+        let remaining = i64::from(deadlines.beta_expiry) - i64::from(now);
+        if remaining < i64::from(config.min_redeem_secs) {
+            warnings.push(Warning::BetaExpiryTooClose);
+        }
+    }
+
+    warnings
+}
+
+fn funding_timeout_passed(deadlines: Deadlines, config: &config::Watchdog, now: Timestamp) -> bool {
+    let total = i64::from(deadlines.beta_expiry) - i64::from(deadlines.accepted_at);
+    let elapsed = i64::from(now) - i64::from(deadlines.accepted_at);
+
+    elapsed * 100 >= total * i64::from(config.funding_timeout_percent)
+}
+
+/// Once an HTLC is redeemed or refunded the outcome of the swap is decided and
+/// there is nothing left to warn about.
+fn is_decided<AA, AH, AT, BA, BH, BT>(
+    alpha_ledger_state: &LedgerState<AA, AH, AT>,
+    beta_ledger_state: &LedgerState<BA, BH, BT>,
+) -> bool {
+    use self::LedgerState::*;
+
+    matches!(alpha_ledger_state, Redeemed { .. } | Refunded { .. })
+        || matches!(beta_ledger_state, Redeemed { .. } | Refunded { .. })
+}
+
+/// Once both HTLCs have expired the counterparty can no longer fund or redeem
+/// in time, whatever is left is refunding which the watchdog doesn't help with.
+fn has_expired(deadlines: Deadlines, now: Timestamp) -> bool {
+    now > deadlines.alpha_expiry && now > deadlines.beta_expiry
+}
+
+/// The warnings the watchdogs found for each swap.
+#[derive(Default, Debug)]
+pub struct SwapWarnings(Mutex<HashMap<SwapId, Vec<Warning>>>);
+
+impl SwapWarnings {
+    pub async fn get(&self, id: &SwapId) -> Vec<Warning> {
+        self.0.lock().await.get(id).cloned().unwrap_or_default()
+    }
+
+    async fn insert(&self, id: SwapId, warnings: Vec<Warning>) {
+        let _ = self.0.lock().await.insert(id, warnings);
+    }
+
+    async fn remove(&self, id: &SwapId) {
+        let _ = self.0.lock().await.remove(id);
+    }
+}
+
+/// Periodically derives the warnings of a swap from its ledger states until
+/// the outcome of the swap is decided or both HTLCs have expired, every new
+/// warning is logged. The warnings of the swap are dropped once it stops.
+#[allow(clippy::too_many_arguments)]
+pub async fn watch<S, AA, AH, AT, BA, BH, BT>(
+    alpha_ledger_state: Arc<S>,
+    beta_ledger_state: Arc<S>,
+    swap_warnings: Arc<SwapWarnings>,
+    id: SwapId,
+    role: Role,
+    deadlines: Deadlines,
+    config: config::Watchdog,
+) where
+    S: Get<LedgerState<AA, AH, AT>> + Get<LedgerState<BA, BH, BT>>,
+{
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    let mut previous = Vec::new();
+
+    loop {
+        interval.tick().await;
+
+        let now = Timestamp::now();
+        if has_expired(deadlines, now) {
+            break;
+        }
+
+        let alpha = Get::<LedgerState<AA, AH, AT>>::get(&*alpha_ledger_state, &id).await;
+        let beta = Get::<LedgerState<BA, BH, BT>>::get(&*beta_ledger_state, &id).await;
+        let (alpha, beta) = match (alpha, beta) {
+            (Ok(Some(alpha)), Ok(Some(beta))) => (alpha, beta),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::warn!("failed to get the ledger states of swap {}: {:?}", id, e);
+                continue;
+            }
+            // the ledger watchers have not been started yet
+            _ => continue,
+        };
+
+        let warnings = warnings(role, &alpha, &beta, deadlines, &config, now);
+
+        for warning in warnings
+            .iter()
+            .filter(|warning| !previous.contains(warning))
+        {
+            tracing::warn!(
+                swap_id = %id,
+                role = %role,
+                warning = ?warning,
+                "counterparty misbehaviour"
+            );
+        }
+
+        swap_warnings.insert(id, warnings.clone()).await;
+        previous = warnings;
+
+        if is_decided(&alpha, &beta) {
+            break;
+        }
+    }
+
+    swap_warnings.remove(&id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    type State = LedgerState<u64, (), ()>;
+
+    const ACCEPTED_AT: u32 = 1_000_000;
+
+    fn deadlines() -> Deadlines {
+        Deadlines {
+            accepted_at: Timestamp::from(ACCEPTED_AT),
+            alpha_expiry: Timestamp::from(ACCEPTED_AT + 2000),
+            beta_expiry: Timestamp::from(ACCEPTED_AT + 1000),
+        }
+    }
+
+    fn config() -> config::Watchdog {
+        config::Watchdog {
+            funding_timeout_percent: 50,
+            min_redeem_secs: 100,
+        }
+    }
+
+    fn funded() -> State {
+        LedgerState::Funded {
+            htlc_location: (),
+            deploy_transaction: (),
+            fund_transaction: (),
+            asset: 1,
+        }
+    }
+
+    fn incorrectly_funded() -> State {
+        LedgerState::IncorrectlyFunded {
+            htlc_location: (),
+            deploy_transaction: (),
+            fund_transaction: (),
+            asset: 2,
+        }
+    }
+
+    fn warnings_at(role: Role, alpha: &State, beta: &State, secs: u32) -> Vec<Warning> {
+        warnings(
+            role,
+            alpha,
+            beta,
+            deadlines(),
+            &config(),
+            Timestamp::from(ACCEPTED_AT + secs),
+        )
+    }
+
+    #[test]
+    fn alice_is_warned_once_bob_is_late_to_fund_beta() {
+        let not_deployed = LedgerState::NotDeployed;
+
+        assert_that(&warnings_at(Role::Alice, &funded(), &not_deployed, 499)).is_empty();
+        assert_that(&warnings_at(Role::Alice, &funded(), &not_deployed, 500))
+            .is_equal_to(vec![Warning::CounterpartyNotFunded]);
+    }
+
+    #[test]
+    fn alice_is_not_warned_about_bob_before_funding_alpha() {
+        let not_deployed = LedgerState::NotDeployed;
+
+        assert_that(&warnings_at(Role::Alice, &not_deployed, &not_deployed, 900)).is_empty();
+    }
+
+    #[test]
+    fn bob_is_warned_once_alice_is_late_to_fund_alpha() {
+        let not_deployed = LedgerState::NotDeployed;
+
+        assert_that(&warnings_at(Role::Bob, &not_deployed, &not_deployed, 600))
+            .is_equal_to(vec![Warning::CounterpartyNotFunded]);
+        assert_that(&warnings_at(Role::Bob, &funded(), &not_deployed, 600)).is_empty();
+    }
+
+    #[test]
+    fn incorrectly_funded_beta_is_a_warning() {
+        assert_that(&warnings_at(
+            Role::Alice,
+            &funded(),
+            &incorrectly_funded(),
+            0,
+        ))
+        .is_equal_to(vec![Warning::BetaIncorrectlyFunded]);
+    }
+
+    #[test]
+    fn alice_is_warned_if_beta_expires_too_soon_to_redeem() {
+        assert_that(&warnings_at(Role::Alice, &funded(), &funded(), 900)).is_empty();
+        assert_that(&warnings_at(Role::Alice, &funded(), &funded(), 901))
+            .is_equal_to(vec![Warning::BetaExpiryTooClose]);
+        assert_that(&warnings_at(Role::Bob, &funded(), &funded(), 901)).is_empty();
+    }
+
+    #[test]
+    fn watchdog_stops_once_both_htlcs_have_expired() {
+        let at = |secs: u32| Timestamp::from(ACCEPTED_AT + secs);
+
+        assert_that(&has_expired(deadlines(), at(1001))).is_false();
+        assert_that(&has_expired(deadlines(), at(2000))).is_false();
+        assert_that(&has_expired(deadlines(), at(2001))).is_true();
+    }
+}
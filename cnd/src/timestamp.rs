@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
@@ -32,6 +33,14 @@ impl From<u32> for Timestamp {
     }
 }
 
+impl From<NaiveDateTime> for Timestamp {
+    // Same as for `Timestamp::now`, dates before 1970 are mapped to 0
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from(date_time: NaiveDateTime) -> Self {
+        Self(date_time.timestamp().max(0) as u32)
+    }
+}
+
 impl From<Timestamp> for u32 {
     fn from(item: Timestamp) -> Self {
         item.0
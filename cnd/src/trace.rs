@@ -1,26 +1,91 @@
+use anyhow::Context;
+use cnd::config::{settings::Logging, LogFormat};
 use log::LevelFilter;
-use tracing::{info, subscriber, Level};
+use tracing::{info, subscriber, Level, Subscriber};
 use tracing_log::LogTracer;
-use tracing_subscriber::FmtSubscriber;
+use tracing_subscriber::{
+    filter, fmt, layer::SubscriberExt, registry::LookupSpan, EnvFilter, Registry,
+};
+
+/// Keeps the OTLP exporter running, spans are flushed when it is dropped.
+pub struct Guard {
+    _otlp: Option<opentelemetry_otlp::Uninstall>,
+}
+
+pub fn init_tracing(logging: &Logging) -> anyhow::Result<Guard> {
+    let level = logging.level;
 
-pub fn init_tracing(level: log::LevelFilter) -> anyhow::Result<()> {
     if level == LevelFilter::Off {
-        return Ok(());
+        return Ok(Guard { _otlp: None });
     }
 
-    // We want upstream library log messages, just only at Info level.
-    LogTracer::init_with_filter(LevelFilter::Info)?;
+    // We want upstream library log messages, just only at Info level, unless
+    // the user asks for more through the filter.
+    match logging.filter {
+        Some(_) => LogTracer::init()?,
+        None => LogTracer::init_with_filter(LevelFilter::Info)?,
+    }
+
+    let filter = env_filter(level_from_level_filter(level), logging.filter.as_deref())?;
+    let registry = Registry::default().with(filter);
+
+    let otlp = match &logging.otlp_endpoint {
+        Some(endpoint) => {
+            let (tracer, uninstall) = opentelemetry_otlp::new_pipeline()
+                .with_endpoint(endpoint)
+                .install()
+                .with_context(|| format!("failed to install OTLP exporter for {}", endpoint))?;
 
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(level_from_level_filter(level))
-        .finish();
+            set_global_default(
+                registry.with(tracing_opentelemetry::layer().with_tracer(tracer)),
+                logging.format,
+            )?;
+
+            Some(uninstall)
+        }
+        None => {
+            set_global_default(registry, logging.format)?;
+
+            None
+        }
+    };
 
-    subscriber::set_global_default(subscriber)?;
     info!("Initialized tracing with level: {}", level);
 
+    Ok(Guard { _otlp: otlp })
+}
+
+fn set_global_default<S>(subscriber: S, format: LogFormat) -> anyhow::Result<()>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+{
+    match format {
+        LogFormat::Text => subscriber::set_global_default(subscriber.with(fmt::layer()))?,
+        LogFormat::Json => subscriber::set_global_default(subscriber.with(fmt::layer().json()))?,
+    }
+
     Ok(())
 }
 
+/// Constructs a filter that enables `level` and the comma separated
+/// `directives`, which take precedence for the modules they name.
+fn env_filter(level: Level, directives: Option<&str>) -> anyhow::Result<EnvFilter> {
+    let filter = EnvFilter::default().add_directive(filter::LevelFilter::from_level(level).into());
+
+    directives
+        .into_iter()
+        .flat_map(|directives| directives.split(','))
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .try_fold(filter, |filter, directive| {
+            let directive = directive
+                .parse()
+                .with_context(|| format!("invalid logging filter directive: {}", directive))?;
+
+            Ok(filter.add_directive(directive))
+        })
+}
+
 fn level_from_level_filter(level: LevelFilter) -> Level {
     match level {
         LevelFilter::Off => unreachable!(),
@@ -31,3 +96,23 @@ fn level_from_level_filter(level: LevelFilter) -> Level {
         LevelFilter::Trace => Level::TRACE,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn filter_accepts_comma_separated_directives() {
+        let filter = env_filter(Level::INFO, Some("cnd::network=trace, libp2p=warn,"));
+
+        assert_that(&filter).is_ok();
+    }
+
+    #[test]
+    fn filter_rejects_invalid_directives() {
+        let filter = env_filter(Level::INFO, Some("cnd=loud"));
+
+        assert_that(&filter).is_err();
+    }
+}
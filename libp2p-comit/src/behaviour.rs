@@ -3,11 +3,14 @@ use crate::{
     handler::{
         InboundMessage, OutboundMessage, PendingInboundResponse, ProtocolInEvent, ProtocolOutEvent,
     },
-    ComitHandler, PendingInboundRequest, PendingOutboundRequest,
+    ComitHandler, PendingInboundRequest, PendingOutboundRequest, RequestError,
 };
 use futures::{
-    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
-    Future, StreamExt, TryFutureExt,
+    channel::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
+    Future, FutureExt, StreamExt,
 };
 use libp2p::{
     core::{connection::ConnectionId, ConnectedPoint, Multiaddr, PeerId},
@@ -18,8 +21,12 @@ use libp2p::{
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     task::{Context, Poll},
+    time::Duration,
 };
 
+/// The default time we wait for a response to a request.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 enum ConnectionState {
     Connected {
@@ -31,6 +38,32 @@ enum ConnectionState {
     },
 }
 
+/// Decides how often a request is re-sent if it could not be delivered to the
+/// peer.
+///
+/// Only requests that provably never reached the peer are re-sent: those whose
+/// peer could not be dialed and those whose substream could not be opened.
+/// Requests that time out or whose connection is lost once they were handed to
+/// the peer are never re-sent because the peer may already have acted upon
+/// them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+}
+
+/// A request we sent that has not been answered yet.
+#[derive(Debug)]
+struct InFlightRequest {
+    peer_id: PeerId,
+    address_hint: Option<Multiaddr>,
+    request: OutboundRequest,
+    retries_left: u32,
+    /// Resolves once the handler received a response or gave up.
+    response: oneshot::Receiver<Result<Response, RequestError>>,
+    /// The channel to whoever called `send_request`.
+    requester: oneshot::Sender<Result<Response, RequestError>>,
+}
+
 /// Events that are caused 'out'-side of this node and emitted by the
 /// `Behaviour` to the application.
 #[derive(Debug)]
@@ -49,6 +82,10 @@ pub struct Comit {
 
     known_request_headers: HashMap<String, HashSet<String>>,
     connections: HashMap<PeerId, ConnectionState>,
+    in_flight: Vec<InFlightRequest>,
+
+    request_timeout: Duration,
+    retry_policy: RetryPolicy,
 }
 
 impl Comit {
@@ -60,6 +97,25 @@ impl Comit {
             events,
             known_request_headers,
             connections: HashMap::new(),
+            in_flight: Vec::new(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets how long we wait for the response to each request.
+    pub fn with_request_timeout(self, request_timeout: Duration) -> Self {
+        Self {
+            request_timeout,
+            ..self
+        }
+    }
+
+    /// Sets how often a request is re-sent if it could not be delivered.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy,
+            ..self
         }
     }
 
@@ -67,12 +123,44 @@ impl Comit {
         &mut self,
         dial_information: (PeerId, Option<Multiaddr>),
         request: OutboundRequest,
-    ) -> impl Future<Output = Result<Response, ()>> + Send + 'static + Unpin {
+    ) -> impl Future<Output = Result<Response, RequestError>> + Send + 'static + Unpin {
         let (peer_id, address_hint) = dial_information;
-        let (sender, receiver) = futures::channel::oneshot::channel();
+        let (requester, receiver) = oneshot::channel();
+
+        let response = self.dispatch(peer_id.clone(), address_hint.clone(), request.clone());
+
+        self.in_flight.push(InFlightRequest {
+            peer_id,
+            address_hint,
+            request,
+            retries_left: self.retry_policy.max_retries,
+            response,
+            requester,
+        });
+
+        receiver.map(|result| {
+            result.unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Sender of response future was unexpectedly dropped before response was received."
+                );
+                Err(RequestError::Connection)
+            })
+        })
+    }
+
+    /// Hands the request to the handler of the connection to `peer_id`,
+    /// dialing the peer first if we are not connected yet.
+    fn dispatch(
+        &mut self,
+        peer_id: PeerId,
+        address_hint: Option<Multiaddr>,
+        request: OutboundRequest,
+    ) -> oneshot::Receiver<Result<Response, RequestError>> {
+        let (sender, receiver) = oneshot::channel();
 
         let request = PendingOutboundRequest {
             request,
+            timeout: self.request_timeout,
             channel: sender,
         };
 
@@ -128,11 +216,43 @@ impl Comit {
             }
         }
 
-        receiver.map_err(|_| {
-            tracing::warn!(
-                "Sender of response future was unexpectedly dropped before response was received."
-            )
-        })
+        receiver
+    }
+
+    /// Forwards the outcome of finished requests to the requesters and
+    /// re-sends undelivered requests if the retry policy allows it.
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>) {
+        // We remove each element from `in_flight` one by one and add them back.
+        for n in (0..self.in_flight.len()).rev() {
+            let mut in_flight = self.in_flight.swap_remove(n);
+
+            match in_flight.response.poll_unpin(cx) {
+                Poll::Pending if in_flight.requester.is_canceled() => {}
+                Poll::Pending => self.in_flight.push(in_flight),
+                Poll::Ready(Ok(Err(RequestError::NotDelivered))) if in_flight.retries_left > 0 => {
+                    tracing::info!(
+                        "request could not be delivered to {}, re-sending it",
+                        in_flight.peer_id
+                    );
+
+                    in_flight.retries_left -= 1;
+                    in_flight.response = self.dispatch(
+                        in_flight.peer_id.clone(),
+                        in_flight.address_hint.clone(),
+                        in_flight.request.clone(),
+                    );
+
+                    self.in_flight.push(in_flight);
+                }
+                Poll::Ready(Ok(result)) => {
+                    let _ = in_flight.requester.send(result);
+                }
+                // The handler dropped the request, it may have been delivered.
+                Poll::Ready(Err(_)) => {
+                    let _ = in_flight.requester.send(Err(RequestError::Connection));
+                }
+            }
+        }
     }
 
     pub fn connected_peers(&mut self) -> impl Iterator<Item = (PeerId, Vec<Multiaddr>)> {
@@ -178,6 +298,27 @@ impl NetworkBehaviour for Comit {
 
     fn inject_connected(&mut self, _: &PeerId) {}
 
+    fn inject_dial_failure(&mut self, peer_id: &PeerId) {
+        tracing::debug!("failed to dial {}", peer_id);
+
+        match self.connections.remove(peer_id) {
+            Some(ConnectionState::Connecting { pending_events, .. }) => {
+                // None of the pending requests reached the peer.
+                for event in pending_events {
+                    match event {
+                        ProtocolInEvent::Message(OutboundMessage::Request(request)) => {
+                            let _ = request.channel.send(Err(RequestError::NotDelivered));
+                        }
+                    }
+                }
+            }
+            Some(connection_state) => {
+                self.connections.insert(peer_id.clone(), connection_state);
+            }
+            None => {}
+        }
+    }
+
     fn inject_disconnected(&mut self, _: &PeerId) {}
 
     fn inject_connection_established(
@@ -273,7 +414,7 @@ impl NetworkBehaviour for Comit {
                 response,
                 channel,
             })) => {
-                let _ = channel.send(response);
+                let _ = channel.send(Ok(response));
             }
         }
     }
@@ -283,8 +424,79 @@ impl NetworkBehaviour for Comit {
         cx: &mut Context<'_>,
         _params: &mut impl PollParameters,
     ) -> Poll<NetworkBehaviourAction<ProtocolInEvent, BehaviourOutEvent>> {
+        self.poll_in_flight(cx);
+
         self.events
             .poll_next_unpin(cx)
             .map(|item| item.expect("unbounded channel never ends"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::task::noop_waker;
+    use spectral::prelude::*;
+
+    #[test]
+    fn request_fails_if_peer_cannot_be_dialed() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut comit = Comit::new(HashMap::new());
+        let peer_id = PeerId::random();
+
+        let mut response =
+            comit.send_request((peer_id.clone(), None), OutboundRequest::new("SWAP"));
+        comit.inject_dial_failure(&peer_id);
+        comit.poll_in_flight(&mut cx);
+
+        assert_that(&response.poll_unpin(&mut cx))
+            .is_equal_to(Poll::Ready(Err(RequestError::NotDelivered)));
+    }
+
+    #[test]
+    fn request_is_resent_according_to_retry_policy() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut comit =
+            Comit::new(HashMap::new()).with_retry_policy(RetryPolicy { max_retries: 1 });
+        let peer_id = PeerId::random();
+
+        let mut response =
+            comit.send_request((peer_id.clone(), None), OutboundRequest::new("SWAP"));
+        comit.inject_dial_failure(&peer_id);
+        comit.poll_in_flight(&mut cx);
+
+        assert_that(&response.poll_unpin(&mut cx)).is_equal_to(Poll::Pending);
+
+        comit.inject_dial_failure(&peer_id);
+        comit.poll_in_flight(&mut cx);
+
+        assert_that(&response.poll_unpin(&mut cx))
+            .is_equal_to(Poll::Ready(Err(RequestError::NotDelivered)));
+    }
+
+    #[test]
+    fn request_handed_to_the_peer_is_not_resent() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut comit =
+            Comit::new(HashMap::new()).with_retry_policy(RetryPolicy { max_retries: 1 });
+        let peer_id = PeerId::random();
+
+        comit
+            .connections
+            .insert(peer_id.clone(), ConnectionState::Connected {
+                addresses: HashSet::new(),
+            });
+        let mut response =
+            comit.send_request((peer_id.clone(), None), OutboundRequest::new("SWAP"));
+        // the handler of the connection drops the request, e.g. because the
+        // connection was closed after the request was sent
+        while let Poll::Ready(Some(_)) = comit.events.poll_next_unpin(&mut cx) {}
+        comit.poll_in_flight(&mut cx);
+
+        assert_that(&response.poll_unpin(&mut cx))
+            .is_equal_to(Poll::Ready(Err(RequestError::Connection)));
+    }
+}
@@ -1,16 +1,58 @@
 use crate::Frame;
 use bytes::BytesMut;
 use futures_codec::{Decoder, Encoder};
-use std::io;
+use std::{convert::TryFrom, io};
+
+/// Frames bigger than this are rejected by the CBOR codec.
+pub const MAX_CBOR_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Size of the length prefix of CBOR frames.
+const LENGTH_PREFIX_SIZE: usize = 4;
 
 #[derive(Debug, thiserror::Error)]
 pub enum CodecError {
     #[error("serde JSON: ")]
     Json(#[from] serde_json::Error),
+    #[error("serde CBOR: ")]
+    Cbor(#[from] serde_cbor::Error),
+    #[error("frame of {0} bytes exceeds the maximum frame length")]
+    FrameTooLarge(usize),
     #[error("io: ")]
     IO(#[from] io::Error),
 }
 
+/// The codec of a substream, depending on the protocol version that was
+/// negotiated for it.
+#[derive(Debug, Clone, Copy)]
+pub enum FrameCodec {
+    Json(JsonFrameCodec),
+    Cbor(CborFrameCodec),
+}
+
+impl Encoder for FrameCodec {
+    type Item = Frame;
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Frame, dst: &mut BytesMut) -> Result<(), CodecError> {
+        match self {
+            FrameCodec::Json(codec) => codec.encode(item, dst),
+            FrameCodec::Cbor(codec) => codec.encode(item, dst),
+        }
+    }
+}
+
+impl Decoder for FrameCodec {
+    type Item = Frame;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Frame>, CodecError> {
+        match self {
+            FrameCodec::Json(codec) => codec.decode(src),
+            FrameCodec::Cbor(codec) => codec.decode(src),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct JsonFrameCodec;
 
@@ -50,6 +92,68 @@ impl Decoder for JsonFrameCodec {
     }
 }
 
+/// Encodes frames as CBOR, each prefixed with its length as a big-endian
+/// `u32`.
+#[derive(Debug, Clone, Copy)]
+pub struct CborFrameCodec;
+
+impl Default for CborFrameCodec {
+    fn default() -> Self {
+        Self {}
+    }
+}
+
+impl Encoder for CborFrameCodec {
+    type Item = Frame;
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Frame, dst: &mut BytesMut) -> Result<(), CodecError> {
+        let bytes = serde_cbor::to_vec(&item)?;
+
+        if bytes.len() > MAX_CBOR_FRAME_LENGTH {
+            return Err(CodecError::FrameTooLarge(bytes.len()));
+        }
+        let length =
+            u32::try_from(bytes.len()).map_err(|_| CodecError::FrameTooLarge(bytes.len()))?;
+
+        dst.reserve(LENGTH_PREFIX_SIZE + bytes.len());
+        dst.extend_from_slice(&length.to_be_bytes());
+        dst.extend_from_slice(&bytes);
+
+        Ok(())
+    }
+}
+
+impl Decoder for CborFrameCodec {
+    type Item = Frame;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Frame>, CodecError> {
+        if src.len() < LENGTH_PREFIX_SIZE {
+            return Ok(None);
+        }
+
+        let mut length_bytes = [0u8; LENGTH_PREFIX_SIZE];
+        length_bytes.copy_from_slice(&src[..LENGTH_PREFIX_SIZE]);
+        let length = u32::from_be_bytes(length_bytes) as usize;
+
+        if length > MAX_CBOR_FRAME_LENGTH {
+            return Err(CodecError::FrameTooLarge(length));
+        }
+
+        if src.len() < LENGTH_PREFIX_SIZE + length {
+            src.reserve(LENGTH_PREFIX_SIZE + length - src.len());
+            return Ok(None);
+        }
+
+        let _ = src.split_to(LENGTH_PREFIX_SIZE);
+        let frame_bytes = src.split_to(length);
+        let frame = serde_cbor::from_slice(frame_bytes.as_ref())?;
+
+        Ok(Some(frame))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -136,4 +240,123 @@ mod tests {
             .is_some()
             .is_equal_to(&expected_frame);
     }
+
+    fn cbor_frame_bytes(frame: &Frame) -> Vec<u8> {
+        let bytes = serde_cbor::to_vec(frame).unwrap();
+        let length = u32::try_from(bytes.len()).unwrap().to_be_bytes();
+
+        [length.as_ref(), bytes.as_ref()].concat()
+    }
+
+    #[test]
+    fn cbor_should_encode_frame_to_length_prefixed_bytes() {
+        let frame = Frame::new(FrameKind::Request, serde_json::Value::Null);
+
+        let mut codec = CborFrameCodec::default();
+
+        let mut bytes = BytesMut::new();
+
+        assert!(codec.encode(frame, &mut bytes).is_ok());
+
+        let expected = [
+            0u8, 0, 0, 23, 0xa2, 0x64, b't', b'y', b'p', b'e', 0x67, b'R', b'E', b'Q', b'U', b'E',
+            b'S', b'T', 0x67, b'p', b'a', b'y', b'l', b'o', b'a', b'd', 0xf6,
+        ];
+
+        assert_eq!(&bytes[..], &expected[..]);
+    }
+
+    #[test]
+    fn cbor_should_decode_bytes_to_frame() {
+        let frame = Frame::new(
+            FrameKind::Response,
+            serde_json::json!({ "headers": { "decision": "accepted" }, "body": [1, 2, 3] }),
+        );
+
+        let mut codec = CborFrameCodec::default();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend(cbor_frame_bytes(&frame));
+
+        assert_that(&codec.decode(&mut bytes))
+            .is_ok()
+            .is_some()
+            .is_equal_to(&frame);
+    }
+
+    #[test]
+    fn cbor_given_not_enough_bytes_should_wait_for_more() {
+        let frame = Frame::new(FrameKind::Request, serde_json::Value::Null);
+        let frame_bytes = cbor_frame_bytes(&frame);
+        let (first_part, second_part) = frame_bytes.split_at(10);
+
+        let mut codec = CborFrameCodec::default();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend(&first_part[..2]);
+
+        assert_that(&codec.decode(&mut bytes)).is_ok().is_none();
+
+        bytes.extend(&first_part[2..]);
+
+        assert_that(&codec.decode(&mut bytes)).is_ok().is_none();
+
+        bytes.extend(second_part);
+
+        assert_that(&codec.decode(&mut bytes))
+            .is_ok()
+            .is_some()
+            .is_equal_to(&frame);
+    }
+
+    #[test]
+    fn cbor_given_two_frames_in_a_row_should_decode_both() {
+        let frame = Frame::new(FrameKind::Response, serde_json::Value::Null);
+        let frame_bytes = cbor_frame_bytes(&frame);
+
+        let mut codec = CborFrameCodec::default();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend([frame_bytes.clone(), frame_bytes].concat());
+
+        let first = codec.decode(&mut bytes);
+        let second = codec.decode(&mut bytes);
+
+        assert_that(&first).is_ok().is_some().is_equal_to(&frame);
+        assert_that(&second).is_ok().is_some().is_equal_to(&frame);
+        assert_that(&bytes.is_empty()).is_true();
+    }
+
+    #[test]
+    fn cbor_given_too_large_length_prefix_should_fail() {
+        let mut codec = CborFrameCodec::default();
+
+        let mut bytes = BytesMut::new();
+        bytes.extend(&u32::max_value().to_be_bytes());
+
+        assert_that(&codec.decode(&mut bytes)).is_err();
+    }
+
+    #[test]
+    fn frame_codec_roundtrips_with_both_encodings() {
+        let frame = || {
+            Frame::new(
+                FrameKind::Request,
+                serde_json::json!({ "type": "SWAP", "headers": { "id": "1" } }),
+            )
+        };
+
+        for codec in &mut [
+            FrameCodec::Json(JsonFrameCodec::default()),
+            FrameCodec::Cbor(CborFrameCodec::default()),
+        ] {
+            let mut bytes = BytesMut::new();
+            codec.encode(frame(), &mut bytes).unwrap();
+
+            assert_that(&codec.decode(&mut bytes))
+                .is_ok()
+                .is_some()
+                .is_equal_to(&frame());
+        }
+    }
 }
@@ -18,7 +18,7 @@ pub struct ValidatedInboundRequest {
     inner: Request,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct OutboundRequest {
     #[serde(flatten)]
     inner: Request,
@@ -128,7 +128,7 @@ impl UnknownMandatoryHeaders {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 struct Request {
     #[serde(rename = "type")]
     request_type: String,
@@ -16,6 +16,7 @@ use std::{
     convert::Infallible,
     fmt::Display,
     task::Context,
+    time::Duration,
 };
 
 #[derive(derivative::Derivative)]
@@ -54,6 +55,20 @@ pub enum Error {
     UnexpectedEOF,
 }
 
+/// Reasons why an outbound request did not yield a response.
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum RequestError {
+    #[error("peer did not respond within {0:?}")]
+    Timeout(Duration),
+    #[error("connection to peer failed or was closed before a response was received")]
+    Connection,
+    /// The request was not handed to the peer, e.g. because the peer could not
+    /// be dialed or the substream could not be opened, hence it is safe to
+    /// send it again.
+    #[error("request could not be delivered to the peer")]
+    NotDelivered,
+}
+
 impl ComitHandler {
     pub fn new(known_headers: HashMap<String, HashSet<String>>) -> Self {
         Self {
@@ -69,7 +84,10 @@ impl ComitHandler {
 #[derive(Debug)]
 pub struct PendingOutboundRequest {
     pub request: OutboundRequest,
-    pub channel: oneshot::Sender<Response>,
+    /// How long we wait for the substream to be negotiated and, once it is,
+    /// for the response to arrive.
+    pub timeout: Duration,
+    pub channel: oneshot::Sender<Result<Response, RequestError>>,
 }
 
 #[derive(Debug)]
@@ -81,7 +99,7 @@ pub struct PendingInboundRequest {
 #[derive(Debug)]
 pub struct PendingInboundResponse {
     pub response: Response,
-    pub channel: oneshot::Sender<Response>,
+    pub channel: oneshot::Sender<Result<Response, RequestError>>,
 }
 
 /// Events that occur 'in' this node (as opposed to events from a peer node).
@@ -130,7 +148,7 @@ impl ProtocolsHandler for ComitHandler {
     type OutboundOpenInfo = ProtocolOutboundOpenInfo;
 
     fn listen_protocol(&self) -> SubstreamProtocol<Self::InboundProtocol> {
-        SubstreamProtocol::new(Config {})
+        SubstreamProtocol::new(Config::default())
     }
 
     fn inject_fully_negotiated_inbound(&mut self, stream: Frames) {
@@ -151,12 +169,16 @@ impl ProtocolsHandler for ComitHandler {
     ) {
         match outbound_open_info {
             ProtocolOutboundOpenInfo::Message(OutboundMessage::Request(
-                PendingOutboundRequest { request, channel },
+                PendingOutboundRequest {
+                    request,
+                    timeout,
+                    channel,
+                },
             )) => {
                 self.outbound_substreams
                     .push(substream::outbound::State::WaitingSend {
                         frame: request.into(),
-                        response_sender: channel,
+                        response_sender: substream::outbound::ResponseSender::new(channel, timeout),
                         stream: Box::pin(stream),
                     });
             }
@@ -181,9 +203,24 @@ impl ProtocolsHandler for ComitHandler {
 
     fn inject_dial_upgrade_error(
         &mut self,
-        _info: Self::OutboundOpenInfo,
-        _error: ProtocolsHandlerUpgrErr<Infallible>,
+        info: Self::OutboundOpenInfo,
+        error: ProtocolsHandlerUpgrErr<Infallible>,
     ) {
+        match info {
+            ProtocolOutboundOpenInfo::Message(OutboundMessage::Request(
+                PendingOutboundRequest {
+                    timeout, channel, ..
+                },
+            )) => {
+                // The request is only written once the substream is negotiated.
+                let error = match error {
+                    ProtocolsHandlerUpgrErr::Timeout => RequestError::Timeout(timeout),
+                    _ => RequestError::NotDelivered,
+                };
+
+                let _ = channel.send(Err(error));
+            }
+        }
     }
 
     fn connection_keep_alive(&self) -> KeepAlive {
@@ -193,7 +230,7 @@ impl ProtocolsHandler for ComitHandler {
     fn poll(&mut self, cx: &mut Context<'_>) -> Poll<ComitHandlerEvent> {
         if let Some(request) = self.to_send.pop() {
             return Poll::Ready(ProtocolsHandlerEvent::OutboundSubstreamRequest {
-                protocol: SubstreamProtocol::new(Config {}),
+                protocol: SubstreamProtocol::new(Config::default()).with_timeout(request.timeout),
                 info: ProtocolOutboundOpenInfo::Message(OutboundMessage::Request(request)),
             });
         }
@@ -24,8 +24,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{self, Value as JsonValue};
 
 pub use self::{
-    behaviour::{BehaviourOutEvent, Comit},
-    handler::{ComitHandler, PendingInboundRequest, PendingOutboundRequest},
+    behaviour::{BehaviourOutEvent, Comit, RetryPolicy},
+    handler::{ComitHandler, PendingInboundRequest, PendingOutboundRequest, RequestError},
     protocol::{Config, Frames},
 };
 use crate::handler::{ProtocolOutEvent, ProtocolOutboundOpenInfo};
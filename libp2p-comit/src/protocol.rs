@@ -1,23 +1,52 @@
-use crate::frame::{self, JsonFrameCodec};
+use crate::frame::{CborFrameCodec, FrameCodec, JsonFrameCodec};
 use futures::future;
 use futures_codec::Framed;
 use libp2p::{
     core::{InboundUpgrade, OutboundUpgrade, UpgradeInfo},
     swarm::NegotiatedSubstream,
 };
-use std::{convert::Infallible, iter};
+use std::convert::Infallible;
 
-pub type Frames = Framed<NegotiatedSubstream, JsonFrameCodec>;
+/// The original protocol version, frames are newline-delimited JSON.
+pub const COMIT_JSON: &[u8] = b"/comit/1.0.0";
+/// Frames are CBOR encoded and prefixed with their length.
+pub const COMIT_CBOR: &[u8] = b"/comit/cbor/1.0.0";
 
+pub type Frames = Framed<NegotiatedSubstream, FrameCodec>;
+
+/// Upgrade to the COMIT messaging protocol.
+///
+/// The CBOR encoding is preferred if `cbor` is enabled, JSON is always
+/// supported so that we can talk to nodes that don't know about CBOR.
 #[derive(Clone, Copy, Debug)]
-pub struct Config {}
+pub struct Config {
+    pub cbor: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { cbor: true }
+    }
+}
 
 impl UpgradeInfo for Config {
     type Info = &'static [u8];
-    type InfoIter = iter::Once<Self::Info>;
+    type InfoIter = std::vec::IntoIter<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        iter::once(b"/comit/1.0.0")
+        if self.cbor {
+            vec![COMIT_CBOR, COMIT_JSON].into_iter()
+        } else {
+            vec![COMIT_JSON].into_iter()
+        }
+    }
+}
+
+fn codec_for(info: &[u8]) -> FrameCodec {
+    if info == COMIT_CBOR {
+        FrameCodec::Cbor(CborFrameCodec::default())
+    } else {
+        FrameCodec::Json(JsonFrameCodec::default())
     }
 }
 
@@ -27,9 +56,8 @@ impl InboundUpgrade<NegotiatedSubstream> for Config {
     type Future = future::Ready<Result<Self::Output, Infallible>>;
 
     #[inline]
-    fn upgrade_inbound(self, socket: NegotiatedSubstream, _: Self::Info) -> Self::Future {
-        let codec = frame::JsonFrameCodec::default();
-        let framed = Framed::new(socket, codec);
+    fn upgrade_inbound(self, socket: NegotiatedSubstream, info: Self::Info) -> Self::Future {
+        let framed = Framed::new(socket, codec_for(info));
 
         future::ok(framed)
     }
@@ -41,10 +69,35 @@ impl OutboundUpgrade<NegotiatedSubstream> for Config {
     type Future = future::Ready<Result<Self::Output, Infallible>>;
 
     #[inline]
-    fn upgrade_outbound(self, socket: NegotiatedSubstream, _: Self::Info) -> Self::Future {
-        let codec = frame::JsonFrameCodec::default();
-        let framed = Framed::new(socket, codec);
+    fn upgrade_outbound(self, socket: NegotiatedSubstream, info: Self::Info) -> Self::Future {
+        let framed = Framed::new(socket, codec_for(info));
 
         future::ok(framed)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spectral::prelude::*;
+
+    #[test]
+    fn cbor_is_preferred_over_json() {
+        let protocols = Config { cbor: true }.protocol_info().collect::<Vec<_>>();
+
+        assert_that(&protocols).is_equal_to(vec![COMIT_CBOR, COMIT_JSON]);
+    }
+
+    #[test]
+    fn json_is_the_only_protocol_without_cbor() {
+        let protocols = Config { cbor: false }.protocol_info().collect::<Vec<_>>();
+
+        assert_that(&protocols).is_equal_to(vec![COMIT_JSON]);
+    }
+
+    #[test]
+    fn negotiated_protocol_selects_codec() {
+        assert!(matches!(codec_for(COMIT_CBOR), FrameCodec::Cbor(_)));
+        assert!(matches!(codec_for(COMIT_JSON), FrameCodec::Json(_)));
+    }
+}
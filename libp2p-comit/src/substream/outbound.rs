@@ -1,18 +1,56 @@
 use crate::{
     frame::Response,
-    handler::{self, InboundMessage, PendingInboundResponse, ProtocolOutEvent},
+    handler::{self, InboundMessage, PendingInboundResponse, ProtocolOutEvent, RequestError},
     protocol::Frames,
     substream::{Advance, Advanced, CloseStream},
     Frame, FrameKind,
 };
-use futures::{channel::oneshot, Sink, Stream};
+use futures::{channel::oneshot, FutureExt, Sink, Stream};
 use libp2p::swarm::ProtocolsHandlerEvent;
 use serde::Deserialize;
 use std::{
     collections::{HashMap, HashSet},
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
+use wasm_timer::Delay;
+
+/// The channel to the requester together with the timer that limits how long
+/// the requester is willing to wait for the response.
+#[allow(missing_debug_implementations)]
+pub struct ResponseSender {
+    channel: oneshot::Sender<Result<Response, RequestError>>,
+    timeout: Duration,
+    delay: Delay,
+}
+
+impl ResponseSender {
+    pub fn new(
+        channel: oneshot::Sender<Result<Response, RequestError>>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            channel,
+            timeout,
+            delay: Delay::new(timeout),
+        }
+    }
+
+    /// Returns `true` if the timeout expired.
+    ///
+    /// Polling the timer registers the current task for wake-up once the
+    /// timeout expires.
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        self.delay.poll_unpin(cx).is_ready()
+    }
+
+    fn send_timeout(self) {
+        tracing::warn!("request timed out after {:?}", self.timeout);
+
+        let _ = self.channel.send(Err(RequestError::Timeout(self.timeout)));
+    }
+}
 
 #[derive(strum_macros::Display)]
 #[allow(missing_debug_implementations)]
@@ -21,17 +59,17 @@ pub enum State {
     /// Waiting to send a message to the remote.
     WaitingSend {
         frame: Frame,
-        response_sender: oneshot::Sender<Response>,
+        response_sender: ResponseSender,
         stream: Pin<Box<Frames>>,
     },
     /// Waiting to flush the substream so that the data arrives at the remote.
     WaitingFlush {
-        response_sender: oneshot::Sender<Response>,
+        response_sender: ResponseSender,
         stream: Pin<Box<Frames>>,
     },
     /// Waiting for the answer to our message.
     WaitingAnswer {
-        response_sender: oneshot::Sender<Response>,
+        response_sender: ResponseSender,
         stream: Pin<Box<Frames>>,
     },
     /// The substream is being closed.
@@ -44,14 +82,61 @@ impl CloseStream for State {
     }
 }
 
+impl State {
+    fn is_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        use self::State::*;
+
+        match self {
+            WaitingSend {
+                response_sender, ..
+            }
+            | WaitingFlush {
+                response_sender, ..
+            }
+            | WaitingAnswer {
+                response_sender, ..
+            } => response_sender.poll_expired(cx),
+            WaitingClose { .. } => false,
+        }
+    }
+
+    fn into_timed_out(self) -> Self {
+        use self::State::*;
+
+        match self {
+            WaitingSend {
+                response_sender,
+                stream,
+                ..
+            }
+            | WaitingFlush {
+                response_sender,
+                stream,
+            }
+            | WaitingAnswer {
+                response_sender,
+                stream,
+            } => {
+                response_sender.send_timeout();
+                WaitingClose { stream }
+            }
+            state @ WaitingClose { .. } => state,
+        }
+    }
+}
+
 impl Advance for State {
     fn advance(
-        self,
+        mut self,
         known_headers: &HashMap<String, HashSet<String>>,
         cx: &mut Context<'_>,
     ) -> Advanced<State> {
         use self::State::*;
 
+        if self.is_expired(cx) {
+            return self.into_timed_out().advance(known_headers, cx);
+        }
+
         match self {
             WaitingSend {
                 frame,
@@ -100,7 +185,7 @@ impl Advance for State {
                             let event = ProtocolOutEvent::Message(InboundMessage::Response(
                                 PendingInboundResponse {
                                     response,
-                                    channel: response_sender,
+                                    channel: response_sender.channel,
                                 },
                             ));
 